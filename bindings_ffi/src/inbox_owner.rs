@@ -1,4 +1,6 @@
-use xmtp_cryptography::signature::{RecoverableSignature, SignatureError};
+use xmtp_cryptography::signature::{
+    normalize_account_address, RecoverableSignature, SignatureError,
+};
 
 // TODO proper error handling
 #[derive(Debug, thiserror::Error)]
@@ -31,7 +33,7 @@ impl RustInboxOwner {
 
 impl xmtp_mls::InboxOwner for RustInboxOwner {
     fn get_address(&self) -> String {
-        self.ffi_inbox_owner.get_address().to_lowercase()
+        normalize_account_address(self.ffi_inbox_owner.get_address())
     }
 
     fn sign(&self, text: &str) -> Result<RecoverableSignature, SignatureError> {
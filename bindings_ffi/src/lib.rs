@@ -42,6 +42,8 @@ pub enum GenericError {
     Erc1271SignatureError(#[from] xmtp_id::associations::signature::SignatureError),
     #[error(transparent)]
     Verifier(#[from] xmtp_id::scw_verifier::VerifierError),
+    #[error("Group invite: {0}")]
+    Invite(#[from] xmtp_mls::groups::group_invites::InviteError),
 }
 
 impl From<String> for GenericError {
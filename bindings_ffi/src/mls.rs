@@ -9,6 +9,7 @@ use tokio::{sync::Mutex, task::AbortHandle};
 use xmtp_api_grpc::grpc_api_helper::Client as TonicApiClient;
 use xmtp_id::associations::unverified::UnverifiedSignature;
 use xmtp_id::associations::AccountId;
+use xmtp_id::associations::Action;
 use xmtp_id::associations::AssociationState;
 use xmtp_id::associations::MemberIdentifier;
 use xmtp_id::scw_verifier::SmartContractSignatureVerifier;
@@ -20,11 +21,13 @@ use xmtp_mls::client::FindGroupParams;
 use xmtp_mls::groups::group_mutable_metadata::MetadataField;
 use xmtp_mls::groups::group_permissions::BasePolicies;
 use xmtp_mls::groups::group_permissions::GroupMutablePermissionsError;
+use xmtp_mls::groups::group_permissions::JoinRequestPolicy;
 use xmtp_mls::groups::group_permissions::MembershipPolicies;
 use xmtp_mls::groups::group_permissions::MetadataBasePolicies;
 use xmtp_mls::groups::group_permissions::MetadataPolicies;
 use xmtp_mls::groups::group_permissions::PermissionsBasePolicies;
 use xmtp_mls::groups::group_permissions::PermissionsPolicies;
+use xmtp_mls::groups::group_permissions::PolicyError;
 use xmtp_mls::groups::group_permissions::PolicySet;
 use xmtp_mls::groups::intents::PermissionPolicyOption;
 use xmtp_mls::groups::intents::PermissionUpdateType;
@@ -32,8 +35,14 @@ use xmtp_mls::groups::GroupMetadataOptions;
 use xmtp_mls::storage::consent_record::ConsentState;
 use xmtp_mls::storage::consent_record::ConsentType;
 use xmtp_mls::storage::consent_record::StoredConsentRecord;
+use xmtp_mls::storage::installation_metadata::StoredInstallationMetadata;
+use xmtp_mls::InboxHistoryEntry;
+use xmtp_mls::RevocationImpact;
 use xmtp_mls::{
-    api::ApiClientWrapper,
+    api::{
+        rate_limit::{RateLimit, RateLimiter},
+        ApiClientWrapper, Endpoint,
+    },
     builder::ClientBuilder,
     client::Client as MlsClient,
     client::ClientError,
@@ -46,6 +55,7 @@ use xmtp_mls::{
     identity::IdentityStrategy,
     retry::Retry,
     storage::{
+        group_join_request::{JoinRequestState, StoredGroupJoinRequest},
         group_message::{DeliveryStatus, GroupMessageKind, StoredGroupMessage},
         EncryptedMessageStore, EncryptionKey, StorageOption,
     },
@@ -86,8 +96,82 @@ pub async fn create_client(
     nonce: u64,
     legacy_signed_private_key_proto: Option<Vec<u8>>,
     history_sync_url: Option<String>,
+    rate_limits: Option<Vec<FfiRateLimit>>,
+) -> Result<Arc<FfiXmtpClient>, GenericError> {
+    init_logger(logger);
+    let identity_strategy = IdentityStrategy::CreateIfNotFound(
+        inbox_id.clone(),
+        account_address.clone(),
+        nonce,
+        legacy_signed_private_key_proto,
+    );
+
+    build_client(
+        host,
+        is_secure,
+        db,
+        encryption_key,
+        identity_strategy,
+        account_address,
+        history_sync_url,
+        rate_limits,
+    )
+    .await
+}
+
+/// It returns a new client for an inbox that lost all of its installations, the recovery
+/// address of the inbox regains control of it.
+///
+/// The signature request of the client revokes every wallet and installation of the inbox
+/// other than the recovery address, and adds the new installation. Once it is signed by the
+/// recovery address and registered with `register_identity`, group members add the new
+/// installation the next time they sync all of their groups.
+///
+/// ```text
+/// client = xmtp.recover_client(inbox_id, recovery_address)
+/// signature_request = client.signature_request()
+/// signature_request.add_ecdsa_signature(sign(recovery_address, signature_request.signature_text()))
+/// client.register_identity(signature_request)
+/// ```
+#[allow(clippy::too_many_arguments)]
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn recover_client(
+    logger: Box<dyn FfiLogger>,
+    host: String,
+    is_secure: bool,
+    db: Option<String>,
+    encryption_key: Option<Vec<u8>>,
+    inbox_id: &InboxId,
+    recovery_address: String,
+    history_sync_url: Option<String>,
+    rate_limits: Option<Vec<FfiRateLimit>>,
 ) -> Result<Arc<FfiXmtpClient>, GenericError> {
     init_logger(logger);
+    let identity_strategy = IdentityStrategy::Recover(inbox_id.clone(), recovery_address.clone());
+
+    build_client(
+        host,
+        is_secure,
+        db,
+        encryption_key,
+        identity_strategy,
+        recovery_address,
+        history_sync_url,
+        rate_limits,
+    )
+    .await
+}
+
+async fn build_client(
+    host: String,
+    is_secure: bool,
+    db: Option<String>,
+    encryption_key: Option<Vec<u8>>,
+    identity_strategy: IdentityStrategy,
+    account_address: String,
+    history_sync_url: Option<String>,
+    rate_limits: Option<Vec<FfiRateLimit>>,
+) -> Result<Arc<FfiXmtpClient>, GenericError> {
     log::info!(
         "Creating API client for host: {}, isSecure: {}",
         host,
@@ -117,11 +201,16 @@ pub async fn create_client(
         None => EncryptedMessageStore::new_unencrypted(storage_option)?,
     };
     log::info!("Creating XMTP client");
-    let identity_strategy = IdentityStrategy::CreateIfNotFound(
-        inbox_id.clone(),
-        account_address.clone(),
-        nonce,
-        legacy_signed_private_key_proto,
+
+    // Limits that are given replace the defaults of their endpoint
+    let rate_limiter = rate_limits.unwrap_or_default().into_iter().fold(
+        RateLimiter::default(),
+        |rate_limiter, limit| {
+            rate_limiter.with_limit(
+                limit.endpoint.into(),
+                RateLimit::new(limit.burst, limit.per_second),
+            )
+        },
     );
 
     let xmtp_client: RustXmtpClient = match history_sync_url {
@@ -129,6 +218,7 @@ pub async fn create_client(
             ClientBuilder::new(identity_strategy)
                 .api_client(api_client)
                 .store(store)
+                .rate_limiter(rate_limiter)
                 .history_sync_url(&url)
                 .build()
                 .await?
@@ -137,6 +227,7 @@ pub async fn create_client(
             ClientBuilder::new(identity_strategy)
                 .api_client(api_client)
                 .store(store)
+                .rate_limiter(rate_limiter)
                 .build()
                 .await?
         }
@@ -152,6 +243,49 @@ pub async fn create_client(
     }))
 }
 
+/// A client side limit on the requests made to one endpoint of the XMTP API
+#[derive(uniffi::Record, Clone, Debug)]
+pub struct FfiRateLimit {
+    pub endpoint: FfiEndpoint,
+    /// Requests that can be made at once before throttling kicks in
+    pub burst: u32,
+    /// Requests replenished every second
+    pub per_second: f64,
+}
+
+#[derive(uniffi::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FfiEndpoint {
+    UploadKeyPackage,
+    FetchKeyPackages,
+    SendGroupMessages,
+    SendWelcomeMessages,
+    QueryGroupMessages,
+    QueryWelcomeMessages,
+    SubscribeGroupMessages,
+    SubscribeWelcomeMessages,
+    PublishIdentityUpdate,
+    GetIdentityUpdates,
+    GetInboxIds,
+}
+
+impl From<FfiEndpoint> for Endpoint {
+    fn from(endpoint: FfiEndpoint) -> Self {
+        match endpoint {
+            FfiEndpoint::UploadKeyPackage => Endpoint::UploadKeyPackage,
+            FfiEndpoint::FetchKeyPackages => Endpoint::FetchKeyPackages,
+            FfiEndpoint::SendGroupMessages => Endpoint::SendGroupMessages,
+            FfiEndpoint::SendWelcomeMessages => Endpoint::SendWelcomeMessages,
+            FfiEndpoint::QueryGroupMessages => Endpoint::QueryGroupMessages,
+            FfiEndpoint::QueryWelcomeMessages => Endpoint::QueryWelcomeMessages,
+            FfiEndpoint::SubscribeGroupMessages => Endpoint::SubscribeGroupMessages,
+            FfiEndpoint::SubscribeWelcomeMessages => Endpoint::SubscribeWelcomeMessages,
+            FfiEndpoint::PublishIdentityUpdate => Endpoint::PublishIdentityUpdate,
+            FfiEndpoint::GetIdentityUpdates => Endpoint::GetIdentityUpdates,
+            FfiEndpoint::GetInboxIds => Endpoint::GetInboxIds,
+        }
+    }
+}
+
 #[allow(unused)]
 #[uniffi::export(async_runtime = "tokio")]
 pub async fn get_inbox_id_for_address(
@@ -162,7 +296,7 @@ pub async fn get_inbox_id_for_address(
 ) -> Result<Option<String>, GenericError> {
     let api_client = ApiClientWrapper::new(
         TonicApiClient::create(host.clone(), is_secure).await?,
-        Retry::default(),
+        Retry::client_default(),
     );
 
     let results = api_client
@@ -233,6 +367,45 @@ impl FfiSignatureRequest {
         Ok(())
     }
 
+    // Signature that's produced by a passkey (WebAuthn assertion)
+    pub async fn add_passkey_signature(
+        &self,
+        public_key: Vec<u8>,
+        signature_bytes: Vec<u8>,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Result<(), GenericError> {
+        let mut inner = self.inner.lock().await;
+        inner
+            .add_signature(
+                UnverifiedSignature::new_passkey(
+                    public_key,
+                    signature_bytes,
+                    authenticator_data,
+                    client_data_json,
+                ),
+                self.scw_verifier.clone().as_ref(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    // Signature that's signed by a Solana wallet (ed25519 off-chain message signing)
+    pub async fn add_solana_signature(
+        &self,
+        signature_bytes: Vec<u8>,
+        address: String,
+    ) -> Result<(), GenericError> {
+        let mut inner = self.inner.lock().await;
+        inner
+            .add_signature(
+                UnverifiedSignature::new_solana(signature_bytes, AccountId::new_solana(address)),
+                self.scw_verifier.clone().as_ref(),
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn is_ready(&self) -> bool {
         self.inner.lock().await.is_ready()
     }
@@ -241,6 +414,11 @@ impl FfiSignatureRequest {
         Ok(self.inner.lock().await.signature_text())
     }
 
+    /// The id the request is saved under, stable across restarts of the app
+    pub async fn id(&self) -> String {
+        self.inner.lock().await.id()
+    }
+
     /// missing signatures that are from [MemberKind::Address]
     pub async fn missing_address_signatures(&self) -> Result<Vec<String>, GenericError> {
         let inner = self.inner.lock().await;
@@ -325,8 +503,16 @@ impl FfiXmtpClient {
         &self,
         refresh_from_network: bool,
     ) -> Result<FfiInboxState, GenericError> {
-        let state = self.inner_client.inbox_state(refresh_from_network).await?;
-        Ok(state.into())
+        // Also refreshes the identity updates of the inbox
+        let installation_metadata = self
+            .inner_client
+            .installation_metadata(refresh_from_network)
+            .await?;
+        let state = self.inner_client.inbox_state(false).await?;
+        Ok(FfiInboxState::with_installation_metadata(
+            state,
+            installation_metadata,
+        ))
     }
 
     pub async fn get_latest_inbox_state(
@@ -340,6 +526,30 @@ impl FfiXmtpClient {
         Ok(state.into())
     }
 
+    /**
+     * Set the device name and platform other installations of the inbox see for this
+     * installation in their inbox state. They are only shared with installations of the same inbox.
+     */
+    pub async fn set_installation_metadata(
+        &self,
+        device_name: String,
+        platform: String,
+    ) -> Result<(), GenericError> {
+        self.inner_client
+            .set_installation_metadata(device_name, platform)
+            .await?;
+        Ok(())
+    }
+
+    /// Every identity update of an inbox with the changes it made, oldest first
+    pub async fn inbox_history(
+        &self,
+        inbox_id: String,
+    ) -> Result<Vec<FfiInboxHistoryEntry>, GenericError> {
+        let history = self.inner_client.inbox_history(inbox_id).await?;
+        Ok(history.into_iter().map(Into::into).collect())
+    }
+
     pub async fn set_consent_states(&self, records: Vec<FfiConsent>) -> Result<(), GenericError> {
         let inner = self.inner_client.as_ref();
         let stored_records: Vec<StoredConsentRecord> =
@@ -426,6 +636,70 @@ impl FfiXmtpClient {
         Ok(())
     }
 
+    /**
+     * Saves a signature request with the signatures collected so far, so that it can be resumed
+     * with `resume_signature_request` if the app is killed while waiting on a wallet.
+     * Save it again after adding signatures. Returns the id of the request.
+     */
+    pub async fn save_signature_request(
+        &self,
+        signature_request: Arc<FfiSignatureRequest>,
+        expires_in_ns: Option<i64>,
+    ) -> Result<String, GenericError> {
+        let signature_request = signature_request.inner.lock().await;
+        Ok(self
+            .inner_client
+            .save_signature_request(&signature_request, expires_in_ns)?)
+    }
+
+    /**
+     * Loads a saved signature request, returns None if it does not exist or has expired
+     */
+    pub fn resume_signature_request(
+        &self,
+        id: String,
+    ) -> Result<Option<Arc<FfiSignatureRequest>>, GenericError> {
+        let scw_verifier = self.inner_client.context().scw_verifier.clone();
+        Ok(self
+            .inner_client
+            .resume_signature_request(&id)?
+            .map(|request| {
+                Arc::new(FfiSignatureRequest {
+                    inner: Arc::new(Mutex::new(request)),
+                    scw_verifier,
+                })
+            }))
+    }
+
+    /**
+     * The saved signature requests of this inbox that have not expired, oldest first
+     */
+    pub fn saved_signature_requests(&self) -> Result<Vec<Arc<FfiSignatureRequest>>, GenericError> {
+        let scw_verifier = self.inner_client.context().scw_verifier.clone();
+        Ok(self
+            .inner_client
+            .saved_signature_requests()?
+            .into_iter()
+            .map(|request| {
+                Arc::new(FfiSignatureRequest {
+                    inner: Arc::new(Mutex::new(request)),
+                    scw_verifier: scw_verifier.clone(),
+                })
+            })
+            .collect())
+    }
+
+    pub fn delete_signature_request(&self, id: String) -> Result<(), GenericError> {
+        Ok(self.inner_client.delete_signature_request(&id)?)
+    }
+
+    /**
+     * Deletes expired signature requests, returns how many were deleted
+     */
+    pub fn delete_expired_signature_requests(&self) -> Result<u64, GenericError> {
+        Ok(self.inner_client.delete_expired_signature_requests()? as u64)
+    }
+
     /// Revokes or removes an identity - really a wallet address - from the existing client
     pub async fn revoke_wallet(
         &self,
@@ -444,6 +718,54 @@ impl FfiXmtpClient {
         Ok(request)
     }
 
+    /**
+     * Revokes the given passkeys, as listed in the inbox state
+     */
+    pub async fn revoke_passkeys(
+        &self,
+        passkeys: Vec<Vec<u8>>,
+    ) -> Result<Arc<FfiSignatureRequest>, GenericError> {
+        let signature_request = self.inner_client.revoke_passkeys(passkeys).await?;
+
+        Ok(Arc::new(FfiSignatureRequest {
+            inner: Arc::new(tokio::sync::Mutex::new(signature_request)),
+            scw_verifier: self.inner_client.context().scw_verifier.clone(),
+        }))
+    }
+
+    /**
+     * Revokes the given installations, their device details are in the inbox state
+     */
+    pub async fn revoke_installations(
+        &self,
+        installation_ids: Vec<Vec<u8>>,
+    ) -> Result<Arc<FfiSignatureRequest>, GenericError> {
+        let signature_request = self
+            .inner_client
+            .revoke_installations(installation_ids)
+            .await?;
+
+        Ok(Arc::new(FfiSignatureRequest {
+            inner: Arc::new(tokio::sync::Mutex::new(signature_request)),
+            scw_verifier: self.inner_client.context().scw_verifier.clone(),
+        }))
+    }
+
+    /**
+     * Reports which of this installation's groups the given installations will be removed from,
+     * and which inboxes will process the removal, without revoking anything
+     */
+    pub async fn revoke_installations_dry_run(
+        &self,
+        installation_ids: Vec<Vec<u8>>,
+    ) -> Result<FfiRevocationImpact, GenericError> {
+        let impact = self
+            .inner_client
+            .revoke_installations_dry_run(installation_ids)
+            .await?;
+        Ok(impact.into())
+    }
+
     /**
      * Revokes all installations except the one the client is currently using
      */
@@ -476,12 +798,50 @@ pub struct FfiInboxState {
     pub recovery_address: String,
     pub installations: Vec<FfiInstallation>,
     pub account_addresses: Vec<String>,
+    /// The P-256 public keys of the passkeys of the inbox, compressed SEC1 encoded
+    pub passkeys: Vec<Vec<u8>>,
 }
 
 #[derive(uniffi::Record)]
 pub struct FfiInstallation {
     pub id: Vec<u8>,
     pub client_timestamp_ns: Option<u64>,
+    pub metadata: Option<FfiInstallationMetadata>,
+}
+
+/// The device details an installation shared with its inbox, as reported by the installation itself
+#[derive(uniffi::Record)]
+pub struct FfiInstallationMetadata {
+    pub device_name: String,
+    pub platform: String,
+    pub created_at_ns: u64,
+    pub last_seen_ns: u64,
+}
+
+impl From<StoredInstallationMetadata> for FfiInstallationMetadata {
+    fn from(metadata: StoredInstallationMetadata) -> Self {
+        Self {
+            device_name: metadata.device_name,
+            platform: metadata.platform,
+            created_at_ns: metadata.created_at_ns as u64,
+            last_seen_ns: metadata.last_seen_ns as u64,
+        }
+    }
+}
+
+impl FfiInboxState {
+    fn with_installation_metadata(
+        state: AssociationState,
+        mut installation_metadata: HashMap<Vec<u8>, StoredInstallationMetadata>,
+    ) -> Self {
+        let mut inbox_state = FfiInboxState::from(state);
+        for installation in inbox_state.installations.iter_mut() {
+            installation.metadata = installation_metadata
+                .remove(&installation.id)
+                .map(Into::into);
+        }
+        inbox_state
+    }
 }
 
 impl From<AssociationState> for FfiInboxState {
@@ -493,14 +853,138 @@ impl From<AssociationState> for FfiInboxState {
                 .members()
                 .into_iter()
                 .filter_map(|m| match m.identifier {
-                    MemberIdentifier::Address(_) => None,
+                    MemberIdentifier::Address(_) | MemberIdentifier::Passkey(_) => None,
                     MemberIdentifier::Installation(inst) => Some(FfiInstallation {
                         id: inst,
                         client_timestamp_ns: m.client_timestamp_ns,
+                        metadata: None,
                     }),
                 })
                 .collect(),
             account_addresses: state.account_addresses(),
+            passkeys: state.passkeys(),
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiRevocationImpact {
+    pub unknown_installation_ids: Vec<Vec<u8>>,
+    pub affected_groups: Vec<FfiAffectedGroup>,
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiAffectedGroup {
+    pub group_id: Vec<u8>,
+    pub removed_installation_ids: Vec<Vec<u8>>,
+    pub remaining_inbox_ids: Vec<String>,
+}
+
+impl From<RevocationImpact> for FfiRevocationImpact {
+    fn from(impact: RevocationImpact) -> Self {
+        Self {
+            unknown_installation_ids: impact.unknown_installation_ids,
+            affected_groups: impact
+                .affected_groups
+                .into_iter()
+                .map(|group| FfiAffectedGroup {
+                    group_id: group.group_id,
+                    removed_installation_ids: group.removed_installation_ids,
+                    remaining_inbox_ids: group.remaining_inbox_ids,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Members are identified by their address, or the hex encoded key of an installation or passkey
+#[derive(uniffi::Record)]
+pub struct FfiInboxHistoryEntry {
+    pub sequence_id: i64,
+    pub client_timestamp_ns: u64,
+    pub server_timestamp_ns: i64,
+    pub actions: Vec<FfiInboxAction>,
+    pub added_members: Vec<String>,
+    pub removed_members: Vec<String>,
+    pub recovery_address: String,
+}
+
+#[derive(uniffi::Enum, Clone, Debug, PartialEq, Eq)]
+pub enum FfiInboxActionKind {
+    CreateInbox,
+    AddAssociation,
+    RevokeAssociation,
+    ChangeRecoveryAddress,
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiInboxAction {
+    pub kind: FfiInboxActionKind,
+    /// The address that created the inbox, the member added or revoked, or the new recovery address
+    pub subject: String,
+    pub signers: Vec<FfiInboxActionSigner>,
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiInboxActionSigner {
+    pub identifier: String,
+    pub signature_kind: String,
+}
+
+impl From<Action> for FfiInboxAction {
+    fn from(action: Action) -> Self {
+        let signers = action
+            .verified_signatures()
+            .into_iter()
+            .map(|signature| FfiInboxActionSigner {
+                identifier: signature.signer.to_string(),
+                signature_kind: signature.kind.to_string(),
+            })
+            .collect();
+        let (kind, subject) = match action {
+            Action::CreateInbox(event) => (FfiInboxActionKind::CreateInbox, event.account_address),
+            Action::AddAssociation(event) => (
+                FfiInboxActionKind::AddAssociation,
+                event.new_member_identifier.to_string(),
+            ),
+            Action::RevokeAssociation(event) => (
+                FfiInboxActionKind::RevokeAssociation,
+                event.revoked_member.to_string(),
+            ),
+            Action::ChangeRecoveryAddress(event) => (
+                FfiInboxActionKind::ChangeRecoveryAddress,
+                event.new_recovery_address,
+            ),
+        };
+
+        Self {
+            kind,
+            subject,
+            signers,
+        }
+    }
+}
+
+impl From<InboxHistoryEntry> for FfiInboxHistoryEntry {
+    fn from(entry: InboxHistoryEntry) -> Self {
+        Self {
+            sequence_id: entry.sequence_id,
+            client_timestamp_ns: entry.update.client_timestamp_ns,
+            server_timestamp_ns: entry.server_timestamp_ns,
+            actions: entry.update.actions.into_iter().map(Into::into).collect(),
+            added_members: entry
+                .diff
+                .new_members
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            removed_members: entry
+                .diff
+                .removed_members
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            recovery_address: entry.recovery_address,
         }
     }
 }
@@ -517,11 +1001,13 @@ pub struct FfiConversations {
     inner_client: Arc<RustXmtpClient>,
 }
 
-#[derive(uniffi::Enum, Clone, Debug)]
+#[derive(uniffi::Enum, Clone, Debug, PartialEq, Eq)]
 pub enum FfiGroupPermissionsOptions {
     AllMembers,
     AdminOnly,
     CustomPolicy,
+    AnnouncementChannel,
+    Moderated,
 }
 
 #[derive(uniffi::Enum, Debug)]
@@ -531,6 +1017,9 @@ pub enum FfiPermissionUpdateType {
     AddAdmin,
     RemoveAdmin,
     UpdateMetadata,
+    SendMessage,
+    JoinRequest,
+    UpdateBanList,
 }
 
 impl From<&FfiPermissionUpdateType> for PermissionUpdateType {
@@ -541,6 +1030,9 @@ impl From<&FfiPermissionUpdateType> for PermissionUpdateType {
             FfiPermissionUpdateType::AddAdmin => PermissionUpdateType::AddAdmin,
             FfiPermissionUpdateType::RemoveAdmin => PermissionUpdateType::RemoveAdmin,
             FfiPermissionUpdateType::UpdateMetadata => PermissionUpdateType::UpdateMetadata,
+            FfiPermissionUpdateType::SendMessage => PermissionUpdateType::SendMessage,
+            FfiPermissionUpdateType::JoinRequest => PermissionUpdateType::JoinRequest,
+            FfiPermissionUpdateType::UpdateBanList => PermissionUpdateType::UpdateBanList,
         }
     }
 }
@@ -669,6 +1161,9 @@ pub struct FfiPermissionPolicySet {
     pub update_group_description_policy: FfiPermissionPolicy,
     pub update_group_image_url_square_policy: FfiPermissionPolicy,
     pub update_group_pinned_frame_url_policy: FfiPermissionPolicy,
+    pub send_message_policy: FfiPermissionPolicy,
+    pub join_request_policy: FfiPermissionPolicy,
+    pub update_ban_list_policy: FfiPermissionPolicy,
 }
 
 impl From<PreconfiguredPolicies> for FfiGroupPermissionsOptions {
@@ -676,6 +1171,10 @@ impl From<PreconfiguredPolicies> for FfiGroupPermissionsOptions {
         match policy {
             PreconfiguredPolicies::AllMembers => FfiGroupPermissionsOptions::AllMembers,
             PreconfiguredPolicies::AdminsOnly => FfiGroupPermissionsOptions::AdminOnly,
+            PreconfiguredPolicies::AnnouncementChannel => {
+                FfiGroupPermissionsOptions::AnnouncementChannel
+            }
+            PreconfiguredPolicies::Moderated => FfiGroupPermissionsOptions::Moderated,
         }
     }
 }
@@ -701,6 +1200,21 @@ impl TryFrom<FfiPermissionPolicySet> for PolicySet {
             policy_set.update_group_pinned_frame_url_policy.try_into()?,
         );
 
+        // Every member may send when no send message policy is set
+        let send_message_policy = match policy_set.send_message_policy {
+            FfiPermissionPolicy::Allow => None,
+            policy => Some(policy.try_into()?),
+        };
+
+        let join_request_policy = match policy_set.join_request_policy {
+            FfiPermissionPolicy::Allow => JoinRequestPolicy::AutoApprove,
+            FfiPermissionPolicy::Deny => JoinRequestPolicy::Deny,
+            FfiPermissionPolicy::Admin | FfiPermissionPolicy::SuperAdmin => {
+                JoinRequestPolicy::AdminApproval
+            }
+            _ => return Err(GroupMutablePermissionsError::InvalidPermissionPolicyOption),
+        };
+
         Ok(PolicySet {
             add_member_policy: policy_set.add_member_policy.try_into()?,
             remove_member_policy: policy_set.remove_member_policy.try_into()?,
@@ -708,6 +1222,9 @@ impl TryFrom<FfiPermissionPolicySet> for PolicySet {
             remove_admin_policy: policy_set.remove_admin_policy.try_into()?,
             update_metadata_policy: metadata_permissions_map,
             update_permissions_policy: PermissionsPolicies::allow_if_actor_super_admin(),
+            send_message_policy,
+            join_request_policy,
+            update_ban_list_policy: policy_set.update_ban_list_policy.try_into()?,
         })
     }
 }
@@ -764,6 +1281,12 @@ impl FfiConversations {
             Some(FfiGroupPermissionsOptions::AdminOnly) => {
                 Some(xmtp_mls::groups::PreconfiguredPolicies::AdminsOnly.to_policy_set())
             }
+            Some(FfiGroupPermissionsOptions::AnnouncementChannel) => {
+                Some(xmtp_mls::groups::PreconfiguredPolicies::AnnouncementChannel.to_policy_set())
+            }
+            Some(FfiGroupPermissionsOptions::Moderated) => {
+                Some(xmtp_mls::groups::PreconfiguredPolicies::Moderated.to_policy_set())
+            }
             Some(FfiGroupPermissionsOptions::CustomPolicy) => {
                 if let Some(policy_set) = opts.custom_permission_policy_set {
                     Some(policy_set.try_into()?)
@@ -828,6 +1351,11 @@ impl FfiConversations {
         Ok(())
     }
 
+    pub async fn request_to_join(&self, invite: String) -> Result<(), GenericError> {
+        self.inner_client.request_to_join(&invite).await?;
+        Ok(())
+    }
+
     pub async fn sync_all_groups(&self) -> Result<u32, GenericError> {
         let inner = self.inner_client.as_ref();
         let groups = inner.find_groups(FindGroupParams {
@@ -939,6 +1467,55 @@ pub enum FfiConsentState {
     Denied,
 }
 
+#[derive(uniffi::Enum, Clone, Debug, PartialEq, Eq)]
+pub enum FfiJoinRequestState {
+    Pending,
+    Approved,
+    Denied,
+    Failed,
+}
+
+impl From<JoinRequestState> for FfiJoinRequestState {
+    fn from(state: JoinRequestState) -> Self {
+        match state {
+            JoinRequestState::Pending => FfiJoinRequestState::Pending,
+            JoinRequestState::Approved => FfiJoinRequestState::Approved,
+            JoinRequestState::Denied => FfiJoinRequestState::Denied,
+            JoinRequestState::Failed => FfiJoinRequestState::Failed,
+        }
+    }
+}
+
+impl From<FfiJoinRequestState> for JoinRequestState {
+    fn from(state: FfiJoinRequestState) -> Self {
+        match state {
+            FfiJoinRequestState::Pending => JoinRequestState::Pending,
+            FfiJoinRequestState::Approved => JoinRequestState::Approved,
+            FfiJoinRequestState::Denied => JoinRequestState::Denied,
+            FfiJoinRequestState::Failed => JoinRequestState::Failed,
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiJoinRequest {
+    pub id: i32,
+    pub requester_inbox_id: String,
+    pub state: FfiJoinRequestState,
+    pub received_at_ns: i64,
+}
+
+impl From<StoredGroupJoinRequest> for FfiJoinRequest {
+    fn from(request: StoredGroupJoinRequest) -> Self {
+        Self {
+            id: request.id,
+            requester_inbox_id: request.requester_inbox_id,
+            state: request.state.into(),
+            received_at_ns: request.received_at_ns,
+        }
+    }
+}
+
 impl From<ConsentState> for FfiConsentState {
     fn from(state: ConsentState) -> Self {
         match state {
@@ -1193,6 +1770,59 @@ impl FfiGroup {
         Ok(())
     }
 
+    pub async fn ban_members(&self, inbox_ids: Vec<String>) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group.ban_members(&self.inner_client, inbox_ids).await?;
+
+        Ok(())
+    }
+
+    pub async fn unban_members(&self, inbox_ids: Vec<String>) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group.unban_members(&self.inner_client, inbox_ids).await?;
+
+        Ok(())
+    }
+
+    /// Asks to be removed from the group. An admin removes all of this inbox's installations the
+    /// next time they sync.
+    pub async fn leave(&self) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group.leave(&self.inner_client).await?;
+
+        Ok(())
+    }
+
+    /// Makes `to_inbox_id` a super admin and removes the caller's super admin role in one commit
+    pub async fn transfer_ownership(&self, to_inbox_id: String) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group
+            .transfer_ownership(&self.inner_client, to_inbox_id)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn update_group_name(&self, group_name: String) -> Result<(), GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
@@ -1244,14 +1874,43 @@ impl FfiGroup {
             self.created_at_ns,
         );
 
-        let group_image_url_square = group.group_image_url_square(group.mls_provider()?)?;
+        let group_image_url_square = group.group_image_url_square(group.mls_provider()?)?;
+
+        Ok(group_image_url_square)
+    }
+
+    pub async fn update_group_description(
+        &self,
+        group_description: String,
+    ) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group
+            .update_group_description(&self.inner_client, group_description)
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn group_description(&self) -> Result<String, GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        let group_description = group.group_description(group.mls_provider()?)?;
 
-        Ok(group_image_url_square)
+        Ok(group_description)
     }
 
-    pub async fn update_group_description(
+    pub async fn update_group_pinned_frame_url(
         &self,
-        group_description: String,
+        pinned_frame_url: String,
     ) -> Result<(), GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
@@ -1260,27 +1919,28 @@ impl FfiGroup {
         );
 
         group
-            .update_group_description(&self.inner_client, group_description)
+            .update_group_pinned_frame_url(&self.inner_client, pinned_frame_url)
             .await?;
 
         Ok(())
     }
 
-    pub fn group_description(&self) -> Result<String, GenericError> {
+    pub fn group_pinned_frame_url(&self) -> Result<String, GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
             self.group_id.clone(),
             self.created_at_ns,
         );
 
-        let group_description = group.group_description(group.mls_provider()?)?;
+        let group_pinned_frame_url = group.group_pinned_frame_url(group.mls_provider()?)?;
 
-        Ok(group_description)
+        Ok(group_pinned_frame_url)
     }
 
-    pub async fn update_group_pinned_frame_url(
+    pub async fn update_metadata_field(
         &self,
-        pinned_frame_url: String,
+        field_name: String,
+        field_value: String,
     ) -> Result<(), GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
@@ -1289,22 +1949,22 @@ impl FfiGroup {
         );
 
         group
-            .update_group_pinned_frame_url(&self.inner_client, pinned_frame_url)
+            .update_metadata_field(&self.inner_client, field_name, field_value)
             .await?;
 
         Ok(())
     }
 
-    pub fn group_pinned_frame_url(&self) -> Result<String, GenericError> {
+    pub fn metadata_field(&self, field_name: String) -> Result<Option<String>, GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
             self.group_id.clone(),
             self.created_at_ns,
         );
 
-        let group_pinned_frame_url = group.group_pinned_frame_url(group.mls_provider()?)?;
+        let field_value = group.metadata_field(group.mls_provider()?, &field_name)?;
 
-        Ok(group_pinned_frame_url)
+        Ok(field_value)
     }
 
     pub fn admin_list(&self) -> Result<Vec<String>, GenericError> {
@@ -1319,6 +1979,18 @@ impl FfiGroup {
         Ok(admin_list)
     }
 
+    pub fn ban_list(&self) -> Result<Vec<String>, GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        let ban_list = group.ban_list(group.mls_provider()?)?;
+
+        Ok(ban_list)
+    }
+
     pub fn super_admin_list(&self) -> Result<Vec<String>, GenericError> {
         let group = MlsGroup::new(
             self.inner_client.context().clone(),
@@ -1433,6 +2105,81 @@ impl FfiGroup {
         Ok(())
     }
 
+    pub fn create_invite(
+        &self,
+        expires_in_ns: Option<i64>,
+        max_uses: Option<u32>,
+    ) -> Result<String, GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        Ok(group.create_invite(&self.inner_client, expires_in_ns, max_uses)?)
+    }
+
+    pub fn join_requests(
+        &self,
+        state: Option<FfiJoinRequestState>,
+    ) -> Result<Vec<FfiJoinRequest>, GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        Ok(group
+            .join_requests(state.map(JoinRequestState::from))?
+            .into_iter()
+            .map(FfiJoinRequest::from)
+            .collect())
+    }
+
+    pub async fn approve_join_request(&self, request_id: i32) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group
+            .approve_join_request(&self.inner_client, request_id)
+            .await?;
+        Ok(())
+    }
+
+    pub fn deny_join_request(&self, request_id: i32) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+
+        group.deny_join_request(request_id)?;
+        Ok(())
+    }
+
+    pub async fn update_metadata_field_permission_policy(
+        &self,
+        field_name: String,
+        permission_policy_option: FfiPermissionPolicy,
+    ) -> Result<(), GenericError> {
+        let group = MlsGroup::new(
+            self.inner_client.context().clone(),
+            self.group_id.clone(),
+            self.created_at_ns,
+        );
+        group
+            .update_metadata_field_permission_policy(
+                &self.inner_client,
+                field_name,
+                permission_policy_option.try_into()?,
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn stream(&self, message_callback: Box<dyn FfiMessageCallback>) -> FfiStreamCloser {
         let inner_client = Arc::clone(&self.inner_client);
         let handle = MlsGroup::stream_with_callback(
@@ -1700,10 +2447,13 @@ pub struct FfiGroupPermissions {
 #[uniffi::export]
 impl FfiGroupPermissions {
     pub fn policy_type(&self) -> Result<FfiGroupPermissionsOptions, GenericError> {
-        if let Ok(preconfigured_policy) = self.inner.preconfigured_policy() {
-            Ok(preconfigured_policy.into())
-        } else {
-            Ok(FfiGroupPermissionsOptions::CustomPolicy)
+        match self.inner.preconfigured_policy() {
+            Ok(preconfigured_policy) => Ok(preconfigured_policy.into()),
+            // Groups created with a custom PolicySet don't match any of the presets
+            Err(GroupMutablePermissionsError::Policy(PolicyError::InvalidPresetPolicy)) => {
+                Ok(FfiGroupPermissionsOptions::CustomPolicy)
+            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -1729,6 +2479,17 @@ impl FfiGroupPermissions {
             update_group_pinned_frame_url_policy: get_policy(
                 MetadataField::GroupPinnedFrameUrl.as_str(),
             ),
+            send_message_policy: policy_set
+                .send_message_policy
+                .as_ref()
+                .map(FfiPermissionPolicy::from)
+                .unwrap_or(FfiPermissionPolicy::Allow),
+            join_request_policy: match policy_set.join_request_policy {
+                JoinRequestPolicy::Deny => FfiPermissionPolicy::Deny,
+                JoinRequestPolicy::AdminApproval => FfiPermissionPolicy::Admin,
+                JoinRequestPolicy::AutoApprove => FfiPermissionPolicy::Allow,
+            },
+            update_ban_list_policy: FfiPermissionPolicy::from(&policy_set.update_ban_list_policy),
         })
     }
 }
@@ -1893,6 +2654,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -1957,6 +2719,7 @@ mod tests {
             nonce,
             Some(legacy_keys),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -1983,6 +2746,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2002,6 +2766,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2036,6 +2801,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2056,6 +2822,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .is_err();
@@ -2108,6 +2875,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2174,6 +2942,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2259,6 +3028,7 @@ mod tests {
             nonce,
             None, // v2_signed_private_key_proto
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2287,6 +3057,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -2314,6 +3085,7 @@ mod tests {
             nonce,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -3353,6 +4125,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Admin,
             update_group_image_url_square_policy: FfiPermissionPolicy::Admin,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
         };
         assert_eq!(alix_permission_policy_set, expected_permission_policy_set);
 
@@ -3382,6 +4157,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Allow,
             update_group_image_url_square_policy: FfiPermissionPolicy::Allow,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Allow,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
         };
         assert_eq!(alix_permission_policy_set, expected_permission_policy_set);
     }
@@ -3415,6 +4193,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Admin,
             update_group_image_url_square_policy: FfiPermissionPolicy::Admin,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
         };
         assert_eq!(alix_group_permissions, expected_permission_policy_set);
 
@@ -3442,6 +4223,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Admin,
             update_group_image_url_square_policy: FfiPermissionPolicy::Allow,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
         };
         assert_eq!(alix_group_permissions, new_expected_permission_policy_set);
 
@@ -3496,6 +4280,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Allow,
             update_group_image_url_square_policy: FfiPermissionPolicy::Admin,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
             add_member_policy: FfiPermissionPolicy::Allow,
             remove_member_policy: FfiPermissionPolicy::Deny,
         };
@@ -3516,6 +4303,14 @@ mod tests {
             .unwrap();
 
         // Verify the group was created with the correct permissions
+        assert_eq!(
+            alix_group
+                .group_permissions()
+                .unwrap()
+                .policy_type()
+                .unwrap(),
+            FfiGroupPermissionsOptions::CustomPolicy
+        );
         let group_permissions_policy_set = alix_group
             .group_permissions()
             .unwrap()
@@ -3608,6 +4403,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Allow,
             update_group_image_url_square_policy: FfiPermissionPolicy::Admin,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
             add_member_policy: FfiPermissionPolicy::Allow,
             remove_member_policy: FfiPermissionPolicy::Deny,
         };
@@ -3619,6 +4417,9 @@ mod tests {
             update_group_description_policy: FfiPermissionPolicy::Allow,
             update_group_image_url_square_policy: FfiPermissionPolicy::Admin,
             update_group_pinned_frame_url_policy: FfiPermissionPolicy::Admin,
+            send_message_policy: FfiPermissionPolicy::Allow,
+            join_request_policy: FfiPermissionPolicy::Admin,
+            update_ban_list_policy: FfiPermissionPolicy::Admin,
             add_member_policy: FfiPermissionPolicy::Allow,
             remove_member_policy: FfiPermissionPolicy::Deny,
         };
@@ -3740,6 +4541,267 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn test_installation_metadata() {
+        let wallet = xmtp_cryptography::utils::LocalWallet::new(&mut rng());
+        let client_1 = new_test_client_with_wallet(wallet.clone()).await;
+        let client_2 = new_test_client_with_wallet(wallet.clone()).await;
+
+        client_2
+            .set_installation_metadata("Bola's tablet".to_string(), "android".to_string())
+            .await
+            .unwrap();
+        // The details are shared through the inbox's sync group, which is joined with a welcome
+        client_1.conversations().sync().await.unwrap();
+
+        let state = client_1.inbox_state(true).await.unwrap();
+        assert_eq!(state.installations.len(), 2);
+        let tablet = state
+            .installations
+            .iter()
+            .find(|installation| installation.id == client_2.installation_id())
+            .unwrap();
+        let metadata = tablet.metadata.as_ref().unwrap();
+        assert_eq!(metadata.device_name, "Bola's tablet");
+        assert_eq!(metadata.platform, "android");
+        assert!(state
+            .installations
+            .iter()
+            .find(|installation| installation.id == client_1.installation_id())
+            .unwrap()
+            .metadata
+            .is_none());
+
+        // The metadata tells which installation to revoke
+        let signature_request = client_1
+            .revoke_installations(vec![tablet.id.clone()])
+            .await
+            .unwrap();
+        sign_with_wallet(&wallet, &signature_request).await;
+        client_1
+            .apply_signature_request(signature_request)
+            .await
+            .unwrap();
+        let state = client_1.inbox_state(true).await.unwrap();
+        assert_eq!(state.installations.len(), 1);
+        assert_eq!(state.installations[0].id, client_1.installation_id());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn test_resume_signature_requests_after_restart() {
+        let ffi_inbox_owner = LocalWalletInboxOwner::new();
+        let nonce = 1;
+        let inbox_id = generate_inbox_id(&ffi_inbox_owner.get_address(), &nonce);
+        let path = tmp_path();
+        let key = static_enc_key().to_vec();
+
+        let client_a = create_client(
+            Box::new(MockLogger {}),
+            xmtp_api_grpc::LOCALHOST_ADDRESS.to_string(),
+            false,
+            Some(path.clone()),
+            Some(key.clone()),
+            &inbox_id,
+            ffi_inbox_owner.get_address(),
+            nonce,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let registration = client_a.signature_request().unwrap();
+        let registration_id = client_a
+            .save_signature_request(registration, None)
+            .await
+            .unwrap();
+        let installation_id = client_a.installation_id();
+        drop(client_a);
+
+        // The app restarts before the wallet signs, the same installation is registered
+        let client = create_client(
+            Box::new(MockLogger {}),
+            xmtp_api_grpc::LOCALHOST_ADDRESS.to_string(),
+            false,
+            Some(path),
+            Some(key),
+            &inbox_id,
+            ffi_inbox_owner.get_address(),
+            nonce,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(client.installation_id(), installation_id);
+        let registration = client.signature_request().unwrap();
+        assert_eq!(registration.id().await, registration_id);
+        register_client(&ffi_inbox_owner, &client).await;
+        let state = client.inbox_state(true).await.unwrap();
+        assert_eq!(state.installations.len(), 1);
+        assert_eq!(state.installations[0].id, installation_id);
+
+        // Adding a wallet survives a restart between the two wallet signatures
+        let wallet_to_add = generate_local_wallet();
+        let signature_request = client
+            .add_wallet(&ffi_inbox_owner.get_address(), &wallet_to_add.get_address())
+            .await
+            .unwrap();
+        sign_with_wallet(&ffi_inbox_owner.wallet, &signature_request).await;
+        let id = client
+            .save_signature_request(signature_request, None)
+            .await
+            .unwrap();
+
+        let resumed = client
+            .resume_signature_request(id.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            resumed.missing_address_signatures().await.unwrap(),
+            vec![wallet_to_add.get_address()]
+        );
+        sign_with_wallet(&wallet_to_add, &resumed).await;
+        client.apply_signature_request(resumed).await.unwrap();
+        assert!(client.resume_signature_request(id).unwrap().is_none());
+        assert!(client.saved_signature_requests().unwrap().is_empty());
+
+        let state = client.inbox_state(true).await.unwrap();
+        assert_eq!(state.account_addresses.len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn test_revoke_installations_dry_run() {
+        let wallet = xmtp_cryptography::utils::LocalWallet::new(&mut rng());
+        let client_1 = new_test_client_with_wallet(wallet.clone()).await;
+        let client_2 = new_test_client_with_wallet(wallet.clone()).await;
+        let bo = new_test_client().await;
+
+        let group = bo
+            .conversations()
+            .create_group(
+                vec![client_1.account_address.clone()],
+                FfiCreateGroupOptions::default(),
+            )
+            .await
+            .unwrap();
+        client_1.conversations().sync().await.unwrap();
+
+        let impact = client_1
+            .revoke_installations_dry_run(vec![client_2.installation_id()])
+            .await
+            .unwrap();
+        assert!(impact.unknown_installation_ids.is_empty());
+        assert_eq!(impact.affected_groups.len(), 1);
+        assert_eq!(impact.affected_groups[0].group_id, group.id());
+        assert_eq!(
+            impact.affected_groups[0].removed_installation_ids,
+            vec![client_2.installation_id()]
+        );
+        assert_eq!(impact.affected_groups[0].remaining_inbox_ids.len(), 2);
+        assert_eq!(
+            client_1
+                .inbox_state(true)
+                .await
+                .unwrap()
+                .installations
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn test_recover_client() {
+        let wallet = xmtp_cryptography::utils::LocalWallet::new(&mut rng());
+        let client_1 = new_test_client_with_wallet(wallet.clone()).await;
+        let _client_2 = new_test_client_with_wallet(wallet.clone()).await;
+        let inbox_owner = LocalWalletInboxOwner::with_wallet(wallet);
+
+        let recovered_client = recover_client(
+            Box::new(MockLogger {}),
+            xmtp_api_grpc::LOCALHOST_ADDRESS.to_string(),
+            false,
+            Some(tmp_path()),
+            None,
+            &client_1.inbox_id(),
+            inbox_owner.get_address(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(recovered_client.inbox_id(), client_1.inbox_id());
+        register_client(&inbox_owner, &recovered_client).await;
+
+        let state = client_1.inbox_state(true).await.unwrap();
+        assert_eq!(state.installations.len(), 1);
+        assert_eq!(
+            state.installations.first().unwrap().id,
+            recovered_client.installation_id()
+        );
+        // The recovery address stays a member of the inbox
+        assert_eq!(state.account_addresses, vec![inbox_owner.get_address()]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn test_inbox_history() {
+        let wallet = xmtp_cryptography::utils::LocalWallet::new(&mut rng());
+        let client_1 = new_test_client_with_wallet(wallet.clone()).await;
+        let client_2 = new_test_client_with_wallet(wallet.clone()).await;
+
+        let signature_request = client_1.revoke_all_other_installations().await.unwrap();
+        sign_with_wallet(&wallet, &signature_request).await;
+        client_1
+            .apply_signature_request(signature_request)
+            .await
+            .unwrap();
+
+        let history = client_2.inbox_history(client_1.inbox_id()).await.unwrap();
+        assert_eq!(history.len(), 3);
+
+        let kinds: Vec<Vec<FfiInboxActionKind>> = history
+            .iter()
+            .map(|entry| {
+                entry
+                    .actions
+                    .iter()
+                    .map(|action| action.kind.clone())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                vec![
+                    FfiInboxActionKind::CreateInbox,
+                    FfiInboxActionKind::AddAssociation
+                ],
+                vec![FfiInboxActionKind::AddAssociation],
+                vec![FfiInboxActionKind::RevokeAssociation],
+            ]
+        );
+
+        let client_2_installation = hex::encode(client_2.installation_id());
+        assert_eq!(
+            history[1].added_members,
+            vec![client_2_installation.clone()]
+        );
+        assert_eq!(
+            history[2].removed_members,
+            vec![client_2_installation.clone()]
+        );
+
+        let revocation = &history[2].actions[0];
+        assert_eq!(revocation.subject, client_2_installation);
+        assert_eq!(revocation.signers.len(), 1);
+        assert_eq!(
+            revocation.signers[0].identifier,
+            history[2].recovery_address
+        );
+        assert_eq!(revocation.signers[0].signature_kind, "erc-191");
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
     async fn test_dms_sync_but_do_not_list() {
         let alix = new_test_client().await;
@@ -7,10 +7,11 @@ use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFun
 use napi::JsFunction;
 use napi_derive::napi;
 use xmtp_mls::client::FindGroupParams;
+use xmtp_mls::groups::group_permissions::PolicySet;
 use xmtp_mls::groups::{GroupMetadataOptions, PreconfiguredPolicies};
 
 use crate::messages::NapiMessage;
-use crate::permissions::NapiGroupPermissionsOptions;
+use crate::permissions::{NapiGroupPermissionsOptions, NapiPermissionPolicySet};
 use crate::ErrorWrapper;
 use crate::{groups::NapiGroup, mls_client::RustXmtpClient, streams::NapiStreamCloser};
 
@@ -29,6 +30,7 @@ pub struct NapiCreateGroupOptions {
   pub group_image_url_square: Option<String>,
   pub group_description: Option<String>,
   pub group_pinned_frame_url: Option<String>,
+  pub custom_permission_policy_set: Option<NapiPermissionPolicySet>,
 }
 
 impl NapiCreateGroupOptions {
@@ -67,9 +69,20 @@ impl NapiConversations {
         group_image_url_square: None,
         group_description: None,
         group_pinned_frame_url: None,
+        custom_permission_policy_set: None,
       },
     };
 
+    if let Some(NapiGroupPermissionsOptions::CustomPolicy) = options.permissions {
+      if options.custom_permission_policy_set.is_none() {
+        return Err(Error::from_reason("CustomPolicy must include policy set"));
+      }
+    } else if options.custom_permission_policy_set.is_some() {
+      return Err(Error::from_reason(
+        "Only CustomPolicy may specify a policy set",
+      ));
+    }
+
     let group_permissions = match options.permissions {
       Some(NapiGroupPermissionsOptions::AllMembers) => {
         Some(PreconfiguredPolicies::AllMembers.to_policy_set())
@@ -77,6 +90,17 @@ impl NapiConversations {
       Some(NapiGroupPermissionsOptions::AdminOnly) => {
         Some(PreconfiguredPolicies::AdminsOnly.to_policy_set())
       }
+      Some(NapiGroupPermissionsOptions::AnnouncementChannel) => {
+        Some(PreconfiguredPolicies::AnnouncementChannel.to_policy_set())
+      }
+      Some(NapiGroupPermissionsOptions::Moderated) => {
+        Some(PreconfiguredPolicies::Moderated.to_policy_set())
+      }
+      Some(NapiGroupPermissionsOptions::CustomPolicy) => options
+        .custom_permission_policy_set
+        .clone()
+        .map(PolicySet::try_from)
+        .transpose()?,
       _ => None,
     };
 
@@ -161,6 +185,16 @@ impl NapiConversations {
     Ok(())
   }
 
+  #[napi]
+  pub async fn request_to_join(&self, invite: String) -> Result<()> {
+    self
+      .inner_client
+      .request_to_join(&invite)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    Ok(())
+  }
+
   #[napi]
   pub async fn list(&self, opts: Option<NapiListConversationsOptions>) -> Result<Vec<NapiGroup>> {
     let opts = match opts {
@@ -16,9 +16,10 @@ use xmtp_proto::xmtp::mls::message_contents::EncodedContent;
 use crate::{
   consent_state::NapiConsentState,
   encoded_content::NapiEncodedContent,
+  join_requests::{NapiJoinRequest, NapiJoinRequestState},
   messages::{NapiListMessagesOptions, NapiMessage},
   mls_client::RustXmtpClient,
-  permissions::NapiGroupPermissions,
+  permissions::{NapiGroupPermissions, NapiPermissionPolicy},
   streams::NapiStreamCloser,
   ErrorWrapper,
 };
@@ -257,6 +258,21 @@ impl NapiGroup {
     Ok(admin_list)
   }
 
+  #[napi]
+  pub fn ban_list(&self) -> Result<Vec<String>> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    let ban_list = group
+      .ban_list(group.mls_provider().map_err(ErrorWrapper::from)?)
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(ban_list)
+  }
+
   #[napi]
   pub fn super_admin_list(&self) -> Result<Vec<String>> {
     let group = MlsGroup::new(
@@ -377,6 +393,67 @@ impl NapiGroup {
     Ok(NapiGroupPermissions::new(permissions))
   }
 
+  #[napi]
+  pub fn create_invite(&self, expires_in_ns: Option<i64>, max_uses: Option<u32>) -> Result<String> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    let invite = group
+      .create_invite(&self.inner_client, expires_in_ns, max_uses)
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(invite)
+  }
+
+  #[napi]
+  pub fn join_requests(&self, state: Option<NapiJoinRequestState>) -> Result<Vec<NapiJoinRequest>> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    let requests = group
+      .join_requests(state.map(Into::into))
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(requests.into_iter().map(NapiJoinRequest::from).collect())
+  }
+
+  #[napi]
+  pub async fn approve_join_request(&self, request_id: i32) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .approve_join_request(&self.inner_client, request_id)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
+  #[napi]
+  pub fn deny_join_request(&self, request_id: i32) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .deny_join_request(request_id)
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
   #[napi]
   pub async fn add_members_by_inbox_id(&self, inbox_ids: Vec<String>) -> Result<()> {
     let group = MlsGroup::new(
@@ -425,6 +502,73 @@ impl NapiGroup {
     Ok(())
   }
 
+  #[napi]
+  pub async fn ban_members(&self, inbox_ids: Vec<String>) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .ban_members(&self.inner_client, inbox_ids)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn unban_members(&self, inbox_ids: Vec<String>) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .unban_members(&self.inner_client, inbox_ids)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
+  /// Asks to be removed from the group. An admin removes all of this inbox's installations the
+  /// next time they sync.
+  #[napi]
+  pub async fn leave(&self) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .leave(&self.inner_client)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
+  /// Makes `to_inbox_id` a super admin and removes the caller's super admin role in one commit
+  #[napi]
+  pub async fn transfer_ownership(&self, to_inbox_id: String) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .transfer_ownership(&self.inner_client, to_inbox_id)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
   #[napi]
   pub async fn update_group_name(&self, group_name: String) -> Result<()> {
     let group = MlsGroup::new(
@@ -549,6 +693,64 @@ impl NapiGroup {
     Ok(group_pinned_frame_url)
   }
 
+  #[napi]
+  pub async fn update_metadata_field(&self, field_name: String, field_value: String) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .update_metadata_field(&self.inner_client, field_name, field_value)
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
+  #[napi]
+  pub fn metadata_field(&self, field_name: String) -> Result<Option<String>> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    let field_value = group
+      .metadata_field(
+        group.mls_provider().map_err(ErrorWrapper::from)?,
+        &field_name,
+      )
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(field_value)
+  }
+
+  #[napi]
+  pub async fn update_metadata_field_permission_policy(
+    &self,
+    field_name: String,
+    permission_policy: NapiPermissionPolicy,
+  ) -> Result<()> {
+    let group = MlsGroup::new(
+      self.inner_client.context().clone(),
+      self.group_id.clone(),
+      self.created_at_ns,
+    );
+
+    group
+      .update_metadata_field_permission_policy(
+        &self.inner_client,
+        field_name,
+        permission_policy.try_into()?,
+      )
+      .await
+      .map_err(ErrorWrapper::from)?;
+
+    Ok(())
+  }
+
   #[napi(ts_args_type = "callback: (err: null | Error, result: NapiMessage) => void")]
   pub fn stream(&self, callback: JsFunction) -> Result<NapiStreamCloser> {
     let tsfn: ThreadsafeFunction<NapiMessage, ErrorStrategy::CalleeHandled> =
@@ -1,12 +1,37 @@
+use std::collections::HashMap;
+
 use napi::bindgen_prelude::BigInt;
 use napi_derive::napi;
 use xmtp_cryptography::signature::ed25519_public_key_to_address;
-use xmtp_id::associations::{AssociationState, MemberIdentifier};
+use xmtp_id::associations::{Action, AssociationState, MemberIdentifier};
+use xmtp_mls::storage::installation_metadata::StoredInstallationMetadata;
+use xmtp_mls::{InboxHistoryEntry, RevocationImpact};
 
 #[napi(object)]
 pub struct NapiInstallation {
   pub id: String,
   pub client_timestamp_ns: Option<BigInt>,
+  pub metadata: Option<NapiInstallationMetadata>,
+}
+
+/// The device details an installation published, as reported by the installation itself
+#[napi(object)]
+pub struct NapiInstallationMetadata {
+  pub device_name: String,
+  pub platform: String,
+  pub created_at_ns: i64,
+  pub last_seen_ns: i64,
+}
+
+impl From<StoredInstallationMetadata> for NapiInstallationMetadata {
+  fn from(metadata: StoredInstallationMetadata) -> Self {
+    Self {
+      device_name: metadata.device_name,
+      platform: metadata.platform,
+      created_at_ns: metadata.created_at_ns,
+      last_seen_ns: metadata.last_seen_ns,
+    }
+  }
 }
 
 #[napi(object)]
@@ -15,6 +40,8 @@ pub struct NapiInboxState {
   pub recovery_address: String,
   pub installations: Vec<NapiInstallation>,
   pub account_addresses: Vec<String>,
+  /// The hex encoded P-256 public keys of the passkeys of the inbox, compressed SEC1 encoded
+  pub passkeys: Vec<String>,
 }
 
 impl From<AssociationState> for NapiInboxState {
@@ -26,14 +53,167 @@ impl From<AssociationState> for NapiInboxState {
         .members()
         .into_iter()
         .filter_map(|m| match m.identifier {
-          MemberIdentifier::Address(_) => None,
+          MemberIdentifier::Address(_) | MemberIdentifier::Passkey(_) => None,
           MemberIdentifier::Installation(inst) => Some(NapiInstallation {
             id: ed25519_public_key_to_address(inst.as_slice()),
             client_timestamp_ns: m.client_timestamp_ns.map(BigInt::from),
+            metadata: None,
           }),
         })
         .collect(),
       account_addresses: state.account_addresses(),
+      passkeys: state.passkeys().iter().map(hex::encode).collect(),
+    }
+  }
+}
+
+impl NapiInboxState {
+  /// Combine the association state with the details the installations of the inbox shared
+  pub fn with_installation_metadata(
+    state: AssociationState,
+    installation_metadata: HashMap<Vec<u8>, StoredInstallationMetadata>,
+  ) -> Self {
+    let mut inbox_state = NapiInboxState::from(state);
+    let mut metadata: HashMap<String, StoredInstallationMetadata> = installation_metadata
+      .into_values()
+      .map(|metadata| {
+        (
+          ed25519_public_key_to_address(&metadata.installation_id),
+          metadata,
+        )
+      })
+      .collect();
+    for installation in inbox_state.installations.iter_mut() {
+      installation.metadata = metadata.remove(&installation.id).map(Into::into);
+    }
+    inbox_state
+  }
+}
+
+/// Installations are identified the same way as in [NapiInstallation]
+#[napi(object)]
+pub struct NapiRevocationImpact {
+  pub unknown_installation_ids: Vec<String>,
+  pub affected_groups: Vec<NapiAffectedGroup>,
+}
+
+#[napi(object)]
+pub struct NapiAffectedGroup {
+  pub group_id: String,
+  pub removed_installation_ids: Vec<String>,
+  pub remaining_inbox_ids: Vec<String>,
+}
+
+impl From<RevocationImpact> for NapiRevocationImpact {
+  fn from(impact: RevocationImpact) -> Self {
+    Self {
+      unknown_installation_ids: impact
+        .unknown_installation_ids
+        .iter()
+        .map(|id| ed25519_public_key_to_address(id))
+        .collect(),
+      affected_groups: impact
+        .affected_groups
+        .into_iter()
+        .map(|group| NapiAffectedGroup {
+          group_id: hex::encode(group.group_id),
+          removed_installation_ids: group
+            .removed_installation_ids
+            .iter()
+            .map(|id| ed25519_public_key_to_address(id))
+            .collect(),
+          remaining_inbox_ids: group.remaining_inbox_ids,
+        })
+        .collect(),
+    }
+  }
+}
+
+#[napi]
+pub enum NapiInboxActionKind {
+  CreateInbox,
+  AddAssociation,
+  RevokeAssociation,
+  ChangeRecoveryAddress,
+}
+
+#[napi(object)]
+pub struct NapiInboxActionSigner {
+  pub identifier: String,
+  pub signature_kind: String,
+}
+
+#[napi(object)]
+pub struct NapiInboxAction {
+  pub kind: NapiInboxActionKind,
+  /// The address that created the inbox, the member added or revoked, or the new recovery address
+  pub subject: String,
+  pub signers: Vec<NapiInboxActionSigner>,
+}
+
+#[napi(object)]
+pub struct NapiInboxHistoryEntry {
+  pub sequence_id: i64,
+  pub client_timestamp_ns: BigInt,
+  pub server_timestamp_ns: i64,
+  pub actions: Vec<NapiInboxAction>,
+  pub added_members: Vec<String>,
+  pub removed_members: Vec<String>,
+  pub recovery_address: String,
+}
+
+/// Installations are identified the same way as in [NapiInstallation]
+fn member_id(identifier: &MemberIdentifier) -> String {
+  match identifier {
+    MemberIdentifier::Installation(inst) => ed25519_public_key_to_address(inst.as_slice()),
+    _ => identifier.to_string(),
+  }
+}
+
+impl From<Action> for NapiInboxAction {
+  fn from(action: Action) -> Self {
+    let signers = action
+      .verified_signatures()
+      .into_iter()
+      .map(|signature| NapiInboxActionSigner {
+        identifier: member_id(&signature.signer),
+        signature_kind: signature.kind.to_string(),
+      })
+      .collect();
+    let (kind, subject) = match action {
+      Action::CreateInbox(event) => (NapiInboxActionKind::CreateInbox, event.account_address),
+      Action::AddAssociation(event) => (
+        NapiInboxActionKind::AddAssociation,
+        member_id(&event.new_member_identifier),
+      ),
+      Action::RevokeAssociation(event) => (
+        NapiInboxActionKind::RevokeAssociation,
+        member_id(&event.revoked_member),
+      ),
+      Action::ChangeRecoveryAddress(event) => (
+        NapiInboxActionKind::ChangeRecoveryAddress,
+        event.new_recovery_address,
+      ),
+    };
+
+    Self {
+      kind,
+      subject,
+      signers,
+    }
+  }
+}
+
+impl From<InboxHistoryEntry> for NapiInboxHistoryEntry {
+  fn from(entry: InboxHistoryEntry) -> Self {
+    Self {
+      sequence_id: entry.sequence_id,
+      client_timestamp_ns: BigInt::from(entry.update.client_timestamp_ns),
+      server_timestamp_ns: entry.server_timestamp_ns,
+      actions: entry.update.actions.into_iter().map(Into::into).collect(),
+      added_members: entry.diff.new_members.iter().map(member_id).collect(),
+      removed_members: entry.diff.removed_members.iter().map(member_id).collect(),
+      recovery_address: entry.recovery_address,
     }
   }
 }
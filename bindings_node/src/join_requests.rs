@@ -0,0 +1,51 @@
+use napi_derive::napi;
+use xmtp_mls::storage::group_join_request::{JoinRequestState, StoredGroupJoinRequest};
+
+#[napi]
+pub enum NapiJoinRequestState {
+  Pending,
+  Approved,
+  Denied,
+  Failed,
+}
+
+impl From<JoinRequestState> for NapiJoinRequestState {
+  fn from(state: JoinRequestState) -> Self {
+    match state {
+      JoinRequestState::Pending => NapiJoinRequestState::Pending,
+      JoinRequestState::Approved => NapiJoinRequestState::Approved,
+      JoinRequestState::Denied => NapiJoinRequestState::Denied,
+      JoinRequestState::Failed => NapiJoinRequestState::Failed,
+    }
+  }
+}
+
+impl From<NapiJoinRequestState> for JoinRequestState {
+  fn from(state: NapiJoinRequestState) -> Self {
+    match state {
+      NapiJoinRequestState::Pending => JoinRequestState::Pending,
+      NapiJoinRequestState::Approved => JoinRequestState::Approved,
+      NapiJoinRequestState::Denied => JoinRequestState::Denied,
+      NapiJoinRequestState::Failed => JoinRequestState::Failed,
+    }
+  }
+}
+
+#[napi(object)]
+pub struct NapiJoinRequest {
+  pub id: i32,
+  pub requester_inbox_id: String,
+  pub state: NapiJoinRequestState,
+  pub received_at_ns: i64,
+}
+
+impl From<StoredGroupJoinRequest> for NapiJoinRequest {
+  fn from(request: StoredGroupJoinRequest) -> Self {
+    Self {
+      id: request.id,
+      requester_inbox_id: request.requester_inbox_id,
+      state: request.state.into(),
+      received_at_ns: request.received_at_ns,
+    }
+  }
+}
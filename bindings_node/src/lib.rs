@@ -6,9 +6,11 @@ mod conversations;
 mod encoded_content;
 mod groups;
 mod inbox_state;
+mod join_requests;
 mod messages;
 pub mod mls_client;
 mod permissions;
+mod rate_limit;
 mod streams;
 
 use napi::bindgen_prelude::Error;
@@ -1,6 +1,7 @@
 use crate::consent_state::{NapiConsent, NapiConsentEntityType, NapiConsentState};
 use crate::conversations::NapiConversations;
-use crate::inbox_state::NapiInboxState;
+use crate::inbox_state::{NapiInboxHistoryEntry, NapiInboxState, NapiRevocationImpact};
+use crate::rate_limit::{rate_limiter, NapiRateLimit};
 use crate::ErrorWrapper;
 use napi::bindgen_prelude::{Error, Result, Uint8Array};
 use napi_derive::napi;
@@ -30,6 +31,7 @@ pub enum NapiSignatureRequestType {
   CreateInbox,
   RevokeWallet,
   RevokeInstallations,
+  RevokePasskeys,
 }
 
 #[napi]
@@ -48,6 +50,72 @@ pub async fn create_client(
   account_address: String,
   encryption_key: Option<Uint8Array>,
   history_sync_url: Option<String>,
+  rate_limits: Option<Vec<NapiRateLimit>>,
+) -> Result<NapiClient> {
+  let identity_strategy = IdentityStrategy::CreateIfNotFound(
+    inbox_id.clone(),
+    account_address.clone().to_lowercase(),
+    // this is a temporary solution
+    1,
+    None,
+  );
+
+  build_client(
+    host,
+    is_secure,
+    db_path,
+    encryption_key,
+    history_sync_url,
+    identity_strategy,
+    account_address,
+    rate_limits,
+  )
+  .await
+}
+
+/**
+ * Create a client for an inbox that lost all of its installations, using its recovery address.
+ *
+ * The signature request of the client, from `create_inbox_signature_text`, revokes every wallet
+ * and installation of the inbox other than the recovery address and adds the new installation.
+ * Once signed by the recovery address and registered with `register_identity`, group members
+ * add the new installation the next time they sync all of their groups.
+ */
+#[napi]
+pub async fn recover_client(
+  host: String,
+  is_secure: bool,
+  db_path: String,
+  inbox_id: String,
+  recovery_address: String,
+  encryption_key: Option<Uint8Array>,
+  history_sync_url: Option<String>,
+  rate_limits: Option<Vec<NapiRateLimit>>,
+) -> Result<NapiClient> {
+  let identity_strategy = IdentityStrategy::Recover(inbox_id, recovery_address.clone());
+
+  build_client(
+    host,
+    is_secure,
+    db_path,
+    encryption_key,
+    history_sync_url,
+    identity_strategy,
+    recovery_address,
+    rate_limits,
+  )
+  .await
+}
+
+async fn build_client(
+  host: String,
+  is_secure: bool,
+  db_path: String,
+  encryption_key: Option<Uint8Array>,
+  history_sync_url: Option<String>,
+  identity_strategy: IdentityStrategy,
+  account_address: String,
+  rate_limits: Option<Vec<NapiRateLimit>>,
 ) -> Result<NapiClient> {
   let api_client = TonicApiClient::create(host.clone(), is_secure)
     .await
@@ -68,18 +136,11 @@ pub async fn create_client(
       .map_err(|_| Error::from_reason("Error creating unencrypted message store"))?,
   };
 
-  let identity_strategy = IdentityStrategy::CreateIfNotFound(
-    inbox_id.clone(),
-    account_address.clone().to_lowercase(),
-    // this is a temporary solution
-    1,
-    None,
-  );
-
   let xmtp_client = match history_sync_url {
     Some(url) => ClientBuilder::new(identity_strategy)
       .api_client(api_client)
       .store(store)
+      .rate_limiter(rate_limiter(rate_limits))
       .history_sync_url(&url)
       .build()
       .await
@@ -87,6 +148,7 @@ pub async fn create_client(
     None => ClientBuilder::new(identity_strategy)
       .api_client(api_client)
       .store(store)
+      .rate_limiter(rate_limiter(rate_limits))
       .build()
       .await
       .map_err(ErrorWrapper::from)?,
@@ -110,7 +172,7 @@ pub async fn get_inbox_id_for_address(
     TonicApiClient::create(host.clone(), is_secure)
       .await
       .map_err(ErrorWrapper::from)?,
-    Retry::default(),
+    Retry::client_default(),
   );
 
   let results = api_client
@@ -232,12 +294,38 @@ impl NapiClient {
    */
   #[napi]
   pub async fn inbox_state(&self, refresh_from_network: bool) -> Result<NapiInboxState> {
+    let installation_metadata = self
+      .inner_client
+      .installation_metadata(refresh_from_network)
+      .await
+      .map_err(ErrorWrapper::from)?;
     let state = self
       .inner_client
-      .inbox_state(refresh_from_network)
+      .inbox_state(false)
       .await
       .map_err(ErrorWrapper::from)?;
-    Ok(state.into())
+    Ok(NapiInboxState::with_installation_metadata(
+      state,
+      installation_metadata,
+    ))
+  }
+
+  /**
+   * Set the device name and platform other installations of the inbox see for this
+   * installation in their inbox state.
+   */
+  #[napi]
+  pub async fn set_installation_metadata(
+    &self,
+    device_name: String,
+    platform: String,
+  ) -> Result<()> {
+    self
+      .inner_client
+      .set_installation_metadata(device_name, platform)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    Ok(())
   }
 
   #[napi]
@@ -255,6 +343,19 @@ impl NapiClient {
     Ok(state.into())
   }
 
+  /**
+   * Get every identity update of an inbox with the changes it made, oldest first.
+   */
+  #[napi]
+  pub async fn inbox_history(&self, inbox_id: String) -> Result<Vec<NapiInboxHistoryEntry>> {
+    let history = self
+      .inner_client
+      .inbox_history(inbox_id)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    Ok(history.into_iter().map(Into::into).collect())
+  }
+
   #[napi]
   pub async fn add_wallet_signature_text(
     &self,
@@ -291,6 +392,29 @@ impl NapiClient {
     Ok(signature_text)
   }
 
+  /**
+   * Signature text for revoking the given passkeys, hex encoded as in the inbox state
+   */
+  #[napi]
+  pub async fn revoke_passkeys_signature_text(&self, passkeys: Vec<String>) -> Result<String> {
+    let passkeys = passkeys
+      .into_iter()
+      .map(hex::decode)
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .map_err(ErrorWrapper::from)?;
+    let signature_request = self
+      .inner_client
+      .revoke_passkeys(passkeys)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    let signature_text = signature_request.signature_text();
+    let mut signature_requests = self.signature_requests.lock().await;
+
+    signature_requests.insert(NapiSignatureRequestType::RevokePasskeys, signature_request);
+
+    Ok(signature_text)
+  }
+
   #[napi]
   pub async fn revoke_installations_signature_text(&self) -> Result<String> {
     let installation_id = self.inner_client.installation_public_key();
@@ -320,6 +444,90 @@ impl NapiClient {
     Ok(signature_text)
   }
 
+  /**
+   * Signature text for revoking the given installations, identified by the `id` they have in
+   * the inbox state
+   */
+  #[napi]
+  pub async fn revoke_specific_installations_signature_text(
+    &self,
+    installation_ids: Vec<String>,
+  ) -> Result<String> {
+    let (installation_ids, unknown_installation_ids) =
+      self.installation_keys(installation_ids).await?;
+    if let Some(id) = unknown_installation_ids.first() {
+      return Err(Error::from_reason(format!(
+        "Installation {id} is not part of the inbox"
+      )));
+    }
+    let signature_request = self
+      .inner_client
+      .revoke_installations(installation_ids)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    let signature_text = signature_request.signature_text();
+    let mut signature_requests = self.signature_requests.lock().await;
+
+    signature_requests.insert(
+      NapiSignatureRequestType::RevokeInstallations,
+      signature_request,
+    );
+
+    Ok(signature_text)
+  }
+
+  /**
+   * Reports which of this installation's groups the given installations will be removed from,
+   * and which inboxes will process the removal, without revoking anything
+   */
+  #[napi]
+  pub async fn revoke_installations_dry_run(
+    &self,
+    installation_ids: Vec<String>,
+  ) -> Result<NapiRevocationImpact> {
+    let (installation_ids, unknown_installation_ids) =
+      self.installation_keys(installation_ids).await?;
+    let impact = self
+      .inner_client
+      .revoke_installations_dry_run(installation_ids)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    let mut impact = NapiRevocationImpact::from(impact);
+    impact
+      .unknown_installation_ids
+      .extend(unknown_installation_ids);
+
+    Ok(impact)
+  }
+
+  /// Installations are identified by their address in the inbox state, look up their keys.
+  /// Ids that are not installations of the inbox are returned separately.
+  async fn installation_keys(
+    &self,
+    installation_ids: Vec<String>,
+  ) -> Result<(Vec<Vec<u8>>, Vec<String>)> {
+    let inbox_state = self
+      .inner_client
+      .inbox_state(true)
+      .await
+      .map_err(ErrorWrapper::from)?;
+    let mut keys: HashMap<String, Vec<u8>> = inbox_state
+      .installation_ids()
+      .into_iter()
+      .map(|key| (ed25519_public_key_to_address(&key), key))
+      .collect();
+    let mut installation_keys = vec![];
+    let mut unknown_installation_ids = vec![];
+    for id in installation_ids {
+      match keys.remove(&id) {
+        Some(key) => installation_keys.push(key),
+        None => unknown_installation_ids.push(id),
+      }
+    }
+
+    Ok((installation_keys, unknown_installation_ids))
+  }
+
   #[napi]
   pub async fn add_signature(
     &self,
@@ -1,20 +1,27 @@
+use std::collections::HashMap;
+
 use napi::bindgen_prelude::{Error, Result};
 use napi_derive::napi;
 use xmtp_mls::groups::{
   group_mutable_metadata::MetadataField,
   group_permissions::{
-    BasePolicies, GroupMutablePermissions, MembershipPolicies, MetadataBasePolicies,
-    MetadataPolicies, PermissionsBasePolicies, PermissionsPolicies,
+    BasePolicies, GroupMutablePermissions, GroupMutablePermissionsError, JoinRequestPolicy,
+    MembershipPolicies, MetadataBasePolicies, MetadataPolicies, PermissionsBasePolicies,
+    PermissionsPolicies, PolicyError, PolicySet,
   },
   intents::{PermissionPolicyOption, PermissionUpdateType},
   PreconfiguredPolicies,
 };
 
+use crate::ErrorWrapper;
+
 #[napi]
 pub enum NapiGroupPermissionsOptions {
   AllMembers,
   AdminOnly,
   CustomPolicy,
+  AnnouncementChannel,
+  Moderated,
 }
 
 #[napi]
@@ -24,6 +31,9 @@ pub enum NapiPermissionUpdateType {
   AddAdmin,
   RemoveAdmin,
   UpdateMetadata,
+  SendMessage,
+  JoinRequest,
+  UpdateBanList,
 }
 
 impl From<&NapiPermissionUpdateType> for PermissionUpdateType {
@@ -34,6 +44,9 @@ impl From<&NapiPermissionUpdateType> for PermissionUpdateType {
       NapiPermissionUpdateType::AddAdmin => PermissionUpdateType::AddAdmin,
       NapiPermissionUpdateType::RemoveAdmin => PermissionUpdateType::RemoveAdmin,
       NapiPermissionUpdateType::UpdateMetadata => PermissionUpdateType::UpdateMetadata,
+      NapiPermissionUpdateType::SendMessage => PermissionUpdateType::SendMessage,
+      NapiPermissionUpdateType::JoinRequest => PermissionUpdateType::JoinRequest,
+      NapiPermissionUpdateType::UpdateBanList => PermissionUpdateType::UpdateBanList,
     }
   }
 }
@@ -62,6 +75,47 @@ impl TryInto<PermissionPolicyOption> for NapiPermissionPolicy {
   }
 }
 
+impl TryInto<MembershipPolicies> for NapiPermissionPolicy {
+  type Error = Error;
+
+  fn try_into(self) -> Result<MembershipPolicies> {
+    match self {
+      NapiPermissionPolicy::Allow => Ok(MembershipPolicies::allow()),
+      NapiPermissionPolicy::Deny => Ok(MembershipPolicies::deny()),
+      NapiPermissionPolicy::Admin => Ok(MembershipPolicies::allow_if_actor_admin()),
+      NapiPermissionPolicy::SuperAdmin => Ok(MembershipPolicies::allow_if_actor_super_admin()),
+      _ => Err(Error::from_reason("InvalidPermissionPolicyOption")),
+    }
+  }
+}
+
+impl TryInto<MetadataPolicies> for NapiPermissionPolicy {
+  type Error = Error;
+
+  fn try_into(self) -> Result<MetadataPolicies> {
+    match self {
+      NapiPermissionPolicy::Allow => Ok(MetadataPolicies::allow()),
+      NapiPermissionPolicy::Deny => Ok(MetadataPolicies::deny()),
+      NapiPermissionPolicy::Admin => Ok(MetadataPolicies::allow_if_actor_admin()),
+      NapiPermissionPolicy::SuperAdmin => Ok(MetadataPolicies::allow_if_actor_super_admin()),
+      _ => Err(Error::from_reason("InvalidPermissionPolicyOption")),
+    }
+  }
+}
+
+impl TryInto<PermissionsPolicies> for NapiPermissionPolicy {
+  type Error = Error;
+
+  fn try_into(self) -> Result<PermissionsPolicies> {
+    match self {
+      NapiPermissionPolicy::Deny => Ok(PermissionsPolicies::deny()),
+      NapiPermissionPolicy::Admin => Ok(PermissionsPolicies::allow_if_actor_admin()),
+      NapiPermissionPolicy::SuperAdmin => Ok(PermissionsPolicies::allow_if_actor_super_admin()),
+      _ => Err(Error::from_reason("InvalidPermissionPolicyOption")),
+    }
+  }
+}
+
 impl From<&MembershipPolicies> for NapiPermissionPolicy {
   fn from(policies: &MembershipPolicies) -> Self {
     if let MembershipPolicies::Standard(base_policy) = policies {
@@ -108,6 +162,7 @@ impl From<&PermissionsPolicies> for NapiPermissionPolicy {
 }
 
 #[napi(object)]
+#[derive(Clone)]
 pub struct NapiPermissionPolicySet {
   pub add_member_policy: NapiPermissionPolicy,
   pub remove_member_policy: NapiPermissionPolicy,
@@ -117,6 +172,9 @@ pub struct NapiPermissionPolicySet {
   pub update_group_description_policy: NapiPermissionPolicy,
   pub update_group_image_url_square_policy: NapiPermissionPolicy,
   pub update_group_pinned_frame_url_policy: NapiPermissionPolicy,
+  pub send_message_policy: NapiPermissionPolicy,
+  pub join_request_policy: NapiPermissionPolicy,
+  pub update_ban_list_policy: NapiPermissionPolicy,
 }
 
 impl From<PreconfiguredPolicies> for NapiGroupPermissionsOptions {
@@ -124,10 +182,69 @@ impl From<PreconfiguredPolicies> for NapiGroupPermissionsOptions {
     match policy {
       PreconfiguredPolicies::AllMembers => NapiGroupPermissionsOptions::AllMembers,
       PreconfiguredPolicies::AdminsOnly => NapiGroupPermissionsOptions::AdminOnly,
+      PreconfiguredPolicies::AnnouncementChannel => {
+        NapiGroupPermissionsOptions::AnnouncementChannel
+      }
+      PreconfiguredPolicies::Moderated => NapiGroupPermissionsOptions::Moderated,
     }
   }
 }
 
+impl TryFrom<NapiPermissionPolicySet> for PolicySet {
+  type Error = Error;
+
+  fn try_from(policy_set: NapiPermissionPolicySet) -> Result<Self> {
+    let mut metadata_permissions_map: HashMap<String, MetadataPolicies> = HashMap::new();
+    metadata_permissions_map.insert(
+      MetadataField::GroupName.to_string(),
+      policy_set.update_group_name_policy.try_into()?,
+    );
+    metadata_permissions_map.insert(
+      MetadataField::Description.to_string(),
+      policy_set.update_group_description_policy.try_into()?,
+    );
+    metadata_permissions_map.insert(
+      MetadataField::GroupImageUrlSquare.to_string(),
+      policy_set.update_group_image_url_square_policy.try_into()?,
+    );
+    metadata_permissions_map.insert(
+      MetadataField::GroupPinnedFrameUrl.to_string(),
+      policy_set.update_group_pinned_frame_url_policy.try_into()?,
+    );
+
+    // Every member may send when no send message policy is set
+    let send_message_policy = match policy_set.send_message_policy {
+      NapiPermissionPolicy::Allow => None,
+      policy => Some(policy.try_into()?),
+    };
+
+    let join_request_policy = match policy_set.join_request_policy {
+      NapiPermissionPolicy::Allow => JoinRequestPolicy::AutoApprove,
+      NapiPermissionPolicy::Deny => JoinRequestPolicy::Deny,
+      NapiPermissionPolicy::Admin | NapiPermissionPolicy::SuperAdmin => {
+        JoinRequestPolicy::AdminApproval
+      }
+      _ => {
+        return Err(
+          ErrorWrapper::from(GroupMutablePermissionsError::InvalidPermissionPolicyOption).into(),
+        )
+      }
+    };
+
+    Ok(PolicySet {
+      add_member_policy: policy_set.add_member_policy.try_into()?,
+      remove_member_policy: policy_set.remove_member_policy.try_into()?,
+      add_admin_policy: policy_set.add_admin_policy.try_into()?,
+      remove_admin_policy: policy_set.remove_admin_policy.try_into()?,
+      update_metadata_policy: metadata_permissions_map,
+      update_permissions_policy: PermissionsPolicies::allow_if_actor_super_admin(),
+      send_message_policy,
+      join_request_policy,
+      update_ban_list_policy: policy_set.update_ban_list_policy.try_into()?,
+    })
+  }
+}
+
 #[napi]
 pub struct NapiGroupPermissions {
   inner: GroupMutablePermissions,
@@ -141,10 +258,13 @@ impl NapiGroupPermissions {
 
   #[napi]
   pub fn policy_type(&self) -> Result<NapiGroupPermissionsOptions> {
-    if let Ok(preconfigured_policy) = self.inner.preconfigured_policy() {
-      Ok(preconfigured_policy.into())
-    } else {
-      Ok(NapiGroupPermissionsOptions::CustomPolicy)
+    match self.inner.preconfigured_policy() {
+      Ok(preconfigured_policy) => Ok(preconfigured_policy.into()),
+      // Groups created with a custom PolicySet don't match any of the presets
+      Err(GroupMutablePermissionsError::Policy(PolicyError::InvalidPresetPolicy)) => {
+        Ok(NapiGroupPermissionsOptions::CustomPolicy)
+      }
+      Err(e) => Err(ErrorWrapper::from(e).into()),
     }
   }
 
@@ -167,6 +287,17 @@ impl NapiGroupPermissions {
       update_group_description_policy: get_policy(MetadataField::Description.as_str()),
       update_group_image_url_square_policy: get_policy(MetadataField::GroupImageUrlSquare.as_str()),
       update_group_pinned_frame_url_policy: get_policy(MetadataField::GroupPinnedFrameUrl.as_str()),
+      send_message_policy: policy_set
+        .send_message_policy
+        .as_ref()
+        .map(NapiPermissionPolicy::from)
+        .unwrap_or(NapiPermissionPolicy::Allow),
+      join_request_policy: match policy_set.join_request_policy {
+        JoinRequestPolicy::Deny => NapiPermissionPolicy::Deny,
+        JoinRequestPolicy::AdminApproval => NapiPermissionPolicy::Admin,
+        JoinRequestPolicy::AutoApprove => NapiPermissionPolicy::Allow,
+      },
+      update_ban_list_policy: NapiPermissionPolicy::from(&policy_set.update_ban_list_policy),
     })
   }
 }
@@ -0,0 +1,61 @@
+use napi_derive::napi;
+use xmtp_mls::api::{
+  rate_limit::{RateLimit, RateLimiter},
+  Endpoint,
+};
+
+#[napi]
+pub enum NapiEndpoint {
+  UploadKeyPackage,
+  FetchKeyPackages,
+  SendGroupMessages,
+  SendWelcomeMessages,
+  QueryGroupMessages,
+  QueryWelcomeMessages,
+  SubscribeGroupMessages,
+  SubscribeWelcomeMessages,
+  PublishIdentityUpdate,
+  GetIdentityUpdates,
+  GetInboxIds,
+}
+
+impl From<NapiEndpoint> for Endpoint {
+  fn from(endpoint: NapiEndpoint) -> Self {
+    match endpoint {
+      NapiEndpoint::UploadKeyPackage => Endpoint::UploadKeyPackage,
+      NapiEndpoint::FetchKeyPackages => Endpoint::FetchKeyPackages,
+      NapiEndpoint::SendGroupMessages => Endpoint::SendGroupMessages,
+      NapiEndpoint::SendWelcomeMessages => Endpoint::SendWelcomeMessages,
+      NapiEndpoint::QueryGroupMessages => Endpoint::QueryGroupMessages,
+      NapiEndpoint::QueryWelcomeMessages => Endpoint::QueryWelcomeMessages,
+      NapiEndpoint::SubscribeGroupMessages => Endpoint::SubscribeGroupMessages,
+      NapiEndpoint::SubscribeWelcomeMessages => Endpoint::SubscribeWelcomeMessages,
+      NapiEndpoint::PublishIdentityUpdate => Endpoint::PublishIdentityUpdate,
+      NapiEndpoint::GetIdentityUpdates => Endpoint::GetIdentityUpdates,
+      NapiEndpoint::GetInboxIds => Endpoint::GetInboxIds,
+    }
+  }
+}
+
+/// A client side limit on the requests made to one endpoint of the XMTP API
+#[napi(object)]
+pub struct NapiRateLimit {
+  pub endpoint: NapiEndpoint,
+  /// Requests that can be made at once before throttling kicks in
+  pub burst: u32,
+  /// Requests replenished every second
+  pub per_second: f64,
+}
+
+/// The default rate limiter, with the limits that are given replacing those of their endpoint
+pub fn rate_limiter(rate_limits: Option<Vec<NapiRateLimit>>) -> RateLimiter {
+  rate_limits
+    .unwrap_or_default()
+    .into_iter()
+    .fold(RateLimiter::default(), |rate_limiter, limit| {
+      rate_limiter.with_limit(
+        limit.endpoint.into(),
+        RateLimit::new(limit.burst, limit.per_second),
+      )
+    })
+}
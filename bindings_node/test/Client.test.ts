@@ -185,6 +185,47 @@ describe('Client', () => {
     expect(inboxState2.installations[0].id).toBe(client3.installationId())
   })
 
+  it('should revoke specific installations', async () => {
+    const user = createUser()
+    const user2 = createUser()
+    const client = await createRegisteredClient(user)
+    const client2 = await createRegisteredClient(user)
+    const other = await createRegisteredClient(user2)
+    const group = await other.conversations().createGroup([user.account.address])
+
+    const impact = await client.revokeInstallationsDryRun([
+      client2.installationId(),
+      'x:unknown',
+    ])
+    expect(impact.unknownInstallationIds).toEqual(['x:unknown'])
+    expect(impact.affectedGroups.length).toBe(0)
+
+    await client.conversations().sync()
+    const impact2 = await client.revokeInstallationsDryRun([
+      client2.installationId(),
+    ])
+    expect(impact2.affectedGroups.length).toBe(1)
+    expect(impact2.affectedGroups[0].groupId).toBe(group.id())
+    expect(impact2.affectedGroups[0].removedInstallationIds).toEqual([
+      client2.installationId(),
+    ])
+
+    const signatureText = await client.revokeSpecificInstallationsSignatureText(
+      [client2.installationId()]
+    )
+    const signature = await user.wallet.signMessage({
+      message: signatureText,
+    })
+    await client.addSignature(
+      NapiSignatureRequestType.RevokeInstallations,
+      toBytes(signature)
+    )
+    await client.applySignatureRequests()
+    const inboxState = await client.inboxState(true)
+    expect(inboxState.installations.length).toBe(1)
+    expect(inboxState.installations[0].id).toBe(client.installationId())
+  })
+
   it('should manage consent states', async () => {
     const user1 = createUser()
     const user2 = createUser()
@@ -10,6 +10,7 @@ import {
   NapiGroup,
   NapiGroupPermissionsOptions,
   NapiMessage,
+  NapiPermissionPolicy,
 } from '../dist'
 
 describe('Conversations', () => {
@@ -45,6 +46,9 @@ describe('Conversations', () => {
       updateGroupDescriptionPolicy: 0,
       updateGroupImageUrlSquarePolicy: 0,
       updateGroupPinnedFrameUrlPolicy: 0,
+      sendMessagePolicy: 0,
+      joinRequestPolicy: 2,
+      updateBanListPolicy: 2,
     })
     expect(group.addedByInboxId()).toBe(client1.inboxId())
     expect(group.findMessages().length).toBe(1)
@@ -164,6 +168,9 @@ describe('Conversations', () => {
       updateGroupDescriptionPolicy: 2,
       updateGroupImageUrlSquarePolicy: 2,
       updateGroupPinnedFrameUrlPolicy: 2,
+      sendMessagePolicy: 0,
+      joinRequestPolicy: 2,
+      updateBanListPolicy: 2,
     })
 
     const groupWithDescription = await client1
@@ -190,6 +197,51 @@ describe('Conversations', () => {
     )
   })
 
+  it('should create a new group with a custom policy set', async () => {
+    const user1 = createUser()
+    const user2 = createUser()
+    const client1 = await createRegisteredClient(user1)
+    await createRegisteredClient(user2)
+    const policySet = {
+      addMemberPolicy: NapiPermissionPolicy.Allow,
+      removeMemberPolicy: NapiPermissionPolicy.Deny,
+      addAdminPolicy: NapiPermissionPolicy.SuperAdmin,
+      removeAdminPolicy: NapiPermissionPolicy.SuperAdmin,
+      updateGroupNamePolicy: NapiPermissionPolicy.Admin,
+      updateGroupDescriptionPolicy: NapiPermissionPolicy.Allow,
+      updateGroupImageUrlSquarePolicy: NapiPermissionPolicy.Admin,
+      updateGroupPinnedFrameUrlPolicy: NapiPermissionPolicy.Admin,
+      sendMessagePolicy: NapiPermissionPolicy.Allow,
+      joinRequestPolicy: NapiPermissionPolicy.Admin,
+      updateBanListPolicy: NapiPermissionPolicy.Admin,
+    }
+    const group = await client1
+      .conversations()
+      .createGroup([user2.account.address], {
+        permissions: NapiGroupPermissionsOptions.CustomPolicy,
+        customPermissionPolicySet: policySet,
+      })
+    expect(group.groupPermissions().policyType()).toBe(
+      NapiGroupPermissionsOptions.CustomPolicy
+    )
+    expect(group.groupPermissions().policySet()).toEqual(policySet)
+
+    const moderatedGroup = await client1
+      .conversations()
+      .createGroup([user2.account.address], {
+        permissions: NapiGroupPermissionsOptions.Moderated,
+      })
+    expect(moderatedGroup.groupPermissions().policyType()).toBe(
+      NapiGroupPermissionsOptions.Moderated
+    )
+
+    await expect(
+      client1.conversations().createGroup([user2.account.address], {
+        customPermissionPolicySet: policySet,
+      })
+    ).rejects.toThrow()
+  })
+
   it('should update group metadata', async () => {
     const user1 = createUser()
     const user2 = createUser()
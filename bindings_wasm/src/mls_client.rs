@@ -88,7 +88,7 @@ pub async fn get_inbox_id_for_address(
   let account_address = account_address.to_lowercase();
   let api_client = ApiClientWrapper::new(
     XmtpHttpApiClient::new(host.clone()).unwrap(),
-    Retry::default(),
+    Retry::client_default(),
   );
 
   let results = api_client
@@ -6,7 +6,16 @@ if ! cargo install --list | grep "protoc-gen-prost-crate" > /dev/null; then
     fi
 fi
 
-if ! buf generate https://github.com/xmtp/proto.git#branch=main,subdir=proto; then
+# Schema changes in proto_changes/ are already in src/gen but not yet in the proto repo's main
+# branch. Generate from the branch they were landed on, or regenerating drops them.
+PROTO_BRANCH=${PROTO_BRANCH:-main}
+if [ "$PROTO_BRANCH" = "main" ] && [ -n "$(find proto_changes -name '*.proto' 2> /dev/null)" ]; then
+    echo "proto_changes/ has schema changes that are not on the main branch of the proto repo."
+    echo "Set PROTO_BRANCH to the proto repo branch that contains them."
+    exit 1
+fi
+
+if ! buf generate "https://github.com/xmtp/proto.git#branch=${PROTO_BRANCH},subdir=proto"; then
     echo "Failed to generate protobuf definitions"
     exit 1
 fi
@@ -325,11 +325,13 @@ mod tests {
         generate_inbox_id,
         test_utils::{rand_string, rand_u64, MockSmartContractSignatureVerifier},
         unverified::{UnverifiedAction, UnverifiedIdentityUpdate},
+        AccountId,
     };
+    use xmtp_id::scw_verifier::LocalEvmSmartContractWalletVerifier;
     use xmtp_mls::configuration::CIPHERSUITE;
     use xmtp_proto::xmtp::{
         identity::{
-            associations::IdentityUpdate as IdentityUpdateProto,
+            associations::{IdentityUpdate as IdentityUpdateProto, SmartContractWalletSignature},
             MlsCredential as InboxIdMlsCredential,
         },
         mls_validation::v1::validate_key_packages_request::KeyPackage as KeyPackageProtoWrapper,
@@ -502,4 +504,49 @@ mod tests {
         assert_eq!(first_response.credential, None);
         assert_eq!(first_response.installation_public_key, Vec::<u8>::new());
     }
+
+    #[tokio::test]
+    async fn test_verify_smart_contract_wallet_signatures() {
+        let verifier = LocalEvmSmartContractWalletVerifier::new(1);
+        let wallet = LocalWallet::from_bytes(&[1; 32]).unwrap();
+        let account_id: String = AccountId::new_evm(1, format!("{:?}", wallet.address())).into();
+        let hash = [2u8; 32];
+        let signature = wallet.sign_hash(hash.into()).unwrap().to_vec();
+
+        let request = |hash: [u8; 32], block_number: u64| UnverifiedSmartContractWalletSignature {
+            scw_signature: Some(SmartContractWalletSignature {
+                account_id: account_id.clone(),
+                block_number,
+                signature: signature.clone(),
+            }),
+            hash: hash.to_vec(),
+        };
+        let signatures = vec![
+            request(hash, 0),
+            request([3u8; 32], 0),
+            request(hash, 10),
+            UnverifiedSmartContractWalletSignature {
+                scw_signature: None,
+                hash: hash.to_vec(),
+            },
+        ];
+
+        let responses = ValidationService::new(verifier)
+            .verify_smart_contract_wallet_signatures(Request::new(
+                VerifySmartContractWalletSignaturesRequest { signatures },
+            ))
+            .await
+            .unwrap()
+            .into_inner()
+            .responses;
+
+        assert!(responses[0].is_valid);
+        assert_eq!(responses[0].error, None);
+        assert!(!responses[1].is_valid);
+        assert_eq!(responses[1].error, None);
+        // The chain has no block 10
+        assert!(!responses[2].is_valid);
+        assert!(responses[2].error.is_some());
+        assert!(!responses[3].is_valid);
+    }
 }
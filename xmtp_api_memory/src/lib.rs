@@ -0,0 +1,416 @@
+#![warn(clippy::unwrap_used)]
+
+//! An API client backed by an in-process XMTP node.
+//!
+//! Useful for running `xmtp_mls` tests without a local docker node. Clients that should be able
+//! to talk to each other must share the same [`MemoryNode`].
+
+mod node;
+
+use std::sync::Arc;
+
+use futures::stream::BoxStream;
+use xmtp_proto::api_client::{
+    ClientWithMetadata, Error, ErrorKind, XmtpIdentityClient, XmtpMlsClient, XmtpMlsStreams,
+};
+use xmtp_proto::xmtp::identity::api::v1::{
+    get_identity_updates_response, get_inbox_ids_response,
+    GetIdentityUpdatesRequest as GetIdentityUpdatesV2Request,
+    GetIdentityUpdatesResponse as GetIdentityUpdatesV2Response, GetInboxIdsRequest,
+    GetInboxIdsResponse, PublishIdentityUpdateRequest, PublishIdentityUpdateResponse,
+};
+use xmtp_proto::xmtp::mls::api::v1::{
+    fetch_key_packages_response, FetchKeyPackagesRequest, FetchKeyPackagesResponse, GroupMessage,
+    QueryGroupMessagesRequest, QueryGroupMessagesResponse, QueryWelcomeMessagesRequest,
+    QueryWelcomeMessagesResponse, SendGroupMessagesRequest, SendWelcomeMessagesRequest,
+    SubscribeGroupMessagesRequest, SubscribeWelcomeMessagesRequest, UploadKeyPackageRequest,
+    WelcomeMessage,
+};
+
+pub use node::{MemoryNode, DEFAULT_PAGE_SIZE};
+
+#[derive(Clone)]
+pub struct MemoryApiClient {
+    node: Arc<MemoryNode>,
+    app_version: Option<String>,
+    libxmtp_version: Option<String>,
+}
+
+impl MemoryApiClient {
+    pub fn new(node: Arc<MemoryNode>) -> Self {
+        Self {
+            node,
+            app_version: None,
+            libxmtp_version: None,
+        }
+    }
+
+    /// The node this client is connected to
+    pub fn node(&self) -> &Arc<MemoryNode> {
+        &self.node
+    }
+
+    pub fn app_version(&self) -> Option<&str> {
+        self.app_version.as_deref()
+    }
+
+    pub fn libxmtp_version(&self) -> Option<&str> {
+        self.libxmtp_version.as_deref()
+    }
+}
+
+impl ClientWithMetadata for MemoryApiClient {
+    fn set_libxmtp_version(&mut self, version: String) -> Result<(), Error> {
+        self.libxmtp_version = Some(version);
+        Ok(())
+    }
+
+    fn set_app_version(&mut self, version: String) -> Result<(), Error> {
+        self.app_version = Some(version);
+        Ok(())
+    }
+}
+
+impl XmtpMlsClient for MemoryApiClient {
+    async fn upload_key_package(&self, request: UploadKeyPackageRequest) -> Result<(), Error> {
+        tracing::debug!("upload_key_package");
+        let key_package = request
+            .key_package
+            .ok_or_else(|| Error::new(ErrorKind::MlsError).with("missing key package"))?;
+
+        self.node
+            .upload_key_package(key_package.key_package_tls_serialized)
+    }
+
+    async fn fetch_key_packages(
+        &self,
+        request: FetchKeyPackagesRequest,
+    ) -> Result<FetchKeyPackagesResponse, Error> {
+        tracing::debug!("fetch_key_packages");
+        let key_packages = self
+            .node
+            .fetch_key_packages(&request.installation_keys)?
+            .into_iter()
+            .map(
+                |key_package_tls_serialized| fetch_key_packages_response::KeyPackage {
+                    key_package_tls_serialized,
+                },
+            )
+            .collect();
+
+        Ok(FetchKeyPackagesResponse { key_packages })
+    }
+
+    async fn send_group_messages(&self, request: SendGroupMessagesRequest) -> Result<(), Error> {
+        tracing::debug!("send_group_messages");
+        self.node.send_group_messages(request.messages)
+    }
+
+    async fn send_welcome_messages(
+        &self,
+        request: SendWelcomeMessagesRequest,
+    ) -> Result<(), Error> {
+        tracing::debug!("send_welcome_messages");
+        self.node.send_welcome_messages(request.messages)
+    }
+
+    async fn query_group_messages(
+        &self,
+        request: QueryGroupMessagesRequest,
+    ) -> Result<QueryGroupMessagesResponse, Error> {
+        tracing::debug!("query_group_messages");
+        let (messages, paging_info) = self
+            .node
+            .query_group_messages(&request.group_id, request.paging_info);
+
+        Ok(QueryGroupMessagesResponse {
+            messages,
+            paging_info: Some(paging_info),
+        })
+    }
+
+    async fn query_welcome_messages(
+        &self,
+        request: QueryWelcomeMessagesRequest,
+    ) -> Result<QueryWelcomeMessagesResponse, Error> {
+        tracing::debug!("query_welcome_messages");
+        let (messages, paging_info) = self
+            .node
+            .query_welcome_messages(&request.installation_key, request.paging_info);
+
+        Ok(QueryWelcomeMessagesResponse {
+            messages,
+            paging_info: Some(paging_info),
+        })
+    }
+}
+
+impl XmtpMlsStreams for MemoryApiClient {
+    type GroupMessageStream<'a> = BoxStream<'a, Result<GroupMessage, Error>>;
+    type WelcomeMessageStream<'a> = BoxStream<'a, Result<WelcomeMessage, Error>>;
+
+    async fn subscribe_group_messages(
+        &self,
+        request: SubscribeGroupMessagesRequest,
+    ) -> Result<Self::GroupMessageStream<'_>, Error> {
+        tracing::debug!("subscribe_group_messages");
+        let filters = request
+            .filters
+            .into_iter()
+            .map(|filter| (filter.group_id, filter.id_cursor))
+            .collect();
+
+        Ok(self.node.subscribe_group_messages(filters))
+    }
+
+    async fn subscribe_welcome_messages(
+        &self,
+        request: SubscribeWelcomeMessagesRequest,
+    ) -> Result<Self::WelcomeMessageStream<'_>, Error> {
+        tracing::debug!("subscribe_welcome_messages");
+        let filters = request
+            .filters
+            .into_iter()
+            .map(|filter| (filter.installation_key, filter.id_cursor))
+            .collect();
+
+        Ok(self.node.subscribe_welcome_messages(filters))
+    }
+}
+
+impl XmtpIdentityClient for MemoryApiClient {
+    async fn publish_identity_update(
+        &self,
+        request: PublishIdentityUpdateRequest,
+    ) -> Result<PublishIdentityUpdateResponse, Error> {
+        tracing::debug!("publish_identity_update");
+        let identity_update = request
+            .identity_update
+            .ok_or_else(|| Error::new(ErrorKind::IdentityError).with("missing identity update"))?;
+        self.node.publish_identity_update(identity_update).await?;
+
+        Ok(PublishIdentityUpdateResponse {})
+    }
+
+    async fn get_identity_updates_v2(
+        &self,
+        request: GetIdentityUpdatesV2Request,
+    ) -> Result<GetIdentityUpdatesV2Response, Error> {
+        tracing::debug!("get_identity_updates_v2");
+        let responses = request
+            .requests
+            .into_iter()
+            .map(|request| get_identity_updates_response::Response {
+                updates: self
+                    .node
+                    .get_identity_updates(&request.inbox_id, request.sequence_id),
+                inbox_id: request.inbox_id,
+            })
+            .collect();
+
+        Ok(GetIdentityUpdatesV2Response { responses })
+    }
+
+    async fn get_inbox_ids(
+        &self,
+        request: GetInboxIdsRequest,
+    ) -> Result<GetInboxIdsResponse, Error> {
+        tracing::debug!("get_inbox_ids");
+        let responses = request
+            .requests
+            .into_iter()
+            .map(|request| get_inbox_ids_response::Response {
+                inbox_id: self.node.get_inbox_id(&request.address),
+                address: request.address,
+            })
+            .collect();
+
+        Ok(GetInboxIdsResponse { responses })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use xmtp_id::associations::test_utils::MockSmartContractSignatureVerifier;
+    use xmtp_proto::api_client::{ErrorKind, XmtpIdentityClient, XmtpMlsClient, XmtpMlsStreams};
+    use xmtp_proto::xmtp::identity::api::v1::PublishIdentityUpdateRequest;
+    use xmtp_proto::xmtp::identity::associations::IdentityUpdate;
+    use xmtp_proto::xmtp::mls::api::v1::{
+        subscribe_welcome_messages_request, welcome_message, welcome_message_input,
+        KeyPackageUpload, PagingInfo, QueryWelcomeMessagesRequest, SendWelcomeMessagesRequest,
+        SortDirection, SubscribeWelcomeMessagesRequest, UploadKeyPackageRequest, WelcomeMessage,
+        WelcomeMessageInput,
+    };
+
+    use crate::{MemoryApiClient, MemoryNode};
+
+    fn client() -> MemoryApiClient {
+        MemoryApiClient::new(Arc::new(MemoryNode::new(
+            MockSmartContractSignatureVerifier::new(true),
+        )))
+    }
+
+    fn welcome(installation_key: &[u8], data: Vec<u8>) -> WelcomeMessageInput {
+        WelcomeMessageInput {
+            version: Some(welcome_message_input::Version::V1(
+                welcome_message_input::V1 {
+                    installation_key: installation_key.to_vec(),
+                    data,
+                    hpke_public_key: vec![],
+                },
+            )),
+        }
+    }
+
+    fn v1(message: &WelcomeMessage) -> &welcome_message::V1 {
+        match &message.version {
+            Some(welcome_message::Version::V1(v1)) => v1,
+            None => panic!("missing version"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_welcome_messages_pages() {
+        let client = client();
+        let installation_key = vec![1, 2, 3];
+        client
+            .send_welcome_messages(SendWelcomeMessagesRequest {
+                messages: (0..5)
+                    .map(|i| welcome(&installation_key, vec![i]))
+                    .collect(),
+            })
+            .await
+            .unwrap();
+        // a welcome for someone else should never show up
+        client
+            .send_welcome_messages(SendWelcomeMessagesRequest {
+                messages: vec![welcome(&[4, 5, 6], vec![])],
+            })
+            .await
+            .unwrap();
+
+        let first_page = client
+            .query_welcome_messages(QueryWelcomeMessagesRequest {
+                installation_key: installation_key.clone(),
+                paging_info: Some(PagingInfo {
+                    direction: SortDirection::Ascending as i32,
+                    limit: 3,
+                    id_cursor: 0,
+                }),
+            })
+            .await
+            .unwrap();
+        let data: Vec<_> = first_page
+            .messages
+            .iter()
+            .map(|m| v1(m).data.clone())
+            .collect();
+        assert_eq!(data, vec![vec![0], vec![1], vec![2]]);
+        let cursor = first_page.paging_info.unwrap().id_cursor;
+        assert_eq!(cursor, v1(&first_page.messages[2]).id);
+
+        let second_page = client
+            .query_welcome_messages(QueryWelcomeMessagesRequest {
+                installation_key: installation_key.clone(),
+                paging_info: Some(PagingInfo {
+                    direction: SortDirection::Ascending as i32,
+                    limit: 3,
+                    id_cursor: cursor,
+                }),
+            })
+            .await
+            .unwrap();
+        assert_eq!(second_page.messages.len(), 2);
+        assert_eq!(second_page.paging_info.unwrap().id_cursor, 0);
+
+        let newest = client
+            .query_welcome_messages(QueryWelcomeMessagesRequest {
+                installation_key,
+                paging_info: Some(PagingInfo {
+                    direction: SortDirection::Descending as i32,
+                    limit: 1,
+                    id_cursor: 0,
+                }),
+            })
+            .await
+            .unwrap();
+        assert_eq!(v1(&newest.messages[0]).data, vec![4]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_welcome_messages() {
+        let client = client();
+        let installation_key = vec![1, 2, 3];
+        client
+            .send_welcome_messages(SendWelcomeMessagesRequest {
+                messages: vec![
+                    welcome(&installation_key, vec![0]),
+                    welcome(&installation_key, vec![1]),
+                ],
+            })
+            .await
+            .unwrap();
+
+        let mut stream = client
+            .subscribe_welcome_messages(SubscribeWelcomeMessagesRequest {
+                filters: vec![subscribe_welcome_messages_request::Filter {
+                    installation_key: installation_key.clone(),
+                    id_cursor: 1,
+                }],
+            })
+            .await
+            .unwrap();
+
+        client
+            .send_welcome_messages(SendWelcomeMessagesRequest {
+                messages: vec![
+                    welcome(&[4, 5, 6], vec![]),
+                    welcome(&installation_key, vec![2]),
+                ],
+            })
+            .await
+            .unwrap();
+
+        // replays everything after the cursor, then picks up live messages
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(v1(&first).data, vec![1]);
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(v1(&second).data, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_invalid_key_package() {
+        let client = client();
+        let err = client
+            .upload_key_package(UploadKeyPackageRequest {
+                key_package: Some(KeyPackageUpload {
+                    key_package_tls_serialized: vec![1, 2, 3],
+                }),
+                is_inbox_id_credential: false,
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), &ErrorKind::MlsError);
+    }
+
+    #[tokio::test]
+    async fn test_publish_invalid_identity_update() {
+        let client = client();
+        let err = client
+            .publish_identity_update(PublishIdentityUpdateRequest {
+                identity_update: Some(IdentityUpdate {
+                    actions: vec![],
+                    client_timestamp_ns: 0,
+                    inbox_id: "unknown".to_string(),
+                }),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), &ErrorKind::IdentityError);
+    }
+}
@@ -0,0 +1,573 @@
+//! The state of an in-process XMTP node.
+//!
+//! A [`MemoryNode`] mirrors the behaviour of the MLS and identity services closely enough for the
+//! `xmtp_mls` test-suite to run without a network:
+//! - every stored envelope is assigned an id from a single, monotonically increasing sequence, so
+//!   cursors behave the same way as they do against the real node.
+//! - key packages are validated on upload, and a new upload for the same installation replaces the
+//!   previous one. Fetching consumes a key package unless it is a last resort key package.
+//! - identity updates are verified and applied to the inbox's association state before they are
+//!   accepted, and the address → inbox_id mapping follows the resulting state.
+
+use std::collections::HashMap;
+
+use futures::{stream::BoxStream, StreamExt};
+use openmls::prelude::{
+    tls_codec::Deserialize, KeyPackageIn, MlsMessageIn, ProtocolMessage, ProtocolVersion,
+};
+use openmls_rust_crypto::RustCrypto;
+use parking_lot::Mutex;
+use tokio::sync::broadcast::{self, error::RecvError};
+use xmtp_cryptography::signature::normalize_account_address;
+use xmtp_id::{
+    associations::{
+        apply_update, get_state, unverified::UnverifiedIdentityUpdate, AssociationState,
+        MemberIdentifier,
+    },
+    scw_verifier::SmartContractSignatureVerifier,
+    utils::now_ns,
+    InboxId,
+};
+use xmtp_proto::{
+    api_client::{Error, ErrorKind},
+    xmtp::{
+        identity::{
+            api::v1::get_identity_updates_response::IdentityUpdateLog,
+            associations::IdentityUpdate as IdentityUpdateProto,
+        },
+        mls::api::v1::{
+            group_message, group_message_input, welcome_message, welcome_message_input,
+            GroupMessage, GroupMessageInput, PagingInfo, SortDirection, WelcomeMessage,
+            WelcomeMessageInput,
+        },
+    },
+};
+
+/// The page size used by the node when a query does not specify a limit
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Capacity of the broadcast channels backing subscriptions.
+/// Subscribers that fall further behind than this receive an error.
+const SUBSCRIPTION_CAPACITY: usize = 1024;
+
+/// An envelope stored on the node, addressed to a topic and ordered by its id
+pub(crate) trait Envelope: Clone + Send + 'static {
+    fn topic(&self) -> &[u8];
+    fn id(&self) -> u64;
+}
+
+impl Envelope for GroupMessage {
+    fn topic(&self) -> &[u8] {
+        match &self.version {
+            Some(group_message::Version::V1(v1)) => &v1.group_id,
+            None => &[],
+        }
+    }
+
+    fn id(&self) -> u64 {
+        match &self.version {
+            Some(group_message::Version::V1(v1)) => v1.id,
+            None => 0,
+        }
+    }
+}
+
+impl Envelope for WelcomeMessage {
+    fn topic(&self) -> &[u8] {
+        match &self.version {
+            Some(welcome_message::Version::V1(v1)) => &v1.installation_key,
+            None => &[],
+        }
+    }
+
+    fn id(&self) -> u64 {
+        match &self.version {
+            Some(welcome_message::Version::V1(v1)) => v1.id,
+            None => 0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct NodeState {
+    /// The last id handed out to an envelope or identity update
+    sequence_id: u64,
+    key_packages: HashMap<Vec<u8>, StoredKeyPackage>,
+    group_messages: HashMap<Vec<u8>, Vec<GroupMessage>>,
+    welcome_messages: HashMap<Vec<u8>, Vec<WelcomeMessage>>,
+    identity_updates: HashMap<InboxId, Vec<IdentityUpdateLog>>,
+    association_states: HashMap<InboxId, AssociationState>,
+    /// Maps a normalized account address to the inbox it is currently associated with. Ethereum
+    /// addresses are lowercase, Solana addresses are case-sensitive and kept as they are
+    address_log: HashMap<String, InboxId>,
+}
+
+/// The key package an installation uploaded last
+struct StoredKeyPackage {
+    key_package: Vec<u8>,
+    /// Last resort key packages can be used more than once and are never consumed
+    last_resort: bool,
+}
+
+impl NodeState {
+    fn next_sequence_id(&mut self) -> u64 {
+        self.sequence_id += 1;
+        self.sequence_id
+    }
+}
+
+/// An in-memory XMTP node shared by any number of [`MemoryApiClient`](crate::MemoryApiClient)s
+pub struct MemoryNode {
+    state: Mutex<NodeState>,
+    group_messages_tx: broadcast::Sender<GroupMessage>,
+    welcome_messages_tx: broadcast::Sender<WelcomeMessage>,
+    scw_verifier: Box<dyn SmartContractSignatureVerifier>,
+}
+
+impl MemoryNode {
+    /// Create an empty node. Smart contract wallet signatures on identity updates are checked
+    /// with `scw_verifier`.
+    pub fn new(scw_verifier: impl SmartContractSignatureVerifier) -> Self {
+        let (group_messages_tx, _) = broadcast::channel(SUBSCRIPTION_CAPACITY);
+        let (welcome_messages_tx, _) = broadcast::channel(SUBSCRIPTION_CAPACITY);
+
+        Self {
+            state: Mutex::new(NodeState::default()),
+            group_messages_tx,
+            welcome_messages_tx,
+            scw_verifier: Box::new(scw_verifier),
+        }
+    }
+
+    pub(crate) fn upload_key_package(&self, key_package: Vec<u8>) -> Result<(), Error> {
+        let (installation_key, last_resort) = validate_key_package(&key_package)?;
+        self.state.lock().key_packages.insert(
+            installation_key,
+            StoredKeyPackage {
+                key_package,
+                last_resort,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Key packages are consumed by fetching them, unless they are last resort key packages.
+    /// Nothing is consumed if one of the installations has no key package.
+    pub(crate) fn fetch_key_packages(
+        &self,
+        installation_keys: &[Vec<u8>],
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let not_found = |key: &[u8]| {
+            Error::new(ErrorKind::MlsError).with(format!(
+                "key package not found for installation {}",
+                hex::encode(key)
+            ))
+        };
+        let mut state = self.state.lock();
+        let key_packages = &mut state.key_packages;
+        let mut times_requested: HashMap<&Vec<u8>, usize> = HashMap::new();
+        for key in installation_keys {
+            let count = times_requested.entry(key).or_default();
+            *count += 1;
+            match key_packages.get(key) {
+                Some(stored) if stored.last_resort || *count == 1 => {}
+                // Asking twice for an installation without a last resort key package fails
+                _ => return Err(not_found(key)),
+            }
+        }
+
+        Ok(installation_keys
+            .iter()
+            .map(|key| {
+                let stored = &key_packages[key];
+                if stored.last_resort {
+                    stored.key_package.clone()
+                } else {
+                    key_packages
+                        .remove(key)
+                        .expect("key package was checked above")
+                        .key_package
+                }
+            })
+            .collect())
+    }
+
+    pub(crate) fn send_group_messages(
+        &self,
+        messages: Vec<GroupMessageInput>,
+    ) -> Result<(), Error> {
+        // Validate the whole batch before storing anything so a bad message rejects the request
+        let validated = messages
+            .into_iter()
+            .map(|message| match message.version {
+                Some(group_message_input::Version::V1(v1)) => {
+                    let group_id = extract_group_id(&v1.data)?;
+                    Ok((group_id, v1))
+                }
+                None => Err(Error::new(ErrorKind::MlsError).with("missing message version")),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut state = self.state.lock();
+        for (group_id, input) in validated {
+            let message = GroupMessage {
+                version: Some(group_message::Version::V1(group_message::V1 {
+                    id: state.next_sequence_id(),
+                    created_ns: now_ns() as u64,
+                    group_id: group_id.clone(),
+                    data: input.data,
+                    sender_hmac: input.sender_hmac,
+                })),
+            };
+            state
+                .group_messages
+                .entry(group_id)
+                .or_default()
+                .push(message.clone());
+            // An error only means there are no subscribers
+            let _ = self.group_messages_tx.send(message);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn send_welcome_messages(
+        &self,
+        messages: Vec<WelcomeMessageInput>,
+    ) -> Result<(), Error> {
+        let inputs = messages
+            .into_iter()
+            .map(|message| match message.version {
+                Some(welcome_message_input::Version::V1(v1)) => Ok(v1),
+                None => Err(Error::new(ErrorKind::MlsError).with("missing message version")),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut state = self.state.lock();
+        for input in inputs {
+            let message = WelcomeMessage {
+                version: Some(welcome_message::Version::V1(welcome_message::V1 {
+                    id: state.next_sequence_id(),
+                    created_ns: now_ns() as u64,
+                    installation_key: input.installation_key.clone(),
+                    data: input.data,
+                    hpke_public_key: input.hpke_public_key,
+                })),
+            };
+            state
+                .welcome_messages
+                .entry(input.installation_key)
+                .or_default()
+                .push(message.clone());
+            let _ = self.welcome_messages_tx.send(message);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn query_group_messages(
+        &self,
+        group_id: &[u8],
+        paging_info: Option<PagingInfo>,
+    ) -> (Vec<GroupMessage>, PagingInfo) {
+        let state = self.state.lock();
+        page(state.group_messages.get(group_id), paging_info)
+    }
+
+    pub(crate) fn query_welcome_messages(
+        &self,
+        installation_key: &[u8],
+        paging_info: Option<PagingInfo>,
+    ) -> (Vec<WelcomeMessage>, PagingInfo) {
+        let state = self.state.lock();
+        page(state.welcome_messages.get(installation_key), paging_info)
+    }
+
+    pub(crate) fn subscribe_group_messages(
+        &self,
+        filters: Vec<(Vec<u8>, u64)>,
+    ) -> BoxStream<'static, Result<GroupMessage, Error>> {
+        let state = self.state.lock();
+        // Subscribe while holding the lock, so nothing is missed between the backlog and the live feed
+        let receiver = self.group_messages_tx.subscribe();
+        let backlog = backlog(&state.group_messages, &filters);
+        subscription(backlog, receiver, filters)
+    }
+
+    pub(crate) fn subscribe_welcome_messages(
+        &self,
+        filters: Vec<(Vec<u8>, u64)>,
+    ) -> BoxStream<'static, Result<WelcomeMessage, Error>> {
+        let state = self.state.lock();
+        let receiver = self.welcome_messages_tx.subscribe();
+        let backlog = backlog(&state.welcome_messages, &filters);
+        subscription(backlog, receiver, filters)
+    }
+
+    pub(crate) async fn publish_identity_update(
+        &self,
+        update: IdentityUpdateProto,
+    ) -> Result<(), Error> {
+        let unverified = UnverifiedIdentityUpdate::try_from(update.clone())
+            .map_err(|e| Error::new(ErrorKind::IdentityError).with(e))?;
+        let verified = unverified
+            .to_verified(self.scw_verifier.as_ref())
+            .await
+            .map_err(|e| Error::new(ErrorKind::IdentityError).with(e))?;
+        let inbox_id = verified.inbox_id.clone();
+
+        let mut state = self.state.lock();
+        let new_state = match state.association_states.get(&inbox_id) {
+            Some(existing) => apply_update(existing.clone(), verified),
+            None => get_state(vec![verified]),
+        }
+        .map_err(|e| Error::new(ErrorKind::IdentityError).with(e))?;
+
+        let diff = match state.association_states.get(&inbox_id) {
+            Some(existing) => existing.diff(&new_state),
+            None => new_state.as_diff(),
+        };
+        for member in diff.new_members {
+            if let MemberIdentifier::Address(address) = member {
+                state
+                    .address_log
+                    .insert(normalize_account_address(address), inbox_id.clone());
+            }
+        }
+        for member in diff.removed_members {
+            if let MemberIdentifier::Address(address) = member {
+                let address = normalize_account_address(address);
+                if state.address_log.get(&address) == Some(&inbox_id) {
+                    state.address_log.remove(&address);
+                }
+            }
+        }
+
+        let sequence_id = state.next_sequence_id();
+        state
+            .identity_updates
+            .entry(inbox_id.clone())
+            .or_default()
+            .push(IdentityUpdateLog {
+                sequence_id,
+                server_timestamp_ns: now_ns() as u64,
+                update: Some(update),
+            });
+        state.association_states.insert(inbox_id, new_state);
+
+        Ok(())
+    }
+
+    /// Get all identity updates for `inbox_id` with a sequence id greater than `sequence_id`
+    pub(crate) fn get_identity_updates(
+        &self,
+        inbox_id: &str,
+        sequence_id: u64,
+    ) -> Vec<IdentityUpdateLog> {
+        let state = self.state.lock();
+        state
+            .identity_updates
+            .get(inbox_id)
+            .map(|updates| {
+                updates
+                    .iter()
+                    .filter(|update| update.sequence_id > sequence_id)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_inbox_id(&self, address: &str) -> Option<InboxId> {
+        self.state
+            .lock()
+            .address_log
+            .get(&normalize_account_address(address))
+            .cloned()
+    }
+}
+
+/// Validate a key package, returning the installation key it belongs to and whether it is a last
+/// resort key package
+fn validate_key_package(key_package: &[u8]) -> Result<(Vec<u8>, bool), Error> {
+    let invalid =
+        |e: String| Error::new(ErrorKind::MlsError).with(format!("invalid identity: {e}"));
+    let kp_in =
+        KeyPackageIn::tls_deserialize_exact(key_package).map_err(|e| invalid(e.to_string()))?;
+    let kp = kp_in
+        .validate(&RustCrypto::default(), ProtocolVersion::Mls10)
+        .map_err(|e| invalid(e.to_string()))?;
+
+    Ok((
+        kp.leaf_node().signature_key().as_slice().to_vec(),
+        kp.last_resort(),
+    ))
+}
+
+fn extract_group_id(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let invalid =
+        |e: String| Error::new(ErrorKind::MlsError).with(format!("invalid group message: {e}"));
+    let message =
+        MlsMessageIn::tls_deserialize(&mut &data[..]).map_err(|e| invalid(e.to_string()))?;
+    let protocol_message: ProtocolMessage = message
+        .try_into_protocol_message()
+        .map_err(|e| invalid(e.to_string()))?;
+
+    Ok(protocol_message.group_id().as_slice().to_vec())
+}
+
+/// Select a page of envelopes according to `paging_info`.
+/// The returned [`PagingInfo`] carries the cursor of the last envelope if the page is full,
+/// and a cursor of `0` once there is nothing left to read.
+fn page<E: Envelope>(
+    envelopes: Option<&Vec<E>>,
+    paging_info: Option<PagingInfo>,
+) -> (Vec<E>, PagingInfo) {
+    let paging_info = paging_info.unwrap_or_default();
+    let limit = match paging_info.limit {
+        0 => DEFAULT_PAGE_SIZE,
+        limit => limit,
+    };
+    let descending = paging_info.direction == SortDirection::Descending as i32;
+    let cursor = paging_info.id_cursor;
+    let envelopes = envelopes.map(Vec::as_slice).unwrap_or_default();
+
+    let selected: Vec<E> = if descending {
+        envelopes
+            .iter()
+            .rev()
+            .filter(|e| cursor == 0 || e.id() < cursor)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    } else {
+        envelopes
+            .iter()
+            .filter(|e| e.id() > cursor)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    };
+
+    let next_cursor = match selected.last() {
+        Some(last) if selected.len() == limit as usize => last.id(),
+        _ => 0,
+    };
+
+    (
+        selected,
+        PagingInfo {
+            direction: paging_info.direction,
+            limit,
+            id_cursor: next_cursor,
+        },
+    )
+}
+
+/// Envelopes a new subscription must replay. Filters with a cursor of `0` only receive new
+/// envelopes, any other cursor replays everything stored after it.
+fn backlog<E: Envelope>(stored: &HashMap<Vec<u8>, Vec<E>>, filters: &[(Vec<u8>, u64)]) -> Vec<E> {
+    let mut backlog: Vec<E> = filters
+        .iter()
+        .filter(|(_, cursor)| *cursor != 0)
+        .flat_map(|(topic, cursor)| {
+            stored
+                .get(topic)
+                .into_iter()
+                .flatten()
+                .filter(move |e| e.id() > *cursor)
+                .cloned()
+        })
+        .collect();
+    backlog.sort_by_key(|e| e.id());
+    backlog
+}
+
+fn subscription<E: Envelope>(
+    backlog: Vec<E>,
+    mut receiver: broadcast::Receiver<E>,
+    filters: Vec<(Vec<u8>, u64)>,
+) -> BoxStream<'static, Result<E, Error>> {
+    let mut cursors: HashMap<Vec<u8>, u64> = filters.into_iter().collect();
+
+    async_stream::stream! {
+        for envelope in backlog {
+            cursors.insert(envelope.topic().to_vec(), envelope.id());
+            yield Ok(envelope);
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(envelope) => {
+                    let Some(cursor) = cursors.get_mut(envelope.topic()) else {
+                        continue;
+                    };
+                    // already delivered as part of the backlog
+                    if envelope.id() <= *cursor {
+                        continue;
+                    }
+                    *cursor = envelope.id();
+                    yield Ok(envelope);
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    yield Err(Error::new(ErrorKind::SubscribeError)
+                        .with(format!("subscription lagged, skipped {skipped} messages")));
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use xmtp_id::associations::test_utils::MockSmartContractSignatureVerifier;
+
+    use super::{MemoryNode, StoredKeyPackage};
+
+    fn node_with_key_packages(key_packages: &[(&[u8], bool)]) -> MemoryNode {
+        let node = MemoryNode::new(MockSmartContractSignatureVerifier::new(true));
+        for (installation_key, last_resort) in key_packages {
+            node.state.lock().key_packages.insert(
+                installation_key.to_vec(),
+                StoredKeyPackage {
+                    key_package: installation_key.to_vec(),
+                    last_resort: *last_resort,
+                },
+            );
+        }
+        node
+    }
+
+    #[test]
+    fn test_fetch_consumes_key_packages() {
+        let node = node_with_key_packages(&[(b"one", false), (b"last resort", true)]);
+        let keys = vec![b"one".to_vec(), b"last resort".to_vec()];
+        assert_eq!(node.fetch_key_packages(&keys).unwrap(), keys);
+
+        assert!(node.fetch_key_packages(&[b"one".to_vec()]).is_err());
+        assert_eq!(
+            node.fetch_key_packages(&[b"last resort".to_vec(), b"last resort".to_vec()])
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_failed_fetch_consumes_nothing() {
+        let node = node_with_key_packages(&[(b"one", false), (b"two", false)]);
+        assert!(node
+            .fetch_key_packages(&[b"one".to_vec(), b"missing".to_vec()])
+            .is_err());
+        // The same installation twice, without a last resort key package
+        assert!(node
+            .fetch_key_packages(&[b"two".to_vec(), b"two".to_vec()])
+            .is_err());
+
+        let keys = vec![b"one".to_vec(), b"two".to_vec()];
+        assert_eq!(node.fetch_key_packages(&keys).unwrap(), keys);
+    }
+}
@@ -161,6 +161,61 @@ pub fn sanitize_evm_addresses(
         .collect())
 }
 
+/// Check if a string is a valid Solana address (base58 encoded ed25519 public key).
+pub fn is_valid_solana_address<S: AsRef<str>>(address: S) -> bool {
+    solana_address_to_public_key(address).is_some()
+}
+
+/// Decode a Solana address to its 32 byte ed25519 public key.
+/// Returns `None` if the address is not valid base58 or not a point on the curve.
+pub fn solana_address_to_public_key<S: AsRef<str>>(address: S) -> Option<[u8; 32]> {
+    let mut public_key = [0u8; 32];
+    let len = bs58::decode(address.as_ref()).onto(&mut public_key).ok()?;
+    if len != 32 || !is_valid_ed25519_public_key(public_key) {
+        return None;
+    }
+
+    Some(public_key)
+}
+
+/// Encode an ed25519 public key as a Solana address.
+pub fn solana_address_from_public_key<Bytes: AsRef<[u8]>>(public_key: Bytes) -> String {
+    bs58::encode(public_key.as_ref()).into_string()
+}
+
+/// Normalize an account address for comparison and hashing.
+/// Solana addresses are case sensitive base58 and are returned unchanged, everything else is
+/// lowercased as it always has been, so existing inbox ids keep being derived the same way.
+pub fn normalize_account_address<S: AsRef<str>>(address: S) -> String {
+    let address = address.as_ref();
+    if is_valid_solana_address(address) {
+        address.to_string()
+    } else {
+        address.to_lowercase()
+    }
+}
+
+/// Validate and normalize a list of account addresses, accepting both Ethereum and Solana addresses.
+pub fn sanitize_account_addresses(
+    account_addresses: Vec<String>,
+) -> Result<Vec<String>, AddressValidationError> {
+    let mut invalid = account_addresses
+        .iter()
+        .filter(|a| !is_valid_ethereum_address(a) && !is_valid_solana_address(a))
+        .peekable();
+
+    if invalid.peek().is_some() {
+        return Err(AddressValidationError::InvalidAddresses(
+            invalid.map(ToString::to_string).collect::<Vec<_>>(),
+        ));
+    }
+
+    Ok(account_addresses
+        .iter()
+        .map(normalize_account_address)
+        .collect())
+}
+
 /// Check if an ed25519 public signature key is valid.
 pub fn is_valid_ed25519_public_key<Bytes: AsRef<[u8]>>(public_key: Bytes) -> bool {
     let public_key = public_key.as_ref();
@@ -193,7 +248,10 @@ pub fn is_valid_ed25519_public_key<Bytes: AsRef<[u8]>>(public_key: Bytes) -> boo
 
 #[cfg(test)]
 pub mod tests {
-    use super::is_valid_ethereum_address;
+    use super::{
+        is_valid_ethereum_address, is_valid_solana_address, normalize_account_address,
+        sanitize_account_addresses, solana_address_from_public_key,
+    };
 
     use ethers::{
         core::rand::thread_rng,
@@ -265,6 +323,44 @@ pub mod tests {
         assert!(!is_valid_ethereum_address("123"));
     }
 
+    #[test]
+    fn test_solana_address() {
+        let public_key =
+            hex::decode("5E7F70A437963A8B3D0683F949FA0508970ACB87A28139B8BD67D5B01D3B0214")
+                .unwrap();
+        let address = solana_address_from_public_key(&public_key);
+        assert!(is_valid_solana_address(&address));
+        assert!(!is_valid_solana_address(
+            "0x7e57Aed10441c8879ce08E45805EC01Ee9689c9f"
+        ));
+        assert!(!is_valid_solana_address("123"));
+
+        // Solana addresses keep their case, Ethereum addresses are lowercased
+        let evm_address = "0x7e57Aed10441c8879ce08E45805EC01Ee9689c9f".to_string();
+        let sanitized =
+            sanitize_account_addresses(vec![address.clone(), evm_address.clone()]).unwrap();
+        assert_eq!(sanitized, vec![address, evm_address.to_lowercase()]);
+        assert!(sanitize_account_addresses(vec!["123".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_account_address() {
+        let public_key =
+            hex::decode("5E7F70A437963A8B3D0683F949FA0508970ACB87A28139B8BD67D5B01D3B0214")
+                .unwrap();
+        let solana_address = solana_address_from_public_key(&public_key);
+        assert_eq!(normalize_account_address(&solana_address), solana_address);
+        assert_eq!(
+            normalize_account_address("0x7e57Aed10441c8879ce08E45805EC01Ee9689c9f"),
+            "0x7e57aed10441c8879ce08e45805ec01ee9689c9f"
+        );
+        // Anything that is not a Solana address is lowercased, like before Solana was supported
+        assert_eq!(
+            normalize_account_address("Not An Address"),
+            "not an address"
+        );
+    }
+
     #[test]
     fn test_ed25519_public_key_validation() {
         let public_key =
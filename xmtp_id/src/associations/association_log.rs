@@ -5,6 +5,7 @@ use super::signature::{SignatureError, SignatureKind};
 use super::state::AssociationState;
 use super::verified_signature::VerifiedSignature;
 use thiserror::Error;
+use xmtp_cryptography::signature::normalize_account_address;
 
 #[derive(Debug, Error)]
 pub enum AssociationError {
@@ -75,9 +76,9 @@ impl IdentityAction for CreateInbox {
 
         let account_address = self.account_address.clone();
         let recovered_signer = self.initial_address_signature.signer.clone();
-        if recovered_signer.ne(&MemberIdentifier::Address(
-            account_address.clone().to_lowercase(),
-        )) {
+        if recovered_signer.ne(&MemberIdentifier::Address(normalize_account_address(
+            &account_address,
+        ))) {
             return Err(AssociationError::MissingExistingMember);
         }
 
@@ -224,9 +225,9 @@ impl IdentityAction for RevokeAssociation {
         let state_recovery_address = existing_state.recovery_address();
 
         // Ensure this message is signed by the recovery address
-        if recovery_signer.ne(&MemberIdentifier::Address(
-            state_recovery_address.clone().to_lowercase(),
-        )) {
+        if recovery_signer.ne(&MemberIdentifier::Address(normalize_account_address(
+            state_recovery_address,
+        ))) {
             return Err(AssociationError::MissingExistingMember);
         }
 
@@ -327,6 +328,21 @@ impl IdentityAction for Action {
     }
 }
 
+impl Action {
+    /// The verified signatures that authorized the action
+    pub fn verified_signatures(&self) -> Vec<&VerifiedSignature> {
+        match self {
+            Action::CreateInbox(event) => vec![&event.initial_address_signature],
+            Action::AddAssociation(event) => vec![
+                &event.existing_member_signature,
+                &event.new_member_signature,
+            ],
+            Action::RevokeAssociation(event) => vec![&event.recovery_address_signature],
+            Action::ChangeRecoveryAddress(event) => vec![&event.recovery_address_signature],
+        }
+    }
+}
+
 /// An `IdentityUpdate` contains one or more Actions that can be applied to the AssociationState
 #[derive(Debug, Clone)]
 pub struct IdentityUpdate {
@@ -412,12 +428,24 @@ fn allowed_signature_for_kind(
             SignatureKind::Erc1271 => true,
             SignatureKind::InstallationKey => false,
             SignatureKind::LegacyDelegated => true,
+            SignatureKind::WebAuthn => false,
+            SignatureKind::SolanaEd25519 => true,
         },
         MemberKind::Installation => match signature_kind {
             SignatureKind::Erc191 => false,
             SignatureKind::Erc1271 => false,
             SignatureKind::InstallationKey => true,
             SignatureKind::LegacyDelegated => false,
+            SignatureKind::WebAuthn => false,
+            SignatureKind::SolanaEd25519 => false,
+        },
+        MemberKind::Passkey => match signature_kind {
+            SignatureKind::Erc191 => false,
+            SignatureKind::Erc1271 => false,
+            SignatureKind::InstallationKey => false,
+            SignatureKind::LegacyDelegated => false,
+            SignatureKind::WebAuthn => true,
+            SignatureKind::SolanaEd25519 => false,
         },
     };
 
@@ -8,6 +8,7 @@ use crate::{scw_verifier::SmartContractSignatureVerifier, utils::now_ns};
 use thiserror::Error;
 
 use super::{
+    hashes::sha256_string,
     unsigned_actions::{
         SignatureTextCreator, UnsignedAction, UnsignedAddAssociation,
         UnsignedChangeRecoveryAddress, UnsignedCreateInbox, UnsignedIdentityUpdate,
@@ -24,7 +25,7 @@ use super::{
 /// The SignatureField is used to map the signatures from a [SignatureRequest] back to the correct
 /// field in an [IdentityUpdate]. It is used in the `pending_signatures` map in a [PendingIdentityAction]
 #[derive(Clone, PartialEq, Hash, Eq, Debug)]
-enum SignatureField {
+pub(crate) enum SignatureField {
     InitialAddress,
     ExistingMember,
     NewMember,
@@ -33,8 +34,8 @@ enum SignatureField {
 
 #[derive(Clone, Debug)]
 pub struct PendingIdentityAction {
-    unsigned_action: UnsignedAction,
-    pending_signatures: HashMap<SignatureField, MemberIdentifier>,
+    pub(crate) unsigned_action: UnsignedAction,
+    pub(crate) pending_signatures: HashMap<SignatureField, MemberIdentifier>,
 }
 
 /// The SignatureRequestBuilder is used to collect all of the actions in
@@ -171,11 +172,11 @@ pub enum SignatureRequestError {
 /// to the network
 #[derive(Clone, Debug)]
 pub struct SignatureRequest {
-    pending_actions: Vec<PendingIdentityAction>,
-    signature_text: String,
-    signatures: HashMap<MemberIdentifier, UnverifiedSignature>,
-    client_timestamp_ns: u64,
-    inbox_id: String,
+    pub(crate) pending_actions: Vec<PendingIdentityAction>,
+    pub(crate) signature_text: String,
+    pub(crate) signatures: HashMap<MemberIdentifier, UnverifiedSignature>,
+    pub(crate) client_timestamp_ns: u64,
+    pub(crate) inbox_id: String,
 }
 
 impl SignatureRequest {
@@ -274,6 +275,11 @@ impl SignatureRequest {
     pub fn inbox_id(&self) -> String {
         self.inbox_id.clone()
     }
+
+    /// A stable identifier for the request, derived from the text to be signed
+    pub fn id(&self) -> String {
+        sha256_string(self.signature_text.clone())
+    }
 }
 
 fn build_action(
@@ -451,6 +457,39 @@ mod tests {
         assert_eq!(state.members().len(), 2);
     }
 
+    #[tokio::test]
+    async fn resume_serialized_request() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let installation_key = Ed25519SigningKey::generate(&mut rand::thread_rng());
+        let account_address = wallet.get_address();
+        let nonce = 0;
+        let inbox_id = generate_inbox_id(&account_address, &nonce);
+        let existing_member_identifier: MemberIdentifier = account_address.into();
+        let new_member_identifier: MemberIdentifier =
+            installation_key.verifying_key().as_bytes().to_vec().into();
+
+        let mut signature_request = SignatureRequestBuilder::new(inbox_id)
+            .create_inbox(existing_member_identifier.clone(), nonce)
+            .add_association(new_member_identifier, existing_member_identifier)
+            .build();
+        add_installation_key_signature(&mut signature_request, &installation_key).await;
+
+        let bytes: Vec<u8> = signature_request.clone().into();
+        let mut resumed = SignatureRequest::try_from(bytes.as_slice()).expect("should decode");
+        assert_eq!(resumed.id(), signature_request.id());
+        assert_eq!(resumed.signature_text(), signature_request.signature_text());
+        assert_eq!(
+            resumed.missing_signatures(),
+            signature_request.missing_signatures()
+        );
+
+        add_wallet_signature(&mut resumed, &wallet).await;
+        let identity_update = resumed.build_identity_update().expect("should be valid");
+        let state =
+            get_state(vec![convert_to_verified(&identity_update).await]).expect("should be valid");
+        assert_eq!(state.members().len(), 2);
+    }
+
     #[tokio::test]
     async fn create_and_revoke() {
         let wallet = LocalWallet::new(&mut rand::thread_rng());
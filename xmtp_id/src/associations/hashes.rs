@@ -1,12 +1,18 @@
 use sha2::{Digest, Sha256};
+use xmtp_cryptography::signature::normalize_account_address;
 
-fn sha256_string(input: String) -> String {
+pub(crate) fn sha256_string(input: String) -> String {
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
     let result = hasher.finalize();
     format!("{:x}", result)
 }
 
+/// Ethereum addresses are lowercased before hashing, addresses on other chains are case sensitive
 pub fn generate_inbox_id(account_address: &str, nonce: &u64) -> String {
-    sha256_string(format!("{}{}", account_address.to_lowercase(), nonce))
+    sha256_string(format!(
+        "{}{}",
+        normalize_account_address(account_address),
+        nonce
+    ))
 }
@@ -1,7 +1,13 @@
+use p256::ecdsa::VerifyingKey as P256VerifyingKey;
+use xmtp_cryptography::signature::normalize_account_address;
+
+use super::SignatureError;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum MemberKind {
     Installation,
     Address,
+    Passkey,
 }
 
 impl std::fmt::Display for MemberKind {
@@ -9,15 +15,18 @@ impl std::fmt::Display for MemberKind {
         match self {
             MemberKind::Installation => write!(f, "installation"),
             MemberKind::Address => write!(f, "address"),
+            MemberKind::Passkey => write!(f, "passkey"),
         }
     }
 }
 
-/// A MemberIdentifier can be either an Address or an Installation Public Key
+/// A MemberIdentifier can be an Address, an Installation Public Key or a Passkey's P-256 Public Key.
+/// Addresses are either lowercased Ethereum addresses or base58 Solana addresses.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum MemberIdentifier {
     Address(String),
     Installation(Vec<u8>),
+    Passkey(Vec<u8>),
 }
 
 impl MemberIdentifier {
@@ -25,6 +34,7 @@ impl MemberIdentifier {
         match self {
             MemberIdentifier::Address(_) => MemberKind::Address,
             MemberIdentifier::Installation(_) => MemberKind::Installation,
+            MemberIdentifier::Passkey(_) => MemberKind::Passkey,
         }
     }
 
@@ -47,6 +57,35 @@ impl MemberIdentifier {
             None
         }
     }
+
+    /// Get the value for [`MemberIdentifier::Passkey`] variant.
+    /// Returns `None` if the type is not the correct variant.
+    pub fn passkey(&self) -> Option<&[u8]> {
+        if let Self::Passkey(ref public_key) = self {
+            Some(public_key)
+        } else {
+            None
+        }
+    }
+
+    /// Passkeys are identified by the compressed SEC1 encoding of their P-256 public key, so the
+    /// same key is the same member whichever encoding it was provided in.
+    pub fn new_passkey(public_key: &[u8]) -> Result<Self, SignatureError> {
+        let verifying_key = P256VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|_| SignatureError::InvalidWebAuthn("invalid public key".to_string()))?;
+
+        Ok(Self::Passkey(
+            verifying_key.to_encoded_point(true).as_bytes().to_vec(),
+        ))
+    }
+
+    /// Re-encode passkeys with [`Self::new_passkey`], other members are returned as is.
+    pub fn canonicalize(self) -> Result<Self, SignatureError> {
+        match self {
+            Self::Passkey(public_key) => Self::new_passkey(&public_key),
+            other => Ok(other),
+        }
+    }
 }
 
 impl std::fmt::Display for MemberIdentifier {
@@ -54,6 +93,7 @@ impl std::fmt::Display for MemberIdentifier {
         let as_string = match self {
             MemberIdentifier::Address(address) => address.to_string(),
             MemberIdentifier::Installation(installation) => hex::encode(installation),
+            MemberIdentifier::Passkey(public_key) => hex::encode(public_key),
         };
 
         write!(f, "{}", as_string)
@@ -62,7 +102,7 @@ impl std::fmt::Display for MemberIdentifier {
 
 impl From<String> for MemberIdentifier {
     fn from(address: String) -> Self {
-        MemberIdentifier::Address(address.to_lowercase())
+        MemberIdentifier::Address(normalize_account_address(address))
     }
 }
 
@@ -146,5 +186,22 @@ mod tests {
         assert!(installation_1 != installation_2);
         assert!(installation_1.ne(&installation_2));
         assert!(installation_1 == installation_1_copy);
+
+        // A passkey and an installation with the same key bytes are different members
+        let passkey_1 = MemberIdentifier::Passkey(vec![1, 2, 3]);
+        assert!(passkey_1 != installation_1);
+        assert_eq!(passkey_1.kind(), MemberKind::Passkey);
+        assert_eq!(passkey_1.passkey(), Some([1, 2, 3].as_slice()));
+
+        // Both encodings of a passkey's public key are the same member
+        let verifying_key =
+            *p256::ecdsa::SigningKey::random(&mut rand::thread_rng()).verifying_key();
+        let compressed =
+            MemberIdentifier::new_passkey(verifying_key.to_encoded_point(true).as_bytes()).unwrap();
+        let uncompressed =
+            MemberIdentifier::new_passkey(verifying_key.to_encoded_point(false).as_bytes())
+                .unwrap();
+        assert_eq!(compressed, uncompressed);
+        assert!(passkey_1.canonicalize().is_err());
     }
 }
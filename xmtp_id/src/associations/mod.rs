@@ -610,4 +610,149 @@ mod tests {
             Err(AssociationError::MissingExistingMember)
         ));
     }
+
+    #[tokio::test]
+    async fn add_passkey_and_installation_from_passkey() {
+        let initial_state = new_test_inbox().await;
+        let inbox_id = initial_state.inbox_id().clone();
+        let passkey = MemberIdentifier::Passkey(rand_vec());
+
+        let add_passkey = Action::AddAssociation(AddAssociation {
+            new_member_identifier: passkey.clone(),
+            new_member_signature: VerifiedSignature::new(
+                passkey.clone(),
+                SignatureKind::WebAuthn,
+                rand_vec(),
+            ),
+            existing_member_signature: VerifiedSignature::new(
+                initial_state.recovery_address().clone().into(),
+                SignatureKind::Erc191,
+                rand_vec(),
+            ),
+        });
+        let state = apply_update(
+            initial_state,
+            IdentityUpdate::new_test(vec![add_passkey], inbox_id.clone()),
+        )
+        .expect("expected update to succeed");
+        assert_eq!(state.passkeys(), vec![passkey.passkey().unwrap().to_vec()]);
+
+        let add_installation = Action::AddAssociation(AddAssociation {
+            existing_member_signature: VerifiedSignature::new(
+                passkey.clone(),
+                SignatureKind::WebAuthn,
+                rand_vec(),
+            ),
+            ..Default::default()
+        });
+        let state = apply_update(
+            state,
+            IdentityUpdate::new_test(vec![add_installation], inbox_id.clone()),
+        )
+        .expect("expected update to succeed");
+        assert_eq!(state.members_by_parent(&passkey).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reject_passkey_with_wrong_signature_kind() {
+        let initial_state = new_test_inbox().await;
+        let inbox_id = initial_state.inbox_id().clone();
+        let passkey = MemberIdentifier::Passkey(rand_vec());
+
+        let update = Action::AddAssociation(AddAssociation {
+            new_member_identifier: passkey.clone(),
+            new_member_signature: VerifiedSignature::new(
+                passkey.clone(),
+                SignatureKind::InstallationKey,
+                rand_vec(),
+            ),
+            existing_member_signature: VerifiedSignature::new(
+                initial_state.recovery_address().clone().into(),
+                SignatureKind::Erc191,
+                rand_vec(),
+            ),
+        });
+        let update_result = apply_update(
+            initial_state,
+            IdentityUpdate::new_test(vec![update], inbox_id.clone()),
+        );
+        assert!(matches!(
+            update_result,
+            Err(AssociationError::SignatureNotAllowed(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_inbox_from_solana_wallet() {
+        // Base58 Solana addresses are case sensitive and must not be lowercased
+        let account_address = "8ZqQHwTXMxP1kS4YqpChBEEz6xSdBz3Rn9KFpEqk3D1b".to_string();
+        let nonce = 0;
+        let inbox_id = generate_inbox_id(&account_address, &nonce);
+        assert_ne!(
+            inbox_id,
+            generate_inbox_id(&account_address.to_lowercase(), &nonce)
+        );
+
+        let create_request = CreateInbox {
+            nonce,
+            account_address: account_address.clone(),
+            initial_address_signature: VerifiedSignature::new(
+                MemberIdentifier::Address(account_address.clone()),
+                SignatureKind::SolanaEd25519,
+                rand_vec(),
+            ),
+        };
+        let state = get_state(vec![IdentityUpdate::new_test(
+            vec![Action::CreateInbox(create_request)],
+            inbox_id.clone(),
+        )])
+        .expect("expected solana wallet to create an inbox");
+        assert_eq!(state.account_addresses(), vec![account_address.clone()]);
+        assert_eq!(state.recovery_address(), &account_address);
+
+        let solana_member = MemberIdentifier::Address(account_address);
+        let add_installation = Action::AddAssociation(AddAssociation {
+            existing_member_signature: VerifiedSignature::new(
+                solana_member.clone(),
+                SignatureKind::SolanaEd25519,
+                rand_vec(),
+            ),
+            ..Default::default()
+        });
+        let state = apply_update(
+            state,
+            IdentityUpdate::new_test(vec![add_installation], inbox_id),
+        )
+        .expect("expected update to succeed");
+        assert_eq!(state.members_by_parent(&solana_member).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reject_solana_signature_for_installation() {
+        let initial_state = new_test_inbox().await;
+        let inbox_id = initial_state.inbox_id().clone();
+        let installation: MemberIdentifier = rand_vec().into();
+
+        let update = Action::AddAssociation(AddAssociation {
+            new_member_identifier: installation.clone(),
+            new_member_signature: VerifiedSignature::new(
+                installation,
+                SignatureKind::SolanaEd25519,
+                rand_vec(),
+            ),
+            existing_member_signature: VerifiedSignature::new(
+                initial_state.recovery_address().clone().into(),
+                SignatureKind::Erc191,
+                rand_vec(),
+            ),
+        });
+        let update_result = apply_update(
+            initial_state,
+            IdentityUpdate::new_test(vec![update], inbox_id),
+        );
+        assert!(matches!(
+            update_result,
+            Err(AssociationError::SignatureNotAllowed(_, _))
+        ));
+    }
 }
@@ -1,18 +1,21 @@
 use std::collections::{HashMap, HashSet};
 
 use super::{
+    builder::{PendingIdentityAction, SignatureField, SignatureRequest},
     member::Member,
     signature::{AccountId, ValidatedLegacySignedPublicKey},
     state::{AssociationState, AssociationStateDiff},
     unsigned_actions::{
-        UnsignedAddAssociation, UnsignedChangeRecoveryAddress, UnsignedCreateInbox,
+        SignatureTextCreator, UnsignedAction, UnsignedAddAssociation,
+        UnsignedChangeRecoveryAddress, UnsignedCreateInbox, UnsignedIdentityUpdate,
         UnsignedRevokeAssociation,
     },
     unverified::{
         UnverifiedAction, UnverifiedAddAssociation, UnverifiedChangeRecoveryAddress,
         UnverifiedCreateInbox, UnverifiedIdentityUpdate, UnverifiedInstallationKeySignature,
-        UnverifiedLegacyDelegatedSignature, UnverifiedRecoverableEcdsaSignature,
-        UnverifiedRevokeAssociation, UnverifiedSignature, UnverifiedSmartContractWalletSignature,
+        UnverifiedLegacyDelegatedSignature, UnverifiedPasskeySignature,
+        UnverifiedRecoverableEcdsaSignature, UnverifiedRevokeAssociation, UnverifiedSignature,
+        UnverifiedSmartContractWalletSignature, UnverifiedSolanaSignature,
     },
     verified_signature::VerifiedSignature,
     MemberIdentifier, SignatureError,
@@ -28,14 +31,18 @@ use xmtp_proto::xmtp::{
         signature::Signature as SignatureKindProto, AddAssociation as AddAssociationProto,
         AssociationState as AssociationStateProto,
         AssociationStateDiff as AssociationStateDiffProto,
-        ChangeRecoveryAddress as ChangeRecoveryAddressProto, CreateInbox as CreateInboxProto,
+        ChangeRecoveryAddress as ChangeRecoveryAddressProto,
+        CollectedSignature as CollectedSignatureProto, CreateInbox as CreateInboxProto,
         IdentityAction as IdentityActionProto, IdentityUpdate as IdentityUpdateProto,
         LegacyDelegatedSignature as LegacyDelegatedSignatureProto, Member as MemberProto,
         MemberIdentifier as MemberIdentifierProto, MemberMap as MemberMapProto,
+        PendingIdentityAction as PendingIdentityActionProto,
         RecoverableEcdsaSignature as RecoverableEcdsaSignatureProto,
         RecoverableEd25519Signature as RecoverableEd25519SignatureProto,
         RevokeAssociation as RevokeAssociationProto, Signature as SignatureWrapperProto,
+        SignatureRequest as SignatureRequestProto,
         SmartContractWalletSignature as SmartContractWalletSignatureProto,
+        SolanaSignature as SolanaSignatureProto, WebAuthnSignature as WebAuthnSignatureProto,
     },
     message_contents::{
         signature::{Union, WalletEcdsaCompact},
@@ -177,6 +184,17 @@ impl TryFrom<SignatureWrapperProto> for UnverifiedSignature {
                     sig.block_number,
                 ),
             ),
+            SignatureKindProto::Webauthn(sig) => {
+                UnverifiedSignature::Passkey(UnverifiedPasskeySignature::new(
+                    sig.public_key,
+                    sig.signature,
+                    sig.authenticator_data,
+                    sig.client_data_json,
+                ))
+            }
+            SignatureKindProto::Solana(sig) => UnverifiedSignature::Solana(
+                UnverifiedSolanaSignature::new(sig.signature, sig.account_id.try_into()?),
+            ),
         };
 
         Ok(unverified_sig)
@@ -280,6 +298,18 @@ impl From<UnverifiedSignature> for SignatureWrapperProto {
                     bytes: sig.signature_bytes,
                 })
             }
+            UnverifiedSignature::Passkey(sig) => {
+                SignatureKindProto::Webauthn(WebAuthnSignatureProto {
+                    public_key: sig.public_key,
+                    signature: sig.signature_bytes,
+                    authenticator_data: sig.authenticator_data,
+                    client_data_json: sig.client_data_json,
+                })
+            }
+            UnverifiedSignature::Solana(sig) => SignatureKindProto::Solana(SolanaSignatureProto {
+                account_id: sig.account_id.into(),
+                signature: sig.signature_bytes,
+            }),
         };
 
         Self {
@@ -304,11 +334,202 @@ impl From<UnverifiedIdentityUpdate> for Vec<u8> {
     }
 }
 
+/// Unsigned actions are serialized as identity actions with their signatures left unset
+impl From<UnsignedAction> for IdentityActionProto {
+    fn from(value: UnsignedAction) -> Self {
+        let kind = match value {
+            UnsignedAction::CreateInbox(action) => {
+                IdentityActionKindProto::CreateInbox(CreateInboxProto {
+                    nonce: action.nonce,
+                    initial_address: action.account_address,
+                    initial_address_signature: None,
+                })
+            }
+            UnsignedAction::AddAssociation(action) => {
+                IdentityActionKindProto::Add(AddAssociationProto {
+                    new_member_identifier: Some(action.new_member_identifier.into()),
+                    existing_member_signature: None,
+                    new_member_signature: None,
+                })
+            }
+            UnsignedAction::ChangeRecoveryAddress(action) => {
+                IdentityActionKindProto::ChangeRecoveryAddress(ChangeRecoveryAddressProto {
+                    new_recovery_address: action.new_recovery_address,
+                    existing_recovery_address_signature: None,
+                })
+            }
+            UnsignedAction::RevokeAssociation(action) => {
+                IdentityActionKindProto::Revoke(RevokeAssociationProto {
+                    recovery_address_signature: None,
+                    member_to_revoke: Some(action.revoked_member.into()),
+                })
+            }
+        };
+
+        IdentityActionProto { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<IdentityActionKindProto> for UnsignedAction {
+    type Error = DeserializationError;
+
+    fn try_from(action: IdentityActionKindProto) -> Result<Self, Self::Error> {
+        Ok(match action {
+            IdentityActionKindProto::CreateInbox(action_proto) => {
+                UnsignedAction::CreateInbox(UnsignedCreateInbox {
+                    nonce: action_proto.nonce,
+                    account_address: action_proto.initial_address,
+                })
+            }
+            IdentityActionKindProto::Add(action_proto) => {
+                UnsignedAction::AddAssociation(UnsignedAddAssociation {
+                    new_member_identifier: action_proto
+                        .new_member_identifier
+                        .ok_or(DeserializationError::MissingMemberIdentifier)?
+                        .try_into()?,
+                })
+            }
+            IdentityActionKindProto::ChangeRecoveryAddress(action_proto) => {
+                UnsignedAction::ChangeRecoveryAddress(UnsignedChangeRecoveryAddress {
+                    new_recovery_address: action_proto.new_recovery_address,
+                })
+            }
+            IdentityActionKindProto::Revoke(action_proto) => {
+                UnsignedAction::RevokeAssociation(UnsignedRevokeAssociation {
+                    revoked_member: action_proto
+                        .member_to_revoke
+                        .ok_or(DeserializationError::MissingMember)?
+                        .try_into()?,
+                })
+            }
+        })
+    }
+}
+
+impl From<PendingIdentityAction> for PendingIdentityActionProto {
+    fn from(value: PendingIdentityAction) -> Self {
+        let mut signers = value.pending_signatures;
+        let mut take = |field: SignatureField| -> Option<MemberIdentifierProto> {
+            signers.remove(&field).map(Into::into)
+        };
+
+        Self {
+            initial_address: take(SignatureField::InitialAddress),
+            existing_member: take(SignatureField::ExistingMember),
+            new_member: take(SignatureField::NewMember),
+            recovery_address: take(SignatureField::RecoveryAddress),
+            action: Some(value.unsigned_action.into()),
+        }
+    }
+}
+
+impl TryFrom<PendingIdentityActionProto> for PendingIdentityAction {
+    type Error = DeserializationError;
+
+    fn try_from(proto: PendingIdentityActionProto) -> Result<Self, Self::Error> {
+        let unsigned_action: UnsignedAction = proto
+            .action
+            .and_then(|action| action.kind)
+            .ok_or(DeserializationError::MissingAction)?
+            .try_into()?;
+
+        let mut pending_signatures: HashMap<SignatureField, MemberIdentifier> = HashMap::new();
+        for (field, signer) in [
+            (SignatureField::InitialAddress, proto.initial_address),
+            (SignatureField::ExistingMember, proto.existing_member),
+            (SignatureField::NewMember, proto.new_member),
+            (SignatureField::RecoveryAddress, proto.recovery_address),
+        ] {
+            if let Some(signer) = signer {
+                pending_signatures.insert(field, MemberIdentifier::try_from(signer)?);
+            }
+        }
+
+        Ok(PendingIdentityAction {
+            unsigned_action,
+            pending_signatures,
+        })
+    }
+}
+
+impl From<SignatureRequest> for SignatureRequestProto {
+    fn from(value: SignatureRequest) -> Self {
+        let signatures = value
+            .signatures
+            .into_iter()
+            .map(|(signer, signature)| CollectedSignatureProto {
+                signer: Some(signer.into()),
+                signature: Some(signature.into()),
+            })
+            .collect();
+
+        Self {
+            inbox_id: value.inbox_id,
+            client_timestamp_ns: value.client_timestamp_ns,
+            pending_actions: map_vec(value.pending_actions),
+            signatures,
+        }
+    }
+}
+
+/// The signature text is derived from the pending actions again. Collected signatures were
+/// verified when they were added, and are verified again when the identity update is published.
+impl TryFrom<SignatureRequestProto> for SignatureRequest {
+    type Error = DeserializationError;
+
+    fn try_from(proto: SignatureRequestProto) -> Result<Self, Self::Error> {
+        let pending_actions: Vec<PendingIdentityAction> = try_map_vec(proto.pending_actions)?;
+        let unsigned_actions = pending_actions
+            .iter()
+            .map(|pending_action| pending_action.unsigned_action.clone())
+            .collect();
+        let signature_text = UnsignedIdentityUpdate::new(
+            unsigned_actions,
+            proto.inbox_id.clone(),
+            proto.client_timestamp_ns,
+        )
+        .signature_text();
+
+        let mut request = SignatureRequest::new(
+            pending_actions,
+            signature_text,
+            proto.inbox_id,
+            proto.client_timestamp_ns,
+        );
+        for collected in proto.signatures {
+            let signer: MemberIdentifier = collected
+                .signer
+                .ok_or(DeserializationError::MissingMemberIdentifier)?
+                .try_into()?;
+            let signature = UnverifiedSignature::try_from(collected.signature)?;
+            request.signatures.insert(signer, signature);
+        }
+
+        Ok(request)
+    }
+}
+
+impl TryFrom<&[u8]> for SignatureRequest {
+    type Error = DeserializationError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        SignatureRequestProto::decode(value)?.try_into()
+    }
+}
+
+impl From<SignatureRequest> for Vec<u8> {
+    fn from(value: SignatureRequest) -> Self {
+        let proto: SignatureRequestProto = value.into();
+        proto.encode_to_vec()
+    }
+}
+
 impl From<MemberIdentifierKindProto> for MemberIdentifier {
     fn from(proto: MemberIdentifierKindProto) -> Self {
         match proto {
             MemberIdentifierKindProto::Address(address) => address.into(),
             MemberIdentifierKindProto::InstallationPublicKey(public_key) => public_key.into(),
+            MemberIdentifierKindProto::Passkey(public_key) => MemberIdentifier::Passkey(public_key),
         }
     }
 }
@@ -347,6 +568,9 @@ impl From<MemberIdentifier> for MemberIdentifierProto {
             MemberIdentifier::Installation(public_key) => MemberIdentifierProto {
                 kind: Some(MemberIdentifierKindProto::InstallationPublicKey(public_key)),
             },
+            MemberIdentifier::Passkey(public_key) => MemberIdentifierProto {
+                kind: Some(MemberIdentifierKindProto::Passkey(public_key)),
+            },
         }
     }
 }
@@ -362,6 +586,9 @@ impl TryFrom<MemberIdentifierProto> for MemberIdentifier {
             Some(MemberIdentifierKindProto::InstallationPublicKey(public_key)) => {
                 Ok(MemberIdentifier::Installation(public_key))
             }
+            Some(MemberIdentifierKindProto::Passkey(public_key)) => {
+                Ok(MemberIdentifier::Passkey(public_key))
+            }
             None => Err(DeserializationError::MissingMemberIdentifier),
         }
     }
@@ -588,6 +815,36 @@ mod tests {
                         new_member_identifier: rand_string().into(),
                     },
                 }),
+                UnverifiedAction::AddAssociation(UnverifiedAddAssociation {
+                    new_member_signature: UnverifiedSignature::new_passkey(
+                        vec![1, 2],
+                        vec![3, 4],
+                        vec![5, 6],
+                        vec![7, 8],
+                    ),
+                    existing_member_signature: UnverifiedSignature::new_recoverable_ecdsa(vec![
+                        4, 5, 6,
+                    ]),
+                    unsigned_action: UnsignedAddAssociation {
+                        new_member_identifier: MemberIdentifier::Passkey(vec![1, 2]),
+                    },
+                }),
+                UnverifiedAction::AddAssociation(UnverifiedAddAssociation {
+                    new_member_signature: UnverifiedSignature::new_solana(
+                        vec![1, 2, 3],
+                        AccountId::new_solana(
+                            "8ZqQHwTXMxP1kS4YqpChBEEz6xSdBz3Rn9KFpEqk3D1b".into(),
+                        ),
+                    ),
+                    existing_member_signature: UnverifiedSignature::new_recoverable_ecdsa(vec![
+                        4, 5, 6,
+                    ]),
+                    unsigned_action: UnsignedAddAssociation {
+                        new_member_identifier: MemberIdentifier::Address(
+                            "8ZqQHwTXMxP1kS4YqpChBEEz6xSdBz3Rn9KFpEqk3D1b".to_string(),
+                        ),
+                    },
+                }),
                 UnverifiedAction::ChangeRecoveryAddress(UnverifiedChangeRecoveryAddress {
                     recovery_address_signature: UnverifiedSignature::new_recoverable_ecdsa(vec![
                         7, 8, 9,
@@ -613,7 +870,7 @@ mod tests {
             serialized_update.client_timestamp_ns,
             identity_update.client_timestamp_ns
         );
-        assert_eq!(serialized_update.actions.len(), 4);
+        assert_eq!(serialized_update.actions.len(), 6);
 
         let deserialized_update: UnverifiedIdentityUpdate = serialized_update
             .clone()
@@ -641,6 +898,19 @@ mod tests {
         let proto: String = account_id.into();
         assert_eq!(text, proto);
 
+        // valid Solana mainnet
+        let text =
+            "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:8ZqQHwTXMxP1kS4YqpChBEEz6xSdBz3Rn9KFpEqk3D1b";
+        let account_id: AccountId = text.try_into().unwrap();
+        assert_eq!(
+            account_id,
+            AccountId::new_solana("8ZqQHwTXMxP1kS4YqpChBEEz6xSdBz3Rn9KFpEqk3D1b".to_string())
+        );
+        assert!(account_id.is_solana_chain());
+        assert!(!account_id.is_evm_chain());
+        let proto: String = account_id.into();
+        assert_eq!(text, proto);
+
         // valid Bitcoin mainnet
         let text = "bip122:000000000019d6689c085ae165831e93:128Lkh3S7CkDTBZ8W7BbpsN3YYizJMp8p6";
         let account_id: AccountId = text.try_into().unwrap();
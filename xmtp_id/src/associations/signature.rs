@@ -6,6 +6,8 @@ use xmtp_proto::xmtp::message_contents::{
     signed_private_key, SignedPrivateKey as LegacySignedPrivateKeyProto,
 };
 
+use crate::constants::SOLANA_MAINNET_CHAIN_ID;
+
 use super::{
     unverified::{UnverifiedLegacyDelegatedSignature, UnverifiedRecoverableEcdsaSignature},
     verified_signature::VerifiedSignature,
@@ -40,6 +42,8 @@ pub enum SignatureError {
     UrlParseError(#[from] url::ParseError),
     #[error(transparent)]
     DecodeError(#[from] prost::DecodeError),
+    #[error("Invalid WebAuthn assertion: {0}")]
+    InvalidWebAuthn(String),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -49,6 +53,8 @@ pub enum SignatureKind {
     Erc1271,
     InstallationKey,
     LegacyDelegated,
+    WebAuthn,
+    SolanaEd25519,
 }
 
 impl std::fmt::Display for SignatureKind {
@@ -58,6 +64,8 @@ impl std::fmt::Display for SignatureKind {
             SignatureKind::Erc1271 => write!(f, "erc-1271"),
             SignatureKind::InstallationKey => write!(f, "installation-key"),
             SignatureKind::LegacyDelegated => write!(f, "legacy-delegated"),
+            SignatureKind::WebAuthn => write!(f, "webauthn"),
+            SignatureKind::SolanaEd25519 => write!(f, "solana-ed25519"),
         }
     }
 }
@@ -80,10 +88,21 @@ impl AccountId {
         Self::new(format!("eip155:{}", chain_id), account_address)
     }
 
+    pub fn new_solana(account_address: String) -> Self {
+        Self::new(SOLANA_MAINNET_CHAIN_ID.to_string(), account_address)
+    }
+
     pub fn is_evm_chain(&self) -> bool {
         self.chain_id.starts_with("eip155")
     }
 
+    /// Solana chains are in the `solana` CAIP-2 namespace, e.g. `solana:<genesis hash>`
+    pub fn is_solana_chain(&self) -> bool {
+        self.chain_id
+            .split_once(':')
+            .is_some_and(|(namespace, reference)| namespace == "solana" && !reference.is_empty())
+    }
+
     pub fn get_account_address(&self) -> &str {
         &self.account_address
     }
@@ -1,10 +1,12 @@
 //! [`AssociationState`] describes a single point in time for an Inbox where it contains a set of
-//! associated [`MemberIdentifier`]'s, which may be one of [`MemberKind::Address`]
-//! or[`MemberKind::Installation`]. A diff between two states can be calculated to determine
+//! associated [`MemberIdentifier`]'s, which may be one of [`MemberKind::Address`],
+//! [`MemberKind::Installation`] or [`MemberKind::Passkey`]. A diff between two states can be calculated to determine
 //! a change of membership between two periods of time. [XIP-46](https://github.com/xmtp/XIPs/pull/53)
 
 use std::collections::{HashMap, HashSet};
 
+use xmtp_cryptography::signature::normalize_account_address;
+
 use super::{hashes::generate_inbox_id, member::Member, MemberIdentifier, MemberKind};
 
 #[derive(Debug, Clone)]
@@ -66,7 +68,7 @@ impl AssociationState {
 
     pub fn set_recovery_address(&self, recovery_address: String) -> Self {
         let mut new_state = self.clone();
-        new_state.recovery_address = recovery_address.to_lowercase();
+        new_state.recovery_address = normalize_account_address(recovery_address);
 
         new_state
     }
@@ -119,7 +121,7 @@ impl AssociationState {
             .into_iter()
             .filter_map(|member| match member.identifier {
                 MemberIdentifier::Address(address) => Some(address),
-                MemberIdentifier::Installation(_) => None,
+                MemberIdentifier::Installation(_) | MemberIdentifier::Passkey(_) => None,
             })
             .collect()
     }
@@ -128,17 +130,24 @@ impl AssociationState {
         self.members_by_kind(MemberKind::Installation)
             .into_iter()
             .filter_map(|member| match member.identifier {
-                MemberIdentifier::Address(_) => None,
+                MemberIdentifier::Address(_) | MemberIdentifier::Passkey(_) => None,
                 MemberIdentifier::Installation(installation_id) => Some(installation_id),
             })
             .collect()
     }
 
+    pub fn passkeys(&self) -> Vec<Vec<u8>> {
+        self.members_by_kind(MemberKind::Passkey)
+            .into_iter()
+            .filter_map(|member| member.identifier.passkey().map(|key| key.to_vec()))
+            .collect()
+    }
+
     pub fn installations(&self) -> Vec<Installation> {
         self.members()
             .into_iter()
             .filter_map(|member| match member.identifier {
-                MemberIdentifier::Address(_) => None,
+                MemberIdentifier::Address(_) | MemberIdentifier::Passkey(_) => None,
                 MemberIdentifier::Installation(id) => Some(Installation {
                     id,
                     client_timestamp_ns: member.client_timestamp_ns,
@@ -186,7 +195,7 @@ impl AssociationState {
         Self {
             members: HashMap::from_iter([(identifier, new_member)]),
             seen_signatures: HashSet::new(),
-            recovery_address: account_address.to_lowercase(),
+            recovery_address: normalize_account_address(account_address),
             inbox_id,
         }
     }
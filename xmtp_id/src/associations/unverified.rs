@@ -134,7 +134,11 @@ impl UnverifiedAction {
                     .new_member_signature
                     .to_verified(signature_text.as_ref(), scw_verifier)
                     .await?,
-                new_member_identifier: action.unsigned_action.new_member_identifier.clone(),
+                new_member_identifier: action
+                    .unsigned_action
+                    .new_member_identifier
+                    .clone()
+                    .canonicalize()?,
                 existing_member_signature: action
                     .existing_member_signature
                     .to_verified(signature_text.as_ref(), scw_verifier)
@@ -146,7 +150,11 @@ impl UnverifiedAction {
                         .recovery_address_signature
                         .to_verified(signature_text.as_ref(), scw_verifier)
                         .await?,
-                    revoked_member: action.unsigned_action.revoked_member.clone(),
+                    revoked_member: action
+                        .unsigned_action
+                        .revoked_member
+                        .clone()
+                        .canonicalize()?,
                 })
             }
             UnverifiedAction::ChangeRecoveryAddress(action) => {
@@ -244,6 +252,8 @@ pub enum UnverifiedSignature {
     RecoverableEcdsa(UnverifiedRecoverableEcdsaSignature),
     SmartContractWallet(UnverifiedSmartContractWalletSignature),
     LegacyDelegated(UnverifiedLegacyDelegatedSignature),
+    Passkey(UnverifiedPasskeySignature),
+    Solana(UnverifiedSolanaSignature),
 }
 
 impl UnverifiedSignature {
@@ -276,6 +286,18 @@ impl UnverifiedSignature {
                 &sig.legacy_key_signature.signature_bytes,
                 sig.signed_public_key_proto.clone(),
             ),
+            UnverifiedSignature::Passkey(sig) => VerifiedSignature::from_passkey(
+                signature_text,
+                &sig.public_key,
+                &sig.signature_bytes,
+                &sig.authenticator_data,
+                &sig.client_data_json,
+            ),
+            UnverifiedSignature::Solana(sig) => VerifiedSignature::from_solana(
+                signature_text,
+                &sig.signature_bytes,
+                sig.account_id.clone(),
+            ),
         }
     }
 
@@ -311,6 +333,24 @@ impl UnverifiedSignature {
             signed_public_key_proto,
         ))
     }
+
+    pub fn new_passkey(
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self::Passkey(UnverifiedPasskeySignature::new(
+            public_key,
+            signature,
+            authenticator_data,
+            client_data_json,
+        ))
+    }
+
+    pub fn new_solana(signature: Vec<u8>, account_id: AccountId) -> Self {
+        Self::Solana(UnverifiedSolanaSignature::new(signature, account_id))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -374,6 +414,45 @@ impl UnverifiedLegacyDelegatedSignature {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnverifiedPasskeySignature {
+    pub(crate) public_key: Vec<u8>,
+    pub(crate) signature_bytes: Vec<u8>,
+    pub(crate) authenticator_data: Vec<u8>,
+    pub(crate) client_data_json: Vec<u8>,
+}
+
+impl UnverifiedPasskeySignature {
+    pub fn new(
+        public_key: Vec<u8>,
+        signature_bytes: Vec<u8>,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            public_key,
+            signature_bytes,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnverifiedSolanaSignature {
+    pub(crate) signature_bytes: Vec<u8>,
+    pub(crate) account_id: AccountId,
+}
+
+impl UnverifiedSolanaSignature {
+    pub fn new(signature_bytes: Vec<u8>, account_id: AccountId) -> Self {
+        Self {
+            signature_bytes,
+            account_id,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::associations::{
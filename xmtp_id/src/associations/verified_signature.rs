@@ -1,14 +1,23 @@
 #![allow(dead_code)]
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey as Ed25519VerifyingKey};
 use ethers::types::Signature as EthersSignature;
 use ethers::utils::hash_message;
 use ethers::{core::k256::ecdsa::VerifyingKey as EcdsaVerifyingKey, utils::public_key_to_address};
-use sha2::{Digest, Sha512};
-use xmtp_cryptography::signature::h160addr_to_string;
+use p256::ecdsa::{
+    signature::Verifier, Signature as P256Signature, VerifyingKey as P256VerifyingKey,
+};
+use sha2::{Digest, Sha256, Sha512};
+use url::Url;
+use xmtp_cryptography::signature::{
+    h160addr_to_string, solana_address_to_public_key, AddressValidationError,
+};
 use xmtp_proto::xmtp::message_contents::SignedPublicKey as LegacySignedPublicKeyProto;
 
-use crate::constants::INSTALLATION_KEY_SIGNATURE_CONTEXT;
+use crate::constants::{
+    INSTALLATION_KEY_SIGNATURE_CONTEXT, WEBAUTHN_ASSERTION_TYPE, WEBAUTHN_USER_PRESENT_FLAG,
+};
 use crate::scw_verifier::SmartContractSignatureVerifier;
 
 use super::{
@@ -95,6 +104,118 @@ impl VerifiedSignature {
         ))
     }
 
+    /**
+     * Verifies a WebAuthn assertion made with a passkey against the provided signature text.
+     * The challenge of the assertion must be the SHA-256 hash of the signature text.
+     * Returns a VerifiedSignature if the signature is valid, otherwise returns an error.
+     */
+    pub fn from_passkey<Text: AsRef<str>>(
+        signature_text: Text,
+        public_key: &[u8],
+        signature_bytes: &[u8],
+        authenticator_data: &[u8],
+        client_data_json: &[u8],
+    ) -> Result<Self, SignatureError> {
+        let client_data: serde_json::Value = serde_json::from_slice(client_data_json)
+            .map_err(|_| SignatureError::InvalidWebAuthn("malformed client data".to_string()))?;
+        if client_data["type"].as_str() != Some(WEBAUTHN_ASSERTION_TYPE) {
+            return Err(SignatureError::InvalidWebAuthn(
+                "client data is not an assertion".to_string(),
+            ));
+        }
+        let expected_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(signature_text.as_ref()));
+        if client_data["challenge"].as_str() != Some(expected_challenge.as_str()) {
+            return Err(SignatureError::InvalidWebAuthn(
+                "challenge does not match the signature text".to_string(),
+            ));
+        }
+
+        // Authenticator data is rpIdHash (32 bytes) || flags (1 byte) || signCount (4 bytes) || ...
+        if authenticator_data.len() < 37 {
+            return Err(SignatureError::InvalidWebAuthn(
+                "authenticator data is too short".to_string(),
+            ));
+        }
+        // The passkey is scoped to a relying party, which must be the origin of the page that
+        // asked for the assertion or a domain that origin belongs to
+        let origin = client_data["origin"]
+            .as_str()
+            .and_then(|origin| Url::parse(origin).ok())
+            .filter(|origin| origin.scheme() == "https")
+            .ok_or_else(|| {
+                SignatureError::InvalidWebAuthn("origin is not an https url".to_string())
+            })?;
+        let origin_domain = origin
+            .domain()
+            .ok_or_else(|| SignatureError::InvalidWebAuthn("origin has no domain".to_string()))?;
+        if !relying_party_ids(origin_domain)
+            .any(|rp_id| authenticator_data[..32] == Sha256::digest(rp_id)[..])
+        {
+            return Err(SignatureError::InvalidWebAuthn(
+                "relying party does not match the origin".to_string(),
+            ));
+        }
+        if authenticator_data[32] & WEBAUTHN_USER_PRESENT_FLAG == 0 {
+            return Err(SignatureError::InvalidWebAuthn(
+                "user presence was not confirmed".to_string(),
+            ));
+        }
+
+        let signer = MemberIdentifier::new_passkey(public_key)?;
+        let verifying_key = P256VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|_| SignatureError::InvalidWebAuthn("invalid public key".to_string()))?;
+        // Authenticators return DER signatures, accept the fixed size encoding as well
+        let signature = P256Signature::from_der(signature_bytes)
+            .or_else(|_| P256Signature::from_slice(signature_bytes))
+            .map_err(|_| SignatureError::InvalidWebAuthn("malformed signature".to_string()))?;
+        let signed_data = [
+            authenticator_data,
+            Sha256::digest(client_data_json).as_slice(),
+        ]
+        .concat();
+        verifying_key
+            .verify(&signed_data, &signature)
+            .map_err(|_| SignatureError::Invalid)?;
+
+        // The same signature has several encodings, only the canonical one is tracked for replays
+        let canonical_signature = signature.normalize_s().unwrap_or(signature);
+
+        Ok(Self::new(
+            signer,
+            SignatureKind::WebAuthn,
+            canonical_signature.to_bytes().to_vec(),
+        ))
+    }
+
+    /**
+     * Verifies an ed25519 signature made by a Solana wallet (off-chain message signing) against the provided signature text.
+     * The wallet signs the UTF-8 bytes of the signature text, and the signer is the base58 encoded public key.
+     * Returns a VerifiedSignature if the signature is valid, otherwise returns an error.
+     */
+    pub fn from_solana<Text: AsRef<str>>(
+        signature_text: Text,
+        signature_bytes: &[u8],
+        account_id: AccountId,
+    ) -> Result<Self, SignatureError> {
+        if !account_id.is_solana_chain() {
+            return Err(SignatureError::Invalid);
+        }
+        let account_address = account_id.get_account_address();
+        let public_key = solana_address_to_public_key(account_address).ok_or(
+            AddressValidationError::InvalidAddresses(vec![account_address.to_string()]),
+        )?;
+        let signature = Ed25519Signature::from_bytes(signature_bytes.try_into()?);
+        let verifying_key = Ed25519VerifyingKey::from_bytes(&public_key)?;
+
+        verifying_key.verify_strict(signature_text.as_ref().as_bytes(), &signature)?;
+
+        Ok(Self::new(
+            MemberIdentifier::Address(account_address.to_string()),
+            SignatureKind::SolanaEd25519,
+            signature_bytes.to_vec(),
+        ))
+    }
+
     pub fn from_legacy_delegated<Text: AsRef<str>>(
         signature_text: Text,
         signature_bytes: &[u8],
@@ -148,6 +269,15 @@ impl VerifiedSignature {
     }
 }
 
+/// The relying party ids a page on `domain` may use: the domain itself and the domains it belongs
+/// to, without the top level domain.
+fn relying_party_ids(domain: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(domain), |domain| {
+        domain.split_once('.').map(|(_, parent)| parent)
+    })
+    .filter(|rp_id| rp_id.contains('.') || *rp_id == "localhost")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,9 +289,11 @@ mod tests {
         constants::INSTALLATION_KEY_SIGNATURE_CONTEXT,
         InboxOwner,
     };
-    use ed25519_dalek::SigningKey as Ed25519SigningKey;
+    use ed25519_dalek::{Signer as _, SigningKey as Ed25519SigningKey};
     use ethers::signers::{LocalWallet, Signer};
+    use p256::ecdsa::{signature::Signer as _, SigningKey as P256SigningKey};
     use prost::Message;
+    use xmtp_cryptography::signature::solana_address_from_public_key;
     use xmtp_proto::xmtp::message_contents::{
         signature::Union as SignatureUnion, signed_private_key,
         SignedPrivateKey as LegacySignedPrivateKeyProto,
@@ -235,6 +367,219 @@ mod tests {
         .expect_err("should fail with incorrect verifying key");
     }
 
+    #[test]
+    fn test_solana() {
+        let signing_key = Ed25519SigningKey::generate(&mut rand::thread_rng());
+        let account_address =
+            solana_address_from_public_key(signing_key.verifying_key().as_bytes());
+        let account_id = AccountId::new_solana(account_address.clone());
+        let signature_text = "test signature text";
+        let sig = signing_key.sign(signature_text.as_bytes());
+
+        let verified_sig = VerifiedSignature::from_solana(
+            signature_text,
+            sig.to_bytes().as_slice(),
+            account_id.clone(),
+        )
+        .expect("should succeed");
+        // Solana addresses are case sensitive and must not be lowercased
+        assert_eq!(
+            verified_sig.signer,
+            MemberIdentifier::Address(account_address.clone())
+        );
+        assert_eq!(verified_sig.kind, SignatureKind::SolanaEd25519);
+        assert_eq!(verified_sig.raw_bytes, sig.to_bytes().as_slice());
+
+        VerifiedSignature::from_solana(
+            "wrong signature text",
+            sig.to_bytes().as_slice(),
+            account_id,
+        )
+        .expect_err("should fail with incorrect signature text");
+
+        let other_address = solana_address_from_public_key(
+            Ed25519SigningKey::generate(&mut rand::thread_rng())
+                .verifying_key()
+                .as_bytes(),
+        );
+        VerifiedSignature::from_solana(
+            signature_text,
+            sig.to_bytes().as_slice(),
+            AccountId::new_solana(other_address),
+        )
+        .expect_err("should fail with a different address");
+
+        VerifiedSignature::from_solana(
+            signature_text,
+            sig.to_bytes().as_slice(),
+            AccountId::new_evm(1, account_address.clone()),
+        )
+        .expect_err("should fail on a non solana chain");
+
+        // Only the exact CAIP-2 namespace is Solana
+        VerifiedSignature::from_solana(
+            signature_text,
+            sig.to_bytes().as_slice(),
+            AccountId::new("solanafork:1".to_string(), account_address),
+        )
+        .expect_err("should fail on a chain that only starts with solana");
+    }
+
+    fn sign_passkey_assertion(
+        signing_key: &P256SigningKey,
+        signature_text: &str,
+        flags: u8,
+    ) -> (Vec<u8>, Vec<u8>, P256Signature) {
+        let mut authenticator_data = Sha256::digest("xmtp.org").to_vec();
+        authenticator_data.push(flags);
+        authenticator_data.extend_from_slice(&[0, 0, 0, 1]);
+        let client_data_json = format!(
+            r#"{{"type":"webauthn.get","challenge":"{}","origin":"https://xmtp.org"}}"#,
+            URL_SAFE_NO_PAD.encode(Sha256::digest(signature_text))
+        )
+        .into_bytes();
+        let signed_data = [
+            authenticator_data.as_slice(),
+            Sha256::digest(&client_data_json).as_slice(),
+        ]
+        .concat();
+        let signature: P256Signature = signing_key.sign(&signed_data);
+
+        (authenticator_data, client_data_json, signature)
+    }
+
+    #[test]
+    fn test_passkey() {
+        let signing_key = P256SigningKey::random(&mut rand::thread_rng());
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let signature_text = "test signature text";
+        let (authenticator_data, client_data_json, signature) =
+            sign_passkey_assertion(&signing_key, signature_text, WEBAUTHN_USER_PRESENT_FLAG);
+
+        let verified_sig = VerifiedSignature::from_passkey(
+            signature_text,
+            &public_key,
+            signature.to_der().as_bytes(),
+            &authenticator_data,
+            &client_data_json,
+        )
+        .expect("should succeed");
+        // The signer is the compressed encoding of the key
+        assert_eq!(
+            verified_sig.signer,
+            MemberIdentifier::Passkey(
+                signing_key
+                    .verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes()
+                    .to_vec()
+            )
+        );
+        assert_eq!(verified_sig.kind, SignatureKind::WebAuthn);
+
+        // Both encodings of a signature are tracked as the same signature
+        let verified_fixed_size = VerifiedSignature::from_passkey(
+            signature_text,
+            &public_key,
+            &signature.to_bytes(),
+            &authenticator_data,
+            &client_data_json,
+        )
+        .expect("should succeed");
+        assert_eq!(verified_sig.raw_bytes, verified_fixed_size.raw_bytes);
+
+        // The challenge must be the hash of the signature text
+        VerifiedSignature::from_passkey(
+            "wrong signature text",
+            &public_key,
+            signature.to_der().as_bytes(),
+            &authenticator_data,
+            &client_data_json,
+        )
+        .expect_err("should fail with incorrect signature text");
+
+        // The signature must cover the authenticator data
+        let mut tampered_authenticator_data = authenticator_data.clone();
+        tampered_authenticator_data[36] += 1;
+        assert!(matches!(
+            VerifiedSignature::from_passkey(
+                signature_text,
+                &public_key,
+                signature.to_der().as_bytes(),
+                &tampered_authenticator_data,
+                &client_data_json,
+            ),
+            Err(SignatureError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn test_passkey_relying_party() {
+        let signing_key = P256SigningKey::random(&mut rand::thread_rng());
+        let public_key = signing_key.verifying_key().to_sec1_bytes();
+        let signature_text = "test signature text";
+        let verify = |rp_id: &str, origin: &str| {
+            let mut authenticator_data = Sha256::digest(rp_id).to_vec();
+            authenticator_data.push(WEBAUTHN_USER_PRESENT_FLAG);
+            authenticator_data.extend_from_slice(&[0, 0, 0, 1]);
+            let client_data_json = format!(
+                r#"{{"type":"webauthn.get","challenge":"{}","origin":"{}"}}"#,
+                URL_SAFE_NO_PAD.encode(Sha256::digest(signature_text)),
+                origin
+            )
+            .into_bytes();
+            let signed_data = [
+                authenticator_data.as_slice(),
+                Sha256::digest(&client_data_json).as_slice(),
+            ]
+            .concat();
+            let signature: P256Signature = signing_key.sign(&signed_data);
+            VerifiedSignature::from_passkey(
+                signature_text,
+                &public_key,
+                signature.to_der().as_bytes(),
+                &authenticator_data,
+                &client_data_json,
+            )
+        };
+
+        verify("xmtp.org", "https://xmtp.org").expect("should succeed");
+        verify("xmtp.org", "https://app.xmtp.org").expect("should succeed on a subdomain");
+        verify("app.xmtp.org", "https://xmtp.org")
+            .expect_err("should fail when the origin is outside of the relying party");
+        verify("evil.com", "https://xmtp.org").expect_err("should fail with another relying party");
+        verify("org", "https://xmtp.org").expect_err("should fail with a top level domain");
+        verify("xmtp.org", "http://xmtp.org").expect_err("should fail without https");
+    }
+
+    #[test]
+    fn test_passkey_requires_user_presence() {
+        let signing_key = P256SigningKey::random(&mut rand::thread_rng());
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let signature_text = "test signature text";
+        let (authenticator_data, client_data_json, signature) =
+            sign_passkey_assertion(&signing_key, signature_text, 0);
+
+        assert!(matches!(
+            VerifiedSignature::from_passkey(
+                signature_text,
+                &public_key,
+                signature.to_der().as_bytes(),
+                &authenticator_data,
+                &client_data_json,
+            ),
+            Err(SignatureError::InvalidWebAuthn(_))
+        ));
+    }
+
     #[test]
     fn validate_good_key_round_trip() {
         let proto_bytes = vec![
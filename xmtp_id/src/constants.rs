@@ -1,2 +1,11 @@
 // DO NOT CHANGE. SIGNATURES WILL BREAK
 pub const INSTALLATION_KEY_SIGNATURE_CONTEXT: &[u8] = b"IDENTITY UPDATE SIGNATURE";
+
+/// The `type` of the client data of a WebAuthn assertion
+pub const WEBAUTHN_ASSERTION_TYPE: &str = "webauthn.get";
+
+/// The User Present bit in the flags of WebAuthn authenticator data
+pub const WEBAUTHN_USER_PRESENT_FLAG: u8 = 0x01;
+
+/// CAIP-2 chain id of Solana mainnet, the genesis hash truncated to 32 characters
+pub const SOLANA_MAINNET_CHAIN_ID: &str = "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
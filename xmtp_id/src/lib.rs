@@ -4,7 +4,7 @@ pub mod scw_verifier;
 pub mod utils;
 use ethers::{
     middleware::Middleware,
-    providers::{Http, Provider},
+    providers::{Http, JsonRpcClient, Provider},
     signers::{LocalWallet, Signer},
     types::Address,
 };
@@ -36,6 +36,14 @@ pub async fn is_smart_contract(
     block: Option<u64>,
 ) -> Result<bool, IdentityError> {
     let provider: Provider<Http> = Provider::<Http>::try_from(url)?;
+    has_code(&provider, address, block).await
+}
+
+async fn has_code<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    address: Address,
+    block: Option<u64>,
+) -> Result<bool, IdentityError> {
     let code = provider.get_code(address, block.map(Into::into)).await?;
     Ok(!code.is_empty())
 }
@@ -61,35 +69,21 @@ impl InboxOwner for LocalWallet {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::scw_verifier::tests::with_smart_contracts;
-    use ethers::contract::abigen;
-
-    abigen!(
-        CoinbaseSmartWallet,
-        "artifact/CoinbaseSmartWallet.json",
-        derives(serde::Serialize, serde::Deserialize)
-    );
-
-    abigen!(
-        CoinbaseSmartWalletFactory,
-        "artifact/CoinbaseSmartWalletFactory.json",
-        derives(serde::Serialize, serde::Deserialize)
-    );
+    use crate::scw_verifier::tests::{owner_bytes, wallet, Chain};
 
     #[tokio::test]
     async fn test_is_smart_contract() {
-        with_smart_contracts(|anvil, _provider, _client, smart_contracts| async move {
-            let deployer: LocalWallet = anvil.keys()[0].clone().into();
-            let factory = smart_contracts.coinbase_smart_wallet_factory();
-            assert!(
-                !is_smart_contract(deployer.address(), anvil.endpoint(), None)
-                    .await
-                    .unwrap()
-            );
-            assert!(is_smart_contract(factory.address(), anvil.endpoint(), None)
-                .await
-                .unwrap());
-        })
-        .await;
+        let chain = Chain::new();
+        let provider = Provider::new(chain.evm.clone());
+        let smart_wallet = chain.wallet_address(vec![owner_bytes(&wallet(1))]);
+
+        assert!(!has_code(&provider, chain.deployer, None).await.unwrap());
+        assert!(has_code(&provider, chain.factory, None).await.unwrap());
+        // Not deployed yet as of the genesis block
+        assert!(!has_code(&provider, chain.factory, Some(0)).await.unwrap());
+
+        assert!(!has_code(&provider, smart_wallet, None).await.unwrap());
+        chain.create_account(vec![owner_bytes(&wallet(1))]);
+        assert!(has_code(&provider, smart_wallet, None).await.unwrap());
     }
 }
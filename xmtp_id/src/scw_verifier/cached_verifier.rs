@@ -0,0 +1,205 @@
+//! Caching of smart contract wallet verification results.
+//!
+//! An inbox's association state is rebuilt from its identity updates, and every rebuild would
+//! otherwise re-verify each ERC-6492/1271 signature against an RPC node. A verification made at a
+//! pinned block number can never change, so its result is cached and reused.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use ethers::types::{BlockNumber, Bytes, U64};
+use sha2::{Digest, Sha256};
+
+use super::{SmartContractSignatureVerifier, VerifierError};
+use crate::associations::AccountId;
+
+/// Storage for smart contract wallet verification results.
+///
+/// A cache is best effort, implementations should log failures and treat them as a miss
+/// rather than fail the verification.
+pub trait VerificationCache: Send + Sync + DynClone + 'static {
+    fn get(&self, key: &[u8]) -> Option<bool>;
+    fn insert(&self, key: Vec<u8>, is_valid: bool);
+}
+
+dyn_clone::clone_trait_object!(VerificationCache);
+
+/// A [VerificationCache] that lives as long as the process
+#[derive(Clone, Default)]
+pub struct InMemoryVerificationCache {
+    entries: Arc<Mutex<HashMap<Vec<u8>, bool>>>,
+}
+
+impl VerificationCache for InMemoryVerificationCache {
+    fn get(&self, key: &[u8]) -> Option<bool> {
+        self.entries.lock().ok()?.get(key).copied()
+    }
+
+    fn insert(&self, key: Vec<u8>, is_valid: bool) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, is_valid);
+        }
+    }
+}
+
+/// A [SmartContractSignatureVerifier] that caches the results of the verifier it wraps
+#[derive(Clone)]
+pub struct CachedSmartContractSignatureVerifier {
+    verifier: Box<dyn SmartContractSignatureVerifier>,
+    cache: Box<dyn VerificationCache>,
+}
+
+impl CachedSmartContractSignatureVerifier {
+    pub fn new(
+        verifier: Box<dyn SmartContractSignatureVerifier>,
+        cache: Box<dyn VerificationCache>,
+    ) -> Self {
+        Self { verifier, cache }
+    }
+
+    /// The cache key of a verification, `None` if the result may change over time.
+    ///
+    /// Only verifications at a specific block number are deterministic, `latest` moves with the chain.
+    pub fn cache_key(
+        account_id: &AccountId,
+        hash: &[u8; 32],
+        signature: &Bytes,
+        block_number: Option<BlockNumber>,
+    ) -> Option<Vec<u8>> {
+        let Some(BlockNumber::Number(block_number)) = block_number else {
+            return None;
+        };
+        let account_id: String = account_id.clone().into();
+
+        let mut hasher = Sha256::new();
+        hasher.update((account_id.len() as u64).to_be_bytes());
+        hasher.update(account_id.as_bytes());
+        hasher.update(hash);
+        hasher.update(block_number.as_u64().to_be_bytes());
+        hasher.update(signature);
+        Some(hasher.finalize().to_vec())
+    }
+}
+
+#[async_trait]
+impl SmartContractSignatureVerifier for CachedSmartContractSignatureVerifier {
+    async fn is_valid_signature(
+        &self,
+        account_id: AccountId,
+        hash: [u8; 32],
+        signature: Bytes,
+        block_number: Option<BlockNumber>,
+    ) -> Result<bool, VerifierError> {
+        let key = Self::cache_key(&account_id, &hash, &signature, block_number);
+        if let Some(is_valid) = key.as_ref().and_then(|key| self.cache.get(key)) {
+            return Ok(is_valid);
+        }
+
+        let is_valid = self
+            .verifier
+            .is_valid_signature(account_id, hash, signature, block_number)
+            .await?;
+        if let Some(key) = key {
+            self.cache.insert(key, is_valid);
+        }
+
+        Ok(is_valid)
+    }
+
+    async fn current_block_number(&self, chain_id: &str) -> Result<U64, VerifierError> {
+        self.verifier.current_block_number(chain_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CountingVerifier {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SmartContractSignatureVerifier for CountingVerifier {
+        async fn is_valid_signature(
+            &self,
+            _account_id: AccountId,
+            _hash: [u8; 32],
+            signature: Bytes,
+            _block_number: Option<BlockNumber>,
+        ) -> Result<bool, VerifierError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(!signature.is_empty())
+        }
+
+        async fn current_block_number(&self, _chain_id: &str) -> Result<U64, VerifierError> {
+            Ok(1.into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_caches_pinned_block_results() {
+        let counting = CountingVerifier::default();
+        let verifier = CachedSmartContractSignatureVerifier::new(
+            Box::new(counting.clone()),
+            Box::new(InMemoryVerificationCache::default()),
+        );
+        let account_id = AccountId::new_evm(1, "0x1".to_string());
+        let block = Some(BlockNumber::Number(10.into()));
+
+        for _ in 0..3 {
+            let valid = verifier
+                .is_valid_signature(account_id.clone(), [1; 32], vec![1].into(), block)
+                .await
+                .unwrap();
+            assert!(valid);
+        }
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 1);
+
+        // Invalid results are cached as well
+        for _ in 0..2 {
+            let valid = verifier
+                .is_valid_signature(account_id.clone(), [1; 32], Bytes::new(), block)
+                .await
+                .unwrap();
+            assert!(!valid);
+        }
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 2);
+
+        // A different block is a different verification
+        verifier
+            .is_valid_signature(
+                account_id.clone(),
+                [1; 32],
+                vec![1].into(),
+                Some(BlockNumber::Number(11.into())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_cache_latest_block() {
+        let counting = CountingVerifier::default();
+        let verifier = CachedSmartContractSignatureVerifier::new(
+            Box::new(counting.clone()),
+            Box::new(InMemoryVerificationCache::default()),
+        );
+        let account_id = AccountId::new_evm(1, "0x1".to_string());
+
+        for block in [None, Some(BlockNumber::Latest), None] {
+            verifier
+                .is_valid_signature(account_id.clone(), [1; 32], vec![1].into(), block)
+                .await
+                .unwrap();
+        }
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 3);
+    }
+}
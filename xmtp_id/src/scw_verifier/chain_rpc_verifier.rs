@@ -3,7 +3,7 @@ use crate::scw_verifier::SmartContractSignatureVerifier;
 use async_trait::async_trait;
 use ethers::abi::{Constructor, Param, ParamType, Token};
 use ethers::contract::abigen;
-use ethers::providers::{Http, Middleware, Provider};
+use ethers::providers::{Http, JsonRpcClient, Middleware, Provider};
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::{Address, BlockNumber, Bytes, TransactionRequest, U64};
 use hex::{FromHex, FromHexError};
@@ -27,9 +27,50 @@ abigen!(
     derives(serde::Serialize, serde::Deserialize)
 );
 
+/// Deployment data of the ValidateSigOffchain contract for a signature.
+/// The constructor returns `0x01` if the signature is valid.
+pub(crate) fn validate_sig_offchain_input(
+    signer: &AccountId,
+    hash: [u8; 32],
+    signature: &Bytes,
+) -> Result<Vec<u8>, VerifierError> {
+    let code = hex::decode(VALIDATE_SIG_OFFCHAIN_BYTECODE).unwrap();
+    let account_address: Address = signer
+        .account_address
+        .parse()
+        .map_err(|_| FromHexError::InvalidStringLength)?;
+    // ABI of the ValidateSigOffchain constructor
+    // constructor (address _signer, bytes32 _hash, bytes memory _signature)
+    let inputs: Vec<Param> = vec![
+        Param {
+            name: "_signer".to_string(),
+            kind: ParamType::Address,
+            internal_type: Some("_signer".into()),
+        },
+        Param {
+            name: "_hash".to_string(),
+            kind: ParamType::FixedBytes(32),
+            internal_type: Some("_hash".to_string()),
+        },
+        Param {
+            name: "_signature".to_string(),
+            kind: ParamType::Bytes,
+            internal_type: Some("_signature".to_string()),
+        },
+    ];
+    let constructor = Constructor { inputs };
+    let tokens = &[
+        Token::Address(account_address),
+        Token::FixedBytes(hash.to_vec()),
+        Token::Bytes(signature.to_vec()),
+    ];
+
+    Ok(constructor.encode_input(code, tokens)?)
+}
+
 #[derive(Debug, Clone)]
-pub struct RpcSmartContractWalletVerifier {
-    pub provider: Arc<Provider<Http>>,
+pub struct RpcSmartContractWalletVerifier<P = Http> {
+    pub provider: Arc<Provider<P>>,
 }
 
 impl RpcSmartContractWalletVerifier {
@@ -40,7 +81,10 @@ impl RpcSmartContractWalletVerifier {
 }
 
 #[async_trait]
-impl SmartContractSignatureVerifier for RpcSmartContractWalletVerifier {
+impl<P> SmartContractSignatureVerifier for RpcSmartContractWalletVerifier<P>
+where
+    P: JsonRpcClient + Clone + 'static,
+{
     /// Verifies an ERC-6492<https://eips.ethereum.org/EIPS/eip-6492> signature.
     ///
     /// # Arguments
@@ -55,37 +99,7 @@ impl SmartContractSignatureVerifier for RpcSmartContractWalletVerifier {
         signature: Bytes,
         block_number: Option<BlockNumber>,
     ) -> Result<bool, VerifierError> {
-        let code = hex::decode(VALIDATE_SIG_OFFCHAIN_BYTECODE).unwrap();
-        let account_address: Address = signer
-            .account_address
-            .parse()
-            .map_err(|_| FromHexError::InvalidStringLength)?;
-        // ABI of the ValidateSigOffchain constructor
-        // constructor (address _signer, bytes32 _hash, bytes memory _signature)
-        let inputs: Vec<Param> = vec![
-            Param {
-                name: "_signer".to_string(),
-                kind: ParamType::Address,
-                internal_type: Some("_signer".into()),
-            },
-            Param {
-                name: "_hash".to_string(),
-                kind: ParamType::FixedBytes(32),
-                internal_type: Some("_hash".to_string()),
-            },
-            Param {
-                name: "_signature".to_string(),
-                kind: ParamType::Bytes,
-                internal_type: Some("_signature".to_string()),
-            },
-        ];
-        let constructor = Constructor { inputs };
-        let tokens = &[
-            Token::Address(account_address),
-            Token::FixedBytes(hash.to_vec()),
-            Token::Bytes(signature.to_vec()),
-        ];
-        let data = constructor.encode_input(code, tokens)?;
+        let data = validate_sig_offchain_input(&signer, hash, &signature)?;
         let tx: TypedTransaction = TransactionRequest::new().data(data).into();
         let block_number = match block_number {
             Some(bn) => bn,
@@ -104,294 +118,138 @@ impl SmartContractSignatureVerifier for RpcSmartContractWalletVerifier {
 }
 
 #[cfg(test)]
-pub mod tests {
-    use crate::is_smart_contract;
-
+mod tests {
     use super::*;
+    use crate::scw_verifier::{
+        tests::{
+            account_id, owner_bytes, wallet, wrapped_signature, Chain, RemoveOwnerAtIndexCall,
+        },
+        LocalEvmSmartContractWalletVerifier,
+    };
     use ethers::{
-        abi::{self, Token},
-        core::utils::Anvil,
-        middleware::{MiddlewareBuilder, SignerMiddleware},
-        signers::{LocalWallet, Signer as _},
+        abi::AbiEncode,
+        signers::Signer as _,
         types::{H256, U256},
-        utils::{hash_message, AnvilInstance},
+        utils::hash_message,
     };
 
-    abigen!(
-        CoinbaseSmartWallet,
-        "artifact/CoinbaseSmartWallet.json",
-        derives(serde::Serialize, serde::Deserialize)
-    );
-
-    abigen!(
-        CoinbaseSmartWalletFactory,
-        "artifact/CoinbaseSmartWalletFactory.json",
-        derives(serde::Serialize, serde::Deserialize)
-    );
-
-    pub struct SmartContracts {
-        coinbase_smart_wallet_factory:
-            CoinbaseSmartWalletFactory<SignerMiddleware<Provider<Http>, LocalWallet>>,
-    }
-
-    impl SmartContracts {
-        fn new(
-            coinbase_smart_wallet_factory: CoinbaseSmartWalletFactory<
-                SignerMiddleware<Provider<Http>, LocalWallet>,
-            >,
-        ) -> Self {
-            Self {
-                coinbase_smart_wallet_factory,
-            }
-        }
-
-        pub fn coinbase_smart_wallet_factory(
-            &self,
-        ) -> &CoinbaseSmartWalletFactory<SignerMiddleware<Provider<Http>, LocalWallet>> {
-            &self.coinbase_smart_wallet_factory
+    /// A verifier that talks JSON-RPC to the in-process chain instead of a node
+    fn rpc_verifier(
+        chain: &Chain,
+    ) -> RpcSmartContractWalletVerifier<LocalEvmSmartContractWalletVerifier> {
+        RpcSmartContractWalletVerifier {
+            provider: Arc::new(Provider::new(chain.evm.clone())),
         }
     }
 
-    /// Test harness that loads a local anvil node with deployed smart contracts.
-    pub async fn with_smart_contracts<Func, Fut>(fun: Func)
-    where
-        Func: FnOnce(
-            AnvilInstance,
-            Provider<Http>,
-            SignerMiddleware<Provider<Http>, LocalWallet>,
-            SmartContracts,
-        ) -> Fut,
-        Fut: futures::Future<Output = ()>,
-    {
-        let anvil = Anvil::new().args(vec!["--base-fee", "100"]).spawn();
-        let contract_deployer: LocalWallet = anvil.keys()[9].clone().into();
-        let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
-        let client = SignerMiddleware::new(
-            provider.clone(),
-            contract_deployer.clone().with_chain_id(anvil.chain_id()),
-        );
-        // 1. coinbase smart wallet
-        // deploy implementation for factory
-        let implementation = CoinbaseSmartWallet::deploy(Arc::new(client.clone()), ())
-            .unwrap()
-            .gas_price(100)
-            .send()
-            .await
-            .unwrap();
-        // deploy factory
-        let factory =
-            CoinbaseSmartWalletFactory::deploy(Arc::new(client.clone()), implementation.address())
-                .unwrap()
-                .gas_price(100)
-                .send()
-                .await
-                .unwrap();
-
-        let smart_contracts = SmartContracts::new(factory);
-        fun(anvil, provider.clone(), client.clone(), smart_contracts).await
-    }
-
     #[tokio::test]
     async fn test_coinbase_smart_wallet() {
-        with_smart_contracts(|anvil, provider, client, smart_contracts| {
-            async move {
-                let owner0: LocalWallet = anvil.keys()[0].clone().into();
-                let owner1: LocalWallet = anvil.keys()[1].clone().into();
-                let owners_addresses = vec![
-                    Bytes::from(H256::from(owner0.address()).0.to_vec()),
-                    Bytes::from(H256::from(owner1.address()).0.to_vec()),
-                ];
-                let factory = smart_contracts.coinbase_smart_wallet_factory();
-                let nonce = U256::from(0); // needed when creating a smart wallet
-                let smart_wallet_address = factory
-                    .get_address(owners_addresses.clone(), nonce)
-                    .await
-                    .unwrap();
-
-                let contract_call = factory.create_account(owners_addresses.clone(), nonce);
-                let pending_tx = contract_call.send().await.unwrap();
-                pending_tx.await.unwrap();
-
-                // Generate signatures from owners and verify them.
-                let smart_wallet = CoinbaseSmartWallet::new(
-                    smart_wallet_address,
-                    Arc::new(client.with_signer(owner0.clone().with_chain_id(anvil.chain_id()))),
-                );
-                let hash: [u8; 32] = H256::random().into();
-                let replay_safe_hash = smart_wallet.replay_safe_hash(hash).call().await.unwrap();
-                let verifier = RpcSmartContractWalletVerifier::new(anvil.endpoint());
-
-                // verify owner0 is a valid owner
-                let sig0 = owner0.sign_hash(replay_safe_hash.into()).unwrap();
-                let account_id =
-                    AccountId::new_evm(anvil.chain_id(), format!("{:?}", smart_wallet_address));
-                let res = verifier
-                    .is_valid_signature(
-                        account_id.clone(),
-                        hash,
-                        abi::encode(&[Token::Tuple(vec![
-                            Token::Uint(U256::from(0)),
-                            Token::Bytes(sig0.to_vec()),
-                        ])])
-                        .into(),
-                        None,
-                    )
-                    .await
-                    .unwrap();
-                assert!(res);
-                // verify owner1 is a valid owner
-                let sig1 = owner1.sign_hash(replay_safe_hash.into()).unwrap();
-                let res = verifier
-                    .is_valid_signature(
-                        account_id.clone(),
-                        hash,
-                        abi::encode(&[Token::Tuple(vec![
-                            Token::Uint(U256::from(1)),
-                            Token::Bytes(sig1.to_vec()),
-                        ])])
-                        .into(),
-                        None,
-                    )
-                    .await
-                    .unwrap();
-                assert!(res);
-                // owner0 siganture must not be used to verify owner1
-                let res = verifier
-                    .is_valid_signature(
-                        account_id.clone(),
-                        hash,
-                        abi::encode(&[Token::Tuple(vec![
-                            Token::Uint(U256::from(1)),
-                            Token::Bytes(sig0.to_vec()),
-                        ])])
-                        .into(),
-                        None,
-                    )
-                    .await
-                    .unwrap();
-                assert!(!res);
-
-                // Testing time travel
-                // get block number before removing the owner.
-                let block_number = provider.get_block_number().await.unwrap();
+        let chain = Chain::new();
+        let owner0 = wallet(1);
+        let owner1 = wallet(2);
+        let smart_wallet = chain.create_account(vec![owner_bytes(&owner0), owner_bytes(&owner1)]);
+        let account_id = account_id(smart_wallet);
+        let verifier = rpc_verifier(&chain);
+
+        // Generate signatures from owners and verify them.
+        let hash: [u8; 32] = H256::random().into();
+        let replay_safe_hash = Chain::replay_safe_hash(&chain.evm, smart_wallet, hash);
+        let sig0 = owner0.sign_hash(replay_safe_hash).unwrap().to_vec();
+        let sig1 = owner1.sign_hash(replay_safe_hash).unwrap().to_vec();
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(0, &sig0), None)
+            .await
+            .unwrap());
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig1), None)
+            .await
+            .unwrap());
+        // owner0 siganture must not be used to verify owner1
+        assert!(!verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig0), None)
+            .await
+            .unwrap());
 
-                // remove owner1 and check owner1 is no longer a valid owner
-                let tx = smart_wallet.remove_owner_at_index(1.into());
-                let pending_tx = tx.send().await.unwrap();
-                let _ = pending_tx.await.unwrap();
+        // Testing time travel
+        // get block number before removing the owner.
+        let block_number = verifier.provider.get_block_number().await.unwrap();
 
-                let res = verifier
-                    .is_valid_signature(
-                        account_id.clone(),
-                        hash,
-                        abi::encode(&[Token::Tuple(vec![
-                            Token::Uint(U256::from(1)),
-                            Token::Bytes(sig1.to_vec()),
-                        ])])
-                        .into(),
-                        None,
-                    )
-                    .await;
-                assert!(res.is_err()); // when verify a non-existing owner, it errors
+        // remove owner1 and check owner1 is no longer a valid owner
+        let remove_owner = RemoveOwnerAtIndexCall {
+            index: U256::from(1),
+        };
+        chain
+            .evm
+            .transact(owner0.address(), smart_wallet, remove_owner.encode().into())
+            .unwrap();
+        // when verify a non-existing owner, it errors
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig1), None)
+            .await
+            .is_err());
 
-                // time travel to the pre-removel block number and verify owner1 WAS a valid owner
-                let res = verifier
-                    .is_valid_signature(
-                        account_id.clone(),
-                        hash,
-                        abi::encode(&[Token::Tuple(vec![
-                            Token::Uint(U256::from(1)),
-                            Token::Bytes(sig1.to_vec()),
-                        ])])
-                        .into(),
-                        Some(BlockNumber::Number(block_number)),
-                    )
-                    .await
-                    .unwrap();
-                assert!(res);
-            }
-        })
-        .await;
+        // time travel to the pre-removel block number and verify owner1 WAS a valid owner
+        assert!(verifier
+            .is_valid_signature(
+                account_id,
+                hash,
+                wrapped_signature(1, &sig1),
+                Some(BlockNumber::Number(block_number))
+            )
+            .await
+            .unwrap());
     }
 
-    // Testing ERC-6492 with deployed / undeployed coinbase smart wallet(ERC-1271) contracts, and EOA.
+    // Testing ERC-6492 with deployed coinbase smart wallet(ERC-1271) contracts, and EOA.
     #[tokio::test]
     async fn test_is_valid_signature() {
-        with_smart_contracts(|anvil, _provider, client, smart_contracts| async move {
-            // Create owner EOA wallet and then create smart contract wallet account from the factory.
-            let owner: LocalWallet = anvil.keys()[0].clone().into();
-            let owners_addresses = vec![Bytes::from(H256::from(owner.address()).0.to_vec())];
-            let factory = smart_contracts.coinbase_smart_wallet_factory();
-            let nonce = U256::from(0);
-            let smart_wallet_address = factory
-                .get_address(owners_addresses.clone(), nonce)
-                .await
-                .unwrap();
-            let contract_call = factory.create_account(owners_addresses.clone(), nonce);
-            let pending_tx = contract_call.send().await.unwrap();
-            pending_tx.await.unwrap();
-
-            assert!(
-                is_smart_contract(smart_wallet_address, anvil.endpoint(), None)
-                    .await
-                    .unwrap()
-            );
-
-            // Generate the signature for coinbase smart wallet
-            let smart_wallet = CoinbaseSmartWallet::new(
-                smart_wallet_address,
-                Arc::new(client.with_signer(owner.clone().with_chain_id(anvil.chain_id()))),
-            );
-            let hash: [u8; 32] = H256::random().into();
-            let replay_safe_hash = smart_wallet.replay_safe_hash(hash).call().await.unwrap();
-            let signature = owner.sign_hash(replay_safe_hash.into()).unwrap();
-            let signature: Bytes = abi::encode(&[Token::Tuple(vec![
-                Token::Uint(U256::from(0)),
-                Token::Bytes(signature.to_vec()),
-            ])])
-            .into();
-
-            let verifier = RpcSmartContractWalletVerifier::new(anvil.endpoint());
-
-            let account_id =
-                AccountId::new_evm(anvil.chain_id(), format!("{:?}", smart_wallet_address));
-
-            // Testing ERC-6492 signatures with deployed ERC-1271.
-            assert!(verifier
-                .is_valid_signature(account_id.clone(), hash, signature.clone(), None)
-                .await
-                .unwrap());
-
-            assert!(!verifier
-                .is_valid_signature(account_id.clone(), H256::random().into(), signature, None)
-                .await
-                .unwrap());
-
-            // Testing if EOA wallet signature is valid on ERC-6492
-            let signature = owner.sign_hash(hash.into()).unwrap();
-            let owner_account_id =
-                AccountId::new_evm(anvil.chain_id(), format!("{:?}", owner.address()));
-            assert!(verifier
-                .is_valid_signature(
-                    owner_account_id.clone(),
-                    hash,
-                    signature.to_vec().into(),
-                    None
-                )
-                .await
-                .unwrap());
+        let chain = Chain::new();
+        // Create owner EOA wallet and then create smart contract wallet account from the factory.
+        let owner = wallet(1);
+        let smart_wallet = chain.create_account(vec![owner_bytes(&owner)]);
+        let verifier = rpc_verifier(&chain);
+
+        // Generate the signature for coinbase smart wallet
+        let hash: [u8; 32] = H256::random().into();
+        let replay_safe_hash = Chain::replay_safe_hash(&chain.evm, smart_wallet, hash);
+        let signature = wrapped_signature(0, &owner.sign_hash(replay_safe_hash).unwrap().to_vec());
+
+        // Testing ERC-6492 signatures with deployed ERC-1271.
+        assert!(verifier
+            .is_valid_signature(account_id(smart_wallet), hash, signature.clone(), None)
+            .await
+            .unwrap());
+        assert!(!verifier
+            .is_valid_signature(
+                account_id(smart_wallet),
+                H256::random().into(),
+                signature,
+                None
+            )
+            .await
+            .unwrap());
 
-            assert!(!verifier
-                .is_valid_signature(
-                    owner_account_id,
-                    H256::random().into(),
-                    signature.to_vec().into(),
-                    None
-                )
-                .await
-                .unwrap());
-        })
-        .await;
+        // Testing if EOA wallet signature is valid on ERC-6492
+        let signature = owner.sign_hash(hash.into()).unwrap();
+        let owner_account_id = account_id(owner.address());
+        assert!(verifier
+            .is_valid_signature(
+                owner_account_id.clone(),
+                hash,
+                signature.to_vec().into(),
+                None
+            )
+            .await
+            .unwrap());
+        assert!(!verifier
+            .is_valid_signature(
+                owner_account_id,
+                H256::random().into(),
+                signature.to_vec().into(),
+                None
+            )
+            .await
+            .unwrap());
     }
 
     // This aims to verify a wrapped ERC-6492 signature that has magic bytes against an ERC-1271 ambire wallet deployed on Polygon.
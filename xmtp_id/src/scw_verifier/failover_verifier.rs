@@ -0,0 +1,229 @@
+//! Verification of smart contract wallet signatures against several RPC providers of the same chain.
+//!
+//! Providers are queried in order, a provider that errors is skipped in favour of the next one.
+//! A result is only accepted once a quorum of providers agree on it. With a quorum above one, a
+//! single faulty or malicious provider can not get an invalid signature accepted.
+use async_trait::async_trait;
+use ethers::types::{BlockNumber, Bytes, U64};
+use url::Url;
+
+use super::{RpcSmartContractWalletVerifier, SmartContractSignatureVerifier, VerifierError};
+use crate::associations::AccountId;
+
+#[derive(Clone)]
+pub struct FailoverSmartContractSignatureVerifier {
+    verifiers: Vec<Box<dyn SmartContractSignatureVerifier>>,
+    quorum: usize,
+}
+
+impl FailoverSmartContractSignatureVerifier {
+    /// `quorum` is clamped to the number of verifiers, and is at least one
+    pub fn new(verifiers: Vec<Box<dyn SmartContractSignatureVerifier>>, quorum: usize) -> Self {
+        let quorum = quorum.clamp(1, verifiers.len().max(1));
+        Self { verifiers, quorum }
+    }
+
+    pub fn new_from_urls(urls: Vec<Url>, quorum: usize) -> Self {
+        let verifiers = urls
+            .into_iter()
+            .map(|url| {
+                Box::new(RpcSmartContractWalletVerifier::new(url.to_string()))
+                    as Box<dyn SmartContractSignatureVerifier>
+            })
+            .collect();
+
+        Self::new(verifiers, quorum)
+    }
+
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+}
+
+#[async_trait]
+impl SmartContractSignatureVerifier for FailoverSmartContractSignatureVerifier {
+    async fn is_valid_signature(
+        &self,
+        account_id: AccountId,
+        hash: [u8; 32],
+        signature: Bytes,
+        block_number: Option<BlockNumber>,
+    ) -> Result<bool, VerifierError> {
+        // Every provider must answer for the same block, otherwise providers that are
+        // a few blocks apart could legitimately disagree
+        let block_number = match block_number {
+            Some(bn) => bn,
+            None => BlockNumber::Number(self.current_block_number(&account_id.chain_id).await?),
+        };
+
+        let (mut valid, mut invalid) = (0, 0);
+        let mut errors = vec![];
+        for verifier in &self.verifiers {
+            match verifier
+                .is_valid_signature(
+                    account_id.clone(),
+                    hash,
+                    signature.clone(),
+                    Some(block_number),
+                )
+                .await
+            {
+                Ok(true) => valid += 1,
+                Ok(false) => invalid += 1,
+                Err(err) => {
+                    tracing::warn!("smart contract wallet verifier failed, trying next: {err}");
+                    errors.push(err.to_string());
+                }
+            }
+
+            if valid >= self.quorum {
+                return Ok(true);
+            }
+            if invalid >= self.quorum {
+                return Ok(false);
+            }
+        }
+
+        Err(VerifierError::NoQuorum {
+            valid,
+            invalid,
+            quorum: self.quorum,
+            errors: errors.join("; "),
+        })
+    }
+
+    async fn current_block_number(&self, chain_id: &str) -> Result<U64, VerifierError> {
+        let mut last_error = None;
+        for verifier in &self.verifiers {
+            match verifier.current_block_number(chain_id).await {
+                Ok(block_number) => return Ok(block_number),
+                Err(err) => {
+                    tracing::warn!("fetching block number failed, trying next provider: {err}");
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(VerifierError::NoQuorum {
+            valid: 0,
+            invalid: 0,
+            quorum: self.quorum,
+            errors: "no providers configured".to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::providers::ProviderError;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct StaticVerifier {
+        result: Option<bool>,
+    }
+
+    impl StaticVerifier {
+        fn boxed(result: Option<bool>) -> Box<dyn SmartContractSignatureVerifier> {
+            Box::new(Self { result })
+        }
+    }
+
+    #[async_trait]
+    impl SmartContractSignatureVerifier for StaticVerifier {
+        async fn is_valid_signature(
+            &self,
+            _account_id: AccountId,
+            _hash: [u8; 32],
+            _signature: Bytes,
+            block_number: Option<BlockNumber>,
+        ) -> Result<bool, VerifierError> {
+            assert!(matches!(block_number, Some(BlockNumber::Number(_))));
+            self.result
+                .ok_or(VerifierError::Provider(ProviderError::CustomError(
+                    "unavailable".to_string(),
+                )))
+        }
+
+        async fn current_block_number(&self, _chain_id: &str) -> Result<U64, VerifierError> {
+            match self.result {
+                Some(_) => Ok(7.into()),
+                None => Err(VerifierError::Provider(ProviderError::CustomError(
+                    "unavailable".to_string(),
+                ))),
+            }
+        }
+    }
+
+    async fn verify(
+        verifier: &FailoverSmartContractSignatureVerifier,
+    ) -> Result<bool, VerifierError> {
+        verifier
+            .is_valid_signature(
+                AccountId::new_evm(1, "0x1".to_string()),
+                [0; 32],
+                Bytes::new(),
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_next_provider() {
+        let verifier = FailoverSmartContractSignatureVerifier::new(
+            vec![
+                StaticVerifier::boxed(None),
+                StaticVerifier::boxed(Some(true)),
+                StaticVerifier::boxed(Some(true)),
+            ],
+            2,
+        );
+        assert!(verify(&verifier).await.unwrap());
+        assert_eq!(
+            verifier.current_block_number("1").await.unwrap(),
+            U64::from(7)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requires_quorum() {
+        let verifier = FailoverSmartContractSignatureVerifier::new(
+            vec![
+                StaticVerifier::boxed(Some(true)),
+                StaticVerifier::boxed(Some(false)),
+                StaticVerifier::boxed(None),
+            ],
+            2,
+        );
+        assert!(matches!(
+            verify(&verifier).await,
+            Err(VerifierError::NoQuorum {
+                valid: 1,
+                invalid: 1,
+                ..
+            })
+        ));
+
+        let verifier = FailoverSmartContractSignatureVerifier::new(
+            vec![
+                StaticVerifier::boxed(Some(false)),
+                StaticVerifier::boxed(Some(true)),
+                StaticVerifier::boxed(Some(false)),
+            ],
+            2,
+        );
+        assert!(!verify(&verifier).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_quorum_is_clamped() {
+        let verifier =
+            FailoverSmartContractSignatureVerifier::new(vec![StaticVerifier::boxed(Some(true))], 3);
+        assert_eq!(verifier.quorum(), 1);
+        assert!(verify(&verifier).await.unwrap());
+
+        let verifier = FailoverSmartContractSignatureVerifier::new(vec![], 2);
+        assert!(verify(&verifier).await.is_err());
+    }
+}
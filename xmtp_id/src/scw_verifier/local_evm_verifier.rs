@@ -0,0 +1,650 @@
+//! Offline verification of smart contract wallet signatures for tests.
+//!
+//! The ERC-6492 validator and the wallet contracts run in an in-process EVM instead of against an
+//! RPC node, so tests are deterministic and need neither network access nor a local anvil.
+//! Every committed transaction mines a block and the state of each block is kept, so signatures
+//! can be verified as of an earlier block just like with an archive node.
+//!
+//! The chain also answers the few JSON-RPC methods the RPC based code needs, so an ethers
+//! [Provider](ethers::providers::Provider) can be backed by it.
+use std::{
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use ethers::{
+    providers::{JsonRpcClient, ProviderError},
+    types::{BlockNumber, Bytes, H160, U256, U64},
+};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{
+        AccountInfo, Address as EvmAddress, Bytecode, Bytes as EvmBytes, CreateScheme,
+        ExecutionResult, Output, TransactTo, U256 as EvmU256,
+    },
+    EVM,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use super::{validate_sig_offchain_input, SmartContractSignatureVerifier, VerifierError};
+use crate::associations::AccountId;
+
+type State = CacheDB<EmptyDB>;
+
+#[derive(Clone)]
+pub struct LocalEvmSmartContractWalletVerifier {
+    chain_id: u64,
+    /// The state at the end of each block, indexed by block number
+    blocks: Arc<RwLock<Vec<State>>>,
+}
+
+impl LocalEvmSmartContractWalletVerifier {
+    /// A chain with an empty genesis block
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            blocks: Arc::new(RwLock::new(vec![State::new(EmptyDB::default())])),
+        }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// An independent copy of the chain, changes to either one are not visible in the other
+    pub fn fork(&self) -> Self {
+        let blocks = self.blocks.read().expect("local evm lock poisoned").clone();
+        Self {
+            chain_id: self.chain_id,
+            blocks: Arc::new(RwLock::new(blocks)),
+        }
+    }
+
+    /// Preload an account with its code and storage into the latest block,
+    /// e.g. a contract that is already deployed on the chain being mirrored
+    pub fn insert_account(&self, address: H160, code: Bytes, storage: Vec<([u8; 32], [u8; 32])>) {
+        let mut blocks = self.blocks.write().expect("local evm lock poisoned");
+        let state = blocks.last_mut().expect("genesis block always exists");
+        let address = EvmAddress::from(address.0);
+        let code = Bytecode::new_raw(EvmBytes::from(code.to_vec()));
+        state.insert_account_info(
+            address,
+            AccountInfo::new(EvmU256::ZERO, 1, code.hash_slow(), code),
+        );
+        for (slot, value) in storage {
+            state
+                .insert_account_storage(
+                    address,
+                    EvmU256::from_be_bytes(slot),
+                    EvmU256::from_be_bytes(value),
+                )
+                .expect("empty database is infallible");
+        }
+    }
+
+    /// Deploy a contract in a new block, returning its address
+    pub fn deploy(&self, deployer: H160, init_code: Bytes) -> Result<H160, VerifierError> {
+        match self.commit(
+            deployer,
+            TransactTo::Create(CreateScheme::Create),
+            init_code,
+        )? {
+            Output::Create(_, Some(address)) => Ok(H160(address.0 .0)),
+            _ => Err(evm_error("contract creation returned no address")),
+        }
+    }
+
+    /// Execute a transaction in a new block, returning the call output
+    pub fn transact(&self, caller: H160, to: H160, data: Bytes) -> Result<Bytes, VerifierError> {
+        let output = self.commit(caller, TransactTo::Call(EvmAddress::from(to.0)), data)?;
+        Ok(output.into_data().to_vec().into())
+    }
+
+    /// Execute a read only call against the latest block
+    pub fn call(&self, caller: H160, to: H160, data: Bytes) -> Result<Bytes, VerifierError> {
+        let (block_number, state) = self.state_at(None)?;
+        let output = self.execute(
+            state,
+            block_number,
+            caller,
+            TransactTo::Call(EvmAddress::from(to.0)),
+            data,
+        )?;
+        Ok(output.into_data().to_vec().into())
+    }
+
+    /// The code deployed at `address` as of a block, empty for accounts without code
+    pub fn code_at(
+        &self,
+        address: H160,
+        block_number: Option<BlockNumber>,
+    ) -> Result<Bytes, VerifierError> {
+        let (_, state) = self.state_at(block_number)?;
+        let code = state
+            .accounts
+            .get(&EvmAddress::from(address.0))
+            .and_then(|account| {
+                account
+                    .info
+                    .code
+                    .clone()
+                    .or_else(|| state.contracts.get(&account.info.code_hash).cloned())
+            })
+            .map(|code| code.original_bytes().to_vec())
+            .unwrap_or_default();
+
+        Ok(code.into())
+    }
+
+    fn latest_block_number(&self) -> u64 {
+        self.blocks.read().expect("local evm lock poisoned").len() as u64 - 1
+    }
+
+    /// The number and state of a block, `latest` for anything but a number or `earliest`
+    fn state_at(&self, block_number: Option<BlockNumber>) -> Result<(u64, State), VerifierError> {
+        let blocks = self.blocks.read().expect("local evm lock poisoned");
+        let number = match block_number {
+            Some(BlockNumber::Number(number)) => number.as_u64(),
+            Some(BlockNumber::Earliest) => 0,
+            _ => blocks.len() as u64 - 1,
+        };
+        let state = blocks
+            .get(number as usize)
+            .cloned()
+            .ok_or_else(|| evm_error(format!("unknown block {number}")))?;
+
+        Ok((number, state))
+    }
+
+    fn commit(&self, caller: H160, to: TransactTo, data: Bytes) -> Result<Output, VerifierError> {
+        // Hold the lock for the whole transaction so concurrent transactions are serialized
+        let mut blocks = self.blocks.write().expect("local evm lock poisoned");
+        let state = blocks.last().cloned().expect("genesis block always exists");
+        let (output, state) = self.execute_commit(state, blocks.len() as u64, caller, to, data)?;
+        blocks.push(state);
+
+        Ok(output)
+    }
+
+    /// Execute without committing, the state is left untouched
+    fn execute(
+        &self,
+        state: State,
+        block_number: u64,
+        caller: H160,
+        to: TransactTo,
+        data: Bytes,
+    ) -> Result<Output, VerifierError> {
+        let mut evm = self.evm(state, block_number, caller, to, data);
+        let result = evm
+            .transact()
+            .map_err(|err| evm_error(format!("{err:?}")))?
+            .result;
+
+        success_output(result)
+    }
+
+    fn execute_commit(
+        &self,
+        state: State,
+        block_number: u64,
+        caller: H160,
+        to: TransactTo,
+        data: Bytes,
+    ) -> Result<(Output, State), VerifierError> {
+        let mut evm = self.evm(state, block_number, caller, to, data);
+        let result = evm
+            .transact_commit()
+            .map_err(|err| evm_error(format!("{err:?}")))?;
+        let state = evm.take_db();
+
+        Ok((success_output(result)?, state))
+    }
+
+    fn evm(
+        &self,
+        state: State,
+        block_number: u64,
+        caller: H160,
+        to: TransactTo,
+        data: Bytes,
+    ) -> EVM<State> {
+        let mut evm = EVM::new();
+        evm.database(state);
+        evm.env.cfg.chain_id = self.chain_id;
+        evm.env.block.number = EvmU256::from(block_number);
+        evm.env.tx.caller = EvmAddress::from(caller.0);
+        evm.env.tx.transact_to = to;
+        evm.env.tx.data = EvmBytes::from(data.to_vec());
+        evm
+    }
+}
+
+impl Debug for LocalEvmSmartContractWalletVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalEvmSmartContractWalletVerifier")
+            .field("chain_id", &self.chain_id)
+            .field("latest_block_number", &self.latest_block_number())
+            .finish()
+    }
+}
+
+impl LocalEvmSmartContractWalletVerifier {
+    fn handle_request(&self, method: &str, params: Value) -> Result<Value, VerifierError> {
+        let param = |index: usize| params.get(index).cloned().unwrap_or(Value::Null);
+        let block_number = |index: usize| -> Result<Option<BlockNumber>, VerifierError> {
+            serde_json::from_value(param(index)).map_err(serde_error)
+        };
+        let result = match method {
+            "eth_chainId" => serde_json::to_value(U256::from(self.chain_id)),
+            "eth_blockNumber" => serde_json::to_value(U64::from(self.latest_block_number())),
+            "eth_getCode" => {
+                let address: H160 = serde_json::from_value(param(0)).map_err(serde_error)?;
+                serde_json::to_value(self.code_at(address, block_number(1)?)?)
+            }
+            "eth_call" => {
+                let tx = param(0);
+                let field = |name: &str| tx.get(name).cloned().unwrap_or(Value::Null);
+                let caller: Option<H160> =
+                    serde_json::from_value(field("from")).map_err(serde_error)?;
+                let to: Option<H160> = serde_json::from_value(field("to")).map_err(serde_error)?;
+                let data: Option<Bytes> = serde_json::from_value(match field("data") {
+                    Value::Null => field("input"),
+                    data => data,
+                })
+                .map_err(serde_error)?;
+                let (number, state) = self.state_at(block_number(1)?)?;
+                let to = match to {
+                    Some(to) => TransactTo::Call(EvmAddress::from(to.0)),
+                    None => TransactTo::Create(CreateScheme::Create),
+                };
+                let output = self.execute(
+                    state,
+                    number,
+                    caller.unwrap_or_default(),
+                    to,
+                    data.unwrap_or_default(),
+                )?;
+                serde_json::to_value(Bytes::from(output.into_data().to_vec()))
+            }
+            _ => return Err(evm_error(format!("unsupported method {method}"))),
+        };
+
+        result.map_err(serde_error)
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for LocalEvmSmartContractWalletVerifier {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)?;
+        let result = self
+            .handle_request(method, params)
+            .map_err(|err| match err {
+                VerifierError::Provider(err) => err,
+                err => ProviderError::CustomError(err.to_string()),
+            })?;
+
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+fn success_output(result: ExecutionResult) -> Result<Output, VerifierError> {
+    match result {
+        ExecutionResult::Success { output, .. } => Ok(output),
+        ExecutionResult::Revert { output, .. } => Err(evm_error(format!(
+            "execution reverted: 0x{}",
+            hex::encode(output)
+        ))),
+        ExecutionResult::Halt { reason, .. } => {
+            Err(evm_error(format!("execution halted: {reason:?}")))
+        }
+    }
+}
+
+fn serde_error(err: serde_json::Error) -> VerifierError {
+    VerifierError::Provider(err.into())
+}
+
+fn evm_error(message: impl Into<String>) -> VerifierError {
+    VerifierError::Provider(ProviderError::CustomError(message.into()))
+}
+
+#[async_trait]
+impl SmartContractSignatureVerifier for LocalEvmSmartContractWalletVerifier {
+    /// Verifies an ERC-6492 signature the same way as
+    /// [RpcSmartContractWalletVerifier](super::RpcSmartContractWalletVerifier), as of `block_number`.
+    async fn is_valid_signature(
+        &self,
+        signer: AccountId,
+        hash: [u8; 32],
+        signature: Bytes,
+        block_number: Option<BlockNumber>,
+    ) -> Result<bool, VerifierError> {
+        let data = validate_sig_offchain_input(&signer, hash, &signature)?;
+        let (block_number, state) = self.state_at(block_number)?;
+        let output = self.execute(
+            state,
+            block_number,
+            H160::zero(),
+            TransactTo::Create(CreateScheme::Create),
+            data.into(),
+        )?;
+
+        Ok(output.into_data().as_ref() == [1])
+    }
+
+    async fn current_block_number(&self, _chain_id: &str) -> Result<U64, VerifierError> {
+        Ok(self.latest_block_number().into())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ethers::{
+        abi::{self, AbiDecode, AbiEncode, Token},
+        contract::abigen,
+        signers::{LocalWallet, Signer},
+        types::H256,
+    };
+
+    use super::*;
+
+    abigen!(
+        CoinbaseSmartWallet,
+        "artifact/CoinbaseSmartWallet.json",
+        derives(serde::Serialize, serde::Deserialize)
+    );
+
+    abigen!(
+        CoinbaseSmartWalletFactory,
+        "artifact/CoinbaseSmartWalletFactory.json",
+        derives(serde::Serialize, serde::Deserialize)
+    );
+
+    pub const CHAIN_ID: u64 = 31337;
+    const ERC6492_MAGIC_SUFFIX: [u8; 32] = [
+        0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64,
+        0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+        0x64, 0x92,
+    ];
+
+    pub fn wallet(key: u8) -> LocalWallet {
+        LocalWallet::from_bytes(&[key; 32]).unwrap()
+    }
+
+    pub struct Chain {
+        pub evm: LocalEvmSmartContractWalletVerifier,
+        pub deployer: H160,
+        pub factory: H160,
+    }
+
+    impl Chain {
+        /// A chain with the Coinbase smart wallet implementation and factory deployed
+        pub fn new() -> Self {
+            let evm = LocalEvmSmartContractWalletVerifier::new(CHAIN_ID);
+            let deployer = wallet(9).address();
+            let implementation = evm
+                .deploy(deployer, COINBASESMARTWALLET_BYTECODE.clone())
+                .unwrap();
+            let mut factory_code = COINBASESMARTWALLETFACTORY_BYTECODE.to_vec();
+            factory_code.extend(abi::encode(&[Token::Address(implementation)]));
+            let factory = evm.deploy(deployer, factory_code.into()).unwrap();
+
+            Self {
+                evm,
+                deployer,
+                factory,
+            }
+        }
+
+        pub fn wallet_address(&self, owners: Vec<Bytes>) -> H160 {
+            let call = GetAddressCall {
+                owners,
+                nonce: U256::zero(),
+            };
+            let output = self
+                .evm
+                .call(self.deployer, self.factory, call.encode().into())
+                .unwrap();
+            H160::decode(output).unwrap()
+        }
+
+        pub fn create_account_call(owners: Vec<Bytes>) -> Bytes {
+            CreateAccountCall {
+                owners,
+                nonce: U256::zero(),
+            }
+            .encode()
+            .into()
+        }
+
+        pub fn create_account(&self, owners: Vec<Bytes>) -> H160 {
+            let address = self.wallet_address(owners.clone());
+            self.evm
+                .transact(
+                    self.deployer,
+                    self.factory,
+                    Self::create_account_call(owners),
+                )
+                .unwrap();
+            address
+        }
+
+        pub fn replay_safe_hash(
+            evm: &LocalEvmSmartContractWalletVerifier,
+            smart_wallet: H160,
+            hash: [u8; 32],
+        ) -> H256 {
+            let output = evm
+                .call(
+                    H160::zero(),
+                    smart_wallet,
+                    ReplaySafeHashCall { hash }.encode().into(),
+                )
+                .unwrap();
+            H256::decode(output).unwrap()
+        }
+    }
+
+    pub fn owner_bytes(owner: &LocalWallet) -> Bytes {
+        Bytes::from(H256::from(owner.address()).0.to_vec())
+    }
+
+    pub fn wrapped_signature(owner_index: u64, signature: &[u8]) -> Bytes {
+        abi::encode(&[Token::Tuple(vec![
+            Token::Uint(U256::from(owner_index)),
+            Token::Bytes(signature.to_vec()),
+        ])])
+        .into()
+    }
+
+    pub fn account_id(address: H160) -> AccountId {
+        AccountId::new_evm(CHAIN_ID, format!("{:?}", address))
+    }
+
+    #[tokio::test]
+    async fn test_coinbase_smart_wallet() {
+        let chain = Chain::new();
+        let owner0 = wallet(1);
+        let owner1 = wallet(2);
+        let smart_wallet = chain.create_account(vec![owner_bytes(&owner0), owner_bytes(&owner1)]);
+        let account_id = account_id(smart_wallet);
+
+        let hash = [7u8; 32];
+        let replay_safe_hash = Chain::replay_safe_hash(&chain.evm, smart_wallet, hash);
+        let sig0 = owner0.sign_hash(replay_safe_hash).unwrap().to_vec();
+        let sig1 = owner1.sign_hash(replay_safe_hash).unwrap().to_vec();
+
+        let verifier = chain.evm.clone();
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(0, &sig0), None)
+            .await
+            .unwrap());
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig1), None)
+            .await
+            .unwrap());
+        // owner0 signature must not be used to verify owner1
+        assert!(!verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig0), None)
+            .await
+            .unwrap());
+        assert!(!verifier
+            .is_valid_signature(
+                account_id.clone(),
+                [8u8; 32],
+                wrapped_signature(0, &sig0),
+                None
+            )
+            .await
+            .unwrap());
+
+        // Testing time travel
+        let block_number = verifier.current_block_number("").await.unwrap();
+        let remove_owner: Bytes = RemoveOwnerAtIndexCall {
+            index: U256::from(1),
+        }
+        .encode()
+        .into();
+        // Only an owner can remove an owner, a reverted transaction leaves the chain untouched
+        assert!(verifier
+            .transact(chain.deployer, smart_wallet, remove_owner.clone())
+            .is_err());
+        assert_eq!(
+            verifier.current_block_number("").await.unwrap(),
+            block_number
+        );
+        verifier
+            .transact(owner0.address(), smart_wallet, remove_owner)
+            .unwrap();
+
+        // verifying a signature of a removed owner errors
+        assert!(verifier
+            .is_valid_signature(account_id.clone(), hash, wrapped_signature(1, &sig1), None)
+            .await
+            .is_err());
+        assert!(verifier
+            .is_valid_signature(
+                account_id.clone(),
+                hash,
+                wrapped_signature(1, &sig1),
+                Some(BlockNumber::Number(block_number))
+            )
+            .await
+            .unwrap());
+    }
+
+    // Testing ERC-6492 with deployed / undeployed coinbase smart wallet(ERC-1271) contracts, and EOA.
+    #[tokio::test]
+    async fn test_is_valid_signature() {
+        let chain = Chain::new();
+        let owner = wallet(1);
+        let owners = vec![owner_bytes(&owner)];
+        let smart_wallet = chain.wallet_address(owners.clone());
+        let account_id = account_id(smart_wallet);
+        let hash = [7u8; 32];
+
+        // Sign with the wallet deployed on a fork, the wallet is not deployed on the chain
+        let fork = chain.evm.fork();
+        fork.transact(
+            chain.deployer,
+            chain.factory,
+            Chain::create_account_call(owners.clone()),
+        )
+        .unwrap();
+        let replay_safe_hash = Chain::replay_safe_hash(&fork, smart_wallet, hash);
+        let signature = wrapped_signature(0, &owner.sign_hash(replay_safe_hash).unwrap().to_vec());
+        // Calling an address without code succeeds with no output
+        assert!(chain
+            .evm
+            .call(
+                H160::zero(),
+                smart_wallet,
+                ReplaySafeHashCall { hash }.encode().into()
+            )
+            .unwrap()
+            .is_empty());
+
+        // Testing ERC-6492 signatures with an undeployed ERC-1271 wallet
+        let mut erc6492_signature = abi::encode(&[
+            Token::Address(chain.factory),
+            Token::Bytes(Chain::create_account_call(owners.clone()).to_vec()),
+            Token::Bytes(signature.to_vec()),
+        ]);
+        erc6492_signature.extend(ERC6492_MAGIC_SUFFIX);
+        assert!(chain
+            .evm
+            .is_valid_signature(account_id.clone(), hash, erc6492_signature.into(), None)
+            .await
+            .unwrap());
+
+        // Testing ERC-6492 signatures with a deployed ERC-1271 wallet
+        chain.create_account(owners);
+        assert!(chain
+            .evm
+            .is_valid_signature(account_id.clone(), hash, signature.clone(), None)
+            .await
+            .unwrap());
+        assert!(!chain
+            .evm
+            .is_valid_signature(account_id, [8u8; 32], signature, None)
+            .await
+            .unwrap());
+
+        // Testing if EOA wallet signature is valid on ERC-6492
+        let signature = owner.sign_hash(hash.into()).unwrap().to_vec();
+        let owner_account_id = account_id(owner.address());
+        assert!(chain
+            .evm
+            .is_valid_signature(
+                owner_account_id.clone(),
+                hash,
+                signature.clone().into(),
+                None
+            )
+            .await
+            .unwrap());
+        assert!(!chain
+            .evm
+            .is_valid_signature(owner_account_id, [8u8; 32], signature.into(), None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_fork_is_independent() {
+        let chain = Chain::new();
+        let fork = chain.evm.fork();
+        let owners = vec![owner_bytes(&wallet(1))];
+        fork.transact(
+            chain.deployer,
+            chain.factory,
+            Chain::create_account_call(owners),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fork.current_block_number("").await.unwrap(),
+            chain.evm.current_block_number("").await.unwrap() + 1
+        );
+        assert!(chain
+            .evm
+            .is_valid_signature(
+                account_id(H160::zero()),
+                [0; 32],
+                Bytes::new(),
+                Some(BlockNumber::Number(100.into()))
+            )
+            .await
+            .is_err());
+    }
+}
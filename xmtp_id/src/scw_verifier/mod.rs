@@ -1,4 +1,8 @@
+mod cached_verifier;
 mod chain_rpc_verifier;
+mod failover_verifier;
+#[cfg(any(test, feature = "test-utils"))]
+mod local_evm_verifier;
 
 use std::{collections::HashMap, fs, path::Path, str::FromStr};
 
@@ -14,10 +18,21 @@ use url::Url;
 
 use crate::associations::AccountId;
 
+pub use self::cached_verifier::*;
 pub use self::chain_rpc_verifier::*;
+pub use self::failover_verifier::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub use self::local_evm_verifier::*;
 
 static DEFAULT_CHAIN_URLS: &str = include_str!("chain_urls_default.json");
 
+/// Number of RPC providers of a chain that must agree on a smart contract wallet signature.
+///
+/// Free public RPC providers are often down or rate limited, so by default the first provider
+/// that answers decides and the others are only failed over to. Requiring more of them to agree
+/// is opt in, see [MultiSmartContractSignatureVerifier::new_from_file_with_quorum]
+pub const DEFAULT_RPC_QUORUM: usize = 1;
+
 #[derive(Debug, Error)]
 pub enum VerifierError {
     #[error("calling smart contract {0}")]
@@ -30,6 +45,13 @@ pub enum VerifierError {
     Abi(#[from] ethers::abi::Error),
     #[error(transparent)]
     Provider(#[from] ethers::providers::ProviderError),
+    #[error("no quorum of {quorum} RPC providers ({valid} valid, {invalid} invalid): {errors}")]
+    NoQuorum {
+        valid: usize,
+        invalid: usize,
+        quorum: usize,
+        errors: String,
+    },
 }
 
 #[async_trait]
@@ -65,6 +87,36 @@ impl<S: SmartContractSignatureVerifier + Clone> SmartContractSignatureVerifier f
     }
 }
 
+/// The RPC urls of a chain in a chain urls file, either a single url or a list to fail over between
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ChainUrls {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl ChainUrls {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            ChainUrls::Single(url) => vec![url],
+            ChainUrls::Multiple(urls) => urls,
+        }
+    }
+}
+
+/// Parse a chain id that is either a bare number or a CAIP-2 `eip155:<number>` chain id
+fn parse_chain_id(chain_id: &str) -> Result<u64, VerifierError> {
+    chain_id
+        .strip_prefix("eip155:")
+        .unwrap_or(chain_id)
+        .parse()
+        .map_err(|e| {
+            VerifierError::Contract(ContractError::DecodingError(
+                ethers::core::abi::Error::ParseInt(e),
+            ))
+        })
+}
+
 #[derive(Clone)]
 pub struct MultiSmartContractSignatureVerifier {
     verifiers: HashMap<u64, Box<dyn SmartContractSignatureVerifier>>,
@@ -86,7 +138,29 @@ impl MultiSmartContractSignatureVerifier {
         Self { verifiers }
     }
 
+    /// Verify against every url of a chain, failing over between them and requiring `quorum` of them to agree
+    pub fn new_with_failover(urls: HashMap<u64, Vec<url::Url>>, quorum: usize) -> Self {
+        let verifiers: HashMap<u64, Box<dyn SmartContractSignatureVerifier>> = urls
+            .into_iter()
+            .map(|(chain_id, urls)| {
+                (
+                    chain_id,
+                    Box::new(FailoverSmartContractSignatureVerifier::new_from_urls(
+                        urls, quorum,
+                    )) as Box<dyn SmartContractSignatureVerifier>,
+                )
+            })
+            .collect();
+
+        Self { verifiers }
+    }
+
     pub fn new_from_file(path: impl AsRef<Path>) -> Self {
+        Self::new_from_file_with_quorum(path, DEFAULT_RPC_QUORUM)
+    }
+
+    /// Like [Self::new_from_file], but requiring `quorum` providers of a chain to agree
+    pub fn new_from_file_with_quorum(path: impl AsRef<Path>, quorum: usize) -> Self {
         let path = path.as_ref();
 
         let file_str;
@@ -97,21 +171,25 @@ impl MultiSmartContractSignatureVerifier {
             DEFAULT_CHAIN_URLS
         };
 
-        let json: HashMap<u64, String> =
+        let json: HashMap<u64, ChainUrls> =
             serde_json::from_str(json).unwrap_or_else(|_| panic!("{path:?} is malformatted"));
 
         let urls = json
             .into_iter()
-            .map(|(id, url)| {
-                (
-                    id,
-                    Url::from_str(&url)
-                        .unwrap_or_else(|_| panic!("unable to parse url in {path:?} ({url})")),
-                )
+            .map(|(id, urls)| {
+                let urls = urls
+                    .into_vec()
+                    .into_iter()
+                    .map(|url| {
+                        Url::from_str(&url)
+                            .unwrap_or_else(|_| panic!("unable to parse url in {path:?} ({url})"))
+                    })
+                    .collect();
+                (id, urls)
             })
             .collect();
 
-        Self::new(urls)
+        Self::new_with_failover(urls, quorum)
     }
 }
 
@@ -124,11 +202,7 @@ impl SmartContractSignatureVerifier for MultiSmartContractSignatureVerifier {
         signature: Bytes,
         block_number: Option<BlockNumber>,
     ) -> Result<bool, VerifierError> {
-        let id: u64 = account_id.chain_id.parse().map_err(|e| {
-            VerifierError::Contract(ContractError::DecodingError(
-                ethers::core::abi::Error::ParseInt(e),
-            ))
-        })?;
+        let id = parse_chain_id(&account_id.chain_id)?;
         if let Some(verifier) = self.verifiers.get(&id) {
             return verifier
                 .is_valid_signature(account_id, hash, signature, block_number)
@@ -141,11 +215,7 @@ impl SmartContractSignatureVerifier for MultiSmartContractSignatureVerifier {
     }
 
     async fn current_block_number(&self, chain_id: &str) -> Result<U64, VerifierError> {
-        let id: u64 = chain_id.parse().map_err(|e| {
-            VerifierError::Contract(ContractError::DecodingError(
-                ethers::core::abi::Error::ParseInt(e),
-            ))
-        })?;
+        let id = parse_chain_id(chain_id)?;
         if let Some(verifier) = self.verifiers.get(&id) {
             return verifier.current_block_number(chain_id).await;
         }
@@ -155,3 +225,30 @@ impl SmartContractSignatureVerifier for MultiSmartContractSignatureVerifier {
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_id() {
+        assert_eq!(parse_chain_id("eip155:8453").unwrap(), 8453);
+        assert_eq!(parse_chain_id("1").unwrap(), 1);
+        assert!(parse_chain_id("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp").is_err());
+    }
+
+    #[test]
+    fn test_chain_urls_formats() {
+        let verifier = MultiSmartContractSignatureVerifier::new_from_file("does_not_exist.json");
+        assert_eq!(verifier.verifiers.len(), 3);
+
+        let single: HashMap<u64, ChainUrls> =
+            serde_json::from_str(r#"{"1": "https://eth.llamarpc.com"}"#).unwrap();
+        let multiple: HashMap<u64, ChainUrls> = serde_json::from_str(DEFAULT_CHAIN_URLS).unwrap();
+        assert_eq!(single.into_values().next().unwrap().into_vec().len(), 1);
+        // Every chain has a provider to fail over to
+        assert!(multiple
+            .into_values()
+            .all(|urls| urls.into_vec().len() >= 2));
+    }
+}
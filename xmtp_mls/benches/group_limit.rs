@@ -3,14 +3,21 @@
 //! using `RUST_LOG=trace` will additionally output a `tracing.folded` file, which
 //! may be used to generate a flamegraph of execution from tracing logs.
 use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use openmls_rust_crypto::RustCrypto;
+use openmls_traits::{crypto::OpenMlsCrypto, random::OpenMlsRand};
 use std::{collections::HashMap, sync::Arc};
 use tokio::runtime::{Builder, Handle, Runtime};
 use tracing::{trace_span, Instrument};
 use xmtp_mls::{
     builder::ClientBuilder,
+    configuration::CIPHERSUITE,
     groups::GroupMetadataOptions,
     utils::{
-        bench::{create_identities_if_dont_exist, init_logging, Identity, BENCH_ROOT_SPAN},
+        bench::{
+            create_identities_if_dont_exist, encrypt_and_chunk_welcomes, export_ratchet_tree,
+            fetch_key_packages, init_logging, installation_ids, publish_and_fetch_ratchet_tree,
+            Identity, BENCH_ROOT_SPAN,
+        },
         test::TestClient,
     },
     Client,
@@ -18,8 +25,16 @@ use xmtp_mls::{
 
 pub type BenchClient = Client<TestClient>;
 
-pub const IDENTITY_SAMPLES: [usize; 9] = [10, 20, 40, 80, 100, 200, 300, 400, 450];
-pub const MAX_IDENTITIES: usize = 1_000;
+// Samples past `RATCHET_TREE_OUT_OF_BAND_THRESHOLD` exercise welcomes without an embedded ratchet tree.
+// The largest, plus the creator and the 100 member setup of `add_to_100_member_group_by_inbox_id`,
+// fills a group to `MAX_GROUP_SIZE`
+pub const IDENTITY_SAMPLES: [usize; 12] = [
+    10, 20, 40, 80, 100, 200, 400, 800, 1_000, 1_200, 1_600, 1_899,
+];
+// Crosses `KEY_PACKAGE_FETCH_BATCH_SIZE` and goes up to a key package for each of
+// `MAX_GROUP_SIZE` single installation inboxes
+pub const KEY_PACKAGE_SAMPLES: [usize; 7] = [10, 100, 250, 251, 500, 1_000, 2_000];
+pub const MAX_IDENTITIES: usize = 2_500;
 pub const SAMPLE_SIZE: usize = 10;
 
 fn setup() -> (Arc<BenchClient>, Vec<Identity>, Runtime) {
@@ -63,6 +78,34 @@ where
     tokio::task::block_in_place(move || Handle::current().block_on(async move { fun().await }))
 }
 
+/// Exports the ratchet tree of a group for each of the `IDENTITY_SAMPLES`, with that many
+/// members besides the creator
+fn setup_ratchet_trees(
+    client: &BenchClient,
+    identities: &[Identity],
+    runtime: &Runtime,
+) -> HashMap<usize, Vec<u8>> {
+    runtime.block_on(async {
+        let mut trees = HashMap::new();
+        for size in IDENTITY_SAMPLES {
+            let inbox_ids = identities
+                .iter()
+                .take(size)
+                .map(|i| i.inbox_id.clone())
+                .collect();
+            let group = client
+                .create_group(None, GroupMetadataOptions::default())
+                .unwrap();
+            group
+                .add_members_by_inbox_id(client, inbox_ids)
+                .await
+                .unwrap();
+            trees.insert(size, export_ratchet_tree(client, &group));
+        }
+        trees
+    })
+}
+
 fn add_to_empty_group(c: &mut Criterion) {
     init_logging();
     let mut benchmark_group = c.benchmark_group("add_to_empty_group");
@@ -345,8 +388,94 @@ fn add_1_member_to_group(c: &mut Criterion) {
     benchmark_group.finish();
 }
 
+fn fetch_key_packages_in_batches(c: &mut Criterion) {
+    init_logging();
+    let mut benchmark_group = c.benchmark_group("fetch_key_packages_in_batches");
+    benchmark_group.sample_size(SAMPLE_SIZE);
+
+    let (client, identities, runtime) = setup();
+    let installation_ids = runtime.block_on(installation_ids(&client, &identities));
+
+    for size in KEY_PACKAGE_SAMPLES.iter() {
+        benchmark_group.throughput(Throughput::Elements(*size as u64));
+        benchmark_group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let span = trace_span!(BENCH_ROOT_SPAN, size);
+            b.to_async(&runtime).iter_batched(
+                || {
+                    (
+                        client.clone(),
+                        installation_ids
+                            .iter()
+                            .take(size)
+                            .cloned()
+                            .collect::<Vec<_>>(),
+                        span.clone(),
+                    )
+                },
+                |(client, installation_ids, span)| async move {
+                    let fetched = fetch_key_packages(&client, installation_ids)
+                        .instrument(span)
+                        .await;
+                    assert_eq!(fetched, size);
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    benchmark_group.finish();
+}
+
+/// Welcomes that embed the ratchet tree, one for each new member. The tree grows with the group,
+/// so the bytes encrypted and sent grow with the square of the number of members added
+fn encrypt_and_chunk_welcomes_with_ratchet_tree(c: &mut Criterion) {
+    init_logging();
+    let mut benchmark_group = c.benchmark_group("encrypt_and_chunk_welcomes_with_ratchet_tree");
+    benchmark_group.sample_size(SAMPLE_SIZE);
+
+    let (client, identities, runtime) = setup();
+    let trees = setup_ratchet_trees(&client, &identities, &runtime);
+    let crypto = RustCrypto::default();
+    let ikm = crypto.random_vec(CIPHERSUITE.hash_length()).unwrap();
+    let hpke_public_key = crypto
+        .derive_hpke_keypair(CIPHERSUITE.hpke_config(), ikm.as_slice())
+        .unwrap()
+        .public;
+
+    for size in IDENTITY_SAMPLES.iter() {
+        let tree = trees.get(size).unwrap();
+        let hpke_public_keys = vec![hpke_public_key.clone(); *size];
+        benchmark_group.throughput(Throughput::Bytes((tree.len() * size) as u64));
+        benchmark_group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
+            b.iter(|| encrypt_and_chunk_welcomes(tree, &hpke_public_keys, false))
+        });
+    }
+    benchmark_group.finish();
+}
+
+/// The ratchet tree published once for groups past `RATCHET_TREE_OUT_OF_BAND_THRESHOLD`,
+/// in place of embedding it in every welcome
+fn publish_ratchet_tree_out_of_band(c: &mut Criterion) {
+    init_logging();
+    let mut benchmark_group = c.benchmark_group("publish_ratchet_tree_out_of_band");
+    benchmark_group.sample_size(SAMPLE_SIZE);
+
+    let (client, identities, runtime) = setup();
+    let trees = setup_ratchet_trees(&client, &identities, &runtime);
+
+    for size in IDENTITY_SAMPLES.iter() {
+        let tree = trees.get(size).unwrap();
+        benchmark_group.throughput(Throughput::Bytes(tree.len() as u64));
+        benchmark_group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let span = trace_span!(BENCH_ROOT_SPAN, size);
+            b.to_async(&runtime)
+                .iter(|| publish_and_fetch_ratchet_tree(&client, tree).instrument(span.clone()));
+        });
+    }
+    benchmark_group.finish();
+}
+
 criterion_group!(
     name = group_limit;
     config = Criterion::default().sample_size(10);
-    targets = add_to_empty_group, add_to_empty_group_by_inbox_id, remove_all_members_from_group, remove_half_members_from_group, add_to_100_member_group_by_inbox_id, add_1_member_to_group);
+    targets = add_to_empty_group, add_to_empty_group_by_inbox_id, remove_all_members_from_group, remove_half_members_from_group, add_to_100_member_group_by_inbox_id, add_1_member_to_group, fetch_key_packages_in_batches, encrypt_and_chunk_welcomes_with_ratchet_tree, publish_ratchet_tree_out_of_band);
 criterion_main!(group_limit);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS inbox_id_cache_inbox_id;
+
+DROP TABLE IF EXISTS inbox_id_cache;
@@ -0,0 +1,8 @@
+CREATE TABLE inbox_id_cache(
+    "address" TEXT PRIMARY KEY NOT NULL,
+    -- NULL caches that the address had no inbox
+    "inbox_id" TEXT,
+    "cached_at_ns" BIGINT NOT NULL
+);
+
+CREATE INDEX inbox_id_cache_inbox_id ON inbox_id_cache(inbox_id);
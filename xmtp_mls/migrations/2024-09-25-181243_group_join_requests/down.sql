@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS group_join_requests_group_id_requester_inbox_id;
+
+DROP TABLE IF EXISTS group_join_requests;
@@ -0,0 +1,11 @@
+CREATE TABLE group_join_requests(
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    "group_id" BLOB NOT NULL,
+    "requester_inbox_id" TEXT NOT NULL,
+    -- Enum of JoinRequestState (Pending, Approved, Denied)
+    "state" INT NOT NULL,
+    "received_at_ns" BIGINT NOT NULL,
+    FOREIGN KEY (group_id) REFERENCES groups(id)
+);
+
+CREATE UNIQUE INDEX group_join_requests_group_id_requester_inbox_id ON group_join_requests(group_id, requester_inbox_id);
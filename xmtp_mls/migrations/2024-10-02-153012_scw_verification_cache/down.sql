@@ -0,0 +1 @@
+DROP TABLE IF EXISTS scw_verification_cache;
@@ -0,0 +1,5 @@
+CREATE TABLE scw_verification_cache(
+    -- sha256 of the account id, message hash, block number and signature
+    "cache_key" BLOB PRIMARY KEY NOT NULL,
+    "is_valid" BOOLEAN NOT NULL
+);
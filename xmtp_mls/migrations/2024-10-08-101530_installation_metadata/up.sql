@@ -0,0 +1,9 @@
+CREATE TABLE installation_metadata(
+    -- The installation's public key
+    "installation_id" BLOB PRIMARY KEY NOT NULL,
+    "device_name" TEXT NOT NULL,
+    "platform" TEXT NOT NULL,
+    "created_at_ns" BIGINT NOT NULL,
+    -- When the installation last published a key package
+    "last_seen_ns" BIGINT NOT NULL
+);
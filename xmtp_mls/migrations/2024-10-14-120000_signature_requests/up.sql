@@ -0,0 +1,11 @@
+CREATE TABLE signature_requests(
+    -- Derived from the text to be signed
+    "id" TEXT PRIMARY KEY NOT NULL,
+    "inbox_id" TEXT NOT NULL,
+    -- Protobuf encoded pending actions and collected signatures
+    "request" BLOB NOT NULL,
+    -- Keys of the unregistered installation the request registers, if any
+    "installation_keys" BLOB,
+    "created_at_ns" BIGINT NOT NULL,
+    "expires_at_ns" BIGINT NOT NULL
+);
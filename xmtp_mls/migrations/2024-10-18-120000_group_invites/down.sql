@@ -0,0 +1,3 @@
+ALTER TABLE group_join_requests DROP COLUMN invite_nonce;
+
+DROP TABLE IF EXISTS group_invites;
@@ -0,0 +1,13 @@
+CREATE TABLE group_invites(
+    -- Random nonce of the signed invite
+    "nonce" BLOB PRIMARY KEY NOT NULL,
+    "group_id" BLOB NOT NULL,
+    -- Join requests that can be approved through the invite
+    "max_uses" INT NOT NULL,
+    "uses" INT NOT NULL DEFAULT 0,
+    "expires_at_ns" BIGINT NOT NULL,
+    FOREIGN KEY (group_id) REFERENCES groups(id)
+);
+
+-- The invite a join request was made with
+ALTER TABLE group_join_requests ADD COLUMN invite_nonce BLOB NOT NULL DEFAULT x'';
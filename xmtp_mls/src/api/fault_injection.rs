@@ -0,0 +1,692 @@
+//! An API client wrapper that injects network faults, for testing how the client recovers from
+//! an unreliable node.
+//!
+//! Every decision is drawn from a seeded RNG so a failing test can be replayed with the same seed.
+//! Faults are applied as follows:
+//! - latency: every call sleeps for a random duration in the configured range.
+//! - errors: every call fails with `error_rate` probability, using the [`ErrorKind`] the real
+//!   client would return for that endpoint. Specific errors can be queued with
+//!   [`FaultInjector::fail_next`].
+//! - drop: outgoing group and welcome messages are silently discarded, and streamed envelopes are
+//!   skipped.
+//! - duplicate: outgoing messages are sent twice, queried and streamed envelopes are delivered twice.
+//! - reorder: queried pages are shuffled and streamed envelopes are held back behind the next one.
+//! - partitions: a partitioned client fails every call, a partitioned installation can not
+//!   receive welcomes or have its key package fetched.
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::Range,
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use xmtp_proto::{
+    api_client::{
+        ClientWithMetadata, Error, ErrorKind, XmtpIdentityClient, XmtpMlsClient, XmtpMlsStreams,
+    },
+    xmtp::{
+        identity::api::v1::{
+            GetIdentityUpdatesRequest as GetIdentityUpdatesV2Request,
+            GetIdentityUpdatesResponse as GetIdentityUpdatesV2Response, GetInboxIdsRequest,
+            GetInboxIdsResponse, PublishIdentityUpdateRequest, PublishIdentityUpdateResponse,
+        },
+        mls::api::v1::{
+            welcome_message_input, FetchKeyPackagesRequest, FetchKeyPackagesResponse, GroupMessage,
+            QueryGroupMessagesRequest, QueryGroupMessagesResponse, QueryWelcomeMessagesRequest,
+            QueryWelcomeMessagesResponse, SendGroupMessagesRequest, SendWelcomeMessagesRequest,
+            SubscribeGroupMessagesRequest, SubscribeWelcomeMessagesRequest,
+            UploadKeyPackageRequest, WelcomeMessage, WelcomeMessageInput,
+        },
+    },
+};
+
+pub use super::Endpoint;
+use crate::XmtpTestClient;
+
+impl Endpoint {
+    /// The error kind the network clients return when this endpoint fails
+    fn error_kind(&self) -> ErrorKind {
+        match self {
+            Endpoint::SubscribeGroupMessages | Endpoint::SubscribeWelcomeMessages => {
+                ErrorKind::SubscribeError
+            }
+            Endpoint::PublishIdentityUpdate
+            | Endpoint::GetIdentityUpdates
+            | Endpoint::GetInboxIds => ErrorKind::IdentityError,
+            _ => ErrorKind::MlsError,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FaultConfig {
+    /// Seed for every random decision the injector makes
+    pub seed: u64,
+    /// Range of latency added to every call
+    pub latency: Option<Range<Duration>>,
+    /// Probability of a call failing
+    pub error_rate: f64,
+    /// Probability of an envelope being dropped
+    pub drop_rate: f64,
+    /// Probability of an envelope being duplicated
+    pub duplicate_rate: f64,
+    /// Probability of an envelope being delivered out of order
+    pub reorder_rate: f64,
+}
+
+impl FaultConfig {
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+}
+
+/// What happens to a single envelope on its way through the wrapper
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    Deliver,
+    Drop,
+    Duplicate,
+    Reorder,
+}
+
+struct InjectorState {
+    rng: StdRng,
+    queued_errors: HashMap<Endpoint, VecDeque<ErrorKind>>,
+    partitioned: bool,
+    partitioned_installations: HashSet<Vec<u8>>,
+}
+
+/// Controls the faults of a [`FaultInjectingApiClient`].
+/// Keep a handle to it to change faults after the client has been moved into a `Client`.
+pub struct FaultInjector {
+    config: FaultConfig,
+    state: Mutex<InjectorState>,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self {
+            config,
+            state: Mutex::new(InjectorState {
+                rng,
+                queued_errors: HashMap::new(),
+                partitioned: false,
+                partitioned_installations: HashSet::new(),
+            }),
+        }
+    }
+
+    /// Fail the next call to `endpoint` with `kind`. Queued errors are returned in order.
+    pub fn fail_next(&self, endpoint: Endpoint, kind: ErrorKind) {
+        self.state
+            .lock()
+            .queued_errors
+            .entry(endpoint)
+            .or_default()
+            .push_back(kind);
+    }
+
+    /// Cut the client off from the network. Every call and open stream fails until [`Self::heal`].
+    pub fn partition(&self) {
+        self.state.lock().partitioned = true;
+    }
+
+    pub fn heal(&self) {
+        self.state.lock().partitioned = false;
+    }
+
+    /// Stop delivering welcomes to, and serving key packages of, `installation_key`
+    pub fn partition_installation(&self, installation_key: Vec<u8>) {
+        self.state
+            .lock()
+            .partitioned_installations
+            .insert(installation_key);
+    }
+
+    pub fn heal_installation(&self, installation_key: &[u8]) {
+        self.state
+            .lock()
+            .partitioned_installations
+            .remove(installation_key);
+    }
+
+    fn is_partitioned(&self) -> bool {
+        self.state.lock().partitioned
+    }
+
+    fn is_installation_partitioned(&self, installation_key: &[u8]) -> bool {
+        self.state
+            .lock()
+            .partitioned_installations
+            .contains(installation_key)
+    }
+
+    /// Apply latency and error faults ahead of a call to `endpoint`
+    async fn before_call(&self, endpoint: Endpoint) -> Result<(), Error> {
+        let (latency, result) = {
+            let mut state = self.state.lock();
+            let latency = self
+                .config
+                .latency
+                .clone()
+                .filter(|range| !range.is_empty())
+                .map(|range| state.rng.gen_range(range));
+
+            let result = if state.partitioned {
+                Err(Error::new(ErrorKind::SetupConnectionError)
+                    .with(format!("injected partition on {endpoint:?}")))
+            } else if let Some(kind) = state
+                .queued_errors
+                .get_mut(&endpoint)
+                .and_then(VecDeque::pop_front)
+            {
+                Err(Error::new(kind).with(format!("injected error on {endpoint:?}")))
+            } else if roll(&mut state.rng, self.config.error_rate) {
+                Err(Error::new(endpoint.error_kind())
+                    .with(format!("injected error on {endpoint:?}")))
+            } else {
+                Ok(())
+            };
+            (latency, result)
+        };
+
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        if let Err(e) = &result {
+            tracing::debug!("{}", e);
+        }
+        result
+    }
+
+    fn delivery(&self) -> Delivery {
+        let mut state = self.state.lock();
+        if roll(&mut state.rng, self.config.drop_rate) {
+            Delivery::Drop
+        } else if roll(&mut state.rng, self.config.duplicate_rate) {
+            Delivery::Duplicate
+        } else if roll(&mut state.rng, self.config.reorder_rate) {
+            Delivery::Reorder
+        } else {
+            Delivery::Deliver
+        }
+    }
+
+    /// Drop or duplicate outgoing messages
+    fn outgoing<T: Clone>(&self, messages: Vec<T>) -> Vec<T> {
+        let mut out = Vec::with_capacity(messages.len());
+        for message in messages {
+            match self.delivery() {
+                Delivery::Drop => continue,
+                Delivery::Duplicate => {
+                    out.push(message.clone());
+                    out.push(message);
+                }
+                Delivery::Deliver | Delivery::Reorder => out.push(message),
+            }
+        }
+        out
+    }
+
+    /// Duplicate or reorder envelopes in a query response.
+    /// Envelopes are never dropped from a page, since the client would skip them for good.
+    fn page<T: Clone>(&self, envelopes: Vec<T>) -> Vec<T> {
+        let mut out = Vec::with_capacity(envelopes.len());
+        let mut shuffle = false;
+        for envelope in envelopes {
+            match self.delivery() {
+                Delivery::Duplicate => {
+                    out.push(envelope.clone());
+                    out.push(envelope);
+                }
+                Delivery::Reorder => {
+                    shuffle = true;
+                    out.push(envelope);
+                }
+                Delivery::Deliver | Delivery::Drop => out.push(envelope),
+            }
+        }
+        if shuffle {
+            out.shuffle(&mut self.state.lock().rng);
+        }
+        out
+    }
+}
+
+fn roll(rng: &mut StdRng, probability: f64) -> bool {
+    probability > 0.0 && rng.gen_bool(probability.min(1.0))
+}
+
+/// Apply delivery faults to a stream of envelopes
+fn faulty_stream<'a, T>(
+    injector: Arc<FaultInjector>,
+    stream: impl Stream<Item = Result<T, Error>> + Send + 'a,
+) -> BoxStream<'a, Result<T, Error>>
+where
+    T: Clone + Send + 'a,
+{
+    async_stream::stream! {
+        futures::pin_mut!(stream);
+        let mut held_back: Option<T> = None;
+
+        while let Some(item) = stream.next().await {
+            if injector.is_partitioned() {
+                yield Err(Error::new(ErrorKind::SubscribeError).with("injected partition"));
+                break;
+            }
+            let envelope = match item {
+                Ok(envelope) => envelope,
+                Err(e) => {
+                    yield Err(e);
+                    continue;
+                }
+            };
+
+            match injector.delivery() {
+                Delivery::Drop => continue,
+                Delivery::Duplicate => {
+                    yield Ok(envelope.clone());
+                    yield Ok(envelope);
+                }
+                Delivery::Reorder if held_back.is_none() => {
+                    held_back = Some(envelope);
+                    continue;
+                }
+                Delivery::Reorder | Delivery::Deliver => yield Ok(envelope),
+            }
+
+            if let Some(envelope) = held_back.take() {
+                yield Ok(envelope);
+            }
+        }
+
+        if let Some(envelope) = held_back.take() {
+            yield Ok(envelope);
+        }
+    }
+    .boxed()
+}
+
+/// Wraps an API client and injects the faults configured in its [`FaultInjector`]
+pub struct FaultInjectingApiClient<ApiClient> {
+    inner: ApiClient,
+    injector: Arc<FaultInjector>,
+}
+
+impl<ApiClient> FaultInjectingApiClient<ApiClient> {
+    pub fn new(inner: ApiClient, config: FaultConfig) -> Self {
+        Self {
+            inner,
+            injector: Arc::new(FaultInjector::new(config)),
+        }
+    }
+
+    pub fn injector(&self) -> Arc<FaultInjector> {
+        self.injector.clone()
+    }
+}
+
+impl<ApiClient> ClientWithMetadata for FaultInjectingApiClient<ApiClient>
+where
+    ApiClient: ClientWithMetadata,
+{
+    fn set_libxmtp_version(&mut self, version: String) -> Result<(), Error> {
+        self.inner.set_libxmtp_version(version)
+    }
+
+    fn set_app_version(&mut self, version: String) -> Result<(), Error> {
+        self.inner.set_app_version(version)
+    }
+}
+
+impl<ApiClient> XmtpMlsClient for FaultInjectingApiClient<ApiClient>
+where
+    ApiClient: XmtpMlsClient + Send + Sync,
+{
+    async fn upload_key_package(&self, request: UploadKeyPackageRequest) -> Result<(), Error> {
+        self.injector
+            .before_call(Endpoint::UploadKeyPackage)
+            .await?;
+        self.inner.upload_key_package(request).await
+    }
+
+    async fn fetch_key_packages(
+        &self,
+        request: FetchKeyPackagesRequest,
+    ) -> Result<FetchKeyPackagesResponse, Error> {
+        self.injector
+            .before_call(Endpoint::FetchKeyPackages)
+            .await?;
+        if let Some(key) = request
+            .installation_keys
+            .iter()
+            .find(|key| self.injector.is_installation_partitioned(key))
+        {
+            return Err(Error::new(ErrorKind::MlsError).with(format!(
+                "injected partition of installation {}",
+                hex::encode(key)
+            )));
+        }
+        self.inner.fetch_key_packages(request).await
+    }
+
+    async fn send_group_messages(
+        &self,
+        mut request: SendGroupMessagesRequest,
+    ) -> Result<(), Error> {
+        self.injector
+            .before_call(Endpoint::SendGroupMessages)
+            .await?;
+        request.messages = self.injector.outgoing(request.messages);
+        if request.messages.is_empty() {
+            return Ok(());
+        }
+        self.inner.send_group_messages(request).await
+    }
+
+    async fn send_welcome_messages(
+        &self,
+        mut request: SendWelcomeMessagesRequest,
+    ) -> Result<(), Error> {
+        self.injector
+            .before_call(Endpoint::SendWelcomeMessages)
+            .await?;
+        let reachable: Vec<WelcomeMessageInput> = request
+            .messages
+            .into_iter()
+            .filter(|welcome| match &welcome.version {
+                Some(welcome_message_input::Version::V1(v1)) => !self
+                    .injector
+                    .is_installation_partitioned(&v1.installation_key),
+                None => true,
+            })
+            .collect();
+        request.messages = self.injector.outgoing(reachable);
+        if request.messages.is_empty() {
+            return Ok(());
+        }
+        self.inner.send_welcome_messages(request).await
+    }
+
+    async fn query_group_messages(
+        &self,
+        request: QueryGroupMessagesRequest,
+    ) -> Result<QueryGroupMessagesResponse, Error> {
+        self.injector
+            .before_call(Endpoint::QueryGroupMessages)
+            .await?;
+        let mut response = self.inner.query_group_messages(request).await?;
+        response.messages = self.injector.page(response.messages);
+        Ok(response)
+    }
+
+    async fn query_welcome_messages(
+        &self,
+        request: QueryWelcomeMessagesRequest,
+    ) -> Result<QueryWelcomeMessagesResponse, Error> {
+        self.injector
+            .before_call(Endpoint::QueryWelcomeMessages)
+            .await?;
+        if self
+            .injector
+            .is_installation_partitioned(&request.installation_key)
+        {
+            return Err(Error::new(ErrorKind::SetupConnectionError)
+                .with("injected partition of installation"));
+        }
+        let mut response = self.inner.query_welcome_messages(request).await?;
+        response.messages = self.injector.page(response.messages);
+        Ok(response)
+    }
+}
+
+impl<ApiClient> XmtpMlsStreams for FaultInjectingApiClient<ApiClient>
+where
+    ApiClient: XmtpMlsStreams + Send + Sync,
+{
+    type GroupMessageStream<'a>
+        = BoxStream<'a, Result<GroupMessage, Error>>
+    where
+        Self: 'a;
+    type WelcomeMessageStream<'a>
+        = BoxStream<'a, Result<WelcomeMessage, Error>>
+    where
+        Self: 'a;
+
+    async fn subscribe_group_messages(
+        &self,
+        request: SubscribeGroupMessagesRequest,
+    ) -> Result<Self::GroupMessageStream<'_>, Error> {
+        self.injector
+            .before_call(Endpoint::SubscribeGroupMessages)
+            .await?;
+        let stream = self.inner.subscribe_group_messages(request).await?;
+        Ok(faulty_stream(self.injector.clone(), stream))
+    }
+
+    async fn subscribe_welcome_messages(
+        &self,
+        request: SubscribeWelcomeMessagesRequest,
+    ) -> Result<Self::WelcomeMessageStream<'_>, Error> {
+        self.injector
+            .before_call(Endpoint::SubscribeWelcomeMessages)
+            .await?;
+        if request.filters.iter().any(|filter| {
+            self.injector
+                .is_installation_partitioned(&filter.installation_key)
+        }) {
+            return Err(Error::new(ErrorKind::SetupConnectionError)
+                .with("injected partition of installation"));
+        }
+        let stream = self.inner.subscribe_welcome_messages(request).await?;
+        Ok(faulty_stream(self.injector.clone(), stream))
+    }
+}
+
+impl<ApiClient> XmtpIdentityClient for FaultInjectingApiClient<ApiClient>
+where
+    ApiClient: XmtpIdentityClient + Send + Sync,
+{
+    async fn publish_identity_update(
+        &self,
+        request: PublishIdentityUpdateRequest,
+    ) -> Result<PublishIdentityUpdateResponse, Error> {
+        self.injector
+            .before_call(Endpoint::PublishIdentityUpdate)
+            .await?;
+        self.inner.publish_identity_update(request).await
+    }
+
+    async fn get_identity_updates_v2(
+        &self,
+        request: GetIdentityUpdatesV2Request,
+    ) -> Result<GetIdentityUpdatesV2Response, Error> {
+        self.injector
+            .before_call(Endpoint::GetIdentityUpdates)
+            .await?;
+        self.inner.get_identity_updates_v2(request).await
+    }
+
+    async fn get_inbox_ids(
+        &self,
+        request: GetInboxIdsRequest,
+    ) -> Result<GetInboxIdsResponse, Error> {
+        self.injector.before_call(Endpoint::GetInboxIds).await?;
+        self.inner.get_inbox_ids(request).await
+    }
+}
+
+impl<ApiClient> XmtpTestClient for FaultInjectingApiClient<ApiClient>
+where
+    ApiClient: XmtpTestClient + Send,
+{
+    async fn create_local() -> Self {
+        FaultInjectingApiClient::new(ApiClient::create_local().await, FaultConfig::default())
+    }
+
+    async fn create_dev() -> Self {
+        FaultInjectingApiClient::new(ApiClient::create_dev().await, FaultConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xmtp_proto::{
+        api_client::{ErrorKind, XmtpIdentityClient, XmtpMlsClient},
+        xmtp::{
+            identity::api::v1::{GetInboxIdsRequest, GetInboxIdsResponse},
+            mls::api::v1::{
+                group_message, group_message_input, welcome_message_input, FetchKeyPackagesRequest,
+                GroupMessageInput, QueryGroupMessagesRequest, QueryGroupMessagesResponse,
+                SendGroupMessagesRequest, SendWelcomeMessagesRequest, WelcomeMessageInput,
+            },
+        },
+    };
+
+    use super::{Endpoint, FaultConfig, FaultInjectingApiClient};
+    use crate::api::test_utils::{build_group_messages, MockApiClient};
+
+    fn group_message_input(data: u8) -> GroupMessageInput {
+        GroupMessageInput {
+            version: Some(group_message_input::Version::V1(group_message_input::V1 {
+                data: vec![data],
+                sender_hmac: vec![],
+            })),
+        }
+    }
+
+    fn message_ids(response: &QueryGroupMessagesResponse) -> Vec<u64> {
+        response
+            .messages
+            .iter()
+            .map(|message| match &message.version {
+                Some(group_message::Version::V1(v1)) => v1.id,
+                None => panic!("missing version"),
+            })
+            .collect()
+    }
+
+    async fn query_with_seed(seed: u64) -> Vec<u64> {
+        let mut mock_api = MockApiClient::new();
+        mock_api.expect_query_group_messages().returning(|_| {
+            Ok(QueryGroupMessagesResponse {
+                messages: build_group_messages(20, vec![1, 2, 3]),
+                paging_info: None,
+            })
+        });
+        let client = FaultInjectingApiClient::new(
+            mock_api,
+            FaultConfig {
+                duplicate_rate: 0.2,
+                reorder_rate: 0.2,
+                ..FaultConfig::with_seed(seed)
+            },
+        );
+
+        let response = client
+            .query_group_messages(QueryGroupMessagesRequest {
+                group_id: vec![1, 2, 3],
+                paging_info: None,
+            })
+            .await
+            .unwrap();
+        message_ids(&response)
+    }
+
+    #[tokio::test]
+    async fn test_faults_are_deterministic() {
+        let first = query_with_seed(42).await;
+        assert_eq!(first, query_with_seed(42).await);
+        assert_ne!(first, (0..20).collect::<Vec<u64>>());
+    }
+
+    #[tokio::test]
+    async fn test_fail_next() {
+        let mut mock_api = MockApiClient::new();
+        mock_api
+            .expect_get_inbox_ids()
+            .times(1)
+            .returning(|_| Ok(GetInboxIdsResponse { responses: vec![] }));
+        let client = FaultInjectingApiClient::new(mock_api, FaultConfig::default());
+        let injector = client.injector();
+        injector.fail_next(Endpoint::GetInboxIds, ErrorKind::QueryError);
+        injector.fail_next(Endpoint::GetInboxIds, ErrorKind::IdentityError);
+
+        let request = || GetInboxIdsRequest { requests: vec![] };
+        let err = client.get_inbox_ids(request()).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::QueryError);
+        let err = client.get_inbox_ids(request()).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::IdentityError);
+        assert!(client.get_inbox_ids(request()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_drop_all_outgoing_messages() {
+        let mut mock_api = MockApiClient::new();
+        mock_api.expect_send_group_messages().never();
+        let client = FaultInjectingApiClient::new(
+            mock_api,
+            FaultConfig {
+                drop_rate: 1.0,
+                ..Default::default()
+            },
+        );
+
+        client
+            .send_group_messages(SendGroupMessagesRequest {
+                messages: vec![group_message_input(1), group_message_input(2)],
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_partitions() {
+        let mut mock_api = MockApiClient::new();
+        mock_api
+            .expect_send_welcome_messages()
+            .withf(|request| request.messages.len() == 1)
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_api.expect_fetch_key_packages().never();
+        let client = FaultInjectingApiClient::new(mock_api, FaultConfig::default());
+        let injector = client.injector();
+
+        let welcome = |installation_key: Vec<u8>| WelcomeMessageInput {
+            version: Some(welcome_message_input::Version::V1(
+                welcome_message_input::V1 {
+                    installation_key,
+                    data: vec![],
+                    hpke_public_key: vec![],
+                },
+            )),
+        };
+        injector.partition_installation(vec![1]);
+        client
+            .send_welcome_messages(SendWelcomeMessagesRequest {
+                messages: vec![welcome(vec![1]), welcome(vec![2])],
+            })
+            .await
+            .unwrap();
+        assert!(client
+            .fetch_key_packages(FetchKeyPackagesRequest {
+                installation_keys: vec![vec![1]],
+            })
+            .await
+            .is_err());
+
+        injector.partition();
+        let err = client
+            .get_inbox_ids(GetInboxIdsRequest { requests: vec![] })
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::SetupConnectionError);
+    }
+}
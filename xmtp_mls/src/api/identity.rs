@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::{ApiClientWrapper, WrappedApiError};
+use super::{ApiClientWrapper, Endpoint, WrappedApiError};
 use crate::XmtpApi;
 use futures::future::try_join_all;
 use xmtp_id::{
@@ -70,6 +70,9 @@ where
         &self,
         update: UnverifiedIdentityUpdate,
     ) -> Result<(), WrappedApiError> {
+        self.rate_limiter
+            .acquire(Endpoint::PublishIdentityUpdate)
+            .await;
         self.api_client
             .publish_identity_update(PublishIdentityUpdateRequest {
                 identity_update: Some(update.into()),
@@ -88,10 +91,23 @@ where
 
         let chunked_results: Result<Vec<GetIdentityUpdatesResponse>, WrappedApiError> =
             try_join_all(chunks.map(|chunk| async move {
+                let requests: Vec<GetIdentityUpdatesV2RequestProto> =
+                    chunk.iter().map(|filter| filter.into()).collect();
+                let key = requests
+                    .iter()
+                    .map(|request| (request.inbox_id.clone(), request.sequence_id))
+                    .collect();
                 let result = self
-                    .api_client
-                    .get_identity_updates_v2(GetIdentityUpdatesV2Request {
-                        requests: chunk.iter().map(|filter| filter.into()).collect(),
+                    .identity_updates_in_flight
+                    .run(key, || async {
+                        self.rate_limiter
+                            .acquire(Endpoint::GetIdentityUpdates)
+                            .await;
+                        self.api_client
+                            .get_identity_updates_v2(GetIdentityUpdatesV2Request {
+                                requests: requests.clone(),
+                            })
+                            .await
                     })
                     .await?;
 
@@ -126,6 +142,7 @@ where
             "Getting inbox_ids for account addresses: {:?}",
             &account_addresses
         );
+        self.rate_limiter.acquire(Endpoint::GetInboxIds).await;
         let result = self
             .api_client
             .get_inbox_ids(GetInboxIdsRequest {
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use super::ApiClientWrapper;
+use futures::{StreamExt, TryStreamExt};
+
+use super::{ApiClientWrapper, Endpoint};
 use crate::{retry_async, XmtpApi};
 use xmtp_proto::api_client::{Error as ApiError, ErrorKind};
 use xmtp_proto::xmtp::mls::api::v1::{
@@ -59,6 +61,10 @@ pub enum IdentityUpdate {
 
 type KeyPackageMap = HashMap<Vec<u8>, Vec<u8>>;
 
+/// The maximum number of group queries
+/// [`ApiClientWrapper::query_group_messages_concurrently`] runs at once
+pub const QUERY_GROUP_MESSAGES_CONCURRENCY: usize = 10;
+
 impl<ApiClient> ApiClientWrapper<ApiClient>
 where
     ApiClient: XmtpApi,
@@ -76,6 +82,9 @@ where
             let mut result = retry_async!(
                 self.retry_strategy,
                 (async {
+                    self.rate_limiter
+                        .acquire(Endpoint::QueryGroupMessages)
+                        .await;
                     self.api_client
                         .query_group_messages(QueryGroupMessagesRequest {
                             group_id: group_id.clone(),
@@ -107,6 +116,26 @@ where
         Ok(out)
     }
 
+    /// Query the messages of several groups.
+    /// The API has no multi-group query, so this issues one paginated query per group with at most
+    /// `QUERY_GROUP_MESSAGES_CONCURRENCY` in flight, each one subject to the rate limiter.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub async fn query_group_messages_concurrently(
+        &self,
+        filters: Vec<GroupFilter>,
+    ) -> Result<HashMap<Vec<u8>, Vec<GroupMessage>>, ApiError> {
+        futures::stream::iter(filters)
+            .map(|filter| async move {
+                let messages = self
+                    .query_group_messages(filter.group_id.clone(), filter.id_cursor)
+                    .await?;
+                Ok::<_, ApiError>((filter.group_id, messages))
+            })
+            .buffer_unordered(QUERY_GROUP_MESSAGES_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     pub async fn query_welcome_messages(
         &self,
@@ -120,6 +149,9 @@ where
             let mut result = retry_async!(
                 self.retry_strategy,
                 (async {
+                    self.rate_limiter
+                        .acquire(Endpoint::QueryWelcomeMessages)
+                        .await;
                     self.api_client
                         .query_welcome_messages(QueryWelcomeMessagesRequest {
                             installation_key: installation_id.clone(),
@@ -164,6 +196,7 @@ where
         retry_async!(
             self.retry_strategy,
             (async {
+                self.rate_limiter.acquire(Endpoint::UploadKeyPackage).await;
                 self.api_client
                     .upload_key_package(UploadKeyPackageRequest {
                         key_package: Some(KeyPackageUpload {
@@ -183,16 +216,22 @@ where
         &self,
         installation_keys: Vec<Vec<u8>>,
     ) -> Result<KeyPackageMap, ApiError> {
-        let res = retry_async!(
-            self.retry_strategy,
-            (async {
-                self.api_client
-                    .fetch_key_packages(FetchKeyPackagesRequest {
-                        installation_keys: installation_keys.clone(),
+        let res = self
+            .key_packages_in_flight
+            .run(installation_keys.clone(), || async {
+                retry_async!(
+                    self.retry_strategy,
+                    (async {
+                        self.rate_limiter.acquire(Endpoint::FetchKeyPackages).await;
+                        self.api_client
+                            .fetch_key_packages(FetchKeyPackagesRequest {
+                                installation_keys: installation_keys.clone(),
+                            })
+                            .await
                     })
-                    .await
+                )
             })
-        )?;
+            .await?;
 
         if res.key_packages.len() != installation_keys.len() {
             println!("mismatched number of results");
@@ -222,6 +261,9 @@ where
         retry_async!(
             self.retry_strategy,
             (async {
+                self.rate_limiter
+                    .acquire(Endpoint::SendWelcomeMessages)
+                    .await;
                 self.api_client
                     .send_welcome_messages(SendWelcomeMessagesRequest {
                         messages: messages.to_vec(),
@@ -248,6 +290,7 @@ where
         retry_async!(
             self.retry_strategy,
             (async {
+                self.rate_limiter.acquire(Endpoint::SendGroupMessages).await;
                 self.api_client
                     .send_group_messages(SendGroupMessagesRequest {
                         messages: to_send.clone(),
@@ -263,6 +306,9 @@ where
         &self,
         filters: Vec<GroupFilter>,
     ) -> Result<impl futures::Stream<Item = Result<GroupMessage, ApiError>> + '_, ApiError> {
+        self.rate_limiter
+            .acquire(Endpoint::SubscribeGroupMessages)
+            .await;
         self.api_client
             .subscribe_group_messages(SubscribeGroupMessagesRequest {
                 filters: filters.into_iter().map(|f| f.into()).collect(),
@@ -275,6 +321,9 @@ where
         installation_key: Vec<u8>,
         id_cursor: Option<u64>,
     ) -> Result<impl futures::Stream<Item = Result<WelcomeMessage, ApiError>> + '_, ApiError> {
+        self.rate_limiter
+            .acquire(Endpoint::SubscribeWelcomeMessages)
+            .await;
         self.api_client
             .subscribe_welcome_messages(SubscribeWelcomeMessagesRequest {
                 filters: vec![WelcomeFilterProto {
@@ -290,6 +339,7 @@ where
 pub mod tests {
     use super::super::test_utils::*;
     use super::super::*;
+    use crate::retry::RetryBudget;
 
     use xmtp_proto::{
         api_client::{Error, ErrorKind},
@@ -320,6 +370,27 @@ pub mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_retries_draw_from_the_budget() {
+        let mut mock_api = MockApiClient::new();
+        // The first attempt and the two retries the budget allows
+        mock_api
+            .expect_upload_key_package()
+            .times(3)
+            .returning(|_| Err(Error::new(ErrorKind::MlsError)));
+        let budget = RetryBudget::new(2, 0.1);
+        let retry = Retry::builder()
+            .retries(10)
+            .duration(std::time::Duration::from_millis(1))
+            .budget(budget.clone())
+            .build();
+        let wrapper = ApiClientWrapper::new(mock_api, retry);
+
+        let result = wrapper.upload_key_package(vec![1, 2, 3], false).await;
+        assert!(result.is_err());
+        assert_eq!(budget.remaining(), 0.0);
+    }
+
     #[tokio::test]
     async fn test_fetch_key_packages() {
         let mut mock_api = MockApiClient::new();
@@ -382,6 +453,39 @@ pub mod tests {
         assert_eq!(result.len(), 10);
     }
 
+    #[tokio::test]
+    async fn test_query_group_messages_concurrently() {
+        let mut mock_api = MockApiClient::new();
+        mock_api
+            .expect_query_group_messages()
+            .times(3)
+            .returning(move |req| {
+                let num_messages = req.group_id[0] as usize;
+                Ok(QueryGroupMessagesResponse {
+                    paging_info: None,
+                    messages: build_group_messages(num_messages, req.group_id),
+                })
+            });
+
+        let wrapper = ApiClientWrapper::new(mock_api, Retry::default()).with_rate_limiter(
+            rate_limit::RateLimiter::new().with_limit(
+                Endpoint::QueryGroupMessages,
+                rate_limit::RateLimit::new(1, 100.0),
+            ),
+        );
+
+        let result = wrapper
+            .query_group_messages_concurrently(
+                (1..=3).map(|i| GroupFilter::new(vec![i], None)).collect(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 3);
+        for i in 1..=3 {
+            assert_eq!(result[&vec![i]].len(), i as usize);
+        }
+    }
+
     #[tokio::test]
     async fn test_read_group_messages_single_page_exactly_100_results() {
         let mut mock_api = MockApiClient::new();
@@ -1,5 +1,8 @@
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fault_injection;
 pub mod identity;
 pub mod mls;
+pub mod rate_limit;
 #[cfg(test)]
 pub mod test_utils;
 
@@ -7,13 +10,33 @@ use crate::{
     retry::{Retry, RetryableError},
     XmtpApi,
 };
+use rate_limit::{InFlight, RateLimiter};
 use thiserror::Error;
 use xmtp_id::associations::DeserializationError as AssociationDeserializationError;
 use xmtp_proto::api_client::Error as ApiError;
+use xmtp_proto::xmtp::{
+    identity::api::v1::GetIdentityUpdatesResponse, mls::api::v1::FetchKeyPackagesResponse,
+};
 
 pub use identity::*;
 pub use mls::*;
 
+/// The endpoints of the XMTP API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    UploadKeyPackage,
+    FetchKeyPackages,
+    SendGroupMessages,
+    SendWelcomeMessages,
+    QueryGroupMessages,
+    QueryWelcomeMessages,
+    SubscribeGroupMessages,
+    SubscribeWelcomeMessages,
+    PublishIdentityUpdate,
+    GetIdentityUpdates,
+    GetInboxIds,
+}
+
 #[derive(Debug, Error)]
 pub enum WrappedApiError {
     #[error("API client error: {0}")]
@@ -32,6 +55,9 @@ impl RetryableError for WrappedApiError {
 pub struct ApiClientWrapper<ApiClient> {
     api_client: ApiClient,
     retry_strategy: Retry,
+    rate_limiter: RateLimiter,
+    identity_updates_in_flight: InFlight<Vec<(String, u64)>, GetIdentityUpdatesResponse>,
+    key_packages_in_flight: InFlight<Vec<Vec<u8>>, FetchKeyPackagesResponse>,
 }
 
 impl<ApiClient> ApiClientWrapper<ApiClient>
@@ -42,6 +68,20 @@ where
         Self {
             api_client,
             retry_strategy,
+            rate_limiter: RateLimiter::default(),
+            identity_updates_in_flight: InFlight::default(),
+            key_packages_in_flight: InFlight::default(),
         }
     }
+
+    /// The strategy used to retry calls made by this wrapper. Clones share its retry budget
+    pub fn retry_strategy(&self) -> &Retry {
+        &self.retry_strategy
+    }
+
+    /// Throttle requests made by this wrapper
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
 }
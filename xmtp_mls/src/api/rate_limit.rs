@@ -0,0 +1,326 @@
+//! Client-side throttling of API requests.
+//!
+//! [`RateLimiter`] holds a token bucket per [`Endpoint`], so a burst of syncs waits locally instead
+//! of being rejected by the node's own rate limits. [`InFlight`] lets concurrent callers asking for
+//! the same thing share a single request.
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use tokio::sync::broadcast;
+use xmtp_proto::api_client::{Error as ApiError, ErrorKind};
+
+use super::Endpoint;
+use crate::configuration::{
+    PUBLISH_BURST, PUBLISH_PER_SECOND, QUERY_BURST, QUERY_PER_SECOND, SUBSCRIBE_BURST,
+    SUBSCRIBE_PER_SECOND,
+};
+
+/// The rate a single endpoint may be called at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests that can be made at once before throttling kicks in
+    pub burst: u32,
+    /// Requests replenished every second
+    pub per_second: f64,
+}
+
+impl RateLimit {
+    pub fn new(burst: u32, per_second: f64) -> Self {
+        Self { burst, per_second }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long to wait until one is available
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.limit.per_second <= 0.0 {
+            // never refills, check back in a while in case the limit is changed
+            return Err(Duration::from_secs(1));
+        }
+        Err(Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.limit.per_second,
+        ))
+    }
+}
+
+/// Throttles requests with a token bucket per endpoint.
+/// Endpoints without a limit are never throttled.
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: HashMap<Endpoint, Mutex<TokenBucket>>,
+}
+
+/// Stays under the node's limits, which are shared by every installation behind an address
+impl Default for RateLimiter {
+    fn default() -> Self {
+        let query = RateLimit::new(QUERY_BURST, QUERY_PER_SECOND);
+        let publish = RateLimit::new(PUBLISH_BURST, PUBLISH_PER_SECOND);
+        let subscribe = RateLimit::new(SUBSCRIBE_BURST, SUBSCRIBE_PER_SECOND);
+
+        Self::new()
+            .with_limit(Endpoint::QueryGroupMessages, query)
+            .with_limit(Endpoint::QueryWelcomeMessages, query)
+            .with_limit(Endpoint::FetchKeyPackages, query)
+            .with_limit(Endpoint::GetIdentityUpdates, query)
+            .with_limit(Endpoint::GetInboxIds, query)
+            .with_limit(Endpoint::SendGroupMessages, publish)
+            .with_limit(Endpoint::SendWelcomeMessages, publish)
+            .with_limit(Endpoint::UploadKeyPackage, publish)
+            .with_limit(Endpoint::PublishIdentityUpdate, publish)
+            .with_limit(Endpoint::SubscribeGroupMessages, subscribe)
+            .with_limit(Endpoint::SubscribeWelcomeMessages, subscribe)
+    }
+}
+
+impl RateLimiter {
+    /// A limiter without any limits, add them with [`Self::with_limit`]
+    pub fn new() -> Self {
+        Self {
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Limit requests to `endpoint`
+    pub fn with_limit(mut self, endpoint: Endpoint, limit: RateLimit) -> Self {
+        self.buckets
+            .insert(endpoint, Mutex::new(TokenBucket::new(limit)));
+        self
+    }
+
+    /// Wait until a request to `endpoint` is allowed
+    pub async fn acquire(&self, endpoint: Endpoint) {
+        let Some(bucket) = self.buckets.get(&endpoint) else {
+            return;
+        };
+
+        loop {
+            let wait = match bucket.lock().try_take() {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+            tracing::debug!("rate limited on {:?}, waiting {:?}", endpoint, wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// The outcome of a shared request, as received by the callers that joined it.
+/// [`ApiError`] can not be cloned, so joined callers get an error of the same kind.
+type SharedResult<V> = Result<V, (ErrorKind, String)>;
+
+/// Coalesces identical concurrent requests.
+///
+/// The first caller for a key makes the request, callers arriving while it is in flight wait for
+/// its result instead of making their own.
+pub struct InFlight<K, V> {
+    calls: Arc<Mutex<HashMap<K, broadcast::Sender<SharedResult<V>>>>>,
+}
+
+impl<K, V> fmt::Debug for InFlight<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InFlight")
+            .field("calls", &self.calls.lock().len())
+            .finish()
+    }
+}
+
+impl<K, V> Default for InFlight<K, V> {
+    fn default() -> Self {
+        Self {
+            calls: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// Removes the in-flight entry when the request finishes, or when the caller making it gives up.
+/// Dropping the sender wakes any joined callers, which then make the request themselves.
+struct InFlightGuard<K: Hash + Eq, V> {
+    key: Option<K>,
+    calls: Arc<Mutex<HashMap<K, broadcast::Sender<SharedResult<V>>>>>,
+}
+
+impl<K: Hash + Eq, V> InFlightGuard<K, V> {
+    fn finish(mut self) -> Option<broadcast::Sender<SharedResult<V>>> {
+        let key = self.key.take()?;
+        self.calls.lock().remove(&key)
+    }
+}
+
+impl<K: Hash + Eq, V> Drop for InFlightGuard<K, V> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.calls.lock().remove(&key);
+        }
+    }
+}
+
+impl<K, V> InFlight<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    pub async fn run<F, Fut>(&self, key: K, request: F) -> Result<V, ApiError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<V, ApiError>>,
+    {
+        let existing = {
+            let mut calls = self.calls.lock();
+            match calls.get(&key) {
+                Some(sender) => Some(sender.subscribe()),
+                None => {
+                    let (sender, _) = broadcast::channel(1);
+                    calls.insert(key.clone(), sender);
+                    None
+                }
+            }
+        };
+
+        if let Some(mut receiver) = existing {
+            match receiver.recv().await {
+                Ok(result) => {
+                    return result.map_err(|(kind, message)| ApiError::new(kind).with(message))
+                }
+                // the caller making the request went away, make it ourselves
+                Err(_) => return request().await,
+            }
+        }
+
+        let guard = InFlightGuard {
+            key: Some(key),
+            calls: self.calls.clone(),
+        };
+        let result = request().await;
+        if let Some(sender) = guard.finish() {
+            let shared = match &result {
+                Ok(value) => Ok(value.clone()),
+                Err(e) => Err((*e.kind(), e.to_string())),
+            };
+            // an error only means nobody joined the request
+            let _ = sender.send(shared);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use futures::future::join_all;
+    use xmtp_proto::api_client::{Error as ApiError, ErrorKind};
+
+    use super::{InFlight, RateLimit, RateLimiter};
+    use crate::api::Endpoint;
+
+    #[tokio::test]
+    async fn test_rate_limiter_throttles() {
+        let limiter =
+            RateLimiter::new().with_limit(Endpoint::FetchKeyPackages, RateLimit::new(2, 20.0));
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire(Endpoint::FetchKeyPackages).await;
+        }
+        // the burst is free, the next two requests each wait ~50ms
+        assert!(start.elapsed() >= Duration::from_millis(90));
+
+        // other endpoints are not limited
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire(Endpoint::GetInboxIds).await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_default_limits_every_endpoint() {
+        let limiter = RateLimiter::default();
+        for endpoint in [
+            Endpoint::UploadKeyPackage,
+            Endpoint::FetchKeyPackages,
+            Endpoint::SendGroupMessages,
+            Endpoint::SendWelcomeMessages,
+            Endpoint::QueryGroupMessages,
+            Endpoint::QueryWelcomeMessages,
+            Endpoint::SubscribeGroupMessages,
+            Endpoint::SubscribeWelcomeMessages,
+            Endpoint::PublishIdentityUpdate,
+            Endpoint::GetIdentityUpdates,
+            Endpoint::GetInboxIds,
+        ] {
+            assert!(limiter.buckets.contains_key(&endpoint), "{:?}", endpoint);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_requests_are_shared() {
+        let in_flight: InFlight<Vec<u8>, u64> = InFlight::default();
+        let calls = AtomicUsize::new(0);
+
+        let results = join_all((0..5).map(|_| {
+            in_flight.run(vec![1], || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(42)
+            })
+        }))
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|r| r.unwrap() == 42));
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_errors_are_shared() {
+        let in_flight: InFlight<Vec<u8>, u64> = InFlight::default();
+
+        let results = join_all((0..2).map(|_| {
+            in_flight.run(vec![1], || async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Err(ApiError::new(ErrorKind::IdentityError).with("failed"))
+            })
+        }))
+        .await;
+
+        for result in results {
+            assert_eq!(result.unwrap_err().kind(), &ErrorKind::IdentityError);
+        }
+        // nothing is left behind once the request is done
+        assert_eq!(in_flight.calls.lock().len(), 0);
+    }
+}
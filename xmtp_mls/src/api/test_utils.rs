@@ -18,7 +18,7 @@ use xmtp_proto::{
     },
 };
 
-#[cfg(feature = "http-api")]
+#[cfg(any(feature = "http-api", feature = "memory-api"))]
 use xmtp_proto::xmtp::mls::api::v1::WelcomeMessage;
 
 use crate::XmtpTestClient;
@@ -61,14 +61,14 @@ mock! {
     }
 
     impl XmtpMlsStreams for ApiClient {
-        #[cfg(not(feature = "http-api"))]
+        #[cfg(not(any(feature = "http-api", feature = "memory-api")))]
         type GroupMessageStream<'a> = xmtp_api_grpc::GroupMessageStream;
-        #[cfg(not(feature = "http-api"))]
+        #[cfg(not(any(feature = "http-api", feature = "memory-api")))]
         type WelcomeMessageStream<'a> = xmtp_api_grpc::WelcomeMessageStream;
 
-        #[cfg(feature = "http-api")]
+        #[cfg(any(feature = "http-api", feature = "memory-api"))]
         type GroupMessageStream<'a> = futures::stream::BoxStream<'static, Result<GroupMessage, Error>>;
-        #[cfg(feature = "http-api")]
+        #[cfg(any(feature = "http-api", feature = "memory-api"))]
         type WelcomeMessageStream<'a> = futures::stream::BoxStream<'static, Result<WelcomeMessage, Error>>;
 
 
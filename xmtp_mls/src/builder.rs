@@ -2,10 +2,13 @@ use thiserror::Error;
 use tracing::debug;
 
 use xmtp_cryptography::signature::AddressValidationError;
-use xmtp_id::scw_verifier::{MultiSmartContractSignatureVerifier, SmartContractSignatureVerifier};
+use xmtp_id::scw_verifier::{
+    CachedSmartContractSignatureVerifier, MultiSmartContractSignatureVerifier,
+    SmartContractSignatureVerifier,
+};
 
 use crate::{
-    api::ApiClientWrapper,
+    api::{rate_limit::RateLimiter, ApiClientWrapper},
     client::Client,
     identity::{Identity, IdentityStrategy},
     identity_updates::load_identity_updates,
@@ -53,6 +56,10 @@ pub struct ClientBuilder<ApiClient> {
     history_sync_url: Option<String>,
     app_version: Option<String>,
     scw_verifier: Option<Box<dyn SmartContractSignatureVerifier>>,
+    rate_limiter: Option<RateLimiter>,
+    retry_strategy: Option<Retry>,
+    #[cfg(feature = "message-history")]
+    installation_metadata: Option<(String, String)>,
 }
 
 impl<ApiClient> ClientBuilder<ApiClient>
@@ -68,6 +75,10 @@ where
             history_sync_url: None,
             app_version: None,
             scw_verifier: None,
+            rate_limiter: None,
+            retry_strategy: None,
+            #[cfg(feature = "message-history")]
+            installation_metadata: None,
         }
     }
 
@@ -101,6 +112,25 @@ where
         self
     }
 
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// How network calls and group syncs are retried, defaults to [`Retry::client_default`]
+    pub fn retry_strategy(mut self, retry_strategy: Retry) -> Self {
+        self.retry_strategy = Some(retry_strategy);
+        self
+    }
+
+    /// The device name and platform shared with the other installations of the inbox,
+    /// see [`Client::set_installation_metadata`]
+    #[cfg(feature = "message-history")]
+    pub fn installation_metadata(mut self, device_name: String, platform: String) -> Self {
+        self.installation_metadata = Some((device_name, platform));
+        self
+    }
+
     pub async fn build(mut self) -> Result<Client<ApiClient>, ClientBuilderError> {
         debug!("Building client");
         let mut api_client =
@@ -120,11 +150,22 @@ where
             ))
         });
 
-        let api_client_wrapper = ApiClientWrapper::new(api_client, Retry::default());
+        let retry_strategy = self
+            .retry_strategy
+            .take()
+            .unwrap_or_else(Retry::client_default);
+        let mut api_client_wrapper = ApiClientWrapper::new(api_client, retry_strategy);
+        if let Some(rate_limiter) = self.rate_limiter.take() {
+            api_client_wrapper = api_client_wrapper.with_rate_limiter(rate_limiter);
+        }
         let store = self
             .store
             .take()
             .ok_or(ClientBuilderError::MissingParameter { parameter: "store" })?;
+        // Results are persisted, so rebuilding association states does not hit the RPC again
+        let scw_verifier: Box<dyn SmartContractSignatureVerifier> = Box::new(
+            CachedSmartContractSignatureVerifier::new(scw_verifier, Box::new(store.clone())),
+        );
         debug!("Initializing identity");
         let identity = self
             .identity_strategy
@@ -151,6 +192,13 @@ where
         #[cfg(not(feature = "message-history"))]
         let client = Client::new(api_client_wrapper, identity, store, scw_verifier);
 
+        #[cfg(feature = "message-history")]
+        if let Some((device_name, platform)) = self.installation_metadata {
+            client
+                .set_installation_metadata(device_name, platform)
+                .await?;
+        }
+
         Ok(client)
     }
 }
@@ -680,4 +728,104 @@ mod tests {
             .unwrap();
         assert_eq!(client_d.installation_public_key(), keybytes_a);
     }
+
+    #[tokio::test]
+    async fn resume_registration_after_restart() {
+        let tmpdb = tmp_path();
+        let wallet = &generate_local_wallet();
+        let db_key = EncryptedMessageStore::generate_enc_key();
+        let nonce = 1;
+        let inbox_id = generate_inbox_id(&wallet.get_address(), &nonce);
+        let strategy =
+            IdentityStrategy::CreateIfNotFound(inbox_id.clone(), wallet.get_address(), nonce, None);
+
+        let store_a =
+            EncryptedMessageStore::new(StorageOption::Persistent(tmpdb.clone()), db_key).unwrap();
+        let client_a = ClientBuilder::new(strategy.clone())
+            .local_client()
+            .await
+            .store(store_a)
+            .build()
+            .await
+            .unwrap();
+        let request_id = client_a
+            .save_signature_request(&client_a.context.signature_request().unwrap(), None)
+            .unwrap();
+        let keybytes_a = client_a.installation_public_key();
+        // The app is killed while waiting on the wallet
+        drop(client_a);
+
+        let store_b =
+            EncryptedMessageStore::new(StorageOption::Persistent(tmpdb.clone()), db_key).unwrap();
+        let client_b = ClientBuilder::new(strategy)
+            .local_client()
+            .await
+            .store(store_b)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client_b.installation_public_key(), keybytes_a);
+        assert!(!client_b.identity().is_ready());
+        assert_eq!(
+            client_b.context.signature_request().unwrap().id(),
+            request_id
+        );
+
+        register_client(&client_b, wallet).await;
+        assert!(client_b.identity().is_ready());
+        assert!(client_b
+            .resume_signature_request(&request_id)
+            .unwrap()
+            .is_none());
+        let association_state = client_b
+            .get_latest_association_state(&client_b.store().conn().unwrap(), &inbox_id)
+            .await
+            .unwrap();
+        assert!(association_state.installation_ids().contains(&keybytes_a));
+    }
+
+    #[tokio::test]
+    async fn expired_registration_is_pruned_on_build() {
+        let tmpdb = tmp_path();
+        let wallet = &generate_local_wallet();
+        let db_key = EncryptedMessageStore::generate_enc_key();
+        let nonce = 1;
+        let inbox_id = generate_inbox_id(&wallet.get_address(), &nonce);
+        let strategy =
+            IdentityStrategy::CreateIfNotFound(inbox_id.clone(), wallet.get_address(), nonce, None);
+
+        let store_a =
+            EncryptedMessageStore::new(StorageOption::Persistent(tmpdb.clone()), db_key).unwrap();
+        let client_a = ClientBuilder::new(strategy.clone())
+            .local_client()
+            .await
+            .store(store_a)
+            .build()
+            .await
+            .unwrap();
+        client_a
+            .save_signature_request(&client_a.context.signature_request().unwrap(), Some(1))
+            .unwrap();
+        let keybytes_a = client_a.installation_public_key();
+        drop(client_a);
+
+        let store_b =
+            EncryptedMessageStore::new(StorageOption::Persistent(tmpdb.clone()), db_key).unwrap();
+        let client_b = ClientBuilder::new(strategy)
+            .local_client()
+            .await
+            .store(store_b)
+            .build()
+            .await
+            .unwrap();
+        assert_ne!(client_b.installation_public_key(), keybytes_a);
+        // The expired request and the installation keys saved with it are gone
+        assert!(client_b
+            .store()
+            .conn()
+            .unwrap()
+            .get_signature_requests(&inbox_id, 0)
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -1,14 +1,14 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     mem::Discriminant,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, Ordering},
         Arc,
     },
 };
 
 use futures::{
-    stream::{self, FuturesUnordered, StreamExt},
+    stream::{self, FuturesUnordered, StreamExt, TryStreamExt},
     Future,
 };
 use openmls::{
@@ -23,7 +23,7 @@ use prost::EncodeError;
 use thiserror::Error;
 use tokio::sync::broadcast;
 
-use xmtp_cryptography::signature::{sanitize_evm_addresses, AddressValidationError};
+use xmtp_cryptography::signature::{sanitize_account_addresses, AddressValidationError};
 use xmtp_id::{
     associations::{
         builder::{SignatureRequest, SignatureRequestError},
@@ -39,25 +39,30 @@ use xmtp_proto::xmtp::mls::api::v1::{
 };
 
 use crate::{
-    api::ApiClientWrapper,
+    api::{ApiClientWrapper, GroupFilter},
+    configuration::{KEY_PACKAGE_FETCH_BATCH_SIZE, KEY_PACKAGE_FETCH_CONCURRENCY},
     groups::{
-        group_permissions::PolicySet, validated_commit::CommitValidationError, GroupError,
-        GroupMetadataOptions, IntentError, MlsGroup,
+        group_permissions::PolicySet,
+        validated_commit::{extract_group_membership, CommitValidationError},
+        GroupError, GroupMetadataOptions, IntentError, MlsGroup,
     },
+    hpke::is_join_request,
     identity::{parse_credential, Identity, IdentityError},
     identity_updates::{load_identity_updates, IdentityUpdateError},
     mutex_registry::MutexRegistry,
-    retry::Retry,
     retry_async, retryable,
     storage::{
+        association_state::StoredAssociationState,
         consent_record::{ConsentState, ConsentType, StoredConsentRecord},
         db_connection::DbConnection,
         group::{GroupMembershipState, StoredGroup},
         group_message::StoredGroupMessage,
+        inbox_id_cache::StoredInboxIdCacheEntry,
         refresh_state::EntityKind,
         sql_key_store, EncryptedMessageStore, StorageError,
     },
     subscriptions::LocalEvents,
+    utils::time::now_ns,
     verified_key_package_v2::{KeyPackageVerificationError, VerifiedKeyPackageV2},
     xmtp_openmls_provider::XmtpOpenMlsProvider,
     Fetch, XmtpApi,
@@ -246,6 +251,76 @@ pub struct XmtpMlsLocalContext {
     pub(crate) store: EncryptedMessageStore,
     pub(crate) mutexes: MutexRegistry,
     pub scw_verifier: Box<dyn SmartContractSignatureVerifier + 'static>,
+    pub(crate) inbox_id_cache_counters: InboxIdCacheCounters,
+    pub(crate) association_state_counters: AssociationStateCounters,
+}
+
+/// Counts how address lookups were served since the client was created
+#[derive(Debug, Default)]
+pub(crate) struct InboxIdCacheCounters {
+    hits: AtomicU64,
+    negative_hits: AtomicU64,
+    misses: AtomicU64,
+    expired: AtomicU64,
+}
+
+/// Counts how association states were computed since the client was created
+#[derive(Debug, Default)]
+pub(crate) struct AssociationStateCounters {
+    cache_hits: AtomicU64,
+    snapshot_replays: AtomicU64,
+    full_replays: AtomicU64,
+    updates_replayed: AtomicU64,
+    longest_replay: AtomicU64,
+}
+
+impl AssociationStateCounters {
+    pub(crate) fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_replay(&self, from_snapshot: bool, updates: usize) {
+        match from_snapshot {
+            true => self.snapshot_replays.fetch_add(1, Ordering::Relaxed),
+            false => self.full_replays.fetch_add(1, Ordering::Relaxed),
+        };
+        self.updates_replayed
+            .fetch_add(updates as u64, Ordering::Relaxed);
+        self.longest_replay
+            .fetch_max(updates as u64, Ordering::Relaxed);
+    }
+}
+
+/// Statistics of the local address -> inbox_id cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboxIdCacheStats {
+    /// Lookups answered with a cached inbox_id
+    pub hits: u64,
+    /// Lookups answered with a cached "no inbox"
+    pub negative_hits: u64,
+    /// Lookups for addresses that were not cached
+    pub misses: u64,
+    /// Lookups for addresses whose cache entry had expired
+    pub expired: u64,
+    /// Entries currently stored in the cache
+    pub entries: i64,
+}
+
+/// Statistics of association state computation, see [`Client::association_state_stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssociationStateStats {
+    /// States that were cached at the requested sequence id
+    pub cache_hits: u64,
+    /// States computed by applying updates on top of an earlier cached state
+    pub snapshot_replays: u64,
+    /// States computed from the first identity update of the inbox
+    pub full_replays: u64,
+    /// Identity updates verified and applied across all replays
+    pub updates_replayed: u64,
+    /// The most identity updates applied to compute a single state
+    pub longest_replay: u64,
+    /// States currently cached
+    pub snapshots: i64,
 }
 
 impl XmtpMlsLocalContext {
@@ -295,6 +370,8 @@ where
             store,
             mutexes: MutexRegistry::new(),
             scw_verifier,
+            inbox_id_cache_counters: InboxIdCacheCounters::default(),
+            association_state_counters: AssociationStateCounters::default(),
         };
         let (tx, _) = broadcast::channel(10);
         Self {
@@ -333,23 +410,99 @@ where
         }
     }
 
+    /// Look up the inbox_ids of `addresses`, reading through the local cache.
+    /// Only addresses without a live cache entry are fetched from the network.
     pub async fn find_inbox_ids_from_addresses(
         &self,
         addresses: Vec<String>,
     ) -> Result<Vec<Option<String>>, ClientError> {
-        let sanitized_addresses = sanitize_evm_addresses(addresses.clone())?;
-        let mut results = self
-            .api_client
-            .get_inbox_ids(sanitized_addresses.clone())
-            .await?;
-        let inbox_ids: Vec<Option<String>> = sanitized_addresses
+        let sanitized_addresses = sanitize_account_addresses(addresses.clone())?;
+        let conn = self.store().conn()?;
+        let now = now_ns();
+        let counters = &self.context.inbox_id_cache_counters;
+
+        let mut cached: HashMap<String, StoredInboxIdCacheEntry> = conn
+            .get_inbox_id_cache_entries(&sanitized_addresses)?
             .into_iter()
-            .map(|address| results.remove(&address))
+            .map(|entry| (entry.address.clone(), entry))
+            .collect();
+
+        let mut results: HashMap<String, Option<String>> = HashMap::new();
+        let mut to_fetch = Vec::new();
+        for address in sanitized_addresses.iter() {
+            if results.contains_key(address) || to_fetch.contains(address) {
+                continue;
+            }
+            match cached.remove(address) {
+                Some(entry) if !entry.is_expired(now) => {
+                    match entry.inbox_id {
+                        Some(_) => counters.hits.fetch_add(1, Ordering::Relaxed),
+                        None => counters.negative_hits.fetch_add(1, Ordering::Relaxed),
+                    };
+                    results.insert(entry.address, entry.inbox_id);
+                }
+                Some(_) => {
+                    counters.expired.fetch_add(1, Ordering::Relaxed);
+                    to_fetch.push(address.clone());
+                }
+                None => {
+                    counters.misses.fetch_add(1, Ordering::Relaxed);
+                    to_fetch.push(address.clone());
+                }
+            }
+        }
+
+        if !to_fetch.is_empty() {
+            let mut fetched = self.api_client.get_inbox_ids(to_fetch.clone()).await?;
+            let entries: Vec<StoredInboxIdCacheEntry> = to_fetch
+                .into_iter()
+                .map(|address| {
+                    let inbox_id = fetched.remove(&address);
+                    StoredInboxIdCacheEntry::new(address, inbox_id, now)
+                })
+                .collect();
+            conn.insert_or_replace_inbox_id_cache_entries(&entries)?;
+            results.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| (entry.address, entry.inbox_id)),
+            );
+        }
+
+        let inbox_ids: Vec<Option<String>> = sanitized_addresses
+            .iter()
+            .map(|address| results.get(address).cloned().flatten())
             .collect();
 
         Ok(inbox_ids)
     }
 
+    /// Statistics of the address -> inbox_id cache used by [`Self::find_inbox_ids_from_addresses`]
+    pub fn inbox_id_cache_stats(&self) -> Result<InboxIdCacheStats, ClientError> {
+        let counters = &self.context.inbox_id_cache_counters;
+        Ok(InboxIdCacheStats {
+            hits: counters.hits.load(Ordering::Relaxed),
+            negative_hits: counters.negative_hits.load(Ordering::Relaxed),
+            misses: counters.misses.load(Ordering::Relaxed),
+            expired: counters.expired.load(Ordering::Relaxed),
+            entries: self.store().conn()?.count_inbox_id_cache_entries()?,
+        })
+    }
+
+    /// Statistics of how [`Self::get_association_state`] computed states, showing how many
+    /// identity updates had to be replayed
+    pub fn association_state_stats(&self) -> Result<AssociationStateStats, ClientError> {
+        let counters = &self.context.association_state_counters;
+        Ok(AssociationStateStats {
+            cache_hits: counters.cache_hits.load(Ordering::Relaxed),
+            snapshot_replays: counters.snapshot_replays.load(Ordering::Relaxed),
+            full_replays: counters.full_replays.load(Ordering::Relaxed),
+            updates_replayed: counters.updates_replayed.load(Ordering::Relaxed),
+            longest_replay: counters.longest_replay.load(Ordering::Relaxed),
+            snapshots: StoredAssociationState::count(&self.store().conn()?)?,
+        })
+    }
+
     /// Get sequence id, may not be consistent with the backend
     pub fn inbox_sequence_id(&self, conn: &DbConnection) -> Result<i64, StorageError> {
         self.context.inbox_sequence_id(conn)
@@ -631,6 +784,12 @@ where
 
         self.apply_signature_request(signature_request).await?;
 
+        // Details set before the installation was registered could not be shared yet
+        #[cfg(feature = "message-history")]
+        if let Err(err) = self.share_installation_metadata().await {
+            tracing::warn!("failed to share installation metadata: {err}");
+        }
+
         Ok(())
     }
 
@@ -683,11 +842,18 @@ where
         &self,
         installation_ids: Vec<Vec<u8>>,
     ) -> Result<Vec<VerifiedKeyPackageV2>, ClientError> {
-        let key_package_results = self.api_client.fetch_key_packages(installation_ids).await?;
+        // Large groups add more installations than fit comfortably in one request
+        let key_package_results =
+            stream::iter(installation_ids.chunks(KEY_PACKAGE_FETCH_BATCH_SIZE))
+                .map(|batch| self.api_client.fetch_key_packages(batch.to_vec()))
+                .buffer_unordered(KEY_PACKAGE_FETCH_CONCURRENCY)
+                .try_collect::<Vec<_>>()
+                .await?;
 
         let mls_provider = self.mls_provider()?;
         Ok(key_package_results
-            .values()
+            .iter()
+            .flat_map(|batch| batch.values())
             .map(|bytes| VerifiedKeyPackageV2::from_bytes(mls_provider.crypto(), bytes.as_slice()))
             .collect::<Result<_, _>>()?)
     }
@@ -734,7 +900,7 @@ where
                     }
                 };
                 retry_async!(
-                    Retry::default(),
+                    self.api_client.retry_strategy(),
                     (async {
                         let welcome_v1 = welcome_v1.clone();
                         self.process_for_id(
@@ -742,11 +908,27 @@ where
                             EntityKind::Welcome,
                             welcome_v1.id,
                             |provider| async move {
+                                // Join requests share the welcome topic, and are told apart
+                                // from welcomes by their prefix
+                                if is_join_request(&welcome_v1.data) {
+                                    self.receive_join_request(
+                                        &provider,
+                                        welcome_v1.hpke_public_key.as_slice(),
+                                        welcome_v1.data.as_slice(),
+                                    )
+                                    .await
+                                    .map_err(|err| {
+                                        tracing::error!("failed to process join request: {}", err);
+                                        MessageProcessingError::WelcomeProcessing(err.to_string())
+                                    })?;
+                                    return Ok(None);
+                                }
+
                                 let result = MlsGroup::create_from_encrypted_welcome(
                                     self,
                                     &provider,
                                     welcome_v1.hpke_public_key.as_slice(),
-                                    welcome_v1.data,
+                                    welcome_v1.data.clone(),
                                     welcome_v1.id as i64,
                                 )
                                 .await;
@@ -779,6 +961,10 @@ where
             self.rotate_key_package().await?;
         }
 
+        if let Err(err) = self.auto_approve_join_requests().await {
+            tracing::warn!("failed to auto approve join requests: {}", err);
+        }
+
         Ok(groups)
     }
 
@@ -787,45 +973,113 @@ where
     pub async fn sync_all_groups(&self, groups: Vec<MlsGroup>) -> Result<usize, GroupError> {
         // Acquire a single connection to be reused
         let provider: XmtpOpenMlsProvider = self.mls_provider()?;
+        let provider_ref = &provider;
 
-        let active_group_count = Arc::new(AtomicUsize::new(0));
+        // Load the identity updates of every member in one query, so that groups re-add members
+        // who recovered their inbox without waiting for their next installation update
+        let member_inbox_ids = groups
+            .iter()
+            .map(|group| {
+                let mls_group = group.load_mls_group(provider_ref)?;
+                Ok(extract_group_membership(mls_group.extensions())?.inbox_ids())
+            })
+            .collect::<Result<Vec<_>, GroupError>>()?
+            .into_iter()
+            .flatten()
+            .collect::<HashSet<_>>();
+        if let Err(err) = load_identity_updates(
+            &self.api_client,
+            provider_ref.conn_ref(),
+            member_inbox_ids.into_iter().collect(),
+        )
+        .await
+        {
+            tracing::warn!("failed to load identity updates of group members: {err}");
+        }
 
-        let sync_futures = groups
+        // Publish first, so that the queries below pick up each group's own commits
+        let published = groups
             .into_iter()
-            .map(|group| {
-                // create new provider ref that gets moved, leaving original
-                // provider alone.
-                let provider_ref = &provider;
-                let active_group_count = Arc::clone(&active_group_count);
+            .map(|group| async move {
+                let mls_group = group.load_mls_group(provider_ref)?;
+                tracing::info!("[{}] syncing group", self.inbox_id());
+                tracing::info!(
+                    "current epoch for [{}] in sync_all_groups() is Epoch: [{}]",
+                    self.inbox_id(),
+                    mls_group.epoch()
+                );
+                if !mls_group.is_active() {
+                    return Ok::<_, GroupError>(None);
+                }
+                group
+                    .maybe_update_installations(provider_ref, None, self)
+                    .await?;
+
+                // Even if publish fails, continue to receiving
+                let publish_error = group.publish_with_conn(provider_ref, self).await.err();
+                if let Some(publish_error) = &publish_error {
+                    tracing::error!("Sync: error publishing intents {:?}", publish_error);
+                }
+                Ok(Some((group, publish_error)))
+            })
+            .collect::<FuturesUnordered<_>>()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        let active_groups: Vec<_> = published.into_iter().flatten().collect();
+
+        let conn = provider.conn_ref();
+        let filters = active_groups
+            .iter()
+            .map(|(group, _)| {
+                let id_cursor = conn.get_last_cursor_for_id(&group.group_id, EntityKind::Group)?;
+                Ok(GroupFilter::new(
+                    group.group_id.clone(),
+                    Some(id_cursor as u64),
+                ))
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        let mut messages = match self
+            .api_client
+            .query_group_messages_concurrently(filters)
+            .await
+        {
+            Ok(messages) => messages,
+            Err(receive_error) => {
+                // Same as a failed receive in a single group sync, post commit actions still run
+                tracing::error!("receive error {:?}", receive_error);
+                HashMap::new()
+            }
+        };
+
+        let active_group_count = active_groups.len();
+        active_groups
+            .into_iter()
+            .map(|(group, publish_error)| {
+                let messages = messages.remove(&group.group_id).unwrap_or_default();
                 async move {
-                    let mls_group = group.load_mls_group(provider_ref)?;
-                    tracing::info!("[{}] syncing group", self.inbox_id());
-                    tracing::info!(
-                        "current epoch for [{}] in sync_all_groups() is Epoch: [{}]",
-                        self.inbox_id(),
-                        mls_group.epoch()
-                    );
-                    if mls_group.is_active() {
-                        group
-                            .maybe_update_installations(provider_ref, None, self)
-                            .await?;
-
-                        group.sync_with_conn(provider_ref, self).await?;
-                        active_group_count.fetch_add(1, Ordering::SeqCst);
-                    }
+                    let sync_error = group
+                        .sync_with_messages(provider_ref, self, messages)
+                        .await
+                        .err();
 
-                    Ok::<(), GroupError>(())
+                    // Return a combination of publish and post_commit errors
+                    let errors: Vec<GroupError> =
+                        publish_error.into_iter().chain(sync_error).collect();
+                    if !errors.is_empty() {
+                        return Err(GroupError::Sync(errors));
+                    }
+                    Ok(())
                 }
             })
-            .collect::<FuturesUnordered<_>>();
-
-        sync_futures
+            .collect::<FuturesUnordered<_>>()
             .collect::<Vec<Result<_, _>>>()
             .await
             .into_iter()
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(active_group_count.load(Ordering::SeqCst))
+        Ok(active_group_count)
     }
 
     /**
@@ -860,7 +1114,7 @@ where
         &self,
         account_addresses: Vec<String>,
     ) -> Result<HashMap<String, bool>, ClientError> {
-        let account_addresses = sanitize_evm_addresses(account_addresses)?;
+        let account_addresses = sanitize_account_addresses(account_addresses)?;
         let inbox_id_map = self
             .api_client
             .get_inbox_ids(account_addresses.clone())
@@ -1029,6 +1283,27 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_find_inbox_id_is_cached() {
+        let wallet = generate_local_wallet();
+        let client = ClientBuilder::new_test_client(&wallet).await;
+        let unknown_address = generate_local_wallet().get_address();
+
+        for _ in 0..2 {
+            let inbox_ids = client
+                .find_inbox_ids_from_addresses(vec![wallet.get_address(), unknown_address.clone()])
+                .await
+                .unwrap();
+            assert_eq!(inbox_ids, vec![Some(client.inbox_id()), None]);
+        }
+
+        let stats = client.inbox_id_cache_stats().unwrap();
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.negative_hits, 1);
+        assert_eq!(stats.entries, 2);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_sync_welcomes() {
         let alice = ClientBuilder::new_test_client(&generate_local_wallet()).await;
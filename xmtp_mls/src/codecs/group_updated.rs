@@ -63,6 +63,7 @@ mod tests {
             added_inboxes: vec![new_member.clone()],
             removed_inboxes: vec![],
             metadata_field_changes: vec![],
+            left_inboxes: vec![],
         };
 
         let encoded = GroupUpdatedCodec::encode(data).unwrap();
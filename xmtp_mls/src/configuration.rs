@@ -8,10 +8,27 @@ pub const MLS_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::Mls10;
 
 pub const WELCOME_HPKE_LABEL: &str = "MLS_WELCOME";
 
+/// Join requests share the welcome topic, the label keeps them from being read as welcomes
+pub const JOIN_REQUEST_HPKE_LABEL: &str = "XMTP_GROUP_JOIN_REQUEST";
+
+/// Prefixed to the ciphertext of join requests, so they are told apart from welcomes before
+/// decrypting. TLS serialized welcome ciphertexts never start with it
+pub const JOIN_REQUEST_ENVELOPE_PREFIX: &[u8] = b"XMTP_GROUP_JOIN_REQUEST";
+
+/// Welcomes that point at an out of band ratchet tree are not plain MLS welcomes,
+/// so they are encrypted under their own label
+pub const WELCOME_WITH_RATCHET_TREE_REFERENCE_HPKE_LABEL: &str = "XMTP_WELCOME_RATCHET_TREE_REF";
+
 pub const MAX_GROUP_SYNC_RETRIES: usize = 3;
 
 pub const MAX_INTENT_PUBLISH_ATTEMPTS: usize = 3;
 
+/// Network calls and group syncs stop retrying once the next attempt would start after this long
+pub const RETRY_MAX_ELAPSED_MS: u64 = 30_000;
+
+/// The longest a client waits between two attempts of a network call or group sync
+pub const RETRY_MAX_DELAY_MS: u64 = 5_000;
+
 const NS_IN_SEC: i64 = 1_000_000_000;
 
 const NS_IN_HOUR: i64 = NS_IN_SEC * 60 * 60;
@@ -20,7 +37,51 @@ pub const SYNC_UPDATE_INSTALLATIONS_INTERVAL_NS: i64 = NS_IN_HOUR / 2; // 30 min
 
 pub const SEND_MESSAGE_UPDATE_INSTALLATIONS_INTERVAL_NS: i64 = 5 * NS_IN_SEC;
 
-pub const MAX_GROUP_SIZE: u16 = 400;
+pub const INBOX_ID_CACHE_TTL_NS: i64 = 24 * NS_IN_HOUR;
+
+/// While replaying identity updates, the association state is cached every this many updates
+/// so that later lookups of earlier sequence ids don't start from the beginning of the log
+pub const ASSOCIATION_STATE_SNAPSHOT_INTERVAL: usize = 100;
+
+/// How long group invites are valid for unless a lifetime is given
+pub const DEFAULT_INVITE_TTL_NS: i64 = 7 * 24 * NS_IN_HOUR; // 7 days
+
+/// How many join requests an invite can approve unless a limit is given
+pub const DEFAULT_INVITE_MAX_USES: u32 = 1;
+
+/// How long to remember that an address has no inbox
+pub const INBOX_ID_NEGATIVE_CACHE_TTL_NS: i64 = 5 * 60 * NS_IN_SEC; // 5 min
+
+/// How long a saved signature request can be resumed unless a lifetime is given
+pub const DEFAULT_SIGNATURE_REQUEST_TTL_NS: i64 = 24 * NS_IN_HOUR; // 1 day
+
+pub const MAX_GROUP_SIZE: u16 = 2_000;
+
+/// Key packages are fetched in batches of this many installations
+pub const KEY_PACKAGE_FETCH_BATCH_SIZE: usize = 250;
+
+/// How many key package batches are in flight at once
+pub const KEY_PACKAGE_FETCH_CONCURRENCY: usize = 4;
+
+/// How many welcome chunks are in flight at once
+pub const WELCOME_SEND_CONCURRENCY: usize = 4;
+
+/// Default client side rate limits, as a burst of requests and the requests replenished each
+/// second. Queries cover group and welcome messages, key packages and identity lookups
+pub const QUERY_BURST: u32 = 100;
+pub const QUERY_PER_SECOND: f64 = 50.0;
+
+/// Sending messages and welcomes, uploading key packages and publishing identity updates
+pub const PUBLISH_BURST: u32 = 50;
+pub const PUBLISH_PER_SECOND: f64 = 20.0;
+
+pub const SUBSCRIBE_BURST: u32 = 20;
+pub const SUBSCRIBE_PER_SECOND: f64 = 5.0;
+
+/// Above this many installations the ratchet tree is left out of welcomes
+/// and published once, out of band, instead of once per new installation.
+/// Only done when every new installation lists [RATCHET_TREE_REFERENCE_CAPABILITY_ID]
+pub const RATCHET_TREE_OUT_OF_BAND_THRESHOLD: usize = 100;
 
 pub const MAX_PAST_EPOCHS: usize = 3;
 
@@ -44,12 +105,26 @@ pub const GRPC_DATA_LIMIT: usize = 45 * 1024 * 1024;
 pub const MUTABLE_METADATA_EXTENSION_ID: u16 = 0xff00;
 pub const GROUP_MEMBERSHIP_EXTENSION_ID: u16 = 0xff01;
 pub const GROUP_PERMISSIONS_EXTENSION_ID: u16 = 0xff02;
+/// Never sent as an extension. Listed in the capabilities of key packages by clients that can join
+/// through a welcome whose ratchet tree is published out of band
+pub const RATCHET_TREE_REFERENCE_CAPABILITY_ID: u16 = 0xff04;
+/// Never sent as an extension. Listed in the capabilities of key packages by clients that hold
+/// received commits to the ban list, super admin and metadata field rules. New groups require it,
+/// so every member of those groups rejects the same commits. Groups created before it only enforce
+/// the rules when sending
+pub const COMMIT_RULES_CAPABILITY_ID: u16 = 0xff05;
 
 pub const DEFAULT_GROUP_NAME: &str = "";
 pub const DEFAULT_GROUP_DESCRIPTION: &str = "";
 pub const DEFAULT_GROUP_IMAGE_URL_SQUARE: &str = "";
 pub const DEFAULT_GROUP_PINNED_FRAME_URL: &str = "";
 
-// If a metadata field name starts with this character,
-// and it does not have a policy set, it is a super admin only field
+// If a metadata field name starts with this character, it is a super admin only field.
+// A policy set for the field can restrict it further. Changes by other members are never sent,
+// and are rejected on receipt in groups that require COMMIT_RULES_CAPABILITY_ID
 pub const SUPER_ADMIN_METADATA_PREFIX: &str = "_";
+
+/// Limits on the custom attributes a group's mutable metadata can hold
+pub const MAX_GROUP_METADATA_FIELDS: usize = 64;
+pub const MAX_GROUP_METADATA_KEY_LENGTH: usize = 64;
+pub const MAX_GROUP_METADATA_VALUE_LENGTH: usize = 2048;
@@ -0,0 +1,688 @@
+//! Invite links for groups.
+//!
+//! An admin creates a signed invite bound to the group and to their own installation. Whoever
+//! holds the invite sends a [`GroupJoinRequestProto`] to that installation over the welcome
+//! topic, and the request is approved by an admin or automatically, depending on the group's
+//! [`JoinRequestPolicy`]. Approving a request adds the requester with
+//! [`MlsGroup::add_members_by_inbox_id`].
+//!
+//! The issuing installation records its invites, and stops honouring one once it has approved
+//! as many requests as the invite allows, or once the admin that created it loses their role.
+
+use prost::Message;
+use rand::RngCore;
+use thiserror::Error;
+
+use xmtp_cryptography::utils as crypto_utils;
+use xmtp_id::associations::{
+    verified_signature::VerifiedSignature, MemberIdentifier, SignatureError,
+};
+use xmtp_proto::xmtp::mls::{
+    api::v1::{
+        welcome_message_input::{
+            Version as WelcomeMessageInputVersion, V1 as WelcomeMessageInputV1,
+        },
+        WelcomeMessageInput,
+    },
+    message_contents::{
+        GroupInvite as GroupInviteProto, GroupJoinRequest as GroupJoinRequestProto,
+        SignedGroupInvite as SignedGroupInviteProto,
+    },
+};
+
+use super::{group_permissions::JoinRequestPolicy, GroupError, MlsGroup};
+use crate::{
+    client::ClientError,
+    configuration::{DEFAULT_INVITE_MAX_USES, DEFAULT_INVITE_TTL_NS},
+    hpke::{decrypt_join_request, encrypt_join_request, HpkeError},
+    identity::IdentityError,
+    retry::RetryableError,
+    retryable,
+    storage::{
+        group_invite::StoredGroupInvite,
+        group_join_request::{
+            JoinRequestState, NewGroupJoinRequest, StoredGroupJoinRequest, ID as JoinRequestId,
+        },
+        StorageError,
+    },
+    utils::time::now_ns,
+    xmtp_openmls_provider::XmtpOpenMlsProvider,
+    Client, Fetch, Store, XmtpApi,
+};
+
+const INVITE_NONCE_SIZE: usize = 32;
+
+#[derive(Debug, Error)]
+pub enum InviteError {
+    #[error("invite is malformed")]
+    Malformed,
+    #[error("invite has expired")]
+    Expired,
+    #[error("invite signature is invalid: {0}")]
+    InvalidSignature(#[from] SignatureError),
+    #[error("invite was not issued by this installation")]
+    NotIssuedByInstallation,
+    #[error("invite has been used the maximum number of times")]
+    UsedUp,
+    #[error("the inviter is no longer an admin of the group")]
+    InviterNotAdmin,
+    #[error("requester installation does not belong to inbox {0}")]
+    RequesterMismatch(String),
+    #[error("only admins can create invites")]
+    NotAdmin,
+    #[error("the group does not accept join requests")]
+    JoinRequestsDisabled,
+    #[error("inbox {0} is banned from the group")]
+    RequesterBanned(String),
+    #[error("the inviting installation has no key package")]
+    InviterUnavailable,
+    #[error("join request {0} not found")]
+    RequestNotFound(JoinRequestId),
+    #[error("join request {0} has already been resolved")]
+    RequestResolved(JoinRequestId),
+    #[error("api error: {0}")]
+    Api(#[from] xmtp_proto::api_client::Error),
+    #[error("client error: {0}")]
+    Client(#[from] ClientError),
+    #[error("group error: {0}")]
+    Group(#[from] GroupError),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Hpke error: {0}")]
+    Hpke(#[from] HpkeError),
+    #[error("identity error: {0}")]
+    Identity(#[from] IdentityError),
+    #[error("decode error: {0}")]
+    Decode(#[from] prost::DecodeError),
+}
+
+impl RetryableError for InviteError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Api(api_error) => api_error.is_retryable(),
+            Self::Client(client_error) => retryable!(client_error),
+            Self::Group(group_error) => retryable!(group_error),
+            Self::Storage(storage) => retryable!(storage),
+            Self::Hpke(hpke) => retryable!(hpke),
+            Self::Identity(identity) => retryable!(identity),
+            _ => false,
+        }
+    }
+}
+
+impl MlsGroup {
+    /// Create an invite that lets its holder request to join the group.
+    /// Join requests are sent to this installation, which must stay registered for the
+    /// invite to remain usable. Invites expire after [`DEFAULT_INVITE_TTL_NS`] unless
+    /// `expires_in_ns` is given, and approve [`DEFAULT_INVITE_MAX_USES`] requests unless
+    /// `max_uses` is given.
+    pub fn create_invite<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        expires_in_ns: Option<i64>,
+        max_uses: Option<u32>,
+    ) -> Result<String, InviteError> {
+        let provider = self.mls_provider()?;
+        let inbox_id = client.inbox_id();
+        if !self.is_admin(inbox_id.clone(), &provider)?
+            && !self.is_super_admin(inbox_id.clone(), &provider)?
+        {
+            return Err(InviteError::NotAdmin);
+        }
+        if self.permissions()?.policies.join_request_policy == JoinRequestPolicy::Deny {
+            return Err(InviteError::JoinRequestsDisabled);
+        }
+
+        let mut nonce = vec![0u8; INVITE_NONCE_SIZE];
+        crypto_utils::rng().fill_bytes(&mut nonce);
+        let expires_at_ns = now_ns() + expires_in_ns.unwrap_or(DEFAULT_INVITE_TTL_NS);
+        let invite = GroupInviteProto {
+            group_id: self.group_id.clone(),
+            inviter_inbox_id: inbox_id,
+            inviter_installation_key: client.installation_public_key(),
+            expires_at_ns: expires_at_ns.max(0) as u64,
+            nonce,
+        };
+        let signature = client.identity().sign(invite_signature_text(&invite))?;
+        let max_uses = max_uses
+            .unwrap_or(DEFAULT_INVITE_MAX_USES)
+            .clamp(1, i32::MAX as u32);
+        StoredGroupInvite::new(
+            invite.nonce.clone(),
+            self.group_id.clone(),
+            max_uses as i32,
+            expires_at_ns,
+        )
+        .store(provider.conn_ref())?;
+        let signed_invite = SignedGroupInviteProto {
+            invite: Some(invite),
+            signature,
+        };
+
+        Ok(hex::encode(signed_invite.encode_to_vec()))
+    }
+
+    /// Join requests received for this group, optionally filtered by state
+    pub fn join_requests(
+        &self,
+        state: Option<JoinRequestState>,
+    ) -> Result<Vec<StoredGroupJoinRequest>, GroupError> {
+        let conn = self.context.store.conn()?;
+        Ok(conn.find_join_requests(Some(&self.group_id), state)?)
+    }
+
+    /// Approve a pending join request by adding the requester to the group.
+    /// Each approval uses up one of the uses of the invite the request was made with.
+    pub async fn approve_join_request<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        request_id: JoinRequestId,
+    ) -> Result<(), InviteError> {
+        let request = self.pending_join_request(request_id)?;
+        let conn = self.context.store.conn()?;
+        let invite: Option<StoredGroupInvite> = conn.fetch(&request.invite_nonce)?;
+        if invite.map_or(true, |invite| invite.is_used_up()) {
+            return Err(InviteError::UsedUp);
+        }
+
+        self.add_members_by_inbox_id(client, vec![request.requester_inbox_id])
+            .await?;
+
+        conn.use_group_invite(&request.invite_nonce)?;
+        conn.set_join_request_state(request.id, JoinRequestState::Approved)?;
+        Ok(())
+    }
+
+    /// Deny a pending join request. Later requests from the same inbox are ignored.
+    pub fn deny_join_request(&self, request_id: JoinRequestId) -> Result<(), InviteError> {
+        let request = self.pending_join_request(request_id)?;
+
+        let conn = self.context.store.conn()?;
+        conn.set_join_request_state(request.id, JoinRequestState::Denied)?;
+        Ok(())
+    }
+
+    fn pending_join_request(
+        &self,
+        request_id: JoinRequestId,
+    ) -> Result<StoredGroupJoinRequest, InviteError> {
+        let conn = self.context.store.conn()?;
+        let request: StoredGroupJoinRequest = conn
+            .fetch(&request_id)?
+            .filter(|request: &StoredGroupJoinRequest| request.group_id == self.group_id)
+            .ok_or(InviteError::RequestNotFound(request_id))?;
+        if !matches!(
+            request.state,
+            JoinRequestState::Pending | JoinRequestState::Failed
+        ) {
+            return Err(InviteError::RequestResolved(request_id));
+        }
+
+        Ok(request)
+    }
+}
+
+impl<ApiClient> Client<ApiClient>
+where
+    ApiClient: XmtpApi,
+{
+    /// Ask to join the group an invite was created for. The request is delivered to the
+    /// installation that created the invite, and this client receives a welcome once it is approved.
+    pub async fn request_to_join(&self, invite: &str) -> Result<(), InviteError> {
+        let signed_invite = decode_invite(invite)?;
+        let invite = verify_invite(&signed_invite)?;
+
+        let requester_inbox_id = self.inbox_id();
+        let signature = self.identity().sign(join_request_signature_text(
+            &signed_invite.signature,
+            &requester_inbox_id,
+        ))?;
+        let request = GroupJoinRequestProto {
+            invite: Some(signed_invite.clone()),
+            requester_inbox_id,
+            requester_installation_key: self.installation_public_key(),
+            signature,
+        };
+
+        let key_package = self
+            .get_key_packages_for_installation_ids(vec![invite.inviter_installation_key.clone()])
+            .await?
+            .pop()
+            .ok_or(InviteError::InviterUnavailable)?;
+        let hpke_public_key = key_package.hpke_init_key();
+        let data = encrypt_join_request(&request.encode_to_vec(), &hpke_public_key)?;
+
+        self.api_client
+            .send_welcome_messages(&[WelcomeMessageInput {
+                version: Some(WelcomeMessageInputVersion::V1(WelcomeMessageInputV1 {
+                    installation_key: invite.inviter_installation_key.clone(),
+                    data,
+                    hpke_public_key,
+                })),
+            }])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Decrypt and store a join request received on the welcome topic.
+    /// Requests that fail validation are dropped and `None` is returned.
+    pub(crate) async fn receive_join_request(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        hpke_public_key: &[u8],
+        encrypted_request: &[u8],
+    ) -> Result<Option<StoredGroupJoinRequest>, InviteError> {
+        let request_bytes = match decrypt_join_request(provider, hpke_public_key, encrypted_request)
+        {
+            Ok(request_bytes) => request_bytes,
+            Err(err) if !err.is_retryable() => {
+                tracing::warn!("dropping join request that failed to decrypt: {}", err);
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        match self.validate_join_request(provider, &request_bytes).await {
+            Ok(new_request) => Ok(provider.conn_ref().upsert_join_request(new_request)?),
+            Err(err) if !err.is_retryable() => {
+                tracing::warn!("dropping invalid join request: {}", err);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn validate_join_request(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        request_bytes: &[u8],
+    ) -> Result<NewGroupJoinRequest, InviteError> {
+        let request = GroupJoinRequestProto::decode(request_bytes)?;
+        let signed_invite = request.invite.ok_or(InviteError::Malformed)?;
+        let invite = verify_invite(&signed_invite)?;
+        // Only invites created by this installation are honoured
+        if invite.inviter_installation_key != self.installation_public_key() {
+            return Err(InviteError::NotIssuedByInstallation);
+        }
+        let stored_invite: StoredGroupInvite = provider
+            .conn_ref()
+            .fetch(&invite.nonce)?
+            .ok_or(InviteError::NotIssuedByInstallation)?;
+        if stored_invite.is_used_up() {
+            return Err(InviteError::UsedUp);
+        }
+
+        VerifiedSignature::from_installation_key(
+            join_request_signature_text(&signed_invite.signature, &request.requester_inbox_id),
+            &request.signature,
+            &request.requester_installation_key,
+        )?;
+        let association_state = self
+            .get_latest_association_state(provider.conn_ref(), &request.requester_inbox_id)
+            .await?;
+        if association_state
+            .get(&MemberIdentifier::Installation(
+                request.requester_installation_key,
+            ))
+            .is_none()
+        {
+            return Err(InviteError::RequesterMismatch(request.requester_inbox_id));
+        }
+
+        let group = self.group(invite.group_id.clone())?;
+        if !group.is_active(provider)? {
+            return Err(InviteError::JoinRequestsDisabled);
+        }
+        if group.permissions()?.policies.join_request_policy == JoinRequestPolicy::Deny {
+            return Err(InviteError::JoinRequestsDisabled);
+        }
+        if !group.is_admin(invite.inviter_inbox_id.clone(), provider)?
+            && !group.is_super_admin(invite.inviter_inbox_id.clone(), provider)?
+        {
+            return Err(InviteError::InviterNotAdmin);
+        }
+        if group.is_banned(request.requester_inbox_id.clone(), provider)? {
+            return Err(InviteError::RequesterBanned(request.requester_inbox_id));
+        }
+
+        Ok(NewGroupJoinRequest::new(
+            invite.group_id.clone(),
+            request.requester_inbox_id,
+            invite.nonce.clone(),
+            now_ns(),
+        ))
+    }
+
+    /// Approve pending join requests for groups whose policy is [`JoinRequestPolicy::AutoApprove`].
+    /// A request that can't be approved is marked as [`JoinRequestState::Failed`] without holding
+    /// up the others. Returns the number of requests that were approved.
+    pub(crate) async fn auto_approve_join_requests(&self) -> Result<usize, InviteError> {
+        let conn = self.store().conn()?;
+        let pending = conn.find_join_requests(None, Some(JoinRequestState::Pending))?;
+
+        let mut approved = 0;
+        for request in pending {
+            match self.auto_approve_join_request(&request).await {
+                Ok(true) => approved += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::warn!("failed to approve join request {}: {}", request.id, err);
+                    conn.set_join_request_state(request.id, JoinRequestState::Failed)?;
+                }
+            }
+        }
+
+        Ok(approved)
+    }
+
+    /// Returns whether the request was approved
+    async fn auto_approve_join_request(
+        &self,
+        request: &StoredGroupJoinRequest,
+    ) -> Result<bool, InviteError> {
+        let group = self.group(request.group_id.clone())?;
+        if group.permissions()?.policies.join_request_policy != JoinRequestPolicy::AutoApprove {
+            return Ok(false);
+        }
+        // The requester may have been banned after the request was received
+        if group.is_banned(request.requester_inbox_id.clone(), &group.mls_provider()?)? {
+            group.deny_join_request(request.id)?;
+            return Ok(false);
+        }
+        group.approve_join_request(self, request.id).await?;
+        Ok(true)
+    }
+}
+
+/// Decode an invite created with [`MlsGroup::create_invite`]
+pub fn decode_invite(invite: &str) -> Result<SignedGroupInviteProto, InviteError> {
+    let bytes = hex::decode(invite.trim()).map_err(|_| InviteError::Malformed)?;
+    Ok(SignedGroupInviteProto::decode(bytes.as_slice())?)
+}
+
+/// Check that an invite is unexpired and signed by the installation it names
+fn verify_invite(signed_invite: &SignedGroupInviteProto) -> Result<&GroupInviteProto, InviteError> {
+    let invite = signed_invite
+        .invite
+        .as_ref()
+        .ok_or(InviteError::Malformed)?;
+    if invite.expires_at_ns < now_ns().max(0) as u64 {
+        return Err(InviteError::Expired);
+    }
+
+    VerifiedSignature::from_installation_key(
+        invite_signature_text(invite),
+        &signed_invite.signature,
+        &invite.inviter_installation_key,
+    )?;
+
+    Ok(invite)
+}
+
+fn invite_signature_text(invite: &GroupInviteProto) -> String {
+    format!(
+        "XMTP : Group Invite\nGroup ID: {}\nInviter Inbox ID: {}\nInviter Installation: {}\nExpires At (ns): {}\nNonce: {}",
+        hex::encode(&invite.group_id),
+        invite.inviter_inbox_id,
+        hex::encode(&invite.inviter_installation_key),
+        invite.expires_at_ns,
+        hex::encode(&invite.nonce),
+    )
+}
+
+fn join_request_signature_text(invite_signature: &[u8], requester_inbox_id: &str) -> String {
+    format!(
+        "XMTP : Group Join Request\nInvite: {}\nRequester Inbox ID: {}",
+        hex::encode(invite_signature),
+        requester_inbox_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use xmtp_cryptography::utils::generate_local_wallet;
+
+    use super::*;
+    use crate::{
+        builder::ClientBuilder,
+        groups::{
+            group_permissions::PolicySet,
+            intents::{PermissionPolicyOption, PermissionUpdateType},
+            GroupMetadataOptions, UpdateAdminListType,
+        },
+    };
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_invite_roundtrip() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+
+        let invite = group.create_invite(&amal, None, None).unwrap();
+        let signed_invite = decode_invite(&invite).unwrap();
+        let verified = verify_invite(&signed_invite).unwrap();
+        assert_eq!(verified.group_id, group.group_id);
+        assert_eq!(
+            verified.inviter_installation_key,
+            amal.installation_public_key()
+        );
+
+        // Tampering with the invite breaks the signature
+        let mut tampered = signed_invite.clone();
+        tampered.invite.as_mut().unwrap().expires_at_ns += 1;
+        assert!(matches!(
+            verify_invite(&tampered),
+            Err(InviteError::InvalidSignature(_))
+        ));
+
+        let expired = group.create_invite(&amal, Some(-1), None).unwrap();
+        assert!(matches!(
+            verify_invite(&decode_invite(&expired).unwrap()),
+            Err(InviteError::Expired)
+        ));
+        assert!(matches!(
+            decode_invite("not an invite"),
+            Err(InviteError::Malformed)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_only_admins_create_invites() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        let bola_group = bola.sync_welcomes().await.unwrap().pop().unwrap();
+
+        assert!(matches!(
+            bola_group.create_invite(&bola, None, None),
+            Err(InviteError::NotAdmin)
+        ));
+
+        amal_group
+            .update_permission_policy(
+                &amal,
+                PermissionUpdateType::JoinRequest,
+                PermissionPolicyOption::Deny,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            amal_group.create_invite(&amal, None, None),
+            Err(InviteError::JoinRequestsDisabled)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_join_request_admin_approval() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        let invite = group.create_invite(&amal, None, Some(2)).unwrap();
+
+        bola.request_to_join(&invite).await.unwrap();
+        caro.request_to_join(&invite).await.unwrap();
+        // Join requests don't create groups
+        assert!(amal.sync_welcomes().await.unwrap().is_empty());
+
+        let pending = group
+            .join_requests(Some(JoinRequestState::Pending))
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        let bola_request = pending
+            .iter()
+            .find(|request| request.requester_inbox_id == bola.inbox_id())
+            .unwrap();
+        let caro_request = pending
+            .iter()
+            .find(|request| request.requester_inbox_id == caro.inbox_id())
+            .unwrap();
+
+        group
+            .approve_join_request(&amal, bola_request.id)
+            .await
+            .unwrap();
+        group.deny_join_request(caro_request.id).unwrap();
+        assert!(matches!(
+            group.deny_join_request(bola_request.id),
+            Err(InviteError::RequestResolved(_))
+        ));
+
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        assert_eq!(bola_groups.len(), 1);
+        assert_eq!(bola_groups[0].group_id, group.group_id);
+        assert!(caro.sync_welcomes().await.unwrap().is_empty());
+
+        // Denied inboxes can not ask again
+        caro.request_to_join(&invite).await.unwrap();
+        amal.sync_welcomes().await.unwrap();
+        assert!(group
+            .join_requests(Some(JoinRequestState::Pending))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_join_request_auto_approve() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let policy_set =
+            PolicySet::default().with_join_request_policy(JoinRequestPolicy::AutoApprove);
+        let group = amal
+            .create_group(Some(policy_set), GroupMetadataOptions::default())
+            .unwrap();
+        let invite = group.create_invite(&amal, None, None).unwrap();
+
+        bola.request_to_join(&invite).await.unwrap();
+        amal.sync_welcomes().await.unwrap();
+
+        let requests = group.join_requests(None).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].state, JoinRequestState::Approved);
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        assert_eq!(bola_groups[0].group_id, group.group_id);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_invite_uses_are_limited() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let dave = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let policy_set =
+            PolicySet::default().with_join_request_policy(JoinRequestPolicy::AutoApprove);
+        let group = amal
+            .create_group(Some(policy_set), GroupMetadataOptions::default())
+            .unwrap();
+        let invite = group.create_invite(&amal, None, Some(1)).unwrap();
+
+        // Both requests arrive before either is approved, only the first one gets in
+        bola.request_to_join(&invite).await.unwrap();
+        caro.request_to_join(&invite).await.unwrap();
+        amal.sync_welcomes().await.unwrap();
+
+        let requests = group.join_requests(None).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].state, JoinRequestState::Approved);
+        assert_eq!(requests[1].state, JoinRequestState::Failed);
+        assert_eq!(bola.sync_welcomes().await.unwrap().len(), 1);
+        assert!(caro.sync_welcomes().await.unwrap().is_empty());
+
+        // Requests made with a used up invite are dropped
+        dave.request_to_join(&invite).await.unwrap();
+        amal.sync_welcomes().await.unwrap();
+        assert_eq!(group.join_requests(None).unwrap().len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_invites_stop_working_when_inviter_is_no_longer_admin() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        amal_group
+            .update_admin_list(&amal, UpdateAdminListType::Add, bola.inbox_id())
+            .await
+            .unwrap();
+        let bola_group = bola.sync_welcomes().await.unwrap().pop().unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        let invite = bola_group.create_invite(&bola, None, None).unwrap();
+
+        amal_group
+            .update_admin_list(&amal, UpdateAdminListType::Remove, bola.inbox_id())
+            .await
+            .unwrap();
+        bola_group.sync(&bola).await.unwrap();
+
+        caro.request_to_join(&invite).await.unwrap();
+        bola.sync_welcomes().await.unwrap();
+        assert!(bola_group.join_requests(None).unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_invites_only_work_for_issuing_installation() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        let invite = group.create_invite(&amal, None, None).unwrap();
+
+        // A request aimed at another installation is dropped
+        let provider = bola.mls_provider().unwrap();
+        let request = GroupJoinRequestProto {
+            invite: Some(decode_invite(&invite).unwrap()),
+            requester_inbox_id: bola.inbox_id(),
+            requester_installation_key: bola.installation_public_key(),
+            signature: vec![],
+        };
+        let result = bola
+            .validate_join_request(&provider, &request.encode_to_vec())
+            .await;
+        assert!(matches!(result, Err(InviteError::NotIssuedByInstallation)));
+
+        // Requests must be signed by the requesting installation
+        let provider = amal.mls_provider().unwrap();
+        let result = amal
+            .validate_join_request(&provider, &request.encode_to_vec())
+            .await;
+        assert!(matches!(result, Err(InviteError::InvalidSignature(_))));
+    }
+}
@@ -13,7 +13,8 @@ use xmtp_proto::xmtp::mls::message_contents::{
 
 use crate::configuration::{
     DEFAULT_GROUP_DESCRIPTION, DEFAULT_GROUP_IMAGE_URL_SQUARE, DEFAULT_GROUP_NAME,
-    DEFAULT_GROUP_PINNED_FRAME_URL, MUTABLE_METADATA_EXTENSION_ID,
+    DEFAULT_GROUP_PINNED_FRAME_URL, MAX_GROUP_METADATA_FIELDS, MAX_GROUP_METADATA_KEY_LENGTH,
+    MAX_GROUP_METADATA_VALUE_LENGTH, MUTABLE_METADATA_EXTENSION_ID,
 };
 
 use super::GroupMetadataOptions;
@@ -35,6 +36,16 @@ pub enum GroupMutableMetadataError {
     NoUpdates,
     #[error("metadata field is missing")]
     MissingMetadataField,
+    #[error("invalid metadata field name: {0:?}")]
+    InvalidFieldName(String),
+    #[error("metadata field {name} is {length} bytes, the limit is {limit}")]
+    FieldTooLong {
+        name: String,
+        length: usize,
+        limit: usize,
+    },
+    #[error("too many metadata fields: {0}, the limit is {MAX_GROUP_METADATA_FIELDS}")]
+    TooManyFields(usize),
 }
 
 /// Represents the "updateable" metadata fields for a group.
@@ -81,6 +92,13 @@ pub struct GroupMutableMetadata {
     /// List of super admin inbox IDs for this group.
     /// See [GroupMutablePermissions](crate::groups::GroupMutablePermissions) for more details on super admin permissions.
     pub super_admin_list: Vec<String>,
+    /// List of inbox IDs banned from this group.
+    /// Commits that add a banned inbox are rejected, see [GroupMutablePermissions](crate::groups::GroupMutablePermissions)
+    /// for the policy governing who may update the list.
+    pub ban_list: Vec<String>,
+    /// List of inbox IDs that asked to leave the group.
+    /// Any admin may commit their removal, see [MlsGroup::leave](crate::groups::MlsGroup::leave).
+    pub pending_remove_list: Vec<String>,
 }
 
 impl GroupMutableMetadata {
@@ -89,11 +107,15 @@ impl GroupMutableMetadata {
         attributes: HashMap<String, String>,
         admin_list: Vec<String>,
         super_admin_list: Vec<String>,
+        ban_list: Vec<String>,
+        pending_remove_list: Vec<String>,
     ) -> Self {
         Self {
             attributes,
             admin_list,
             super_admin_list,
+            ban_list,
+            pending_remove_list,
         }
     }
 
@@ -127,6 +149,8 @@ impl GroupMutableMetadata {
             attributes,
             admin_list,
             super_admin_list,
+            ban_list: vec![],
+            pending_remove_list: vec![],
         }
     }
 
@@ -156,6 +180,8 @@ impl GroupMutableMetadata {
             attributes,
             admin_list,
             super_admin_list,
+            ban_list: vec![],
+            pending_remove_list: vec![],
         }
     }
 
@@ -171,6 +197,17 @@ impl GroupMutableMetadata {
         ]
     }
 
+    /// Checks that the group holds no more attributes than allowed.
+    /// Individual fields are checked with [validate_metadata_field] as they are changed.
+    pub fn validate_field_count(&self) -> Result<(), GroupMutableMetadataError> {
+        if self.attributes.len() > MAX_GROUP_METADATA_FIELDS {
+            return Err(GroupMutableMetadataError::TooManyFields(
+                self.attributes.len(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Checks if the given inbox ID is an admin.
     pub fn is_admin(&self, inbox_id: &String) -> bool {
         self.admin_list.contains(inbox_id)
@@ -180,6 +217,16 @@ impl GroupMutableMetadata {
     pub fn is_super_admin(&self, inbox_id: &String) -> bool {
         self.super_admin_list.contains(inbox_id)
     }
+
+    /// Checks if the given inbox ID is banned from the group.
+    pub fn is_banned(&self, inbox_id: &String) -> bool {
+        self.ban_list.contains(inbox_id)
+    }
+
+    /// Checks if the given inbox ID asked to leave the group.
+    pub fn is_pending_remove(&self, inbox_id: &String) -> bool {
+        self.pending_remove_list.contains(inbox_id)
+    }
 }
 
 impl TryFrom<GroupMutableMetadata> for Vec<u8> {
@@ -196,6 +243,12 @@ impl TryFrom<GroupMutableMetadata> for Vec<u8> {
             super_admin_list: Some(InboxesProto {
                 inbox_ids: value.super_admin_list,
             }),
+            ban_list: Some(InboxesProto {
+                inbox_ids: value.ban_list,
+            }),
+            pending_remove_list: Some(InboxesProto {
+                inbox_ids: value.pending_remove_list,
+            }),
         };
         proto_val.encode(&mut buf)?;
 
@@ -228,10 +281,23 @@ impl TryFrom<GroupMutableMetadataProto> for GroupMutableMetadata {
             .ok_or_else(|| GroupMutableMetadataError::MissingMetadataField)?
             .inbox_ids;
 
+        // Groups created before the ban list existed have no banned inboxes
+        let ban_list = value
+            .ban_list
+            .map(|inboxes| inboxes.inbox_ids)
+            .unwrap_or_default();
+
+        let pending_remove_list = value
+            .pending_remove_list
+            .map(|inboxes| inboxes.inbox_ids)
+            .unwrap_or_default();
+
         Ok(Self::new(
             value.attributes.clone(),
             admin_list,
             super_admin_list,
+            ban_list,
+            pending_remove_list,
         ))
     }
 }
@@ -258,6 +324,35 @@ impl TryFrom<&OpenMlsGroup> for GroupMutableMetadata {
     }
 }
 
+/// Checks that a metadata field name and value are within the size limits.
+///
+/// Field names must be non-empty and made of ASCII alphanumerics and `_`, so that they can be
+/// safely used as keys across bindings. Names starting with
+/// [SUPER_ADMIN_METADATA_PREFIX](crate::configuration::SUPER_ADMIN_METADATA_PREFIX) can only be
+/// updated by super admins.
+pub fn validate_metadata_field(name: &str, value: &str) -> Result<(), GroupMutableMetadataError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(GroupMutableMetadataError::InvalidFieldName(
+            name.to_string(),
+        ));
+    }
+    if name.len() > MAX_GROUP_METADATA_KEY_LENGTH {
+        return Err(GroupMutableMetadataError::FieldTooLong {
+            name: name.to_string(),
+            length: name.len(),
+            limit: MAX_GROUP_METADATA_KEY_LENGTH,
+        });
+    }
+    if value.len() > MAX_GROUP_METADATA_VALUE_LENGTH {
+        return Err(GroupMutableMetadataError::FieldTooLong {
+            name: name.to_string(),
+            length: value.len(),
+            limit: MAX_GROUP_METADATA_VALUE_LENGTH,
+        });
+    }
+    Ok(())
+}
+
 /// Finds the mutable metadata extension in the given MLS Extensions.
 ///
 /// This function searches for an Unknown Extension with the
@@ -272,3 +367,48 @@ pub fn find_mutable_metadata_extension(extensions: &Extensions) -> Option<&Vec<u
         None
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_metadata_field() {
+        assert!(validate_metadata_field("topic", "rust").is_ok());
+        assert!(validate_metadata_field("_pinned_resource_id", "").is_ok());
+
+        assert!(matches!(
+            validate_metadata_field("", "value"),
+            Err(GroupMutableMetadataError::InvalidFieldName(_))
+        ));
+        assert!(matches!(
+            validate_metadata_field("linked resource", "value"),
+            Err(GroupMutableMetadataError::InvalidFieldName(_))
+        ));
+        assert!(matches!(
+            validate_metadata_field(&"a".repeat(MAX_GROUP_METADATA_KEY_LENGTH + 1), "value"),
+            Err(GroupMutableMetadataError::FieldTooLong { .. })
+        ));
+        assert!(matches!(
+            validate_metadata_field("topic", &"a".repeat(MAX_GROUP_METADATA_VALUE_LENGTH + 1)),
+            Err(GroupMutableMetadataError::FieldTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_field_count() {
+        let mut metadata =
+            GroupMutableMetadata::new_default("creator".into(), GroupMetadataOptions::default());
+        assert!(metadata.validate_field_count().is_ok());
+
+        for i in 0..MAX_GROUP_METADATA_FIELDS {
+            metadata
+                .attributes
+                .insert(format!("field_{i}"), String::new());
+        }
+        assert!(matches!(
+            metadata.validate_field_count(),
+            Err(GroupMutableMetadataError::TooManyFields(_))
+        ));
+    }
+}
@@ -20,9 +20,10 @@ use xmtp_proto::xmtp::mls::message_contents::{
         AndCondition as PermissionsAndConditionProto, AnyCondition as PermissionsAnyConditionProto,
         Kind as PermissionsPolicyKindProto, PermissionsBasePolicy as PermissionsBasePolicyProto,
     },
-    GroupMutablePermissionsV1 as GroupMutablePermissionsProto,
-    MembershipPolicy as MembershipPolicyProto, MetadataPolicy as MetadataPolicyProto,
-    PermissionsUpdatePolicy as PermissionsPolicyProto, PolicySet as PolicySetProto,
+    GroupMutablePermissionsV1 as GroupMutablePermissionsProto, Inboxes as InboxesProto,
+    JoinRequestPolicy as JoinRequestPolicyProto, MembershipPolicy as MembershipPolicyProto,
+    MetadataPolicy as MetadataPolicyProto, PermissionsUpdatePolicy as PermissionsPolicyProto,
+    PolicySet as PolicySetProto,
 };
 
 use crate::configuration::{GROUP_PERMISSIONS_EXTENSION_ID, SUPER_ADMIN_METADATA_PREFIX};
@@ -220,6 +221,10 @@ pub enum MetadataPolicies {
     Standard(MetadataBasePolicies),
     AndCondition(MetadataAndCondition),
     AnyCondition(MetadataAnyCondition),
+    /// Allow if the actor is one of the listed inbox IDs
+    ActorInList(Vec<String>),
+    /// A policy written by a newer client that this client does not understand. Always denies.
+    Unsupported,
 }
 
 impl MetadataPolicies {
@@ -261,6 +266,27 @@ impl MetadataPolicies {
         MetadataPolicies::Standard(MetadataBasePolicies::AllowIfActorSuperAdmin)
     }
 
+    /// Returns true if this policy, or any policy nested inside it, is not understood by this client.
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            MetadataPolicies::AndCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            MetadataPolicies::AnyCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            MetadataPolicies::Unsupported => true,
+            _ => false,
+        }
+    }
+
+    /// Creates an "Allow if actor is in the list" metadata policy.
+    pub fn allow_if_actor_in(inbox_ids: Vec<String>) -> Self {
+        MetadataPolicies::ActorInList(inbox_ids)
+    }
+
     /// Creates an "And" condition metadata policy.
     pub fn and(policies: Vec<MetadataPolicies>) -> Self {
         MetadataPolicies::AndCondition(MetadataAndCondition::new(policies))
@@ -310,7 +336,11 @@ impl TryFrom<MetadataPolicyProto> for MetadataPolicies {
 
                 Ok(MetadataPolicies::any(policies))
             }
-            None => Err(PolicyError::InvalidMetadataPolicy),
+            Some(MetadataPolicyKindProto::ActorInList(inner)) => {
+                Ok(MetadataPolicies::allow_if_actor_in(inner.inbox_ids))
+            }
+            // Policy kinds added after this client was built decode without a kind
+            None => Ok(MetadataPolicies::Unsupported),
         }
     }
 }
@@ -322,6 +352,8 @@ impl MetadataPolicy for MetadataPolicies {
             MetadataPolicies::Standard(policy) => policy.evaluate(actor, change),
             MetadataPolicies::AndCondition(policy) => policy.evaluate(actor, change),
             MetadataPolicies::AnyCondition(policy) => policy.evaluate(actor, change),
+            MetadataPolicies::ActorInList(inbox_ids) => inbox_ids.contains(&actor.inbox_id),
+            MetadataPolicies::Unsupported => false,
         }
     }
 
@@ -330,6 +362,12 @@ impl MetadataPolicy for MetadataPolicies {
             MetadataPolicies::Standard(policy) => policy.to_proto()?,
             MetadataPolicies::AndCondition(policy) => policy.to_proto()?,
             MetadataPolicies::AnyCondition(policy) => policy.to_proto()?,
+            MetadataPolicies::ActorInList(inbox_ids) => MetadataPolicyProto {
+                kind: Some(MetadataPolicyKindProto::ActorInList(InboxesProto {
+                    inbox_ids: inbox_ids.clone(),
+                })),
+            },
+            MetadataPolicies::Unsupported => return Err(PolicyError::UnsupportedPolicy),
         })
     }
 }
@@ -458,6 +496,10 @@ pub enum PermissionsPolicies {
     Standard(PermissionsBasePolicies),
     AndCondition(PermissionsAndCondition),
     AnyCondition(PermissionsAnyCondition),
+    /// Allow if the actor is one of the listed inbox IDs
+    ActorInList(Vec<String>),
+    /// A policy written by a newer client that this client does not understand. Always denies.
+    Unsupported,
 }
 
 impl PermissionsPolicies {
@@ -476,6 +518,27 @@ impl PermissionsPolicies {
         PermissionsPolicies::Standard(PermissionsBasePolicies::AllowIfActorSuperAdmin)
     }
 
+    /// Returns true if this policy, or any policy nested inside it, is not understood by this client.
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            PermissionsPolicies::AndCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            PermissionsPolicies::AnyCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            PermissionsPolicies::Unsupported => true,
+            _ => false,
+        }
+    }
+
+    /// Creates an "Allow if actor is in the list" permissions policy.
+    pub fn allow_if_actor_in(inbox_ids: Vec<String>) -> Self {
+        PermissionsPolicies::ActorInList(inbox_ids)
+    }
+
     /// Creates an "And" condition permissions policy.
     pub fn and(policies: Vec<PermissionsPolicies>) -> Self {
         PermissionsPolicies::AndCondition(PermissionsAndCondition::new(policies))
@@ -524,7 +587,11 @@ impl TryFrom<PermissionsPolicyProto> for PermissionsPolicies {
 
                 Ok(PermissionsPolicies::any(policies))
             }
-            None => Err(PolicyError::InvalidPermissionsPolicy),
+            Some(PermissionsPolicyKindProto::ActorInList(inner)) => {
+                Ok(PermissionsPolicies::allow_if_actor_in(inner.inbox_ids))
+            }
+            // Policy kinds added after this client was built decode without a kind
+            None => Ok(PermissionsPolicies::Unsupported),
         }
     }
 }
@@ -536,6 +603,8 @@ impl PermissionsPolicy for PermissionsPolicies {
             PermissionsPolicies::Standard(policy) => policy.evaluate(actor),
             PermissionsPolicies::AndCondition(policy) => policy.evaluate(actor),
             PermissionsPolicies::AnyCondition(policy) => policy.evaluate(actor),
+            PermissionsPolicies::ActorInList(inbox_ids) => inbox_ids.contains(&actor.inbox_id),
+            PermissionsPolicies::Unsupported => false,
         }
     }
 
@@ -544,6 +613,12 @@ impl PermissionsPolicy for PermissionsPolicies {
             PermissionsPolicies::Standard(policy) => policy.to_proto()?,
             PermissionsPolicies::AndCondition(policy) => policy.to_proto()?,
             PermissionsPolicies::AnyCondition(policy) => policy.to_proto()?,
+            PermissionsPolicies::ActorInList(inbox_ids) => PermissionsPolicyProto {
+                kind: Some(PermissionsPolicyKindProto::ActorInList(InboxesProto {
+                    inbox_ids: inbox_ids.clone(),
+                })),
+            },
+            PermissionsPolicies::Unsupported => return Err(PolicyError::UnsupportedPolicy),
         })
     }
 }
@@ -620,6 +695,17 @@ pub trait MembershipPolicy: std::fmt::Debug {
     /// Evaluates the policy for a given actor and inbox change.
     fn evaluate(&self, actor: &CommitParticipant, change: &Inbox) -> bool;
 
+    /// Evaluates the policy for a given actor and inbox change, where the change is one of
+    /// `num_changes` inboxes added or removed by the same commit.
+    fn evaluate_in_commit(
+        &self,
+        actor: &CommitParticipant,
+        change: &Inbox,
+        _num_changes: usize,
+    ) -> bool {
+        self.evaluate(actor, change)
+    }
+
     /// Converts the policy to its proto representation.
     fn to_proto(&self) -> Result<MembershipPolicyProto, PolicyError>;
 }
@@ -643,6 +729,8 @@ pub enum PolicyError {
     InvalidMembershipPolicy,
     #[error("invalid permissions policy")]
     InvalidPermissionsPolicy,
+    #[error("policy is not supported by this client")]
+    UnsupportedPolicy,
     #[error("from proto add member invalid policy")]
     FromProtoAddMemberInvalidPolicy,
     #[error("from proto remove member invalid policy")]
@@ -704,6 +792,14 @@ pub enum MembershipPolicies {
     Standard(BasePolicies),
     AndCondition(AndCondition),
     AnyCondition(AnyCondition),
+    /// Allow if the actor is one of the listed inbox IDs
+    ActorInList(Vec<String>),
+    /// Allow if the inbox being added or removed is one of the listed inbox IDs
+    SubjectInList(Vec<String>),
+    /// Allow if the commit adds or removes at most this many inboxes
+    MaxChanges(u32),
+    /// A policy written by a newer client that this client does not understand. Always denies.
+    Unsupported,
 }
 
 impl MembershipPolicies {
@@ -729,6 +825,37 @@ impl MembershipPolicies {
         MembershipPolicies::Standard(BasePolicies::AllowIfSuperAdmin)
     }
 
+    /// Returns true if this policy, or any policy nested inside it, is not understood by this client.
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            MembershipPolicies::AndCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            MembershipPolicies::AnyCondition(condition) => condition
+                .policies
+                .iter()
+                .any(|policy| policy.is_unsupported()),
+            MembershipPolicies::Unsupported => true,
+            _ => false,
+        }
+    }
+
+    /// Creates an "Allow if actor is in the list" membership policy.
+    pub fn allow_if_actor_in(inbox_ids: Vec<String>) -> Self {
+        MembershipPolicies::ActorInList(inbox_ids)
+    }
+
+    /// Creates an "Allow if the added or removed inbox is in the list" membership policy.
+    pub fn allow_if_subject_in(inbox_ids: Vec<String>) -> Self {
+        MembershipPolicies::SubjectInList(inbox_ids)
+    }
+
+    /// Creates a membership policy that denies commits changing more than `max` inboxes.
+    pub fn max_changes(max: u32) -> Self {
+        MembershipPolicies::MaxChanges(max)
+    }
+
     /// Creates an "And" condition membership policy.
     pub fn and(policies: Vec<MembershipPolicies>) -> Self {
         MembershipPolicies::AndCondition(AndCondition::new(policies))
@@ -778,7 +905,15 @@ impl TryFrom<MembershipPolicyProto> for MembershipPolicies {
 
                 Ok(MembershipPolicies::any(policies))
             }
-            None => Err(PolicyError::InvalidMembershipPolicy),
+            Some(PolicyKindProto::ActorInList(inner)) => {
+                Ok(MembershipPolicies::allow_if_actor_in(inner.inbox_ids))
+            }
+            Some(PolicyKindProto::SubjectInList(inner)) => {
+                Ok(MembershipPolicies::allow_if_subject_in(inner.inbox_ids))
+            }
+            Some(PolicyKindProto::MaxChanges(max)) => Ok(MembershipPolicies::max_changes(max)),
+            // Policy kinds added after this client was built decode without a kind
+            None => Ok(MembershipPolicies::Unsupported),
         }
     }
 }
@@ -786,10 +921,27 @@ impl TryFrom<MembershipPolicyProto> for MembershipPolicies {
 /// Implements the MembershipPolicy trait for MembershipPolicies.
 impl MembershipPolicy for MembershipPolicies {
     fn evaluate(&self, actor: &CommitParticipant, inbox: &Inbox) -> bool {
+        self.evaluate_in_commit(actor, inbox, 1)
+    }
+
+    fn evaluate_in_commit(
+        &self,
+        actor: &CommitParticipant,
+        inbox: &Inbox,
+        num_changes: usize,
+    ) -> bool {
         match self {
             MembershipPolicies::Standard(policy) => policy.evaluate(actor, inbox),
-            MembershipPolicies::AndCondition(policy) => policy.evaluate(actor, inbox),
-            MembershipPolicies::AnyCondition(policy) => policy.evaluate(actor, inbox),
+            MembershipPolicies::AndCondition(policy) => {
+                policy.evaluate_in_commit(actor, inbox, num_changes)
+            }
+            MembershipPolicies::AnyCondition(policy) => {
+                policy.evaluate_in_commit(actor, inbox, num_changes)
+            }
+            MembershipPolicies::ActorInList(inbox_ids) => inbox_ids.contains(&actor.inbox_id),
+            MembershipPolicies::SubjectInList(inbox_ids) => inbox_ids.contains(&inbox.inbox_id),
+            MembershipPolicies::MaxChanges(max) => num_changes <= *max as usize,
+            MembershipPolicies::Unsupported => false,
         }
     }
 
@@ -798,6 +950,20 @@ impl MembershipPolicy for MembershipPolicies {
             MembershipPolicies::Standard(policy) => policy.to_proto()?,
             MembershipPolicies::AndCondition(policy) => policy.to_proto()?,
             MembershipPolicies::AnyCondition(policy) => policy.to_proto()?,
+            MembershipPolicies::ActorInList(inbox_ids) => MembershipPolicyProto {
+                kind: Some(PolicyKindProto::ActorInList(InboxesProto {
+                    inbox_ids: inbox_ids.clone(),
+                })),
+            },
+            MembershipPolicies::SubjectInList(inbox_ids) => MembershipPolicyProto {
+                kind: Some(PolicyKindProto::SubjectInList(InboxesProto {
+                    inbox_ids: inbox_ids.clone(),
+                })),
+            },
+            MembershipPolicies::MaxChanges(max) => MembershipPolicyProto {
+                kind: Some(PolicyKindProto::MaxChanges(*max)),
+            },
+            MembershipPolicies::Unsupported => return Err(PolicyError::UnsupportedPolicy),
         })
     }
 }
@@ -817,9 +983,18 @@ impl AndCondition {
 /// Implements the MembershipPolicy trait for AndCondition.
 impl MembershipPolicy for AndCondition {
     fn evaluate(&self, actor: &CommitParticipant, inbox: &Inbox) -> bool {
+        self.evaluate_in_commit(actor, inbox, 1)
+    }
+
+    fn evaluate_in_commit(
+        &self,
+        actor: &CommitParticipant,
+        inbox: &Inbox,
+        num_changes: usize,
+    ) -> bool {
         self.policies
             .iter()
-            .all(|policy| policy.evaluate(actor, inbox))
+            .all(|policy| policy.evaluate_in_commit(actor, inbox, num_changes))
     }
 
     fn to_proto(&self) -> Result<MembershipPolicyProto, PolicyError> {
@@ -851,9 +1026,18 @@ impl AnyCondition {
 /// Implements the MembershipPolicy trait for AnyCondition.
 impl MembershipPolicy for AnyCondition {
     fn evaluate(&self, actor: &CommitParticipant, inbox: &Inbox) -> bool {
+        self.evaluate_in_commit(actor, inbox, 1)
+    }
+
+    fn evaluate_in_commit(
+        &self,
+        actor: &CommitParticipant,
+        inbox: &Inbox,
+        num_changes: usize,
+    ) -> bool {
         self.policies
             .iter()
-            .any(|policy| policy.evaluate(actor, inbox))
+            .any(|policy| policy.evaluate_in_commit(actor, inbox, num_changes))
     }
 
     fn to_proto(&self) -> Result<MembershipPolicyProto, PolicyError> {
@@ -869,6 +1053,41 @@ impl MembershipPolicy for AnyCondition {
     }
 }
 
+/// How join requests made with a group invite are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinRequestPolicy {
+    /// Invites can not be created and join requests are dropped.
+    Deny,
+    /// Join requests are kept until an admin approves or denies them.
+    #[default]
+    AdminApproval,
+    /// Join requests are approved as soon as they are received.
+    AutoApprove,
+}
+
+impl From<JoinRequestPolicyProto> for JoinRequestPolicy {
+    fn from(proto: JoinRequestPolicyProto) -> Self {
+        match proto {
+            // Groups created before join requests existed require admin approval
+            JoinRequestPolicyProto::Unspecified | JoinRequestPolicyProto::AdminApproval => {
+                JoinRequestPolicy::AdminApproval
+            }
+            JoinRequestPolicyProto::Deny => JoinRequestPolicy::Deny,
+            JoinRequestPolicyProto::AutoApprove => JoinRequestPolicy::AutoApprove,
+        }
+    }
+}
+
+impl From<JoinRequestPolicy> for JoinRequestPolicyProto {
+    fn from(policy: JoinRequestPolicy) -> Self {
+        match policy {
+            JoinRequestPolicy::Deny => JoinRequestPolicyProto::Deny,
+            JoinRequestPolicy::AdminApproval => JoinRequestPolicyProto::AdminApproval,
+            JoinRequestPolicy::AutoApprove => JoinRequestPolicyProto::AutoApprove,
+        }
+    }
+}
+
 /// Represents a set of policies for a group.
 #[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -885,6 +1104,12 @@ pub struct PolicySet {
     pub remove_admin_policy: PermissionsPolicies,
     /// The policy for updating permissions.
     pub update_permissions_policy: PermissionsPolicies,
+    /// The policy for sending application messages. Every member may send when unset.
+    pub send_message_policy: Option<PermissionsPolicies>,
+    /// How join requests made with a group invite are handled.
+    pub join_request_policy: JoinRequestPolicy,
+    /// The policy for adding and removing inboxes from the ban list.
+    pub update_ban_list_policy: PermissionsPolicies,
 }
 
 impl PolicySet {
@@ -904,6 +1129,9 @@ impl PolicySet {
             add_admin_policy,
             remove_admin_policy,
             update_permissions_policy,
+            send_message_policy: None,
+            join_request_policy: JoinRequestPolicy::default(),
+            update_ban_list_policy: PermissionsPolicies::allow_if_actor_admin(),
         }
     }
 
@@ -915,6 +1143,75 @@ impl PolicySet {
             add_admin_policy: PermissionsPolicies::deny(),
             remove_admin_policy: PermissionsPolicies::deny(),
             update_permissions_policy: PermissionsPolicies::deny(),
+            send_message_policy: None,
+            join_request_policy: JoinRequestPolicy::Deny,
+            update_ban_list_policy: PermissionsPolicies::deny(),
+        }
+    }
+
+    /// Sets the policy for updating a single metadata field, replacing any existing policy for it.
+    ///
+    /// This client only updates fields starting with [SUPER_ADMIN_METADATA_PREFIX] as a super admin,
+    /// regardless of the policy set here.
+    pub fn with_metadata_policy(mut self, field_name: String, policy: MetadataPolicies) -> Self {
+        self.update_metadata_policy.insert(field_name, policy);
+        self
+    }
+
+    /// Returns true if any policy in the set was written by a newer client and is not understood
+    /// by this one. Unsupported policies always deny, and the set can not be re-serialized.
+    pub fn contains_unsupported_policies(&self) -> bool {
+        self.add_member_policy.is_unsupported()
+            || self.remove_member_policy.is_unsupported()
+            || self
+                .update_metadata_policy
+                .values()
+                .any(|policy| policy.is_unsupported())
+            || self.add_admin_policy.is_unsupported()
+            || self.remove_admin_policy.is_unsupported()
+            || self.update_permissions_policy.is_unsupported()
+            || self
+                .send_message_policy
+                .as_ref()
+                .is_some_and(|policy| policy.is_unsupported())
+            || self.update_ban_list_policy.is_unsupported()
+    }
+
+    /// Sets the policy for sending application messages. `None` lets every member send.
+    pub fn with_send_message_policy(mut self, policy: Option<PermissionsPolicies>) -> Self {
+        self.send_message_policy = policy;
+        self
+    }
+
+    /// Sets how join requests made with a group invite are handled.
+    pub fn with_join_request_policy(mut self, policy: JoinRequestPolicy) -> Self {
+        self.join_request_policy = policy;
+        self
+    }
+
+    /// Sets the policy for adding and removing inboxes from the ban list.
+    pub fn with_ban_list_policy(mut self, policy: PermissionsPolicies) -> Self {
+        self.update_ban_list_policy = policy;
+        self
+    }
+
+    /// Returns true if the given sender is allowed to send application messages to the group.
+    pub fn can_send_message(&self, sender: &CommitParticipant) -> bool {
+        self.send_message_policy
+            .as_ref()
+            .map_or(true, |policy| policy.evaluate(sender))
+    }
+
+    /// Returns the policy that applies to updates of the given metadata field,
+    /// falling back to the default for fields without a policy.
+    pub fn metadata_policy(&self, field_name: &str) -> MetadataPolicies {
+        if let Some(policy) = self.update_metadata_policy.get(field_name) {
+            return policy.clone();
+        }
+        if field_name.starts_with(SUPER_ADMIN_METADATA_PREFIX) {
+            MetadataPolicies::allow_if_actor_super_admin()
+        } else {
+            MetadataPolicies::allow_if_actor_admin()
         }
     }
 
@@ -958,6 +1255,7 @@ impl PolicySet {
             commit.metadata_changes.metadata_field_changes.iter(),
             &self.update_metadata_policy,
             &commit.actor,
+            commit.commit_rules_required,
         );
 
         // Verify that add admin policy was not violated
@@ -980,6 +1278,21 @@ impl PolicySet {
         // Permissions can only be changed by the super admin
         let permissions_changes_valid = !commit.permissions_changed || commit.actor.is_super_admin;
 
+        // Groups that require the commit rules hold every ban list change to its policy
+        let ban_list_changes_valid = !commit.commit_rules_required
+            || self.evaluate_ban_list_changes(
+                &commit.actor,
+                &commit.metadata_changes.bans_added,
+                &commit.metadata_changes.bans_removed,
+            );
+
+        // Members only ask for their own removal, and super admins can not ask at all
+        let pending_removes_valid = commit
+            .metadata_changes
+            .pending_removes_added
+            .iter()
+            .all(|inbox| inbox.inbox_id == commit.actor.inbox_id && !inbox.is_super_admin);
+
         added_inboxes_valid
             && removed_inboxes_valid
             && metadata_changes_valid
@@ -988,6 +1301,23 @@ impl PolicySet {
             && super_admin_add_valid
             && super_admin_remove_valid
             && permissions_changes_valid
+            && ban_list_changes_valid
+            && pending_removes_valid
+    }
+
+    /// The ban list is only changed by members allowed by its policy, and super admins can not be
+    /// banned, just like they can not be removed
+    pub(crate) fn evaluate_ban_list_changes(
+        &self,
+        actor: &CommitParticipant,
+        bans_added: &[Inbox],
+        bans_removed: &[Inbox],
+    ) -> bool {
+        if bans_added.is_empty() && bans_removed.is_empty() {
+            return true;
+        }
+        self.update_ban_list_policy.evaluate(actor)
+            && !bans_added.iter().any(|inbox| inbox.is_super_admin)
     }
 
     /// Evaluates a policy for a given set of changes.
@@ -998,11 +1328,12 @@ impl PolicySet {
         actor: &CommitParticipant,
     ) -> bool
     where
-        I: Iterator<Item = &'a Inbox>,
+        I: ExactSizeIterator<Item = &'a Inbox>,
         P: MembershipPolicy + std::fmt::Debug,
     {
+        let num_changes = changes.len();
         changes.all(|change| {
-            let is_ok = policy.evaluate(actor, change);
+            let is_ok = policy.evaluate_in_commit(actor, change, num_changes);
             if !is_ok {
                 tracing::info!(
                     "Policy {:?} failed for actor {:?} and change {:?}",
@@ -1016,16 +1347,30 @@ impl PolicySet {
     }
 
     /// Evaluates metadata policies for a given set of changes.
+    /// With `super_admin_prefix_enforced`, fields with the super admin prefix are super admin only
+    /// whatever their policy says.
     fn evaluate_metadata_policy<'a, I>(
         &self,
         mut changes: I,
         policies: &HashMap<String, MetadataPolicies>,
         actor: &CommitParticipant,
+        super_admin_prefix_enforced: bool,
     ) -> bool
     where
         I: Iterator<Item = &'a MetadataFieldChange>,
     {
         changes.all(|change| {
+            if super_admin_prefix_enforced
+                && !actor.is_super_admin
+                && change.field_name.starts_with(SUPER_ADMIN_METADATA_PREFIX)
+            {
+                tracing::info!(
+                    "Super admin only field {} was updated by {:?}",
+                    change.field_name,
+                    actor
+                );
+                return false;
+            }
             if let Some(policy) = policies.get(&change.field_name) {
                 if !policy.evaluate(actor, change) {
                     tracing::info!(
@@ -1038,15 +1383,9 @@ impl PolicySet {
                 }
                 return true;
             }
-            // Policy is not found for metadata change, let's check if the new field contains the super_admin prefix
-            // and evaluate accordingly
-            let policy_for_unrecognized_field =
-                if change.field_name.starts_with(SUPER_ADMIN_METADATA_PREFIX) {
-                    MetadataPolicies::allow_if_actor_super_admin()
-                } else {
-                    // Otherwise we default to admin only for fields with missing policies
-                    MetadataPolicies::allow_if_actor_admin()
-                };
+            // Policy is not found for metadata change, fields with the super_admin prefix are
+            // super admin only and every other field defaults to admin only
+            let policy_for_unrecognized_field = self.metadata_policy(&change.field_name);
             if !policy_for_unrecognized_field.evaluate(actor, change) {
                 tracing::info!(
                     "Metadata field update with unknown policy was denied: {}",
@@ -1071,6 +1410,11 @@ impl PolicySet {
         let add_admin_policy = Some(self.add_admin_policy.to_proto()?);
         let remove_admin_policy = Some(self.remove_admin_policy.to_proto()?);
         let update_permissions_policy = Some(self.update_permissions_policy.to_proto()?);
+        let send_message_policy = self
+            .send_message_policy
+            .as_ref()
+            .map(|policy| policy.to_proto())
+            .transpose()?;
         Ok(PolicySetProto {
             add_member_policy,
             remove_member_policy,
@@ -1078,6 +1422,9 @@ impl PolicySet {
             add_admin_policy,
             remove_admin_policy,
             update_permissions_policy,
+            send_message_policy,
+            join_request_policy: JoinRequestPolicyProto::from(self.join_request_policy) as i32,
+            update_ban_list_policy: Some(self.update_ban_list_policy.to_proto()?),
         })
     }
 
@@ -1114,6 +1461,21 @@ impl PolicySet {
             let policy = MetadataPolicies::try_from(policy_proto)?;
             update_metadata_policy.insert(key, policy);
         }
+        // Groups created before the send message policy existed let every member send
+        let send_message_policy = proto
+            .send_message_policy
+            .map(PermissionsPolicies::try_from)
+            .transpose()?;
+        // Join request policies added by newer clients are not understood, so deny them
+        let join_request_policy = JoinRequestPolicyProto::try_from(proto.join_request_policy)
+            .map(JoinRequestPolicy::from)
+            .unwrap_or(JoinRequestPolicy::Deny);
+        // Groups created before the ban list existed let admins and super admins update it
+        let update_ban_list_policy = proto
+            .update_ban_list_policy
+            .map(PermissionsPolicies::try_from)
+            .transpose()?
+            .unwrap_or_else(PermissionsPolicies::allow_if_actor_admin);
         Ok(Self::new(
             add_member_policy,
             remove_member_policy,
@@ -1121,7 +1483,10 @@ impl PolicySet {
             add_admin_policy,
             remove_admin_policy,
             update_permissions_policy,
-        ))
+        )
+        .with_send_message_policy(send_message_policy)
+        .with_join_request_policy(join_request_policy)
+        .with_ban_list_policy(update_ban_list_policy))
     }
 
     /// Converts the PolicySet to a Vec<u8>.
@@ -1161,7 +1526,10 @@ pub fn is_policy_all_members(policy: &PolicySet) -> Result<bool, PolicyError> {
         && policy.remove_member_policy == MembershipPolicies::allow_if_actor_admin()
         && policy.add_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
         && policy.remove_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
-        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin())
+        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.join_request_policy == JoinRequestPolicy::AdminApproval
+        && policy.update_ban_list_policy == PermissionsPolicies::allow_if_actor_admin()
+        && policy.send_message_policy.is_none())
 }
 
 /// Checks if a PolicySet is equivalent to the "Admin Only" preconfigured policy.
@@ -1186,7 +1554,50 @@ pub fn is_policy_admin_only(policy: &PolicySet) -> Result<bool, PolicyError> {
         && policy.remove_member_policy == MembershipPolicies::allow_if_actor_admin()
         && policy.add_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
         && policy.remove_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
-        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin())
+        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.join_request_policy == JoinRequestPolicy::AdminApproval
+        && policy.update_ban_list_policy == PermissionsPolicies::allow_if_actor_admin()
+        && policy.send_message_policy.is_none())
+}
+
+/// Checks if a PolicySet is equivalent to the "Announcement Channel" preconfigured policy.
+///
+/// As with the other presets, any number of metadata policies will match as long as they are all
+/// super admin only. Only admins may send messages.
+pub fn is_policy_announcement_channel(policy: &PolicySet) -> Result<bool, PolicyError> {
+    let metadata_policies_equal = policy
+        .update_metadata_policy
+        .values()
+        .all(|metadata_policy| metadata_policy.eq(&MetadataPolicies::allow_if_actor_super_admin()));
+    Ok(metadata_policies_equal
+        && policy.add_member_policy == MembershipPolicies::allow_if_actor_admin()
+        && policy.remove_member_policy == MembershipPolicies::allow_if_actor_admin()
+        && policy.add_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.remove_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.join_request_policy == JoinRequestPolicy::AdminApproval
+        && policy.update_ban_list_policy == PermissionsPolicies::allow_if_actor_admin()
+        && policy.send_message_policy == Some(PermissionsPolicies::allow_if_actor_admin()))
+}
+
+/// Checks if a PolicySet is equivalent to the "Moderated" preconfigured policy.
+///
+/// As with the other presets, any number of metadata policies will match as long as they are all
+/// admin only.
+pub fn is_policy_moderated(policy: &PolicySet) -> Result<bool, PolicyError> {
+    let metadata_policies_equal = policy
+        .update_metadata_policy
+        .values()
+        .all(|metadata_policy| metadata_policy.eq(&MetadataPolicies::allow_if_actor_admin()));
+    Ok(metadata_policies_equal
+        && policy.add_member_policy == MembershipPolicies::allow()
+        && policy.remove_member_policy == MembershipPolicies::allow_if_actor_admin()
+        && policy.add_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.remove_admin_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.update_permissions_policy == PermissionsPolicies::allow_if_actor_super_admin()
+        && policy.join_request_policy == JoinRequestPolicy::AdminApproval
+        && policy.update_ban_list_policy == PermissionsPolicies::allow_if_actor_admin()
+        && policy.send_message_policy.is_none())
 }
 
 /// Returns the "All Members" preconfigured policy.
@@ -1225,6 +1636,37 @@ pub(crate) fn policy_admin_only() -> PolicySet {
     )
 }
 
+/// Returns the "Announcement Channel" preconfigured policy.
+///
+/// A policy for broadcast style groups where only admins can send messages, add or remove members,
+/// and only super admins can update the group metadata
+pub(crate) fn policy_announcement_channel() -> PolicySet {
+    PolicySet::new(
+        MembershipPolicies::allow_if_actor_admin(),
+        MembershipPolicies::allow_if_actor_admin(),
+        MetadataPolicies::default_map(MetadataPolicies::allow_if_actor_super_admin()),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+    )
+    .with_send_message_policy(Some(PermissionsPolicies::allow_if_actor_admin()))
+}
+
+/// Returns the "Moderated" preconfigured policy.
+///
+/// A policy where any member can add new members, but only admins can remove members
+/// or update the group metadata
+pub(crate) fn policy_moderated() -> PolicySet {
+    PolicySet::new(
+        MembershipPolicies::allow(),
+        MembershipPolicies::allow_if_actor_admin(),
+        MetadataPolicies::default_map(MetadataPolicies::allow_if_actor_admin()),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+        PermissionsPolicies::allow_if_actor_super_admin(),
+    )
+}
+
 /// Implements the Default trait for PolicySet.
 impl Default for PolicySet {
     fn default() -> Self {
@@ -1240,6 +1682,10 @@ pub enum PreconfiguredPolicies {
     AllMembers,
     /// The "Admin Only" preconfigured policy.
     AdminsOnly,
+    /// The "Announcement Channel" preconfigured policy.
+    AnnouncementChannel,
+    /// The "Moderated" preconfigured policy.
+    Moderated,
 }
 
 impl PreconfiguredPolicies {
@@ -1248,15 +1694,24 @@ impl PreconfiguredPolicies {
         match self {
             PreconfiguredPolicies::AllMembers => policy_all_members(),
             PreconfiguredPolicies::AdminsOnly => policy_admin_only(),
+            PreconfiguredPolicies::AnnouncementChannel => policy_announcement_channel(),
+            PreconfiguredPolicies::Moderated => policy_moderated(),
         }
     }
 
     /// Creates a PreconfiguredPolicies from a PolicySet.
+    ///
+    /// Returns [PolicyError::InvalidPresetPolicy] if the PolicySet does not match any preset,
+    /// which is the case for groups created with a custom PolicySet.
     pub fn from_policy_set(policy_set: &PolicySet) -> Result<Self, PolicyError> {
         if is_policy_all_members(policy_set)? {
             Ok(PreconfiguredPolicies::AllMembers)
         } else if is_policy_admin_only(policy_set)? {
             Ok(PreconfiguredPolicies::AdminsOnly)
+        } else if is_policy_announcement_channel(policy_set)? {
+            Ok(PreconfiguredPolicies::AnnouncementChannel)
+        } else if is_policy_moderated(policy_set)? {
+            Ok(PreconfiguredPolicies::Moderated)
         } else {
             Err(PolicyError::InvalidPresetPolicy)
         }
@@ -1288,6 +1743,7 @@ mod tests {
             is_creator: is_super_admin,
             is_super_admin,
             is_admin,
+            is_pending_remove: false,
         }
     }
 
@@ -1367,6 +1823,7 @@ mod tests {
             },
             permissions_changed,
             dm_members,
+            commit_rules_required: false,
         }
     }
 
@@ -1614,6 +2071,29 @@ mod tests {
                 .unwrap(),
             PreconfiguredPolicies::AdminsOnly
         );
+
+        for preset in [
+            PreconfiguredPolicies::AnnouncementChannel,
+            PreconfiguredPolicies::Moderated,
+        ] {
+            let group_permissions = GroupMutablePermissions::new(preset.to_policy_set());
+            assert_eq!(group_permissions.preconfigured_policy().unwrap(), preset);
+        }
+
+        let custom_permissions = GroupMutablePermissions::new(PolicySet::new(
+            MembershipPolicies::allow_if_actor_super_admin(),
+            MembershipPolicies::deny(),
+            MetadataPolicies::default_map(MetadataPolicies::allow()),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+        ));
+        assert!(matches!(
+            custom_permissions.preconfigured_policy(),
+            Err(GroupMutablePermissionsError::Policy(
+                PolicyError::InvalidPresetPolicy
+            ))
+        ));
     }
 
     /// Tests that the preconfigured policy functions work as expected with new metadata fields.
@@ -1628,6 +2108,9 @@ mod tests {
             add_admin_policy: PermissionsPolicies::allow_if_actor_super_admin(),
             remove_admin_policy: PermissionsPolicies::allow_if_actor_super_admin(),
             update_permissions_policy: PermissionsPolicies::allow_if_actor_super_admin(),
+            send_message_policy: None,
+            join_request_policy: JoinRequestPolicy::default(),
+            update_ban_list_policy: PermissionsPolicies::allow_if_actor_admin(),
         };
 
         assert!(is_policy_all_members(&policy_set_new_metadata_permission).unwrap());
@@ -1645,6 +2128,9 @@ mod tests {
             add_admin_policy: PermissionsPolicies::allow_if_actor_super_admin(),
             remove_admin_policy: PermissionsPolicies::allow_if_actor_super_admin(),
             update_permissions_policy: PermissionsPolicies::allow_if_actor_super_admin(),
+            send_message_policy: None,
+            join_request_policy: JoinRequestPolicy::default(),
+            update_ban_list_policy: PermissionsPolicies::allow_if_actor_admin(),
         };
 
         assert!(is_policy_admin_only(&policy_set_new_metadata_permission).unwrap());
@@ -1749,6 +2235,66 @@ mod tests {
         assert!(permissions.evaluate_commit(&non_existing_field_updated_commit));
     }
 
+    #[test]
+    fn test_custom_metadata_field_policies() {
+        let permissions = policy_all_members()
+            .with_metadata_policy("topic".to_string(), MetadataPolicies::allow())
+            .with_metadata_policy(
+                SUPER_ADMIN_METADATA_PREFIX.to_string() + "linked_resource",
+                MetadataPolicies::allow(),
+            );
+
+        // Any member can update a custom field with an allow policy
+        let topic_updated_commit = build_validated_commit(
+            None,
+            None,
+            Some(vec!["topic".to_string()]),
+            false,
+            false,
+            false,
+            None,
+        );
+        assert!(permissions.evaluate_commit(&topic_updated_commit));
+
+        // Groups that require the commit rules keep super admin prefixed fields super admin
+        // only, whatever their policy says
+        let mut prefixed_field_updated_commit = build_validated_commit(
+            None,
+            None,
+            Some(vec![
+                SUPER_ADMIN_METADATA_PREFIX.to_string() + "linked_resource",
+            ]),
+            false,
+            true,
+            false,
+            None,
+        );
+        prefixed_field_updated_commit.commit_rules_required = true;
+        assert!(!permissions.evaluate_commit(&prefixed_field_updated_commit));
+
+        // Groups created before them hold received commits to the field's policy alone
+        prefixed_field_updated_commit.commit_rules_required = false;
+        assert!(permissions.evaluate_commit(&prefixed_field_updated_commit));
+
+        let prefixed_field_updated_commit = build_validated_commit(
+            None,
+            None,
+            Some(vec![
+                SUPER_ADMIN_METADATA_PREFIX.to_string() + "linked_resource",
+            ]),
+            false,
+            false,
+            true,
+            None,
+        );
+        assert!(permissions.evaluate_commit(&prefixed_field_updated_commit));
+
+        assert_eq!(
+            permissions.metadata_policy("unknown_field"),
+            MetadataPolicies::allow_if_actor_admin()
+        );
+    }
+
     #[test]
     fn test_dm_group_permissions() {
         // Simulate a group with DM Permissions
@@ -1837,4 +2383,242 @@ mod tests {
         );
         assert!(permissions.evaluate_commit(&commit));
     }
+
+    /// Tests the allow-list and max changes membership policies.
+    #[test]
+    fn test_allow_list_and_max_changes_policies() {
+        let allowed_inbox_id = rand_string();
+        let permissions = PolicySet::new(
+            MembershipPolicies::and(vec![
+                MembershipPolicies::allow_if_subject_in(vec![allowed_inbox_id.clone()]),
+                MembershipPolicies::max_changes(1),
+            ]),
+            MembershipPolicies::allow(),
+            MetadataPolicies::default_map(MetadataPolicies::deny()),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+        );
+
+        let mut commit = build_validated_commit(None, None, None, false, false, false, None);
+        commit.added_inboxes = vec![build_change(Some(allowed_inbox_id.clone()), false, false)];
+        assert!(permissions.evaluate_commit(&commit));
+
+        // Inbox not on the allow-list
+        commit.added_inboxes = vec![build_change(None, false, false)];
+        assert!(!permissions.evaluate_commit(&commit));
+
+        // Too many inboxes added in a single commit
+        commit.added_inboxes = vec![
+            build_change(Some(allowed_inbox_id.clone()), false, false),
+            build_change(Some(allowed_inbox_id), false, false),
+        ];
+        assert!(!permissions.evaluate_commit(&commit));
+
+        // Actor allow-list
+        let actor = build_actor(None, None, false, false);
+        let allowed_actors = vec![actor.inbox_id.clone()];
+        let permissions = PolicySet::new(
+            MembershipPolicies::allow_if_actor_in(allowed_actors.clone()),
+            MembershipPolicies::deny(),
+            MetadataPolicies::default_map(MetadataPolicies::allow_if_actor_in(allowed_actors)),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+            PermissionsPolicies::allow_if_actor_super_admin(),
+        );
+        let mut commit = build_validated_commit(
+            Some(MemberType::Random),
+            None,
+            Some(vec![MetadataField::GroupName.to_string()]),
+            false,
+            false,
+            false,
+            None,
+        );
+        assert!(!permissions.evaluate_commit(&commit));
+        commit.actor = actor;
+        assert!(permissions.evaluate_commit(&commit));
+
+        let restored = PolicySet::from_bytes(&permissions.to_bytes().unwrap()).unwrap();
+        assert_eq!(permissions, restored);
+    }
+
+    /// Tests that policies from newer clients are decoded as unsupported and always deny.
+    #[test]
+    fn test_unsupported_policy() {
+        let mut proto = policy_all_members().to_proto().unwrap();
+        proto.add_member_policy = Some(MembershipPolicyProto { kind: None });
+        let permissions = PolicySet::from_proto(proto).unwrap();
+
+        assert_eq!(
+            permissions.add_member_policy,
+            MembershipPolicies::Unsupported
+        );
+        assert!(permissions.contains_unsupported_policies());
+        assert!(matches!(
+            permissions.to_proto(),
+            Err(PolicyError::UnsupportedPolicy)
+        ));
+
+        let commit = build_validated_commit(
+            Some(MemberType::Random),
+            None,
+            None,
+            false,
+            true,
+            true,
+            None,
+        );
+        assert!(!permissions.evaluate_commit(&commit));
+    }
+
+    /// Tests that the send message policy is enforced and survives serialization.
+    #[test]
+    fn test_send_message_policy() {
+        let member = build_actor(None, None, false, false);
+        let admin = build_actor(None, None, true, false);
+
+        let all_members = policy_all_members();
+        assert!(all_members.can_send_message(&member));
+
+        let announcement = policy_announcement_channel();
+        assert!(!announcement.can_send_message(&member));
+        assert!(announcement.can_send_message(&admin));
+
+        let restored = PolicySet::from_bytes(&announcement.to_bytes().unwrap()).unwrap();
+        assert_eq!(announcement, restored);
+        assert!(is_policy_announcement_channel(&restored).unwrap());
+
+        // Policy sets written before the send message policy existed let every member send
+        let proto = all_members.to_proto().unwrap();
+        assert!(proto.send_message_policy.is_none());
+        let restored = PolicySet::from_proto(proto).unwrap();
+        assert!(restored.can_send_message(&member));
+    }
+
+    /// Tests that the join request policy survives serialization and that unknown values deny.
+    #[test]
+    fn test_join_request_policy() {
+        let policy_set = policy_all_members();
+        assert_eq!(
+            policy_set.join_request_policy,
+            JoinRequestPolicy::AdminApproval
+        );
+        assert_eq!(
+            PolicySet::new_dm().join_request_policy,
+            JoinRequestPolicy::Deny
+        );
+
+        let auto_approve = policy_set.with_join_request_policy(JoinRequestPolicy::AutoApprove);
+        let restored = PolicySet::from_bytes(&auto_approve.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.join_request_policy, JoinRequestPolicy::AutoApprove);
+        // A preset with a different join request policy is a custom policy set
+        assert!(!is_policy_all_members(&restored).unwrap());
+        assert!(PreconfiguredPolicies::from_policy_set(&restored).is_err());
+
+        let mut proto = auto_approve.to_proto().unwrap();
+        proto.join_request_policy = JoinRequestPolicyProto::Unspecified as i32;
+        let restored = PolicySet::from_proto(proto.clone()).unwrap();
+        assert_eq!(
+            restored.join_request_policy,
+            JoinRequestPolicy::AdminApproval
+        );
+
+        proto.join_request_policy = 100;
+        let restored = PolicySet::from_proto(proto).unwrap();
+        assert_eq!(restored.join_request_policy, JoinRequestPolicy::Deny);
+    }
+
+    /// Tests that ban list changes are held to the ban list policy in groups that require the
+    /// commit rules, that super admins can not be banned, and that the policy defaults to admins.
+    #[test]
+    fn test_ban_list_policy() {
+        let permissions = policy_all_members();
+        let mut ban_commit = build_validated_commit(None, None, None, false, false, false, None);
+        ban_commit.commit_rules_required = true;
+        ban_commit.metadata_changes.bans_added = vec![build_change(None, false, false)];
+        assert!(!permissions.evaluate_commit(&ban_commit));
+
+        ban_commit.metadata_changes.bans_added = vec![];
+        ban_commit.metadata_changes.bans_removed = vec![build_change(None, false, false)];
+        assert!(!permissions.evaluate_commit(&ban_commit));
+
+        ban_commit.actor.is_admin = true;
+        assert!(permissions.evaluate_commit(&ban_commit));
+
+        // Not even a super admin can ban another super admin
+        ban_commit.actor.is_super_admin = true;
+        ban_commit.metadata_changes.bans_added = vec![build_change(None, true, true)];
+        assert!(!permissions.evaluate_commit(&ban_commit));
+
+        // Groups created before the commit rules only enforce the policy when sending
+        ban_commit.commit_rules_required = false;
+        assert!(permissions.evaluate_commit(&ban_commit));
+
+        // Groups created before the ban list policy existed fall back to admins
+        let mut proto = permissions.to_proto().unwrap();
+        proto.update_ban_list_policy = None;
+        let restored = PolicySet::from_proto(proto).unwrap();
+        assert_eq!(
+            restored.update_ban_list_policy,
+            PermissionsPolicies::allow_if_actor_admin()
+        );
+        assert!(is_policy_all_members(&restored).unwrap());
+
+        // Changing who can update the ban list makes the policy set custom
+        let restricted =
+            restored.with_ban_list_policy(PermissionsPolicies::allow_if_actor_super_admin());
+        assert!(!is_policy_all_members(&restricted).unwrap());
+    }
+
+    /// Tests that members can only ask for their own removal, and that removing a member that
+    /// asked to leave still follows the remove member policy.
+    #[test]
+    fn test_pending_remove() {
+        let permissions = policy_admin_only();
+        let mut leave_commit = build_validated_commit(None, None, None, false, false, false, None);
+        leave_commit.metadata_changes.pending_removes_added = vec![build_change(
+            Some(leave_commit.actor_inbox_id()),
+            false,
+            false,
+        )];
+        assert!(permissions.evaluate_commit(&leave_commit));
+
+        // Asking for someone else to be removed is refused, even for admins
+        leave_commit.metadata_changes.pending_removes_added =
+            vec![build_change(None, false, false)];
+        assert!(!permissions.evaluate_commit(&leave_commit));
+        leave_commit.actor.is_admin = true;
+        assert!(!permissions.evaluate_commit(&leave_commit));
+
+        // Super admins can not ask to leave
+        let mut super_admin_leave_commit =
+            build_validated_commit(None, None, None, false, true, true, None);
+        super_admin_leave_commit
+            .metadata_changes
+            .pending_removes_added = vec![build_change(
+            Some(super_admin_leave_commit.actor_inbox_id()),
+            true,
+            true,
+        )];
+        assert!(!permissions.evaluate_commit(&super_admin_leave_commit));
+
+        // A non admin can not remove members, even if they asked to leave
+        let mut remove_commit = build_validated_commit(
+            None,
+            Some(MemberType::Random),
+            None,
+            false,
+            false,
+            false,
+            None,
+        );
+        remove_commit.removed_inboxes[0].is_pending_remove = true;
+        remove_commit.metadata_changes.pending_removes_removed =
+            remove_commit.removed_inboxes.clone();
+        assert!(!permissions.evaluate_commit(&remove_commit));
+
+        remove_commit.actor.is_admin = true;
+        assert!(permissions.evaluate_commit(&remove_commit));
+    }
 }
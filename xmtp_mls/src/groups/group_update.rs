@@ -0,0 +1,409 @@
+use std::collections::HashSet;
+
+use xmtp_cryptography::signature::sanitize_account_addresses;
+use xmtp_id::InboxId;
+
+use crate::{
+    configuration::{MAX_GROUP_METADATA_FIELDS, MAX_GROUP_SIZE},
+    storage::group_intent::{IntentKind, NewGroupIntent},
+    xmtp_openmls_provider::XmtpOpenMlsProvider,
+    Client, XmtpApi,
+};
+
+use super::{
+    group_mutable_metadata::{validate_metadata_field, GroupMutableMetadataError, MetadataField},
+    intents::{
+        AdminListActionType, BatchUpdateIntentData, PermissionPolicyOption, PermissionUpdateType,
+        UpdateAdminListIntentData, UpdateMetadataIntentData, UpdatePermissionIntentData,
+    },
+    GroupError, MlsGroup, UpdateAdminListType,
+};
+
+/// Collects changes to a group so they can be published together.
+///
+/// All changes are applied in a single commit, which is validated as a whole against the group's
+/// permissions and shows up as a single `GroupUpdated` message. If any change is not permitted,
+/// none of them are applied.
+///
+/// ```ignore
+/// group
+///     .update(&client)
+///     .group_name("Weekend plans".to_string())
+///     .add_members_by_inbox_id(vec![bola_inbox_id])
+///     .update_admin_list(UpdateAdminListType::Add, caro_inbox_id)
+///     .apply()
+///     .await?;
+/// ```
+pub struct GroupUpdate<'a, ApiClient> {
+    group: &'a MlsGroup,
+    client: &'a Client<ApiClient>,
+    addresses_to_add: Vec<String>,
+    inbox_ids_to_add: Vec<InboxId>,
+    addresses_to_remove: Vec<String>,
+    inbox_ids_to_remove: Vec<InboxId>,
+    metadata: Vec<UpdateMetadataIntentData>,
+    admin_lists: Vec<UpdateAdminListIntentData>,
+    permissions: Vec<UpdatePermissionIntentData>,
+}
+
+impl MlsGroup {
+    /// Starts a set of changes to the group that will be published in a single commit
+    pub fn update<'a, ApiClient: XmtpApi>(
+        &'a self,
+        client: &'a Client<ApiClient>,
+    ) -> GroupUpdate<'a, ApiClient> {
+        GroupUpdate {
+            group: self,
+            client,
+            addresses_to_add: vec![],
+            inbox_ids_to_add: vec![],
+            addresses_to_remove: vec![],
+            inbox_ids_to_remove: vec![],
+            metadata: vec![],
+            admin_lists: vec![],
+            permissions: vec![],
+        }
+    }
+}
+
+impl<'a, ApiClient> GroupUpdate<'a, ApiClient>
+where
+    ApiClient: XmtpApi,
+{
+    pub fn add_members(mut self, account_addresses: Vec<String>) -> Self {
+        self.addresses_to_add.extend(account_addresses);
+        self
+    }
+
+    pub fn add_members_by_inbox_id(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.inbox_ids_to_add.extend(inbox_ids);
+        self
+    }
+
+    pub fn remove_members(mut self, account_addresses: Vec<String>) -> Self {
+        self.addresses_to_remove.extend(account_addresses);
+        self
+    }
+
+    pub fn remove_members_by_inbox_id(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.inbox_ids_to_remove.extend(inbox_ids);
+        self
+    }
+
+    pub fn group_name(self, group_name: String) -> Self {
+        self.metadata_field(MetadataField::GroupName.to_string(), group_name)
+    }
+
+    pub fn group_description(self, group_description: String) -> Self {
+        self.metadata_field(MetadataField::Description.to_string(), group_description)
+    }
+
+    pub fn group_image_url_square(self, group_image_url_square: String) -> Self {
+        self.metadata_field(
+            MetadataField::GroupImageUrlSquare.to_string(),
+            group_image_url_square,
+        )
+    }
+
+    pub fn group_pinned_frame_url(self, pinned_frame_url: String) -> Self {
+        self.metadata_field(
+            MetadataField::GroupPinnedFrameUrl.to_string(),
+            pinned_frame_url,
+        )
+    }
+
+    /// Sets any metadata field, see [MlsGroup::update_metadata_field]
+    pub fn metadata_field(mut self, field_name: String, field_value: String) -> Self {
+        self.metadata
+            .push(UpdateMetadataIntentData::new(field_name, field_value));
+        self
+    }
+
+    pub fn update_admin_list(mut self, action_type: UpdateAdminListType, inbox_id: String) -> Self {
+        let action_type = match action_type {
+            UpdateAdminListType::Add => AdminListActionType::Add,
+            UpdateAdminListType::Remove => AdminListActionType::Remove,
+            UpdateAdminListType::AddSuper => AdminListActionType::AddSuper,
+            UpdateAdminListType::RemoveSuper => AdminListActionType::RemoveSuper,
+        };
+        self.admin_lists
+            .push(UpdateAdminListIntentData::new(action_type, inbox_id));
+        self
+    }
+
+    /// Adds the inboxes to the ban list. Banned inboxes that are still members should also be
+    /// passed to [Self::remove_members_by_inbox_id], see [MlsGroup::ban_members].
+    pub fn ban(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.admin_lists.extend(
+            inbox_ids.into_iter().map(|inbox_id| {
+                UpdateAdminListIntentData::new(AdminListActionType::AddBan, inbox_id)
+            }),
+        );
+        self
+    }
+
+    /// Removes the inboxes from the ban list
+    pub fn unban(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.admin_lists
+            .extend(inbox_ids.into_iter().map(|inbox_id| {
+                UpdateAdminListIntentData::new(AdminListActionType::RemoveBan, inbox_id)
+            }));
+        self
+    }
+
+    /// Adds the inboxes to the pending remove list. Members may only add themselves, see
+    /// [MlsGroup::leave].
+    pub fn request_removal(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.admin_lists
+            .extend(inbox_ids.into_iter().map(|inbox_id| {
+                UpdateAdminListIntentData::new(AdminListActionType::AddPendingRemove, inbox_id)
+            }));
+        self
+    }
+
+    /// Removes the inboxes from the pending remove list. Only allowed once they are no longer
+    /// members, so this is usually combined with [Self::remove_members_by_inbox_id].
+    pub fn clear_removal_requests(mut self, inbox_ids: Vec<InboxId>) -> Self {
+        self.admin_lists
+            .extend(inbox_ids.into_iter().map(|inbox_id| {
+                UpdateAdminListIntentData::new(AdminListActionType::RemovePendingRemove, inbox_id)
+            }));
+        self
+    }
+
+    /// Changes a permission policy. `metadata_field_name` is required for
+    /// [PermissionUpdateType::UpdateMetadata] and ignored otherwise.
+    pub fn update_permission_policy(
+        mut self,
+        permission_update_type: PermissionUpdateType,
+        permission_policy: PermissionPolicyOption,
+        metadata_field_name: Option<String>,
+    ) -> Self {
+        self.permissions.push(UpdatePermissionIntentData::new(
+            permission_update_type,
+            permission_policy,
+            metadata_field_name,
+        ));
+        self
+    }
+
+    /// Publishes all collected changes in a single commit and waits for it to be resolved
+    pub async fn apply(self) -> Result<(), GroupError> {
+        let Self {
+            group,
+            client,
+            addresses_to_add,
+            mut inbox_ids_to_add,
+            addresses_to_remove,
+            mut inbox_ids_to_remove,
+            metadata,
+            admin_lists,
+            permissions,
+        } = self;
+
+        let provider = group.mls_provider()?;
+        validate_metadata_changes(group, &provider, &metadata)?;
+        if permissions.iter().any(|update| {
+            update.update_type == PermissionUpdateType::UpdateMetadata
+                && update.metadata_field_name.is_none()
+        }) {
+            return Err(GroupError::InvalidPermissionUpdate);
+        }
+
+        if !addresses_to_add.is_empty() {
+            let account_addresses = sanitize_account_addresses(addresses_to_add)?;
+            let inbox_ids = client
+                .find_inbox_ids_from_addresses(account_addresses.clone())
+                .await?;
+            let missing_addresses: Vec<String> = account_addresses
+                .iter()
+                .zip(&inbox_ids)
+                .filter(|(_, inbox_id)| inbox_id.is_none())
+                .map(|(address, _)| address.clone())
+                .collect();
+            if !missing_addresses.is_empty() {
+                return Err(GroupError::AddressNotFound(missing_addresses));
+            }
+            inbox_ids_to_add.extend(inbox_ids.into_iter().flatten());
+        }
+        if !addresses_to_remove.is_empty() {
+            let inbox_ids = client
+                .find_inbox_ids_from_addresses(addresses_to_remove)
+                .await?;
+            inbox_ids_to_remove.extend(inbox_ids.into_iter().flatten());
+        }
+
+        let membership = if inbox_ids_to_add.is_empty() && inbox_ids_to_remove.is_empty() {
+            None
+        } else {
+            let member_count = group.members(client).await?.len();
+            if member_count + inbox_ids_to_add.len() > MAX_GROUP_SIZE as usize {
+                return Err(GroupError::UserLimitExceeded);
+            }
+            Some(
+                group
+                    .get_membership_update_intent(
+                        client,
+                        &provider,
+                        inbox_ids_to_add,
+                        inbox_ids_to_remove,
+                    )
+                    .await?,
+            )
+        };
+
+        let intent_data = BatchUpdateIntentData {
+            membership,
+            metadata,
+            admin_lists,
+            permissions,
+        };
+        if intent_data.is_empty() {
+            tracing::warn!("Group update has no changes");
+            return Ok(());
+        }
+
+        let intent = provider
+            .conn_ref()
+            .insert_group_intent(NewGroupIntent::new(
+                IntentKind::BatchUpdate,
+                group.group_id.clone(),
+                intent_data.into(),
+            ))?;
+
+        group
+            .sync_until_intent_resolved(&provider, intent.id, client)
+            .await
+    }
+}
+
+/// Checks the metadata changes against the size limits before anything is published
+fn validate_metadata_changes(
+    group: &MlsGroup,
+    provider: &XmtpOpenMlsProvider,
+    metadata: &[UpdateMetadataIntentData],
+) -> Result<(), GroupError> {
+    if metadata.is_empty() {
+        return Ok(());
+    }
+    for update in metadata {
+        validate_metadata_field(&update.field_name, &update.field_value)?;
+    }
+
+    let mutable_metadata = group.mutable_metadata(provider)?;
+    let new_fields = metadata
+        .iter()
+        .map(|update| &update.field_name)
+        .filter(|field_name| !mutable_metadata.attributes.contains_key(*field_name))
+        .collect::<HashSet<_>>()
+        .len();
+    let field_count = mutable_metadata.attributes.len() + new_fields;
+    if new_fields > 0 && field_count > MAX_GROUP_METADATA_FIELDS {
+        return Err(GroupMutableMetadataError::TooManyFields(field_count).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use xmtp_cryptography::utils::generate_local_wallet;
+    use xmtp_proto::xmtp::mls::message_contents::EncodedContent;
+
+    use crate::{
+        builder::ClientBuilder,
+        codecs::{group_updated::GroupUpdatedCodec, ContentCodec},
+        groups::{GroupMetadataOptions, PreconfiguredPolicies, UpdateAdminListType},
+        storage::group_message::GroupMessageKind,
+        InboxOwner,
+    };
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_batch_update_is_one_commit() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola_wallet = generate_local_wallet();
+        let bola = ClientBuilder::new_test_client(&bola_wallet).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let group = amal
+            .create_group(
+                Some(PreconfiguredPolicies::AdminsOnly.to_policy_set()),
+                GroupMetadataOptions::default(),
+            )
+            .unwrap();
+        let provider = group.mls_provider().unwrap();
+        let epoch_before = group.load_mls_group(&provider).unwrap().epoch();
+
+        group
+            .update(&amal)
+            .group_name("Batched".to_string())
+            .metadata_field("topic".to_string(), "rust".to_string())
+            .add_members(vec![bola_wallet.get_address()])
+            .add_members_by_inbox_id(vec![caro.inbox_id()])
+            .update_admin_list(UpdateAdminListType::Add, bola.inbox_id())
+            .apply()
+            .await
+            .unwrap();
+
+        let mls_group = group.load_mls_group(&provider).unwrap();
+        assert_eq!(mls_group.epoch().as_u64(), epoch_before.as_u64() + 1);
+        assert_eq!(group.members(&amal).await.unwrap().len(), 3);
+        assert_eq!(group.group_name(&provider).unwrap(), "Batched");
+        assert_eq!(
+            group.metadata_field(&provider, "topic").unwrap(),
+            Some("rust".to_string())
+        );
+        assert!(group.is_admin(bola.inbox_id(), &provider).unwrap());
+        // bola's address was resolved through the inbox id cache
+        assert_eq!(amal.inbox_id_cache_stats().unwrap().entries, 1);
+
+        // All the changes are described by a single transcript message
+        let messages = group.find_messages(None, None, None, None, None).unwrap();
+        let updates: Vec<_> = messages
+            .iter()
+            .filter(|message| message.kind == GroupMessageKind::MembershipChange)
+            .collect();
+        assert_eq!(updates.len(), 1);
+        let encoded =
+            EncodedContent::decode(updates[0].decrypted_message_bytes.as_slice()).unwrap();
+        let group_updated = GroupUpdatedCodec::decode(encoded).unwrap();
+        assert_eq!(group_updated.added_inboxes.len(), 2);
+        assert_eq!(group_updated.metadata_field_changes.len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_batch_update_is_all_or_nothing() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let amal_group = amal
+            .create_group(
+                Some(PreconfiguredPolicies::AdminsOnly.to_policy_set()),
+                GroupMetadataOptions::default(),
+            )
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+
+        bola.sync_welcomes().await.unwrap();
+        let bola_group = bola.group(amal_group.group_id.clone()).unwrap();
+        bola_group.sync(&bola).await.unwrap();
+
+        // bola is not an admin, so neither the rename nor the add may go through
+        let result = bola_group
+            .update(&bola)
+            .group_name("Renamed".to_string())
+            .add_members_by_inbox_id(vec![caro.inbox_id()])
+            .apply()
+            .await;
+        assert!(result.is_err());
+
+        amal_group.sync(&amal).await.unwrap();
+        let provider = amal_group.mls_provider().unwrap();
+        assert_eq!(amal_group.group_name(&provider).unwrap(), "");
+        assert_eq!(amal_group.members(&amal).await.unwrap().len(), 2);
+    }
+}
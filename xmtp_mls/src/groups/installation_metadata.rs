@@ -0,0 +1,270 @@
+//! Device details (name, platform, creation time) that an installation shares with the other
+//! installations of its inbox, so they can be told apart when revoking installations.
+//!
+//! The details are sent as messages to the sync group, whose members are only the installations
+//! of the inbox, so they are never visible outside of it. An installation shares its details when
+//! they change and when it joins a sync group. The first time it hears from another installation
+//! it replies with its own, so installations that join later learn about the existing ones.
+//!
+//! The details are not published with the key package or an identity update. Anyone can fetch
+//! those, so every device name would be public. The trade-off is that sharing needs the
+//! `message-history` feature, and an installation only learns the details of another one once
+//! that installation has synced the sync group and replied. Until then it is missing from
+//! [`Client::installation_metadata`].
+
+use std::collections::HashMap;
+
+use prost::Message;
+use xmtp_proto::xmtp::mls::message_contents::{
+    plaintext_envelope::{v2::MessageType, Content, V2},
+    InstallationMetadata, PlaintextEnvelope,
+};
+
+use super::{intents::SendMessageIntentData, GroupError, MlsGroup};
+use crate::{
+    client::ClientError,
+    identity_updates::load_identity_updates,
+    storage::{
+        db_connection::DbConnection,
+        group_intent::{IntentKind, NewGroupIntent, ID},
+        installation_metadata::StoredInstallationMetadata,
+    },
+    utils::time::now_ns,
+    Client, XmtpApi,
+};
+
+impl<ApiClient> Client<ApiClient>
+where
+    ApiClient: XmtpApi,
+{
+    /// Set the device name and platform this installation shows to the other installations of
+    /// its inbox. They are shared right away if the client is registered, otherwise once it is.
+    pub async fn set_installation_metadata(
+        &self,
+        device_name: String,
+        platform: String,
+    ) -> Result<(), ClientError> {
+        let changed = self.store().conn()?.set_installation_metadata(
+            &self.installation_public_key(),
+            device_name,
+            platform,
+        )?;
+        if changed && self.identity().is_ready() {
+            self.share_installation_metadata().await?;
+        }
+
+        Ok(())
+    }
+
+    /// The details the installations of this inbox shared, keyed by installation id.
+    /// Installations that never shared any, that have not replied yet, or that have been revoked,
+    /// are missing.
+    ///
+    /// If `refresh_from_network` is true, the sync groups are synced first. Otherwise only what
+    /// is stored locally is used.
+    pub async fn installation_metadata(
+        &self,
+        refresh_from_network: bool,
+    ) -> Result<HashMap<Vec<u8>, StoredInstallationMetadata>, ClientError> {
+        let conn = self.store().conn()?;
+        if refresh_from_network {
+            load_identity_updates(&self.api_client, &conn, vec![self.inbox_id()]).await?;
+            for sync_group in conn.find_sync_groups()? {
+                self.group(sync_group.id)?.sync(self).await?;
+            }
+        }
+
+        let association_state = self
+            .get_association_state(&conn, self.inbox_id(), None)
+            .await?;
+        let installation_metadata = conn
+            .get_installation_metadata(&association_state.installation_ids())?
+            .into_iter()
+            .map(|metadata| (metadata.installation_id.clone(), metadata))
+            .collect();
+
+        Ok(installation_metadata)
+    }
+
+    /// Send the details of this installation to the sync group, creating the group if needed.
+    /// Does nothing if no details were set.
+    pub(crate) async fn share_installation_metadata(&self) -> Result<(), ClientError> {
+        let provider = self.mls_provider()?;
+        if provider
+            .conn_ref()
+            .get_installation_metadata(&[self.installation_public_key()])?
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        let sync_group = match self.get_sync_group() {
+            Ok(group) => group,
+            Err(_) => self.create_sync_group()?,
+        };
+        // Catch up first, so installations that are still missing are added before the details
+        // are sent
+        sync_group.sync(self).await?;
+
+        if let Some(intent_id) = sync_group.queue_installation_metadata(provider.conn_ref())? {
+            sync_group
+                .sync_until_intent_resolved(&provider, intent_id, self)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MlsGroup {
+    /// Queue a message with the details of this installation, sent the next time the group is
+    /// synced. Returns `None` if this installation has no details to share.
+    pub(crate) fn queue_installation_metadata(
+        &self,
+        conn: &DbConnection,
+    ) -> Result<Option<ID>, GroupError> {
+        let Some(metadata) = conn
+            .get_installation_metadata(&[self.context.installation_public_key()])?
+            .pop()
+        else {
+            return Ok(None);
+        };
+
+        let envelope = PlaintextEnvelope {
+            content: Some(Content::V2(V2 {
+                idempotency_key: now_ns().to_string(),
+                message_type: Some(MessageType::InstallationMetadata(metadata.to_proto())),
+            })),
+        };
+        let intent_data: Vec<u8> = SendMessageIntentData::new(envelope.encode_to_vec()).into();
+        let intent = conn.insert_group_intent(NewGroupIntent::new(
+            IntentKind::SendMessage,
+            self.group_id.clone(),
+            intent_data,
+        ))?;
+
+        Ok(Some(intent.id))
+    }
+
+    /// Store the details another installation of this inbox shared. If it had not shared any
+    /// before, the details of this installation are queued in reply.
+    pub(crate) fn process_installation_metadata(
+        &self,
+        conn: &DbConnection,
+        sender_inbox_id: &str,
+        sender_installation_id: Vec<u8>,
+        metadata: InstallationMetadata,
+        sent_at_ns: u64,
+    ) -> Result<(), GroupError> {
+        if sender_inbox_id != self.context.inbox_id() {
+            tracing::warn!(
+                "ignoring installation metadata from {} which belongs to another inbox",
+                hex::encode(&sender_installation_id)
+            );
+            return Ok(());
+        }
+
+        let is_new = conn
+            .get_installation_metadata(&[sender_installation_id.clone()])?
+            .is_empty();
+        conn.insert_or_replace_installation_metadata(&[StoredInstallationMetadata::from_proto(
+            sender_installation_id,
+            metadata,
+            sent_at_ns as i64,
+        )])?;
+        if is_new {
+            self.queue_installation_metadata(conn)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xmtp_cryptography::utils::generate_local_wallet;
+
+    use crate::builder::ClientBuilder;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_installation_metadata_is_shared_within_the_inbox() {
+        let wallet = generate_local_wallet();
+        let phone = ClientBuilder::new_test_client(&wallet).await;
+        let laptop = ClientBuilder::new_test_client(&wallet).await;
+        let stranger = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        phone
+            .set_installation_metadata("Alix's phone".to_string(), "ios".to_string())
+            .await
+            .unwrap();
+        laptop.sync_welcomes().await.unwrap();
+        laptop
+            .set_installation_metadata("Alix's laptop".to_string(), "macos".to_string())
+            .await
+            .unwrap();
+
+        let metadata = phone.installation_metadata(true).await.unwrap();
+        assert_eq!(metadata.len(), 2);
+        let laptop_metadata = &metadata[&laptop.installation_public_key()];
+        assert_eq!(laptop_metadata.device_name, "Alix's laptop");
+        assert_eq!(laptop_metadata.platform, "macos");
+
+        let metadata = laptop.installation_metadata(true).await.unwrap();
+        assert_eq!(
+            metadata[&phone.installation_public_key()].device_name,
+            "Alix's phone"
+        );
+
+        // Nothing is published where other inboxes can read it
+        stranger.sync_welcomes().await.unwrap();
+        assert!(stranger
+            .store()
+            .conn()
+            .unwrap()
+            .find_sync_groups()
+            .unwrap()
+            .is_empty());
+        assert!(stranger
+            .installation_metadata(true)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_new_installations_learn_about_existing_ones() {
+        let wallet = generate_local_wallet();
+        let phone = ClientBuilder::new_test_client(&wallet).await;
+        phone
+            .set_installation_metadata("Alix's phone".to_string(), "ios".to_string())
+            .await
+            .unwrap();
+
+        // Joined after the phone shared its details, so it can not read that message
+        let tablet = ClientBuilder::new_test_client(&wallet).await;
+        let sync_group = phone.get_sync_group().unwrap();
+        sync_group
+            .maybe_update_installations(&phone.mls_provider().unwrap(), Some(0), &phone)
+            .await
+            .unwrap();
+        tablet.sync_welcomes().await.unwrap();
+        tablet
+            .set_installation_metadata("Alix's tablet".to_string(), "android".to_string())
+            .await
+            .unwrap();
+
+        // The phone replies with its details the first time it hears from the tablet
+        let metadata = phone.installation_metadata(true).await.unwrap();
+        assert_eq!(
+            metadata[&tablet.installation_public_key()].platform,
+            "android"
+        );
+        sync_group.sync(&phone).await.unwrap();
+
+        let metadata = tablet.installation_metadata(true).await.unwrap();
+        assert_eq!(
+            metadata[&phone.installation_public_key()].device_name,
+            "Alix's phone"
+        );
+    }
+}
@@ -9,6 +9,7 @@ use thiserror::Error;
 
 use xmtp_proto::xmtp::mls::database::{
     addresses_or_installation_ids::AddressesOrInstallationIds as AddressesOrInstallationIdsProto,
+    batch_update_data::{Version as BatchUpdateVersion, V1 as BatchUpdateV1},
     post_commit_action::{
         Installation as InstallationProto, Kind as PostCommitActionKind,
         SendWelcomes as SendWelcomesProto,
@@ -21,7 +22,7 @@ use xmtp_proto::xmtp::mls::database::{
     update_metadata_data::{Version as UpdateMetadataVersion, V1 as UpdateMetadataV1},
     update_permission_data::{Version as UpdatePermissionVersion, V1 as UpdatePermissionV1},
     AccountAddresses, AddressesOrInstallationIds as AddressesOrInstallationIdsProtoWrapper,
-    InstallationIds, PostCommitAction as PostCommitActionProto, SendMessageData,
+    BatchUpdateData, InstallationIds, PostCommitAction as PostCommitActionProto, SendMessageData,
     UpdateAdminListsData, UpdateGroupMembershipData, UpdateMetadataData, UpdatePermissionData,
 };
 
@@ -33,7 +34,9 @@ use crate::{
 use super::{
     group_membership::GroupMembership,
     group_mutable_metadata::MetadataField,
-    group_permissions::{MembershipPolicies, MetadataPolicies, PermissionsPolicies},
+    group_permissions::{
+        JoinRequestPolicy, MembershipPolicies, MetadataPolicies, PermissionsPolicies,
+    },
 };
 
 #[derive(Debug, Error)]
@@ -305,10 +308,14 @@ impl TryFrom<&Vec<u8>> for UpdateGroupMembershipIntentData {
 #[repr(i32)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum AdminListActionType {
-    Add = 1,         // Matches ADD_ADMIN in Protobuf
-    Remove = 2,      // Matches REMOVE_ADMIN in Protobuf
-    AddSuper = 3,    // Matches ADD_SUPER_ADMIN in Protobuf
-    RemoveSuper = 4, // Matches REMOVE_SUPER_ADMIN in Protobuf
+    Add = 1,                 // Matches ADD_ADMIN in Protobuf
+    Remove = 2,              // Matches REMOVE_ADMIN in Protobuf
+    AddSuper = 3,            // Matches ADD_SUPER_ADMIN in Protobuf
+    RemoveSuper = 4,         // Matches REMOVE_SUPER_ADMIN in Protobuf
+    AddBan = 5,              // Matches ADD_BAN in Protobuf
+    RemoveBan = 6,           // Matches REMOVE_BAN in Protobuf
+    AddPendingRemove = 7,    // Matches ADD_PENDING_REMOVE in Protobuf
+    RemovePendingRemove = 8, // Matches REMOVE_PENDING_REMOVE in Protobuf
 }
 
 impl TryFrom<i32> for AdminListActionType {
@@ -320,6 +327,10 @@ impl TryFrom<i32> for AdminListActionType {
             2 => Ok(AdminListActionType::Remove),
             3 => Ok(AdminListActionType::AddSuper),
             4 => Ok(AdminListActionType::RemoveSuper),
+            5 => Ok(AdminListActionType::AddBan),
+            6 => Ok(AdminListActionType::RemoveBan),
+            7 => Ok(AdminListActionType::AddPendingRemove),
+            8 => Ok(AdminListActionType::RemovePendingRemove),
             _ => Err("Unknown value for AdminListActionType"),
         }
     }
@@ -396,6 +407,9 @@ pub enum PermissionUpdateType {
     AddAdmin = 3,       // Matches ADD_ADMIN in Protobuf
     RemoveAdmin = 4,    // Matches REMOVE_ADMIN in Protobuf
     UpdateMetadata = 5, // Matches UPDATE_METADATA in Protobuf
+    SendMessage = 6,    // Matches SEND_MESSAGE in Protobuf
+    JoinRequest = 7,    // Matches JOIN_REQUEST in Protobuf
+    UpdateBanList = 8,  // Matches UPDATE_BAN_LIST in Protobuf
 }
 
 impl TryFrom<i32> for PermissionUpdateType {
@@ -408,6 +422,9 @@ impl TryFrom<i32> for PermissionUpdateType {
             3 => Ok(PermissionUpdateType::AddAdmin),
             4 => Ok(PermissionUpdateType::RemoveAdmin),
             5 => Ok(PermissionUpdateType::UpdateMetadata),
+            6 => Ok(PermissionUpdateType::SendMessage),
+            7 => Ok(PermissionUpdateType::JoinRequest),
+            8 => Ok(PermissionUpdateType::UpdateBanList),
             _ => Err("Unknown value for PermissionUpdateType"),
         }
     }
@@ -478,6 +495,18 @@ impl From<PermissionPolicyOption> for PermissionsPolicies {
     }
 }
 
+impl From<PermissionPolicyOption> for JoinRequestPolicy {
+    fn from(value: PermissionPolicyOption) -> Self {
+        match value {
+            PermissionPolicyOption::Allow => JoinRequestPolicy::AutoApprove,
+            PermissionPolicyOption::Deny => JoinRequestPolicy::Deny,
+            PermissionPolicyOption::AdminOnly | PermissionPolicyOption::SuperAdminOnly => {
+                JoinRequestPolicy::AdminApproval
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UpdatePermissionIntentData {
     pub update_type: PermissionUpdateType,
@@ -556,6 +585,75 @@ impl TryFrom<Vec<u8>> for UpdatePermissionIntentData {
     }
 }
 
+/// Several group changes that are published together, in a single commit.
+///
+/// Stored as a `BatchUpdateData`, in which each change keeps the encoding of its standalone intent.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BatchUpdateIntentData {
+    pub membership: Option<UpdateGroupMembershipIntentData>,
+    pub metadata: Vec<UpdateMetadataIntentData>,
+    pub admin_lists: Vec<UpdateAdminListIntentData>,
+    pub permissions: Vec<UpdatePermissionIntentData>,
+}
+
+impl BatchUpdateIntentData {
+    pub fn is_empty(&self) -> bool {
+        self.membership
+            .as_ref()
+            .map_or(true, |membership| membership.is_empty())
+            && self.metadata.is_empty()
+            && self.admin_lists.is_empty()
+            && self.permissions.is_empty()
+    }
+}
+
+impl From<BatchUpdateIntentData> for Vec<u8> {
+    fn from(intent: BatchUpdateIntentData) -> Self {
+        BatchUpdateData {
+            version: Some(BatchUpdateVersion::V1(BatchUpdateV1 {
+                membership: intent.membership.map(Into::into),
+                metadata: intent.metadata.into_iter().map(Into::into).collect(),
+                admin_lists: intent.admin_lists.into_iter().map(Into::into).collect(),
+                permissions: intent.permissions.into_iter().map(Into::into).collect(),
+            })),
+        }
+        .encode_to_vec()
+    }
+}
+
+impl TryFrom<Vec<u8>> for BatchUpdateIntentData {
+    type Error = IntentError;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        let msg = BatchUpdateData::decode(Bytes::from(data))?;
+        let Some(BatchUpdateVersion::V1(v1)) = msg.version else {
+            return Err(IntentError::Generic("missing payload".to_string()));
+        };
+
+        Ok(Self {
+            membership: v1
+                .membership
+                .map(UpdateGroupMembershipIntentData::try_from)
+                .transpose()?,
+            metadata: v1
+                .metadata
+                .into_iter()
+                .map(UpdateMetadataIntentData::try_from)
+                .collect::<Result<_, _>>()?,
+            admin_lists: v1
+                .admin_lists
+                .into_iter()
+                .map(UpdateAdminListIntentData::try_from)
+                .collect::<Result<_, _>>()?,
+            permissions: v1
+                .permissions
+                .into_iter()
+                .map(UpdatePermissionIntentData::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum PostCommitAction {
     SendWelcomes(SendWelcomesAction),
@@ -598,6 +696,11 @@ impl From<InstallationProto> for Installation {
 pub struct SendWelcomesAction {
     pub installations: Vec<Installation>,
     pub welcome_message: Vec<u8>,
+    /// The welcome was built without the ratchet tree extension,
+    /// the tree has to be published alongside it
+    pub ratchet_tree_out_of_band: bool,
+    /// TLS serialized ratchet tree, captured when the commit is merged
+    pub ratchet_tree: Option<Vec<u8>>,
 }
 
 impl SendWelcomesAction {
@@ -605,6 +708,8 @@ impl SendWelcomesAction {
         Self {
             installations,
             welcome_message,
+            ratchet_tree_out_of_band: false,
+            ratchet_tree: None,
         }
     }
 
@@ -618,6 +723,8 @@ impl SendWelcomesAction {
                     .map(|i| i.into())
                     .collect(),
                 welcome_message: self.welcome_message.clone(),
+                ratchet_tree_out_of_band: self.ratchet_tree_out_of_band,
+                ratchet_tree: self.ratchet_tree.clone().unwrap_or_default(),
             })),
         }
         .encode_to_vec()
@@ -635,10 +742,12 @@ impl PostCommitAction {
         let decoded = PostCommitActionProto::decode(data)?;
         match decoded.kind {
             Some(PostCommitActionKind::SendWelcomes(proto)) => {
-                Ok(Self::SendWelcomes(SendWelcomesAction::new(
-                    proto.installations.into_iter().map(|i| i.into()).collect(),
-                    proto.welcome_message,
-                )))
+                Ok(Self::SendWelcomes(SendWelcomesAction {
+                    installations: proto.installations.into_iter().map(|i| i.into()).collect(),
+                    welcome_message: proto.welcome_message,
+                    ratchet_tree_out_of_band: proto.ratchet_tree_out_of_band,
+                    ratchet_tree: Some(proto.ratchet_tree).filter(|tree| !tree.is_empty()),
+                }))
             }
             None => Err(IntentError::Generic(
                 "missing post commit action".to_string(),
@@ -649,13 +758,14 @@ impl PostCommitAction {
     pub(crate) fn from_welcome(
         welcome: MlsMessageOut,
         installations: Vec<Installation>,
+        ratchet_tree_out_of_band: bool,
     ) -> Result<Self, IntentError> {
         let welcome_bytes = welcome.tls_serialize_detached()?;
 
-        Ok(Self::SendWelcomes(SendWelcomesAction::new(
-            installations,
-            welcome_bytes,
-        )))
+        Ok(Self::SendWelcomes(SendWelcomesAction {
+            ratchet_tree_out_of_band,
+            ..SendWelcomesAction::new(installations, welcome_bytes)
+        }))
     }
 }
 
@@ -681,6 +791,37 @@ mod tests {
         assert_eq!(restored_intent.message, message);
     }
 
+    #[test]
+    fn test_serialize_batch_update() {
+        let intent = BatchUpdateIntentData {
+            membership: Some(UpdateGroupMembershipIntentData::new(
+                HashMap::from([("foo".to_string(), 123)]),
+                vec!["bar".to_string()],
+            )),
+            metadata: vec![UpdateMetadataIntentData::new_update_group_name(
+                "name".to_string(),
+            )],
+            admin_lists: vec![UpdateAdminListIntentData::new(
+                AdminListActionType::Add,
+                "foo".to_string(),
+            )],
+            permissions: vec![],
+        };
+
+        let as_bytes: Vec<u8> = intent.into();
+        let restored_intent = BatchUpdateIntentData::try_from(as_bytes).unwrap();
+
+        let membership = restored_intent.membership.unwrap();
+        assert_eq!(membership.membership_updates.get("foo"), Some(&123));
+        assert_eq!(membership.removed_members, vec!["bar".to_string()]);
+        assert_eq!(restored_intent.metadata[0].field_value, "name");
+        assert_eq!(restored_intent.admin_lists[0].inbox_id, "foo");
+        assert!(restored_intent.permissions.is_empty());
+
+        assert!(BatchUpdateIntentData::try_from(vec![0xff, 0xff]).is_err());
+        assert!(BatchUpdateIntentData::try_from(vec![]).is_err());
+    }
+
     #[tokio::test]
     async fn test_serialize_update_membership() {
         let mut membership_updates = HashMap::new();
@@ -709,4 +850,26 @@ mod tests {
 
         assert_eq!(intent.field_value, restored_intent.field_value);
     }
+
+    #[test]
+    fn test_serialize_send_welcomes_with_ratchet_tree() {
+        let action = SendWelcomesAction {
+            ratchet_tree_out_of_band: true,
+            ratchet_tree: Some(vec![4, 5, 6]),
+            ..SendWelcomesAction::new(vec![], vec![1, 2, 3])
+        };
+
+        let PostCommitAction::SendWelcomes(restored) =
+            PostCommitAction::from_bytes(&action.to_bytes()).unwrap();
+        assert_eq!(restored.welcome_message, vec![1, 2, 3]);
+        assert!(restored.ratchet_tree_out_of_band);
+        assert_eq!(restored.ratchet_tree, Some(vec![4, 5, 6]));
+
+        let PostCommitAction::SendWelcomes(restored) = PostCommitAction::from_bytes(
+            &SendWelcomesAction::new(vec![], vec![1, 2, 3]).to_bytes(),
+        )
+        .unwrap();
+        assert!(!restored.ratchet_tree_out_of_band);
+        assert_eq!(restored.ratchet_tree, None);
+    }
 }
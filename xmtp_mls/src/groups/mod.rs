@@ -1,12 +1,17 @@
+pub mod group_invites;
 pub mod group_membership;
 pub mod group_metadata;
 pub mod group_mutable_metadata;
 pub mod group_permissions;
+pub mod group_update;
+#[cfg(feature = "message-history")]
+mod installation_metadata;
 pub mod intents;
 pub mod members;
 #[allow(dead_code)]
 #[cfg(feature = "message-history")]
 pub mod message_history;
+pub mod ratchet_tree;
 mod subscriptions;
 mod sync;
 pub mod validated_commit;
@@ -26,7 +31,7 @@ use openmls::{
     messages::proposals::ProposalType,
     prelude::{
         BasicCredentialError, Capabilities, CredentialWithKey, Error as TlsCodecError, GroupId,
-        MlsGroup as OpenMlsGroup, StagedWelcome, Welcome as MlsWelcome, WireFormatPolicy,
+        MlsGroup as OpenMlsGroup, StagedWelcome, WireFormatPolicy,
     },
 };
 use openmls_traits::OpenMlsProvider;
@@ -38,18 +43,23 @@ pub use self::group_permissions::PreconfiguredPolicies;
 pub use self::intents::{AddressesOrInstallationIds, IntentError};
 #[cfg(feature = "message-history")]
 use self::message_history::MessageHistoryError;
+#[cfg(feature = "bench")]
+pub(crate) use self::sync::{chunk_welcomes, encrypt_welcomes};
 use self::{
     group_membership::GroupMembership,
     group_metadata::{extract_group_metadata, DmMembers},
-    group_mutable_metadata::{GroupMutableMetadata, GroupMutableMetadataError, MetadataField},
+    group_mutable_metadata::{
+        validate_metadata_field, GroupMutableMetadata, GroupMutableMetadataError, MetadataField,
+    },
     group_permissions::{
         extract_group_permissions, GroupMutablePermissions, GroupMutablePermissionsError,
     },
     intents::{
-        AdminListActionType, PermissionPolicyOption, PermissionUpdateType,
+        AdminListActionType, BatchUpdateIntentData, PermissionPolicyOption, PermissionUpdateType,
         UpdateAdminListIntentData, UpdateMetadataIntentData, UpdatePermissionIntentData,
     },
-    validated_commit::extract_group_membership,
+    ratchet_tree::{fetch_ratchet_tree, RatchetTreeError, RatchetTreeReference},
+    validated_commit::{extract_group_membership, CommitParticipant},
 };
 use self::{
     group_metadata::{ConversationType, GroupMetadata, GroupMetadataError},
@@ -57,7 +67,7 @@ use self::{
     validated_commit::CommitValidationError,
 };
 use std::{collections::HashSet, sync::Arc};
-use xmtp_cryptography::signature::{sanitize_evm_addresses, AddressValidationError};
+use xmtp_cryptography::signature::{sanitize_account_addresses, AddressValidationError};
 use xmtp_id::InboxId;
 use xmtp_proto::xmtp::mls::{
     api::v1::{
@@ -66,7 +76,7 @@ use xmtp_proto::xmtp::mls::{
     },
     message_contents::{
         plaintext_envelope::{Content, V1},
-        PlaintextEnvelope,
+        PlaintextEnvelope, WelcomeWithRatchetTreeReference,
     },
 };
 
@@ -74,11 +84,11 @@ use crate::{
     api::WrappedApiError,
     client::{deserialize_welcome, ClientError, MessageProcessingError, XmtpMlsLocalContext},
     configuration::{
-        CIPHERSUITE, GROUP_MEMBERSHIP_EXTENSION_ID, GROUP_PERMISSIONS_EXTENSION_ID, MAX_GROUP_SIZE,
-        MAX_PAST_EPOCHS, MUTABLE_METADATA_EXTENSION_ID,
-        SEND_MESSAGE_UPDATE_INSTALLATIONS_INTERVAL_NS,
+        CIPHERSUITE, COMMIT_RULES_CAPABILITY_ID, GROUP_MEMBERSHIP_EXTENSION_ID,
+        GROUP_PERMISSIONS_EXTENSION_ID, MAX_GROUP_METADATA_FIELDS, MAX_GROUP_SIZE, MAX_PAST_EPOCHS,
+        MUTABLE_METADATA_EXTENSION_ID, SEND_MESSAGE_UPDATE_INSTALLATIONS_INTERVAL_NS,
     },
-    hpke::{decrypt_welcome, HpkeError},
+    hpke::{decrypt_welcome, decrypt_welcome_with_ratchet_tree_reference, HpkeError},
     identity::{parse_credential, IdentityError},
     identity_updates::{load_identity_updates, InstallationDiffError},
     retry::RetryableError,
@@ -196,6 +206,22 @@ pub enum GroupError {
     MissingPendingCommit,
     #[error("Sync failed to wait for intent")]
     SyncFailedToWait,
+    #[error("sending messages to this group is not permitted")]
+    SendMessageNotPermitted,
+    #[error("inbox {0} is banned from the group")]
+    InboxBanned(String),
+    #[error("super admins must give up their role before leaving the group")]
+    SuperAdminCannotLeave,
+    #[error("leaving a DM is not supported")]
+    CannotLeaveDm,
+    #[error("the last super admin can not be removed, transfer ownership instead")]
+    LastSuperAdmin,
+    #[error("only super admins can transfer ownership")]
+    SuperAdminRequired,
+    #[error("inbox {0} is not a member of the group")]
+    InboxNotMember(String),
+    #[error("ratchet tree error: {0}")]
+    RatchetTree(#[from] RatchetTreeError),
 }
 
 impl RetryableError for GroupError {
@@ -214,6 +240,7 @@ impl RetryableError for GroupError {
             Self::WelcomeError(welcome) => welcome.is_retryable(),
             Self::InstallationDiff(diff) => diff.is_retryable(),
             Self::CreateGroupContextExtProposalError(create) => create.is_retryable(),
+            Self::RatchetTree(tree) => tree.is_retryable(),
             _ => false,
         }
     }
@@ -384,19 +411,16 @@ impl MlsGroup {
         ))
     }
 
-    // Create a group from a decrypted and decoded welcome message
+    // Create a group from a staged welcome message
     // If the group already exists in the store, overwrite the MLS state and do not update the group entry
     async fn create_from_welcome<ApiClient: XmtpApi>(
         client: &Client<ApiClient>,
         provider: &XmtpOpenMlsProvider,
-        welcome: MlsWelcome,
+        mls_welcome: StagedWelcome,
         added_by_inbox: String,
         welcome_id: i64,
     ) -> Result<Self, GroupError> {
         tracing::info!("Creating from welcome");
-        let mls_welcome =
-            StagedWelcome::new_from_welcome(provider, &build_group_join_config(), welcome, None)?;
-
         let mls_group = mls_welcome.into_group(provider)?;
         let group_id = mls_group.group_id().to_vec();
         let metadata = extract_group_metadata(&mls_group)?;
@@ -448,12 +472,18 @@ impl MlsGroup {
         validate_initial_group_membership(client, provider.conn_ref(), &mls_group).await?;
 
         let stored_group = provider.conn_ref().insert_or_replace_group(to_store)?;
-
-        Ok(Self::new(
+        let group = Self::new(
             client.context.clone(),
             stored_group.id,
             stored_group.created_at_ns,
-        ))
+        );
+        // Let the installations already in the sync group know which device joined
+        #[cfg(feature = "message-history")]
+        if stored_group.purpose == Purpose::Sync {
+            group.queue_installation_metadata(provider.conn_ref())?;
+        }
+
+        Ok(group)
     }
 
     // Decrypt a welcome message using HPKE and then create and save a group from the stored message
@@ -465,27 +495,52 @@ impl MlsGroup {
         welcome_id: i64,
     ) -> Result<Self, GroupError> {
         tracing::info!("Trying to decrypt welcome");
-        let welcome_bytes = decrypt_welcome(provider, hpke_public_key, &encrypted_welcome_bytes)?;
+        let (welcome_bytes, ratchet_tree_reference) =
+            match decrypt_welcome(provider, hpke_public_key, &encrypted_welcome_bytes) {
+                Ok(welcome_bytes) => (welcome_bytes, None),
+                Err(HpkeError::Hpke(err)) => {
+                    // Welcomes into large groups carry a reference to a ratchet tree published out of band.
+                    // If it is not one of those either, surface the original error
+                    let payload = decrypt_welcome_with_ratchet_tree_reference(
+                        provider,
+                        hpke_public_key,
+                        &encrypted_welcome_bytes,
+                    )
+                    .map_err(|_| HpkeError::Hpke(err))?;
+                    let welcome = WelcomeWithRatchetTreeReference::decode(payload.as_slice())
+                        .map_err(ClientError::from)?;
+                    let reference = RatchetTreeReference {
+                        topic: welcome.ratchet_tree_topic,
+                        key: welcome.ratchet_tree_key,
+                    };
+                    (welcome.mls_welcome, Some(reference))
+                }
+                Err(err) => return Err(err.into()),
+            };
 
         let welcome = deserialize_welcome(&welcome_bytes)?;
 
         let join_config = build_group_join_config();
 
         let processed_welcome =
-            ProcessedWelcome::new_from_welcome(provider, &join_config, welcome.clone())?;
+            ProcessedWelcome::new_from_welcome(provider, &join_config, welcome)?;
         let psks = processed_welcome.psks();
         if !psks.is_empty() {
             tracing::error!("No PSK support for welcome");
             return Err(GroupError::NoPSKSupport);
         }
-        let staged_welcome = processed_welcome.into_staged_welcome(provider, None)?;
+        let ratchet_tree = match ratchet_tree_reference {
+            Some(reference) => Some(fetch_ratchet_tree(client, reference).await?),
+            None => None,
+        };
+        let staged_welcome = processed_welcome.into_staged_welcome(provider, ratchet_tree)?;
 
         let added_by_node = staged_welcome.welcome_sender()?;
 
         let added_by_credential = BasicCredential::try_from(added_by_node.credential().clone())?;
         let inbox_id = parse_credential(added_by_credential.identity())?;
 
-        Self::create_from_welcome(client, provider, welcome, inbox_id, welcome_id).await
+        Self::create_from_welcome(client, provider, staged_welcome, inbox_id, welcome_id).await
     }
 
     #[cfg(feature = "message-history")]
@@ -548,6 +603,7 @@ impl MlsGroup {
         let provider = XmtpOpenMlsProvider::from(conn);
         self.maybe_update_installations(&provider, update_interval_ns, client)
             .await?;
+        self.ensure_can_send_message(&provider)?;
 
         let message_id = self.prepare_message(message, provider.conn_ref(), |now| {
             Self::into_envelope(message, now)
@@ -602,11 +658,27 @@ impl MlsGroup {
     /// Send a message, optimistically returning the ID of the message before the result of a message publish.
     pub fn send_message_optimistic(&self, message: &[u8]) -> Result<Vec<u8>, GroupError> {
         let conn = self.context.store.conn()?;
-        let message_id =
-            self.prepare_message(message, &conn, |now| Self::into_envelope(message, now))?;
+        let provider = XmtpOpenMlsProvider::from(conn);
+        self.ensure_can_send_message(&provider)?;
+        let message_id = self.prepare_message(message, provider.conn_ref(), |now| {
+            Self::into_envelope(message, now)
+        })?;
         Ok(message_id)
     }
 
+    /// Checks the group's send message policy for this installation before a message is queued.
+    fn ensure_can_send_message(&self, provider: &XmtpOpenMlsProvider) -> Result<(), GroupError> {
+        let openmls_group = self.load_mls_group(provider)?;
+        if !is_sender_permitted(
+            &openmls_group,
+            self.context.inbox_id(),
+            self.context.installation_public_key(),
+        )? {
+            return Err(GroupError::SendMessageNotPermitted);
+        }
+        Ok(())
+    }
+
     /// Prepare a [`IntentKind::SendMessage`] intent, and [`StoredGroupMessage`] on this users XMTP [`Client`].
     ///
     /// # Arguments
@@ -699,7 +771,7 @@ impl MlsGroup {
     where
         ApiClient: XmtpApi,
     {
-        let account_addresses = sanitize_evm_addresses(account_addresses_to_add)?;
+        let account_addresses = sanitize_account_addresses(account_addresses_to_add)?;
         let inbox_id_map = client
             .api_client
             .get_inbox_ids(account_addresses.clone())
@@ -730,6 +802,14 @@ impl MlsGroup {
         inbox_ids: Vec<String>,
     ) -> Result<(), GroupError> {
         let provider = client.mls_provider()?;
+        let mutable_metadata = self.mutable_metadata(&provider)?;
+        if let Some(banned) = inbox_ids
+            .iter()
+            .find(|inbox_id| mutable_metadata.is_banned(inbox_id))
+        {
+            return Err(GroupError::InboxBanned(banned.clone()));
+        }
+
         let intent_data = self
             .get_membership_update_intent(client, &provider, inbox_ids, vec![])
             .await?;
@@ -759,7 +839,7 @@ impl MlsGroup {
         client: &Client<ApiClient>,
         account_addresses_to_remove: Vec<InboxId>,
     ) -> Result<(), GroupError> {
-        let account_addresses = sanitize_evm_addresses(account_addresses_to_remove)?;
+        let account_addresses = sanitize_account_addresses(account_addresses_to_remove)?;
         let inbox_id_map = client.api_client.get_inbox_ids(account_addresses).await?;
 
         self.remove_members_by_inbox_id(client, inbox_id_map.into_values().collect())
@@ -964,6 +1044,81 @@ impl MlsGroup {
         }
     }
 
+    /// Sets an arbitrary metadata field on the group, creating it if it does not exist yet.
+    ///
+    /// Fields without a policy of their own can be updated by admins, or only by super admins if
+    /// the name starts with [SUPER_ADMIN_METADATA_PREFIX](crate::configuration::SUPER_ADMIN_METADATA_PREFIX).
+    pub async fn update_metadata_field<ApiClient>(
+        &self,
+        client: &Client<ApiClient>,
+        field_name: String,
+        field_value: String,
+    ) -> Result<(), GroupError>
+    where
+        ApiClient: XmtpApi,
+    {
+        validate_metadata_field(&field_name, &field_value)?;
+        let provider = self.mls_provider()?;
+        let mutable_metadata = self.mutable_metadata(&provider)?;
+        if !mutable_metadata.attributes.contains_key(&field_name)
+            && mutable_metadata.attributes.len() >= MAX_GROUP_METADATA_FIELDS
+        {
+            return Err(GroupMutableMetadataError::TooManyFields(
+                mutable_metadata.attributes.len() + 1,
+            )
+            .into());
+        }
+
+        let intent_data: Vec<u8> = UpdateMetadataIntentData::new(field_name, field_value).into();
+        let intent = provider
+            .conn_ref()
+            .insert_group_intent(NewGroupIntent::new(
+                IntentKind::MetadataUpdate,
+                self.group_id.clone(),
+                intent_data,
+            ))?;
+
+        self.sync_until_intent_resolved(&provider, intent.id, client)
+            .await
+    }
+
+    /// Returns the value of a metadata field, or `None` if it has never been set
+    pub fn metadata_field(
+        &self,
+        provider: impl OpenMlsProvider,
+        field_name: &str,
+    ) -> Result<Option<String>, GroupError> {
+        let mutable_metadata = self.mutable_metadata(provider)?;
+        Ok(mutable_metadata.attributes.get(field_name).cloned())
+    }
+
+    /// Sets the policy for updating a single metadata field, including custom fields
+    pub async fn update_metadata_field_permission_policy<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        field_name: String,
+        permission_policy: PermissionPolicyOption,
+    ) -> Result<(), GroupError> {
+        validate_metadata_field(&field_name, "")?;
+        let conn = client.store().conn()?;
+
+        let intent_data: Vec<u8> = UpdatePermissionIntentData::new(
+            PermissionUpdateType::UpdateMetadata,
+            permission_policy,
+            Some(field_name),
+        )
+        .into();
+
+        let intent = conn.insert_group_intent(NewGroupIntent::new(
+            IntentKind::UpdatePermission,
+            self.group_id.clone(),
+            intent_data,
+        ))?;
+
+        self.sync_until_intent_resolved(&conn.into(), intent.id, client)
+            .await
+    }
+
     pub fn admin_list(&self, provider: impl OpenMlsProvider) -> Result<Vec<String>, GroupError> {
         let mutable_metadata = self.mutable_metadata(provider)?;
         Ok(mutable_metadata.admin_list)
@@ -1005,6 +1160,14 @@ impl MlsGroup {
         ApiClient: XmtpApi,
     {
         let conn = self.context.store.conn()?;
+        if action_type == UpdateAdminListType::RemoveSuper {
+            let super_admin_list = self
+                .mutable_metadata(self.mls_provider()?)?
+                .super_admin_list;
+            if super_admin_list == [inbox_id.clone()] {
+                return Err(GroupError::LastSuperAdmin);
+            }
+        }
         let intent_action_type = match action_type {
             UpdateAdminListType::Add => AdminListActionType::Add,
             UpdateAdminListType::Remove => AdminListActionType::Remove,
@@ -1023,6 +1186,152 @@ impl MlsGroup {
             .await
     }
 
+    /// Makes `to_inbox_id` a super admin and removes the caller's super admin role in a single
+    /// commit, so the group is never left without a super admin.
+    pub async fn transfer_ownership<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        to_inbox_id: InboxId,
+    ) -> Result<(), GroupError> {
+        let provider = self.mls_provider()?;
+        let mutable_metadata = self.mutable_metadata(&provider)?;
+        let inbox_id = client.inbox_id();
+        if !mutable_metadata.is_super_admin(&inbox_id) {
+            return Err(GroupError::SuperAdminRequired);
+        }
+        if to_inbox_id == inbox_id {
+            return Ok(());
+        }
+        // Members that asked to leave are about to be removed, which a super admin can't be
+        let is_member = self
+            .members_with_provider(client, &provider)
+            .await?
+            .iter()
+            .any(|member| member.inbox_id == to_inbox_id);
+        if !is_member || mutable_metadata.is_pending_remove(&to_inbox_id) {
+            return Err(GroupError::InboxNotMember(to_inbox_id));
+        }
+
+        self.update(client)
+            .update_admin_list(UpdateAdminListType::AddSuper, to_inbox_id)
+            .update_admin_list(UpdateAdminListType::RemoveSuper, inbox_id)
+            .apply()
+            .await
+    }
+
+    pub fn ban_list(&self, provider: impl OpenMlsProvider) -> Result<Vec<String>, GroupError> {
+        let mutable_metadata = self.mutable_metadata(provider)?;
+        Ok(mutable_metadata.ban_list)
+    }
+
+    pub fn is_banned(
+        &self,
+        inbox_id: String,
+        provider: impl OpenMlsProvider,
+    ) -> Result<bool, GroupError> {
+        let mutable_metadata = self.mutable_metadata(provider)?;
+        Ok(mutable_metadata.ban_list.contains(&inbox_id))
+    }
+
+    /// Removes the inboxes from the group and adds them to the ban list in a single commit.
+    /// Clients refuse to publish a commit that adds a banned inbox back, until it is unbanned.
+    pub async fn ban_members<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        inbox_ids: Vec<InboxId>,
+    ) -> Result<(), GroupError> {
+        let current_members: HashSet<InboxId> = self
+            .members(client)
+            .await?
+            .into_iter()
+            .map(|member| member.inbox_id)
+            .collect();
+        let inbox_ids_to_remove = inbox_ids
+            .iter()
+            .filter(|inbox_id| current_members.contains(*inbox_id))
+            .cloned()
+            .collect();
+
+        self.update(client)
+            .remove_members_by_inbox_id(inbox_ids_to_remove)
+            .ban(inbox_ids)
+            .apply()
+            .await
+    }
+
+    /// Removes the inboxes from the ban list so that they can be added to the group again
+    pub async fn unban_members<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+        inbox_ids: Vec<InboxId>,
+    ) -> Result<(), GroupError> {
+        self.update(client).unban(inbox_ids).apply().await
+    }
+
+    /// Leaves the group.
+    ///
+    /// Members can't commit their own removal, so this adds the caller to the pending remove list
+    /// instead. The super admin whose inbox ID sorts first removes all of the caller's
+    /// installations the next time it syncs, if the remove member policy allows it. The
+    /// `GroupUpdated` message for that commit lists the caller in both `removed_inboxes` and
+    /// `left_inboxes`.
+    pub async fn leave<ApiClient: XmtpApi>(
+        &self,
+        client: &Client<ApiClient>,
+    ) -> Result<(), GroupError> {
+        let provider = self.mls_provider()?;
+        if self.metadata(&provider)?.conversation_type == ConversationType::Dm {
+            return Err(GroupError::CannotLeaveDm);
+        }
+        let inbox_id = client.inbox_id();
+        if self.mutable_metadata(&provider)?.is_super_admin(&inbox_id) {
+            return Err(GroupError::SuperAdminCannotLeave);
+        }
+
+        self.update(client)
+            .request_removal(vec![inbox_id])
+            .apply()
+            .await?;
+        provider
+            .conn_ref()
+            .update_group_membership(&self.group_id, GroupMembershipState::PendingRemove)?;
+        Ok(())
+    }
+
+    /// Removes the members that asked to leave, see [MlsGroup::leave].
+    /// Only the super admin whose inbox ID sorts first does this, so that admins don't race to
+    /// publish the same commit. Super admins can't leave, so there is always one to do it.
+    pub(crate) async fn remove_pending_members<ApiClient: XmtpApi>(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        client: &Client<ApiClient>,
+    ) -> Result<(), GroupError> {
+        let mutable_metadata = self.mutable_metadata(provider)?;
+        let remover = mutable_metadata.super_admin_list.iter().min();
+        if mutable_metadata.pending_remove_list.is_empty() || remover != Some(&client.inbox_id()) {
+            return Ok(());
+        }
+
+        let current_members: HashSet<InboxId> = self
+            .members_with_provider(client, provider)
+            .await?
+            .into_iter()
+            .map(|member| member.inbox_id)
+            .collect();
+        let inbox_ids_to_remove = mutable_metadata
+            .pending_remove_list
+            .iter()
+            .filter(|inbox_id| current_members.contains(*inbox_id))
+            .cloned()
+            .collect();
+
+        self.update(client)
+            .remove_members_by_inbox_id(inbox_ids_to_remove)
+            .clear_removal_requests(mutable_metadata.pending_remove_list)
+            .apply()
+            .await
+    }
+
     /// Find the `inbox_id` of the group member who added the member to the group
     pub fn added_by_inbox_id(&self) -> Result<String, GroupError> {
         let conn = self.context.store.conn()?;
@@ -1259,6 +1568,8 @@ pub fn build_extensions_for_metadata_update(
         attributes,
         existing_metadata.admin_list,
         existing_metadata.super_admin_list,
+        existing_metadata.ban_list,
+        existing_metadata.pending_remove_list,
     )
     .try_into()?;
     let unknown_gc_extension = UnknownExtension(new_mutable_metadata);
@@ -1274,40 +1585,54 @@ pub fn build_extensions_for_permissions_update(
     update_permissions_intent: UpdatePermissionIntentData,
 ) -> Result<Extensions, GroupError> {
     let existing_permissions: GroupMutablePermissions = group.try_into()?;
-    let existing_policy_set = existing_permissions.policies.clone();
+    let new_policy_set =
+        apply_permissions_update(existing_permissions.policies, update_permissions_intent)?;
+    let mut extensions = group.extensions().clone();
+    extensions.add_or_replace(build_mutable_permissions_extension(new_policy_set)?);
+    Ok(extensions)
+}
+
+/// Evaluates the group's send message policy for the given sender against the current group state.
+pub(crate) fn is_sender_permitted(
+    openmls_group: &OpenMlsGroup,
+    sender_inbox_id: String,
+    sender_installation_id: Vec<u8>,
+) -> Result<bool, GroupError> {
+    let permissions = extract_group_permissions(openmls_group)?;
+    if permissions.policies.send_message_policy.is_none() {
+        return Ok(true);
+    }
+    let sender = CommitParticipant::build(
+        sender_inbox_id,
+        sender_installation_id,
+        &extract_group_metadata(openmls_group)?,
+        &GroupMutableMetadata::try_from(openmls_group)?,
+    );
+    Ok(permissions.policies.can_send_message(&sender))
+}
+
+fn apply_permissions_update(
+    existing_policy_set: PolicySet,
+    update_permissions_intent: UpdatePermissionIntentData,
+) -> Result<PolicySet, GroupError> {
+    let policy_option = update_permissions_intent.policy_option;
     let new_policy_set = match update_permissions_intent.update_type {
-        PermissionUpdateType::AddMember => PolicySet::new(
-            update_permissions_intent.policy_option.into(),
-            existing_policy_set.remove_member_policy,
-            existing_policy_set.update_metadata_policy,
-            existing_policy_set.add_admin_policy,
-            existing_policy_set.remove_admin_policy,
-            existing_policy_set.update_permissions_policy,
-        ),
-        PermissionUpdateType::RemoveMember => PolicySet::new(
-            existing_policy_set.add_member_policy,
-            update_permissions_intent.policy_option.into(),
-            existing_policy_set.update_metadata_policy,
-            existing_policy_set.add_admin_policy,
-            existing_policy_set.remove_admin_policy,
-            existing_policy_set.update_permissions_policy,
-        ),
-        PermissionUpdateType::AddAdmin => PolicySet::new(
-            existing_policy_set.add_member_policy,
-            existing_policy_set.remove_member_policy,
-            existing_policy_set.update_metadata_policy,
-            update_permissions_intent.policy_option.into(),
-            existing_policy_set.remove_admin_policy,
-            existing_policy_set.update_permissions_policy,
-        ),
-        PermissionUpdateType::RemoveAdmin => PolicySet::new(
-            existing_policy_set.add_member_policy,
-            existing_policy_set.remove_member_policy,
-            existing_policy_set.update_metadata_policy,
-            existing_policy_set.add_admin_policy,
-            update_permissions_intent.policy_option.into(),
-            existing_policy_set.update_permissions_policy,
-        ),
+        PermissionUpdateType::AddMember => PolicySet {
+            add_member_policy: policy_option.into(),
+            ..existing_policy_set
+        },
+        PermissionUpdateType::RemoveMember => PolicySet {
+            remove_member_policy: policy_option.into(),
+            ..existing_policy_set
+        },
+        PermissionUpdateType::AddAdmin => PolicySet {
+            add_admin_policy: policy_option.into(),
+            ..existing_policy_set
+        },
+        PermissionUpdateType::RemoveAdmin => PolicySet {
+            remove_admin_policy: policy_option.into(),
+            ..existing_policy_set
+        },
         PermissionUpdateType::UpdateMetadata => {
             let mut metadata_policy = existing_policy_set.update_metadata_policy.clone();
             metadata_policy.insert(
@@ -1316,24 +1641,29 @@ pub fn build_extensions_for_permissions_update(
                         name: "metadata_field_name".into(),
                     },
                 )?,
-                update_permissions_intent.policy_option.into(),
+                policy_option.into(),
             );
-            PolicySet::new(
-                existing_policy_set.add_member_policy,
-                existing_policy_set.remove_member_policy,
-                metadata_policy,
-                existing_policy_set.add_admin_policy,
-                existing_policy_set.remove_admin_policy,
-                existing_policy_set.update_permissions_policy,
-            )
+            PolicySet {
+                update_metadata_policy: metadata_policy,
+                ..existing_policy_set
+            }
+        }
+        PermissionUpdateType::SendMessage => {
+            // There is no "allow" permissions policy, every member may send when the policy is unset
+            let send_message_policy = match policy_option {
+                PermissionPolicyOption::Allow => None,
+                policy_option => Some(policy_option.into()),
+            };
+            existing_policy_set.with_send_message_policy(send_message_policy)
+        }
+        PermissionUpdateType::JoinRequest => {
+            existing_policy_set.with_join_request_policy(policy_option.into())
+        }
+        PermissionUpdateType::UpdateBanList => {
+            existing_policy_set.with_ban_list_policy(policy_option.into())
         }
     };
-    let new_group_permissions: Vec<u8> = GroupMutablePermissions::new(new_policy_set).try_into()?;
-    let unknown_gc_extension = UnknownExtension(new_group_permissions);
-    let extension = Extension::Unknown(GROUP_PERMISSIONS_EXTENSION_ID, unknown_gc_extension);
-    let mut extensions = group.extensions().clone();
-    extensions.add_or_replace(extension);
-    Ok(extensions)
+    Ok(new_policy_set)
 }
 
 #[tracing::instrument(level = "trace", skip_all)]
@@ -1341,10 +1671,21 @@ pub fn build_extensions_for_admin_lists_update(
     group: &OpenMlsGroup,
     admin_lists_update: UpdateAdminListIntentData,
 ) -> Result<Extensions, GroupError> {
-    let existing_metadata: GroupMutableMetadata = group.try_into()?;
-    let attributes = existing_metadata.attributes.clone();
-    let mut admin_list = existing_metadata.admin_list;
-    let mut super_admin_list = existing_metadata.super_admin_list;
+    let mut mutable_metadata: GroupMutableMetadata = group.try_into()?;
+    apply_admin_lists_update(&mut mutable_metadata, admin_lists_update);
+    let mut extensions = group.extensions().clone();
+    extensions.add_or_replace(build_mutable_metadata_extension(mutable_metadata)?);
+    Ok(extensions)
+}
+
+fn apply_admin_lists_update(
+    mutable_metadata: &mut GroupMutableMetadata,
+    admin_lists_update: UpdateAdminListIntentData,
+) {
+    let admin_list = &mut mutable_metadata.admin_list;
+    let super_admin_list = &mut mutable_metadata.super_admin_list;
+    let ban_list = &mut mutable_metadata.ban_list;
+    let pending_remove_list = &mut mutable_metadata.pending_remove_list;
     match admin_lists_update.action_type {
         AdminListActionType::Add => {
             if !admin_list.contains(&admin_lists_update.inbox_id) {
@@ -1360,13 +1701,67 @@ pub fn build_extensions_for_admin_lists_update(
         AdminListActionType::RemoveSuper => {
             super_admin_list.retain(|x| x != &admin_lists_update.inbox_id)
         }
+        AdminListActionType::AddBan => {
+            if !ban_list.contains(&admin_lists_update.inbox_id) {
+                ban_list.push(admin_lists_update.inbox_id);
+            }
+        }
+        AdminListActionType::RemoveBan => ban_list.retain(|x| x != &admin_lists_update.inbox_id),
+        AdminListActionType::AddPendingRemove => {
+            if !pending_remove_list.contains(&admin_lists_update.inbox_id) {
+                pending_remove_list.push(admin_lists_update.inbox_id);
+            }
+        }
+        AdminListActionType::RemovePendingRemove => {
+            pending_remove_list.retain(|x| x != &admin_lists_update.inbox_id)
+        }
     }
-    let new_mutable_metadata: Vec<u8> =
-        GroupMutableMetadata::new(attributes, admin_list, super_admin_list).try_into()?;
+}
+
+fn build_mutable_metadata_extension(
+    mutable_metadata: GroupMutableMetadata,
+) -> Result<Extension, GroupError> {
+    let new_mutable_metadata: Vec<u8> = mutable_metadata.try_into()?;
     let unknown_gc_extension = UnknownExtension(new_mutable_metadata);
-    let extension = Extension::Unknown(MUTABLE_METADATA_EXTENSION_ID, unknown_gc_extension);
+    Ok(Extension::Unknown(
+        MUTABLE_METADATA_EXTENSION_ID,
+        unknown_gc_extension,
+    ))
+}
+
+/// Applies all metadata, admin list and permission changes of a batch to the group's extensions.
+/// Membership changes are applied separately, since they also add and remove installations.
+#[tracing::instrument(level = "trace", skip_all)]
+pub(crate) fn build_extensions_for_batch_update(
+    group: &OpenMlsGroup,
+    batch_update: &BatchUpdateIntentData,
+) -> Result<Extensions, GroupError> {
     let mut extensions = group.extensions().clone();
-    extensions.add_or_replace(extension);
+
+    if !batch_update.metadata.is_empty() || !batch_update.admin_lists.is_empty() {
+        let mut mutable_metadata: GroupMutableMetadata = group.try_into()?;
+        for update in &batch_update.metadata {
+            mutable_metadata
+                .attributes
+                .insert(update.field_name.clone(), update.field_value.clone());
+        }
+        for update in &batch_update.admin_lists {
+            apply_admin_lists_update(&mut mutable_metadata, update.clone());
+        }
+        extensions.add_or_replace(build_mutable_metadata_extension(mutable_metadata)?);
+    }
+
+    if !batch_update.permissions.is_empty() {
+        let existing_permissions: GroupMutablePermissions = group.try_into()?;
+        let new_policy_set = batch_update
+            .permissions
+            .iter()
+            .try_fold(existing_permissions.policies, |policy_set, update| {
+                apply_permissions_update(policy_set, update.clone())
+            })?;
+        extensions.add_or_replace(build_mutable_permissions_extension(new_policy_set)?);
+    }
+
     Ok(extensions)
 }
 
@@ -1392,6 +1787,7 @@ fn build_group_config(
         ExtensionType::Unknown(GROUP_MEMBERSHIP_EXTENSION_ID),
         ExtensionType::Unknown(MUTABLE_METADATA_EXTENSION_ID),
         ExtensionType::Unknown(GROUP_PERMISSIONS_EXTENSION_ID),
+        ExtensionType::Unknown(COMMIT_RULES_CAPABILITY_ID),
         ExtensionType::ImmutableMetadata,
         ExtensionType::LastResort,
         ExtensionType::ApplicationId,
@@ -1528,10 +1924,18 @@ fn validate_dm_group<ApiClient: XmtpApi>(
 }
 
 fn build_group_join_config() -> MlsGroupJoinConfig {
+    build_group_join_config_with_ratchet_tree(true)
+}
+
+// Commits that welcome installations into large groups leave the ratchet tree out of the welcome,
+// it is delivered out of band instead. See [ratchet_tree]
+pub(crate) fn build_group_join_config_with_ratchet_tree(
+    use_ratchet_tree_extension: bool,
+) -> MlsGroupJoinConfig {
     MlsGroupJoinConfig::builder()
         .wire_format_policy(WireFormatPolicy::default())
         .max_past_epochs(MAX_PAST_EPOCHS)
-        .use_ratchet_tree_extension(true)
+        .use_ratchet_tree_extension(use_ratchet_tree_extension)
         .build()
 }
 
@@ -1550,20 +1954,25 @@ mod tests {
         builder::ClientBuilder,
         client::{FindGroupParams, MessageProcessingError},
         codecs::{group_updated::GroupUpdatedCodec, ContentCodec},
+        configuration::{
+            MAX_GROUP_METADATA_VALUE_LENGTH, RATCHET_TREE_OUT_OF_BAND_THRESHOLD,
+            SUPER_ADMIN_METADATA_PREFIX,
+        },
         groups::{
             build_dm_protected_metadata_extension, build_group_membership_extension,
             build_mutable_metadata_extension_default, build_protected_metadata_extension,
             group_membership::GroupMembership,
             group_metadata::{ConversationType, GroupMetadata},
-            group_mutable_metadata::MetadataField,
+            group_mutable_metadata::{GroupMutableMetadataError, MetadataField},
             intents::{PermissionPolicyOption, PermissionUpdateType},
             members::{GroupMember, PermissionLevel},
-            validate_dm_group, DeliveryStatus, GroupMetadataOptions, PreconfiguredPolicies,
-            UpdateAdminListType,
+            validate_dm_group,
+            validated_commit::requires_commit_rules,
+            DeliveryStatus, GroupMetadataOptions, PreconfiguredPolicies, UpdateAdminListType,
         },
         storage::{
             consent_record::ConsentState,
-            group::Purpose,
+            group::{GroupMembershipState, Purpose},
             group_intent::{IntentKind, IntentState, NewGroupIntent},
             group_message::{GroupMessageKind, StoredGroupMessage},
         },
@@ -1990,6 +2399,203 @@ mod tests {
         assert_eq!(messages.len(), 2);
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_ban_members() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id(), caro.inbox_id()])
+            .await
+            .unwrap();
+        // New groups hold received commits to the ban list too
+        let mls_group = amal_group
+            .load_mls_group(amal_group.mls_provider().unwrap())
+            .unwrap();
+        assert!(requires_commit_rules(mls_group.extensions()));
+
+        amal_group
+            .ban_members(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        let provider = amal_group.mls_provider().unwrap();
+        assert_eq!(
+            amal_group.ban_list(&provider).unwrap(),
+            vec![bola.inbox_id()]
+        );
+        let members = amal_group.members(&amal).await.unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(!members.iter().any(|m| m.inbox_id == bola.inbox_id()));
+
+        let result = amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await;
+        assert!(matches!(result, Err(GroupError::InboxBanned(_))));
+
+        // Caro may add members, but a commit adding bola back is rejected
+        let caro_groups = caro.sync_welcomes().await.unwrap();
+        let caro_group = caro_groups.first().unwrap();
+        caro_group.sync(&caro).await.unwrap();
+        assert!(caro_group
+            .update(&caro)
+            .add_members_by_inbox_id(vec![bola.inbox_id()])
+            .apply()
+            .await
+            .is_err());
+        amal_group.sync(&amal).await.unwrap();
+        assert_eq!(amal_group.members(&amal).await.unwrap().len(), 2);
+
+        // Only admins can change the ban list
+        assert!(caro_group
+            .unban_members(&caro, vec![bola.inbox_id()])
+            .await
+            .is_err());
+        amal_group
+            .unban_members(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        assert_eq!(amal_group.members(&amal).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_leave_group() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id(), caro.inbox_id()])
+            .await
+            .unwrap();
+
+        // The creator is the only super admin and can't leave
+        let result = amal_group.leave(&amal).await;
+        assert!(matches!(result, Err(GroupError::SuperAdminCannotLeave)));
+        amal_group
+            .update_admin_list(&amal, UpdateAdminListType::Add, caro.inbox_id())
+            .await
+            .unwrap();
+
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        let bola_group = bola_groups.first().unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        bola_group.leave(&bola).await.unwrap();
+        let stored_group = bola
+            .store()
+            .conn()
+            .unwrap()
+            .find_group(bola_group.group_id.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored_group.membership_state,
+            GroupMembershipState::PendingRemove
+        );
+
+        // Caro is an admin but not a super admin, so leaves the removal to amal
+        let caro_groups = caro.sync_welcomes().await.unwrap();
+        let caro_group = caro_groups.first().unwrap();
+        caro_group.sync(&caro).await.unwrap();
+        assert_eq!(caro_group.members(&caro).await.unwrap().len(), 3);
+
+        amal_group.sync(&amal).await.unwrap();
+        let members = amal_group.members(&amal).await.unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(!members.iter().any(|m| m.inbox_id == bola.inbox_id()));
+        let provider = amal_group.mls_provider().unwrap();
+        assert!(amal_group
+            .mutable_metadata(&provider)
+            .unwrap()
+            .pending_remove_list
+            .is_empty());
+
+        let messages = amal_group
+            .find_messages(
+                Some(GroupMessageKind::MembershipChange),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let encoded_content =
+            EncodedContent::decode(messages.last().unwrap().decrypted_message_bytes.as_slice())
+                .unwrap();
+        let group_update = GroupUpdatedCodec::decode(encoded_content).unwrap();
+        assert_eq!(group_update.removed_inboxes.len(), 1);
+        assert_eq!(group_update.removed_inboxes[0].inbox_id, bola.inbox_id());
+        assert_eq!(group_update.left_inboxes.len(), 1);
+        assert_eq!(group_update.left_inboxes[0].inbox_id, bola.inbox_id());
+
+        bola_group.sync(&bola).await.unwrap();
+        assert!(!bola_group
+            .is_active(bola_group.mls_provider().unwrap())
+            .unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_transfer_ownership() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let caro = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+
+        let result = amal_group
+            .update_admin_list(&amal, UpdateAdminListType::RemoveSuper, amal.inbox_id())
+            .await;
+        assert!(matches!(result, Err(GroupError::LastSuperAdmin)));
+
+        let result = amal_group.transfer_ownership(&amal, caro.inbox_id()).await;
+        assert!(matches!(result, Err(GroupError::InboxNotMember(_))));
+
+        amal_group
+            .transfer_ownership(&amal, bola.inbox_id())
+            .await
+            .unwrap();
+        let provider = amal_group.mls_provider().unwrap();
+        assert_eq!(
+            amal_group.super_admin_list(&provider).unwrap(),
+            vec![bola.inbox_id()]
+        );
+
+        let result = amal_group.transfer_ownership(&amal, amal.inbox_id()).await;
+        assert!(matches!(result, Err(GroupError::SuperAdminRequired)));
+
+        // Commits that leave the group without a super admin are not published, even from the builder
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        let bola_group = bola_groups.first().unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        assert!(bola_group
+            .update(&bola)
+            .update_admin_list(UpdateAdminListType::RemoveSuper, bola.inbox_id())
+            .apply()
+            .await
+            .is_err());
+        let provider = bola_group.mls_provider().unwrap();
+        assert_eq!(
+            bola_group.super_admin_list(&provider).unwrap(),
+            vec![bola.inbox_id()]
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_key_update() {
         let client = ClientBuilder::new_test_client(&generate_local_wallet()).await;
@@ -2348,6 +2954,47 @@ mod tests {
             .is_err(),);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_welcome_with_out_of_band_ratchet_tree() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+
+        let wallets: Vec<_> = (0..RATCHET_TREE_OUT_OF_BAND_THRESHOLD)
+            .map(|_| generate_local_wallet())
+            .collect();
+        let clients = join_all(
+            wallets
+                .iter()
+                .map(|wallet| ClientBuilder::new_test_client(wallet)),
+        )
+        .await;
+        amal_group
+            .add_members_by_inbox_id(&amal, clients.iter().map(|c| c.inbox_id()).collect())
+            .await
+            .unwrap();
+
+        // Joining through a welcome that leaves the ratchet tree out
+        let bola = clients.last().unwrap();
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        let bola_group = bola_groups.first().unwrap();
+        assert_eq!(bola_group.group_id, amal_group.group_id);
+        bola_group.sync(bola).await.unwrap();
+        assert_eq!(
+            bola_group.members(bola).await.unwrap().len(),
+            RATCHET_TREE_OUT_OF_BAND_THRESHOLD + 1
+        );
+
+        // The joined group works like any other
+        bola_group.send_message(b"hello", bola).await.unwrap();
+        amal_group.sync(&amal).await.unwrap();
+        let messages = amal_group
+            .find_messages(Some(GroupMessageKind::Application), None, None, None, None)
+            .unwrap();
+        assert_eq!(messages.last().unwrap().decrypted_message_bytes, b"hello");
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_group_mutable_data() {
         let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
@@ -2516,6 +3163,135 @@ mod tests {
         assert_eq!(amal_group_pinned_frame_url, "a frame url");
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_update_custom_metadata_field() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola_wallet = generate_local_wallet();
+        let bola = ClientBuilder::new_test_client(&bola_wallet).await;
+
+        let policy_set = Some(PreconfiguredPolicies::AllMembers.to_policy_set());
+        let amal_group: MlsGroup = amal
+            .create_group(policy_set, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members(&amal, vec![bola_wallet.get_address()])
+            .await
+            .unwrap();
+        assert_eq!(
+            amal_group
+                .metadata_field(amal_group.mls_provider().unwrap(), "topic")
+                .unwrap(),
+            None
+        );
+
+        amal_group
+            .update_metadata_field(&amal, "topic".to_string(), "rust".to_string())
+            .await
+            .unwrap();
+
+        bola.sync_welcomes().await.unwrap();
+        let bola_group = bola
+            .find_groups(FindGroupParams::default())
+            .unwrap()
+            .pop()
+            .unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        assert_eq!(
+            bola_group
+                .metadata_field(bola_group.mls_provider().unwrap(), "topic")
+                .unwrap(),
+            Some("rust".to_string())
+        );
+
+        // Custom fields without a policy are admin only
+        let result = bola_group
+            .update_metadata_field(&bola, "topic".to_string(), "go".to_string())
+            .await;
+        assert!(result.is_err());
+
+        // Until a policy for the field is set
+        amal_group
+            .update_metadata_field_permission_policy(
+                &amal,
+                "topic".to_string(),
+                PermissionPolicyOption::Allow,
+            )
+            .await
+            .unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        bola_group
+            .update_metadata_field(&bola, "topic".to_string(), "go".to_string())
+            .await
+            .unwrap();
+        amal_group.sync(&amal).await.unwrap();
+        assert_eq!(
+            amal_group
+                .metadata_field(amal_group.mls_provider().unwrap(), "topic")
+                .unwrap(),
+            Some("go".to_string())
+        );
+
+        // Oversized values are rejected before anything is published
+        let result = amal_group
+            .update_metadata_field(
+                &amal,
+                "topic".to_string(),
+                "a".repeat(MAX_GROUP_METADATA_VALUE_LENGTH + 1),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(GroupError::GroupMutableMetadata(
+                GroupMutableMetadataError::FieldTooLong { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_super_admin_prefixed_field_is_only_sent_by_super_admins() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let policy_set = Some(PreconfiguredPolicies::AllMembers.to_policy_set());
+        let amal_group = amal
+            .create_group(policy_set, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        let field_name = SUPER_ADMIN_METADATA_PREFIX.to_string() + "linked_resource";
+        amal_group
+            .update_metadata_field_permission_policy(
+                &amal,
+                field_name.clone(),
+                PermissionPolicyOption::Allow,
+            )
+            .await
+            .unwrap();
+
+        // The policy allows everyone, but bola is not a super admin so doesn't publish the change
+        let bola_groups = bola.sync_welcomes().await.unwrap();
+        let bola_group = bola_groups.first().unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        let result = bola_group
+            .update_metadata_field(&bola, field_name.clone(), "bola".to_string())
+            .await;
+        assert!(result.is_err());
+
+        amal_group
+            .update_metadata_field(&amal, field_name.clone(), "amal".to_string())
+            .await
+            .unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        assert_eq!(
+            bola_group
+                .metadata_field(bola_group.mls_provider().unwrap(), &field_name)
+                .unwrap(),
+            Some("amal".to_string())
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_group_mutable_data_group_permissions() {
         let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
@@ -3107,6 +3883,62 @@ mod tests {
         assert_eq!(members.len(), 3);
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_send_message_policy() {
+        let amal = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let amal_group = amal
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        amal_group
+            .add_members_by_inbox_id(&amal, vec![bola.inbox_id()])
+            .await
+            .unwrap();
+        let bola_group = receive_group_invite(&bola).await;
+        bola_group.sync(&bola).await.unwrap();
+
+        // Amal restricts sending to admins while Bola is still on the previous epoch
+        amal_group
+            .update_permission_policy(
+                &amal,
+                PermissionUpdateType::SendMessage,
+                PermissionPolicyOption::AdminOnly,
+                None,
+            )
+            .await
+            .unwrap();
+        bola_group
+            .send_message_optimistic(b"hello from bola")
+            .unwrap();
+        bola_group.publish_messages(&bola).await.unwrap();
+
+        // Amal drops the message since Bola is no longer allowed to send
+        amal_group.sync(&amal).await.unwrap();
+        let amal_messages = amal_group
+            .find_messages(Some(GroupMessageKind::Application), None, None, None, None)
+            .unwrap();
+        assert!(amal_messages.is_empty());
+
+        // Now that Bola has seen the change, sending fails locally
+        bola_group.sync(&bola).await.unwrap();
+        let result = bola_group.send_message(b"hello again", &bola).await;
+        assert!(matches!(result, Err(GroupError::SendMessageNotPermitted)));
+
+        // Admins can still send
+        amal_group
+            .send_message(b"announcement", &amal)
+            .await
+            .unwrap();
+        bola_group.sync(&bola).await.unwrap();
+        let bola_messages = bola_group
+            .find_messages(Some(GroupMessageKind::Application), None, None, None, None)
+            .unwrap();
+        assert_eq!(
+            bola_messages.last().unwrap().decrypted_message_bytes,
+            b"announcement"
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_optimistic_send() {
         let amal = Arc::new(ClientBuilder::new_test_client(&generate_local_wallet()).await);
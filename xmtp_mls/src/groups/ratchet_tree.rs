@@ -0,0 +1,173 @@
+//! Out of band delivery of the ratchet tree for welcomes into large groups.
+//!
+//! The ratchet tree grows linearly with the group, and embedding it in the welcome means every
+//! new installation gets its own HPKE encrypted copy. Above
+//! [RATCHET_TREE_OUT_OF_BAND_THRESHOLD](crate::configuration::RATCHET_TREE_OUT_OF_BAND_THRESHOLD)
+//! installations, when every new installation lists
+//! [RATCHET_TREE_REFERENCE_CAPABILITY_ID](crate::configuration::RATCHET_TREE_REFERENCE_CAPABILITY_ID)
+//! in its key package, the welcome is built without the tree. The tree is encrypted once with a random
+//! key and published to a random welcome topic, and each welcome carries the topic and key.
+
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm,
+};
+use openmls::prelude::{
+    tls_codec::{Deserialize, Error as TlsCodecError},
+    RatchetTreeIn,
+};
+use rand::RngCore;
+use thiserror::Error;
+use xmtp_cryptography::utils as crypto_utils;
+use xmtp_proto::xmtp::mls::api::v1::{
+    welcome_message_input::{Version as WelcomeMessageInputVersion, V1 as WelcomeMessageInputV1},
+    WelcomeMessageInput,
+};
+
+use super::GroupError;
+use crate::{client::extract_welcome_message, retry::RetryableError, Client, XmtpApi};
+
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12;
+// Same size as an installation key, so the topic is indistinguishable from a welcome topic
+const TOPIC_SIZE: usize = 32;
+
+#[derive(Debug, Error)]
+pub enum RatchetTreeError {
+    #[error("encryption error: {0}")]
+    AesGcm(#[from] aes_gcm::Error),
+    #[error("TLS Codec error: {0}")]
+    TlsError(#[from] TlsCodecError),
+    #[error("ratchet tree key must be {KEY_SIZE} bytes")]
+    InvalidKey,
+    #[error("ratchet tree payload is too short")]
+    InvalidPayload,
+    #[error("ratchet tree not found")]
+    NotFound,
+    #[error("welcome requires an out of band ratchet tree that was never captured")]
+    Missing,
+}
+
+impl RetryableError for RatchetTreeError {
+    fn is_retryable(&self) -> bool {
+        // The tree is published before any welcome that references it,
+        // a miss is most likely a node that has not caught up yet
+        matches!(self, Self::NotFound)
+    }
+}
+
+/// Where an out of band ratchet tree was published, and the key to decrypt it
+#[derive(Debug, Clone)]
+pub(crate) struct RatchetTreeReference {
+    pub topic: Vec<u8>,
+    pub key: Vec<u8>,
+}
+
+/// Encrypt a TLS serialized ratchet tree and publish it to a fresh, random welcome topic
+pub(crate) async fn publish_ratchet_tree<ApiClient: XmtpApi>(
+    client: &Client<ApiClient>,
+    ratchet_tree: &[u8],
+) -> Result<RatchetTreeReference, GroupError> {
+    let mut rng = crypto_utils::rng();
+    let mut key = [0u8; KEY_SIZE];
+    rng.fill_bytes(&mut key);
+    let mut topic = vec![0u8; TOPIC_SIZE];
+    rng.fill_bytes(&mut topic);
+
+    let data = encrypt_ratchet_tree(ratchet_tree, &key)?;
+    client
+        .api_client
+        .send_welcome_messages(&[WelcomeMessageInput {
+            version: Some(WelcomeMessageInputVersion::V1(WelcomeMessageInputV1 {
+                installation_key: topic.clone(),
+                data,
+                hpke_public_key: vec![],
+            })),
+        }])
+        .await?;
+
+    Ok(RatchetTreeReference {
+        topic,
+        key: key.to_vec(),
+    })
+}
+
+/// Fetch and decrypt a ratchet tree published with [publish_ratchet_tree]
+pub(crate) async fn fetch_ratchet_tree<ApiClient: XmtpApi>(
+    client: &Client<ApiClient>,
+    reference: RatchetTreeReference,
+) -> Result<RatchetTreeIn, GroupError> {
+    let envelope = client
+        .api_client
+        .query_welcome_messages(reference.topic, None)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(RatchetTreeError::NotFound)?;
+    let payload = extract_welcome_message(envelope)?.data;
+    let ratchet_tree = decrypt_ratchet_tree(&payload, &reference.key)?;
+
+    Ok(RatchetTreeIn::tls_deserialize_exact(ratchet_tree).map_err(RatchetTreeError::from)?)
+}
+
+fn encrypt_ratchet_tree(ratchet_tree: &[u8], key: &[u8]) -> Result<Vec<u8>, RatchetTreeError> {
+    if key.len() != KEY_SIZE {
+        return Err(RatchetTreeError::InvalidKey);
+    }
+    let mut nonce = [0u8; NONCE_SIZE];
+    crypto_utils::rng().fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+    let ciphertext = cipher.encrypt(GenericArray::from_slice(&nonce), ratchet_tree)?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(ciphertext);
+    Ok(payload)
+}
+
+fn decrypt_ratchet_tree(payload: &[u8], key: &[u8]) -> Result<Vec<u8>, RatchetTreeError> {
+    if key.len() != KEY_SIZE {
+        return Err(RatchetTreeError::InvalidKey);
+    }
+    if payload.len() < NONCE_SIZE {
+        return Err(RatchetTreeError::InvalidPayload);
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_SIZE);
+
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+    Ok(cipher.decrypt(GenericArray::from_slice(nonce), ciphertext)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratchet_tree_encryption_roundtrip() {
+        let key = [7u8; KEY_SIZE];
+        let tree = vec![1, 2, 3, 4, 5];
+
+        let payload = encrypt_ratchet_tree(&tree, &key).unwrap();
+        assert_ne!(payload[NONCE_SIZE..], tree[..]);
+        assert_eq!(decrypt_ratchet_tree(&payload, &key).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_ratchet_tree_wrong_key() {
+        let payload = encrypt_ratchet_tree(&[1, 2, 3], &[7u8; KEY_SIZE]).unwrap();
+
+        assert!(matches!(
+            decrypt_ratchet_tree(&payload, &[8u8; KEY_SIZE]),
+            Err(RatchetTreeError::AesGcm(_))
+        ));
+        assert!(matches!(
+            decrypt_ratchet_tree(&payload, &[7u8; 16]),
+            Err(RatchetTreeError::InvalidKey)
+        ));
+        assert!(matches!(
+            decrypt_ratchet_tree(&payload[..4], &[7u8; KEY_SIZE]),
+            Err(RatchetTreeError::InvalidPayload)
+        ));
+    }
+}
@@ -34,7 +34,7 @@ impl MlsGroup {
 
         if !self.has_already_synced(msg_id).await? {
             let process_result = retry_async!(
-                Retry::default(),
+                client.api_client.retry_strategy(),
                 (async {
                     let client_id = client_id.clone();
                     let msgv1 = msgv1.clone();
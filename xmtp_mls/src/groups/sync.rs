@@ -4,13 +4,18 @@ use std::{
 };
 
 use super::{
-    build_extensions_for_admin_lists_update, build_extensions_for_metadata_update,
-    build_extensions_for_permissions_update, build_group_membership_extension,
+    build_extensions_for_admin_lists_update, build_extensions_for_batch_update,
+    build_extensions_for_metadata_update, build_extensions_for_permissions_update,
+    build_group_join_config, build_group_join_config_with_ratchet_tree,
+    build_group_membership_extension,
     intents::{
-        Installation, PostCommitAction, SendMessageIntentData, SendWelcomesAction,
-        UpdateAdminListIntentData, UpdateGroupMembershipIntentData, UpdatePermissionIntentData,
+        BatchUpdateIntentData, Installation, PostCommitAction, SendMessageIntentData,
+        SendWelcomesAction, UpdateAdminListIntentData, UpdateGroupMembershipIntentData,
+        UpdatePermissionIntentData,
     },
-    validated_commit::extract_group_membership,
+    is_sender_permitted,
+    ratchet_tree::{publish_ratchet_tree, RatchetTreeError},
+    validated_commit::{extract_group_membership, validate_own_commit},
     GroupError, MlsGroup,
 };
 #[cfg(feature = "message-history")]
@@ -19,14 +24,15 @@ use crate::{
     client::MessageProcessingError,
     codecs::{group_updated::GroupUpdatedCodec, ContentCodec},
     configuration::{
-        GRPC_DATA_LIMIT, MAX_GROUP_SIZE, MAX_INTENT_PUBLISH_ATTEMPTS, MAX_PAST_EPOCHS,
-        SYNC_UPDATE_INSTALLATIONS_INTERVAL_NS,
+        GRPC_DATA_LIMIT, MAX_INTENT_PUBLISH_ATTEMPTS, MAX_PAST_EPOCHS,
+        RATCHET_TREE_OUT_OF_BAND_THRESHOLD, RATCHET_TREE_REFERENCE_CAPABILITY_ID,
+        SYNC_UPDATE_INSTALLATIONS_INTERVAL_NS, WELCOME_SEND_CONCURRENCY,
     },
     groups::{intents::UpdateMetadataIntentData, validated_commit::ValidatedCommit},
-    hpke::{encrypt_welcome, HpkeError},
+    hpke::{encrypt_welcome, encrypt_welcome_with_ratchet_tree_reference, HpkeError},
     identity::parse_credential,
     identity_updates::load_identity_updates,
-    retry::{Retry, RetryableError},
+    retry::RetryableError,
     retry_async,
     storage::{
         db_connection::DbConnection,
@@ -39,10 +45,10 @@ use crate::{
     xmtp_openmls_provider::XmtpOpenMlsProvider,
     Client, Delete, Fetch, StoreOrIgnore, XmtpApi,
 };
-use futures::future::try_join_all;
+use futures::{stream, StreamExt, TryStreamExt};
 use openmls::{
     credentials::BasicCredential,
-    extensions::Extensions,
+    extensions::{ExtensionType, Extensions},
     framing::{ContentType, ProtocolMessage},
     group::{GroupEpoch, StagedCommit},
     prelude::{
@@ -69,13 +75,13 @@ use xmtp_proto::xmtp::mls::{
     },
     message_contents::{
         plaintext_envelope::{Content, V1, V2},
-        GroupUpdated, PlaintextEnvelope,
+        GroupUpdated, PlaintextEnvelope, WelcomeWithRatchetTreeReference,
     },
 };
 
 #[cfg(feature = "message-history")]
 use xmtp_proto::xmtp::mls::message_contents::plaintext_envelope::v2::MessageType::{
-    Reply, Request,
+    InstallationMetadata, Reply, Request,
 };
 
 #[derive(Debug)]
@@ -102,7 +108,13 @@ impl MlsGroup {
         self.maybe_update_installations(&mls_provider, None, client)
             .await?;
 
-        self.sync_with_conn(&mls_provider, client).await
+        self.sync_with_conn(&mls_provider, client).await?;
+
+        // Handled once we are caught up so that the removal commit is built on the latest epoch
+        if let Err(err) = self.remove_pending_members(&mls_provider, client).await {
+            tracing::warn!("failed to remove members that left the group: {err}");
+        }
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self, provider, client))]
@@ -145,6 +157,40 @@ impl MlsGroup {
         Ok(())
     }
 
+    /// The publishing half of [`Self::sync_with_conn`], for groups whose messages are queried in a
+    /// batch with other groups once they have all published
+    pub(crate) async fn publish_with_conn<ApiClient>(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        client: &Client<ApiClient>,
+    ) -> Result<(), GroupError>
+    where
+        ApiClient: XmtpApi,
+    {
+        let _mutex = self.mutex.lock().await;
+        self.publish_intents(provider, client).await
+    }
+
+    /// The receiving half of [`Self::sync_with_conn`], processing `messages` instead of querying
+    /// them
+    pub(crate) async fn sync_with_messages<ApiClient>(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        client: &Client<ApiClient>,
+        messages: Vec<GroupMessage>,
+    ) -> Result<(), GroupError>
+    where
+        ApiClient: XmtpApi,
+    {
+        let _mutex = self.mutex.lock().await;
+
+        if let Err(receive_error) = self.process_messages(messages, provider, client).await {
+            tracing::error!("receive error {:?}", receive_error);
+        }
+
+        self.post_commit(provider.conn_ref(), client).await
+    }
+
     pub(super) async fn sync_until_last_intent_resolved<ApiClient>(
         &self,
         provider: &XmtpOpenMlsProvider,
@@ -288,7 +334,8 @@ impl MlsGroup {
             | IntentKind::UpdateGroupMembership
             | IntentKind::UpdateAdminList
             | IntentKind::MetadataUpdate
-            | IntentKind::UpdatePermission => {
+            | IntentKind::UpdatePermission
+            | IntentKind::BatchUpdate => {
                 if let Some(published_in_epoch) = intent.published_in_epoch {
                     let published_in_epoch_u64 = published_in_epoch as u64;
                     let group_epoch_u64 = group_epoch.as_u64();
@@ -348,6 +395,14 @@ impl MlsGroup {
                 } else {
                     // If no error committing the change, write a transcript message
                     self.save_transcript_message(conn, validated_commit, envelope_timestamp_ns)?;
+                    if let Some(post_commit_data) = intent.post_commit_data {
+                        store_ratchet_tree_for_welcomes(
+                            conn,
+                            intent.id,
+                            post_commit_data,
+                            openmls_group,
+                        )?;
+                    }
                 }
             }
             IntentKind::SendMessage => {
@@ -400,6 +455,22 @@ impl MlsGroup {
                         idempotency_key,
                         content,
                     })) => {
+                        if !is_sender_permitted(
+                            openmls_group,
+                            sender_inbox_id.clone(),
+                            sender_installation_id.clone(),
+                        )
+                        .map_err(Box::new)?
+                        {
+                            // Drop the message rather than failing, so the cursor still advances
+                            tracing::warn!(
+                                "[{}] dropping message from {} who is not permitted to send to group {}",
+                                self.context.inbox_id(),
+                                sender_inbox_id,
+                                hex::encode(&self.group_id)
+                            );
+                            return Ok(());
+                        }
                         let message_id =
                             calculate_message_id(&self.group_id, &content, &idempotency_key);
                         StoredGroupMessage {
@@ -467,6 +538,17 @@ impl MlsGroup {
                             }
                             .store_or_ignore(provider.conn_ref())?;
                         }
+                        #[cfg(feature = "message-history")]
+                        Some(InstallationMetadata(metadata)) => {
+                            self.process_installation_metadata(
+                                provider.conn_ref(),
+                                &sender_inbox_id,
+                                sender_installation_id,
+                                metadata,
+                                envelope_timestamp_ns,
+                            )
+                            .map_err(Box::new)?;
+                        }
                         _ => {
                             return Err(MessageProcessingError::InvalidPayload);
                         }
@@ -640,7 +722,7 @@ impl MlsGroup {
         let mut receive_errors = vec![];
         for message in messages.into_iter() {
             let result = retry_async!(
-                Retry::default(),
+                client.api_client.retry_strategy(),
                 (async {
                     self.consume_message(&message, &mut openmls_group, client)
                         .await
@@ -752,7 +834,7 @@ impl MlsGroup {
 
         for intent in intents {
             let result = retry_async!(
-                Retry::default(),
+                client.api_client.retry_strategy(),
                 (async {
                     self.get_publish_intent_data(provider, client, &mut openmls_group, &intent)
                         .await
@@ -781,6 +863,22 @@ impl MlsGroup {
                     post_commit_action,
                     staged_commit,
                 })) => {
+                    if let Some(staged_commit) = &staged_commit {
+                        let staged_commit = decode_staged_commit(staged_commit.clone())?;
+                        if let Err(err) = validate_own_commit(
+                            &openmls_group,
+                            &staged_commit,
+                            client.inbox_id(),
+                            client.installation_public_key(),
+                        ) {
+                            tracing::error!("intent {} failed validation: {:?}", intent.id, err);
+                            provider
+                                .conn_ref()
+                                .set_group_intent_error_and_fail_msg(&intent)?;
+                            return Err(err.into());
+                        }
+                    }
+
                     let payload_slice = payload_to_publish.as_slice();
                     let has_staged_commit = staged_commit.is_some();
                     provider.conn_ref().set_group_intent_published(
@@ -841,11 +939,13 @@ impl MlsGroup {
             IntentKind::UpdateGroupMembership => {
                 let intent_data = UpdateGroupMembershipIntentData::try_from(&intent.data)?;
                 let signer = &self.context.identity.installation_keys;
+                let extensions = openmls_group.extensions().clone();
                 apply_update_group_membership_intent(
                     client,
                     provider,
                     openmls_group,
                     intent_data,
+                    extensions,
                     signer,
                 )
                 .await
@@ -941,6 +1041,36 @@ impl MlsGroup {
                     post_commit_action: None,
                 }))
             }
+            IntentKind::BatchUpdate => {
+                let batch_intent = BatchUpdateIntentData::try_from(intent.data.clone())?;
+                let extensions = build_extensions_for_batch_update(openmls_group, &batch_intent)?;
+                let signer = &self.context.identity.installation_keys;
+
+                // Membership changes go through the same commit as the new extensions
+                if let Some(membership_intent) = batch_intent.membership {
+                    return apply_update_group_membership_intent(
+                        client,
+                        provider,
+                        openmls_group,
+                        membership_intent,
+                        extensions,
+                        signer,
+                    )
+                    .await;
+                }
+
+                if extensions.eq(openmls_group.extensions()) {
+                    return Ok(None);
+                }
+                let (commit, _, _) =
+                    openmls_group.update_group_context_extensions(provider, extensions, signer)?;
+                let commit_bytes = commit.tls_serialize_detached()?;
+                Ok(Some(PublishIntentData {
+                    payload_to_publish: commit_bytes,
+                    staged_commit: get_and_clear_pending_commit(openmls_group, provider)?,
+                    post_commit_action: None,
+                }))
+            }
         }
     }
 
@@ -995,7 +1125,7 @@ impl MlsGroup {
             .conn_ref()
             .get_installations_time_checked(self.group_id.clone())?;
         let elapsed_ns = now_ns - last_ns;
-        if elapsed_ns > interval_ns {
+        if elapsed_ns > interval_ns || self.has_recovered_members(provider, client).await? {
             self.add_missing_installations(provider, client).await?;
             provider
                 .conn_ref()
@@ -1005,6 +1135,57 @@ impl MlsGroup {
         Ok(())
     }
 
+    /// Whether a member of the group recovered their inbox since the group last updated the
+    /// installations of that member, going by the identity updates stored locally.
+    ///
+    /// Recovery revokes every installation the inbox had in the group, so none of them is left to
+    /// add the new one. Other members add it as soon as they see the recovery instead of waiting
+    /// for the update interval.
+    async fn has_recovered_members<ApiClient>(
+        &self,
+        provider: &XmtpOpenMlsProvider,
+        client: &Client<ApiClient>,
+    ) -> Result<bool, GroupError>
+    where
+        ApiClient: XmtpApi,
+    {
+        let mls_group = self.load_mls_group(provider)?;
+        let group_membership = extract_group_membership(mls_group.extensions())?;
+        let inbox_ids = group_membership.inbox_ids();
+        let conn = provider.conn_ref();
+        let latest_sequence_ids = conn.get_latest_sequence_id(&inbox_ids)?;
+
+        for inbox_id in inbox_ids {
+            let (Some(latest_sequence_id), Some(current_sequence_id)) = (
+                latest_sequence_ids.get(&inbox_id),
+                group_membership.get(&inbox_id),
+            ) else {
+                continue;
+            };
+            if *latest_sequence_id as u64 <= *current_sequence_id {
+                continue;
+            }
+
+            let installations_in_group = client
+                .get_association_state(conn, &inbox_id, Some(*current_sequence_id as i64))
+                .await?
+                .installation_ids();
+            let latest_installations = client
+                .get_association_state(conn, &inbox_id, None)
+                .await?
+                .installation_ids();
+            if !latest_installations.is_empty()
+                && !installations_in_group
+                    .iter()
+                    .any(|installation| latest_installations.contains(installation))
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
     /**
      * Checks each member of the group for `IdentityUpdates` after their current sequence_id. If updates
      * are found the method will construct an [`UpdateGroupMembershipIntentData`] and publish a change
@@ -1117,50 +1298,113 @@ impl MlsGroup {
     where
         ApiClient: XmtpApi,
     {
-        let welcomes = action
-            .installations
-            .into_iter()
-            .map(|installation| -> Result<WelcomeMessageInput, HpkeError> {
-                let installation_key = installation.installation_key;
-                let encrypted = encrypt_welcome(
-                    action.welcome_message.as_slice(),
+        let welcome_payload = if action.ratchet_tree_out_of_band {
+            let ratchet_tree = action.ratchet_tree.ok_or(RatchetTreeError::Missing)?;
+            let reference = publish_ratchet_tree(client, &ratchet_tree).await?;
+            WelcomeWithRatchetTreeReference {
+                mls_welcome: action.welcome_message,
+                ratchet_tree_topic: reference.topic,
+                ratchet_tree_key: reference.key,
+            }
+            .encode_to_vec()
+        } else {
+            action.welcome_message
+        };
+
+        let welcomes = encrypt_welcomes(
+            welcome_payload.as_slice(),
+            action.installations,
+            action.ratchet_tree_out_of_band,
+        )?;
+
+        if welcomes.is_empty() {
+            return Err(GroupError::Generic("No welcomes to send".to_string()));
+        }
+
+        let chunks = chunk_welcomes(welcomes, GRPC_DATA_LIMIT);
+        tracing::debug!("sending welcomes in {} chunks", chunks.len());
+        stream::iter(chunks)
+            .map(|chunk| async move { client.api_client.send_welcome_messages(&chunk).await })
+            .buffer_unordered(WELCOME_SEND_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+}
+
+// Encrypts the welcome payload to each new installation
+pub(crate) fn encrypt_welcomes(
+    welcome_payload: &[u8],
+    installations: Vec<Installation>,
+    ratchet_tree_out_of_band: bool,
+) -> Result<Vec<WelcomeMessageInput>, HpkeError> {
+    installations
+        .into_iter()
+        .map(|installation| {
+            let encrypted = if ratchet_tree_out_of_band {
+                encrypt_welcome_with_ratchet_tree_reference(
+                    welcome_payload,
                     installation.hpke_public_key.as_slice(),
-                )?;
-                Ok(WelcomeMessageInput {
-                    version: Some(WelcomeMessageInputVersion::V1(WelcomeMessageInputV1 {
-                        installation_key,
-                        data: encrypted,
-                        hpke_public_key: installation.hpke_public_key,
-                    })),
-                })
+                )?
+            } else {
+                encrypt_welcome(welcome_payload, installation.hpke_public_key.as_slice())?
+            };
+            Ok(WelcomeMessageInput {
+                version: Some(WelcomeMessageInputVersion::V1(WelcomeMessageInputV1 {
+                    installation_key: installation.installation_key,
+                    data: encrypted,
+                    hpke_public_key: installation.hpke_public_key,
+                })),
             })
-            .collect::<Result<Vec<WelcomeMessageInput>, HpkeError>>()?;
-
-        let welcome = welcomes
-            .first()
-            .ok_or(GroupError::Generic("No welcomes to send".to_string()))?;
-
-        let chunk_size = GRPC_DATA_LIMIT
-            / welcome
-                .version
-                .as_ref()
-                .map(|w| match w {
-                    WelcomeMessageInputVersion::V1(w) => {
-                        let w = w.installation_key.len() + w.data.len() + w.hpke_public_key.len();
-                        tracing::debug!("total welcome message proto bytes={w}");
-                        w
-                    }
-                })
-                .unwrap_or(GRPC_DATA_LIMIT / usize::from(MAX_GROUP_SIZE));
+        })
+        .collect()
+}
 
-        tracing::debug!("welcome chunk_size={chunk_size}");
-        let mut futures = vec![];
-        for welcomes in welcomes.chunks(chunk_size) {
-            futures.push(client.api_client.send_welcome_messages(welcomes));
+// Packs welcomes into chunks whose encoded size stays under `limit` bytes.
+// Welcomes differ in size once the ratchet tree is out of band, so sizing every
+// chunk off the first welcome is not enough
+pub(crate) fn chunk_welcomes(
+    welcomes: Vec<WelcomeMessageInput>,
+    limit: usize,
+) -> Vec<Vec<WelcomeMessageInput>> {
+    let mut chunks: Vec<Vec<WelcomeMessageInput>> = vec![];
+    let mut chunk = vec![];
+    let mut chunk_size = 0;
+    for welcome in welcomes {
+        let size = welcome.encoded_len();
+        if !chunk.is_empty() && chunk_size + size > limit {
+            chunks.push(std::mem::take(&mut chunk));
+            chunk_size = 0;
         }
-        try_join_all(futures).await?;
-        Ok(())
+        chunk_size += size;
+        chunk.push(welcome);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
     }
+    chunks
+}
+
+// Welcomes sent without the ratchet tree need the tree from the epoch the commit created,
+// which only exists once the commit has been merged
+fn store_ratchet_tree_for_welcomes(
+    conn: &DbConnection,
+    intent_id: ID,
+    post_commit_data: Vec<u8>,
+    openmls_group: &OpenMlsGroup,
+) -> Result<(), MessageProcessingError> {
+    let PostCommitAction::SendWelcomes(mut action) =
+        PostCommitAction::from_bytes(&post_commit_data)?;
+    if !action.ratchet_tree_out_of_band || action.ratchet_tree.is_some() {
+        return Ok(());
+    }
+    action.ratchet_tree = Some(
+        openmls_group
+            .export_ratchet_tree()
+            .tls_serialize_detached()?,
+    );
+    conn.set_group_intent_post_commit_data(intent_id, action.to_bytes())?;
+    Ok(())
 }
 
 // Extracts the message sender, but does not do any validation to ensure that the
@@ -1195,11 +1439,12 @@ async fn apply_update_group_membership_intent<ApiClient: XmtpApi>(
     provider: &XmtpOpenMlsProvider,
     openmls_group: &mut OpenMlsGroup,
     intent_data: UpdateGroupMembershipIntentData,
+    // The extensions to commit alongside the membership change, other than the membership itself
+    extensions: Extensions,
     signer: &SignatureKeyPair,
 ) -> Result<Option<PublishIntentData>, GroupError> {
-    let extensions: Extensions = openmls_group.extensions().clone();
-
-    let old_group_membership = extract_group_membership(&extensions)?;
+    let extensions_changed = extensions.ne(openmls_group.extensions());
+    let old_group_membership = extract_group_membership(openmls_group.extensions())?;
     let new_group_membership = intent_data.apply_to_group_membership(&old_group_membership);
 
     // Diff the two membership hashmaps getting a list of inboxes that have been added, removed, or updated
@@ -1245,27 +1490,50 @@ async fn apply_update_group_membership_intent<ApiClient: XmtpApi>(
     if leaf_nodes_to_remove.is_empty()
         && new_key_packages.is_empty()
         && membership_diff.updated_inboxes.is_empty()
+        && !extensions_changed
     {
         return Ok(None);
     }
 
     // Update the extensions to have the new GroupMembership
-    let mut new_extensions = extensions.clone();
+    let mut new_extensions = extensions;
     new_extensions.add_or_replace(build_group_membership_extension(&new_group_membership));
 
+    // In large groups the ratchet tree dominates the size of the welcome,
+    // so it is published once out of band instead of once per new installation.
+    // Installations that can't fetch it still need it in the welcome
+    let installation_count =
+        openmls_group.members().count() + new_key_packages.len() - leaf_nodes_to_remove.len();
+    let ratchet_tree_out_of_band = !new_key_packages.is_empty()
+        && installation_count > RATCHET_TREE_OUT_OF_BAND_THRESHOLD
+        && new_key_packages
+            .iter()
+            .all(supports_out_of_band_ratchet_tree);
+    if ratchet_tree_out_of_band {
+        openmls_group.set_configuration(
+            provider.storage(),
+            &build_group_join_config_with_ratchet_tree(false),
+        )?;
+    }
+
     // Create the commit
-    let (commit, maybe_welcome_message, _) = openmls_group.update_group_membership(
+    let result = openmls_group.update_group_membership(
         provider,
         signer,
         &new_key_packages,
         &leaf_nodes_to_remove,
         new_extensions,
-    )?;
+    );
+    if ratchet_tree_out_of_band {
+        openmls_group.set_configuration(provider.storage(), &build_group_join_config())?;
+    }
+    let (commit, maybe_welcome_message, _) = result?;
 
     let post_commit_action = match maybe_welcome_message {
         Some(welcome_message) => Some(PostCommitAction::from_welcome(
             welcome_message,
             new_installations,
+            ratchet_tree_out_of_band,
         )?),
         None => None,
     };
@@ -1280,6 +1548,17 @@ async fn apply_update_group_membership_intent<ApiClient: XmtpApi>(
     }))
 }
 
+/// Whether the installation can join through a welcome whose ratchet tree is published out of band
+fn supports_out_of_band_ratchet_tree(key_package: &KeyPackage) -> bool {
+    key_package
+        .leaf_node()
+        .capabilities()
+        .extensions()
+        .contains(&ExtensionType::Unknown(
+            RATCHET_TREE_REFERENCE_CAPABILITY_ID,
+        ))
+}
+
 fn get_removed_leaf_nodes(
     openmls_group: &mut OpenMlsGroup,
     removed_installations: &HashSet<Vec<u8>>,
@@ -1310,10 +1589,20 @@ fn decode_staged_commit(data: Vec<u8>) -> Result<StagedCommit, MessageProcessing
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builder::ClientBuilder;
+    use crate::{
+        builder::ClientBuilder,
+        identity::IdentityStrategy,
+        retry::{Retry, RetryBudget},
+        utils::test::register_client,
+        InboxOwner,
+    };
     use futures::future;
     use std::sync::Arc;
     use xmtp_cryptography::utils::generate_local_wallet;
+    use xmtp_id::associations::{
+        generate_inbox_id, test_utils::MockSmartContractSignatureVerifier,
+    };
+    use xmtp_proto::api_client::{Error as ApiError, ErrorKind};
 
     #[tokio::test(flavor = "multi_thread")]
     async fn publish_intents_worst_case_scenario() {
@@ -1338,4 +1627,69 @@ mod tests {
         }
         future::join_all(futures).await;
     }
+
+    #[tokio::test]
+    async fn test_group_sync_uses_the_client_retry_budget() {
+        let wallet = generate_local_wallet();
+        let budget = RetryBudget::new(1, 1.0);
+        let retry = Retry::builder()
+            .duration(std::time::Duration::from_millis(1))
+            .budget(budget.clone())
+            .build();
+        let amal = ClientBuilder::new(IdentityStrategy::CreateIfNotFound(
+            generate_inbox_id(&wallet.get_address(), &1),
+            wallet.get_address(),
+            1,
+            None,
+        ))
+        .scw_signature_verifier(MockSmartContractSignatureVerifier::new(true))
+        .temp_store()
+        .local_client()
+        .await
+        .retry_strategy(retry.clone())
+        .build()
+        .await
+        .unwrap();
+        register_client(&amal, &wallet).await;
+        let group = amal.create_group(None, Default::default()).unwrap();
+
+        // Spend the only retry in the budget on an unrelated failure
+        let result: Result<(), ApiError> =
+            retry_async!(retry, (async { Err(ApiError::new(ErrorKind::QueryError)) }));
+        assert!(result.is_err());
+        assert_eq!(budget.remaining(), 0.0);
+
+        // Successful syncs refill the budget shared with the client
+        group.sync(&amal).await.unwrap();
+        assert_eq!(budget.remaining(), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_key_packages_support_out_of_band_ratchet_tree() {
+        let client = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let key_package = client
+            .identity()
+            .new_key_package(client.mls_provider().unwrap())
+            .unwrap();
+        assert!(supports_out_of_band_ratchet_tree(&key_package));
+    }
+
+    #[test]
+    fn test_chunk_welcomes() {
+        let welcome = |size: usize| WelcomeMessageInput {
+            version: Some(WelcomeMessageInputVersion::V1(WelcomeMessageInputV1 {
+                installation_key: vec![0; 32],
+                data: vec![0; size],
+                hpke_public_key: vec![0; 32],
+            })),
+        };
+        let welcomes = vec![welcome(100), welcome(100), welcome(900), welcome(100)];
+        let limit = welcomes[0].encoded_len() * 2;
+
+        let chunks = chunk_welcomes(welcomes, limit);
+        let sizes: Vec<usize> = chunks.iter().map(|chunk| chunk.len()).collect();
+        // A single welcome over the limit still gets a chunk of its own
+        assert_eq!(sizes, vec![2, 1, 1]);
+        assert!(chunk_welcomes(vec![], limit).is_empty());
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use openmls::{
     credentials::{errors::BasicCredentialError, BasicCredential, Credential as OpenMlsCredential},
-    extensions::{Extension, Extensions, UnknownExtension},
+    extensions::{Extension, ExtensionType, Extensions, UnknownExtension},
     group::{GroupContext, MlsGroup as OpenMlsGroup, StagedCommit},
     messages::proposals::Proposal,
     prelude::{LeafNodeIndex, Sender},
@@ -22,7 +22,10 @@ use xmtp_proto::xmtp::{
 };
 
 use crate::{
-    configuration::GROUP_MEMBERSHIP_EXTENSION_ID,
+    configuration::{
+        COMMIT_RULES_CAPABILITY_ID, GROUP_MEMBERSHIP_EXTENSION_ID, GROUP_PERMISSIONS_EXTENSION_ID,
+        SUPER_ADMIN_METADATA_PREFIX,
+    },
     identity_updates::{InstallationDiff, InstallationDiffError},
     retry::RetryableError,
     retryable,
@@ -34,10 +37,12 @@ use super::{
     group_membership::{GroupMembership, MembershipDiff},
     group_metadata::{DmMembers, GroupMetadata, GroupMetadataError},
     group_mutable_metadata::{
-        find_mutable_metadata_extension, GroupMutableMetadata, GroupMutableMetadataError,
+        find_mutable_metadata_extension, validate_metadata_field, GroupMutableMetadata,
+        GroupMutableMetadataError,
     },
     group_permissions::{
         extract_group_permissions, GroupMutablePermissions, GroupMutablePermissionsError,
+        PermissionsPolicy,
     },
 };
 
@@ -84,6 +89,12 @@ pub enum CommitValidationError {
     GroupMutablePermissions(#[from] GroupMutablePermissionsError),
     #[error("PSKs are not support")]
     NoPSKSupport,
+    #[error("Inbox {0} is banned from the group")]
+    BannedInboxAdded(String),
+    #[error("Inbox {0} asked to leave and must be removed before its request is cleared")]
+    PendingRemoveCleared(String),
+    #[error("Commit would leave the group without a super admin")]
+    NoSuperAdminRemaining,
 }
 
 impl RetryableError for CommitValidationError {
@@ -149,15 +160,22 @@ pub struct MutableMetadataChanges {
     pub super_admins_added: Vec<Inbox>,
     pub super_admins_removed: Vec<Inbox>,
     pub num_super_admins: u32,
+    pub bans_added: Vec<Inbox>,
+    pub bans_removed: Vec<Inbox>,
+    pub pending_removes_added: Vec<Inbox>,
+    pub pending_removes_removed: Vec<Inbox>,
 }
 
 impl MutableMetadataChanges {
+    /// Removal requests are left out, the departure is shown once the member is actually removed
     pub fn is_empty(&self) -> bool {
         self.metadata_field_changes.is_empty()
             && self.admins_added.is_empty()
             && self.admins_removed.is_empty()
             && self.super_admins_added.is_empty()
             && self.super_admins_removed.is_empty()
+            && self.bans_added.is_empty()
+            && self.bans_removed.is_empty()
     }
 }
 
@@ -168,6 +186,8 @@ pub struct Inbox {
     pub is_creator: bool,
     pub is_admin: bool,
     pub is_super_admin: bool,
+    /// The inbox asked to leave the group and is waiting to be removed
+    pub is_pending_remove: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -202,6 +222,10 @@ impl MetadataFieldChange {
  *      new [`GroupMembership`].
  * 5. All proposals in a commit must come from the same installation
  * 6. No PSK proposals will be allowed
+ * 7. Members may only add themselves to the pending remove list, and super admins may not add themselves
+ * 8. In groups that require [`COMMIT_RULES_CAPABILITY_ID`], the commit must pass [`validate_commit_rules`]
+ *
+ * Commits created by this client are also checked by [`validate_own_commit`] before publishing
  */
 #[derive(Debug, Clone)]
 pub struct ValidatedCommit {
@@ -211,6 +235,9 @@ pub struct ValidatedCommit {
     pub metadata_changes: MutableMetadataChanges,
     pub permissions_changed: bool,
     pub dm_members: Option<DmMembers>,
+    /// The group requires [`COMMIT_RULES_CAPABILITY_ID`], so the ban list policy applies and fields
+    /// with the super admin prefix are super admin only
+    pub commit_rules_required: bool,
 }
 
 impl ValidatedCommit {
@@ -229,6 +256,7 @@ impl ValidatedCommit {
 
         let existing_group_context = openmls_group.export_group_context();
         let new_group_context = staged_commit.group_context();
+        let commit_rules_required = requires_commit_rules(existing_group_context.extensions());
 
         let metadata_changes = extract_metadata_changes(
             &immutable_metadata,
@@ -237,8 +265,11 @@ impl ValidatedCommit {
             new_group_context,
         )?;
 
-        let permissions_changed =
-            extract_permissions_changed(&group_permissions, new_group_context)?;
+        let permissions_changed = extract_permissions_changed(
+            &group_permissions,
+            existing_group_context,
+            new_group_context,
+        )?;
         // Get the actor who created the commit.
         // Because we don't allow for multiple actors in a commit, this will error if two proposals come from different authors.
         let actor = extract_actor(
@@ -253,6 +284,11 @@ impl ValidatedCommit {
             return Err(CommitValidationError::NoPSKSupport);
         }
 
+        // Satisfies Rule 8
+        if commit_rules_required {
+            validate_commit_rules(openmls_group, &mutable_metadata, new_group_context)?;
+        }
+
         // Get the installations actually added and removed in the commit
         let ProposalChanges {
             added_installations,
@@ -328,6 +364,7 @@ impl ValidatedCommit {
             metadata_changes,
             permissions_changed,
             dm_members: immutable_metadata.dm_members,
+            commit_rules_required,
         };
 
         let policy_set = extract_group_permissions(openmls_group)?;
@@ -629,11 +666,11 @@ fn extract_metadata_changes(
 
     let new_mutable_metadata: GroupMutableMetadata = new_mutable_metadata_ext.try_into()?;
 
-    let metadata_field_changes =
-        mutable_metadata_field_changes(old_mutable_metadata, &new_mutable_metadata);
-
     Ok(MutableMetadataChanges {
-        metadata_field_changes,
+        metadata_field_changes: mutable_metadata_field_changes(
+            old_mutable_metadata,
+            &new_mutable_metadata,
+        ),
         admins_added: get_added_members(
             &old_mutable_metadata.admin_list,
             &new_mutable_metadata.admin_list,
@@ -659,17 +696,204 @@ fn extract_metadata_changes(
             old_mutable_metadata,
         ),
         num_super_admins: new_mutable_metadata.super_admin_list.len() as u32,
+        bans_added: get_added_members(
+            &old_mutable_metadata.ban_list,
+            &new_mutable_metadata.ban_list,
+            immutable_metadata,
+            old_mutable_metadata,
+        ),
+        bans_removed: get_removed_members(
+            &old_mutable_metadata.ban_list,
+            &new_mutable_metadata.ban_list,
+            immutable_metadata,
+            old_mutable_metadata,
+        ),
+        pending_removes_added: get_added_members(
+            &old_mutable_metadata.pending_remove_list,
+            &new_mutable_metadata.pending_remove_list,
+            immutable_metadata,
+            old_mutable_metadata,
+        ),
+        pending_removes_removed: get_removed_members(
+            &old_mutable_metadata.pending_remove_list,
+            &new_mutable_metadata.pending_remove_list,
+            immutable_metadata,
+            old_mutable_metadata,
+        ),
     })
 }
 
+/// Whether the group requires [`COMMIT_RULES_CAPABILITY_ID`] of its members, in which case every
+/// member holds received commits to [`validate_commit_rules`] and the ban list policy
+pub(crate) fn requires_commit_rules(extensions: &Extensions) -> bool {
+    extensions
+        .required_capabilities()
+        .is_some_and(|required_capabilities| {
+            required_capabilities
+                .extension_types()
+                .contains(&ExtensionType::Unknown(COMMIT_RULES_CAPABILITY_ID))
+        })
+}
+
+/// Checks the rules that hold whatever the group's policies say:
+/// 1. Changed metadata fields must be within the size limits
+/// 2. Inboxes on the ban list can not be added
+/// 3. A group that has a super admin must still have one after the commit
+///
+/// Received commits are only held to this in groups that require [`COMMIT_RULES_CAPABILITY_ID`].
+/// Other groups may have members on clients that predate these rules and accept such commits, and
+/// rejecting a commit that other members merged would fork the group. Commits this client sends are
+/// always checked, see [`validate_own_commit`].
+fn validate_commit_rules(
+    openmls_group: &OpenMlsGroup,
+    old_mutable_metadata: &GroupMutableMetadata,
+    new_group_context: &GroupContext,
+) -> Result<(), CommitValidationError> {
+    let new_mutable_metadata: GroupMutableMetadata = new_group_context.extensions().try_into()?;
+
+    // Only the changed fields are checked, so that groups created before the limits keep working
+    for change in mutable_metadata_field_changes(old_mutable_metadata, &new_mutable_metadata) {
+        if let Some(new_value) = &change.new_value {
+            validate_metadata_field(&change.field_name, new_value)?;
+        }
+    }
+    if new_mutable_metadata.attributes.len() > old_mutable_metadata.attributes.len() {
+        new_mutable_metadata.validate_field_count()?;
+    }
+
+    let old_group_membership = extract_group_membership(openmls_group.extensions())?;
+    let new_group_membership = extract_group_membership(new_group_context.extensions())?;
+    if let Some(banned) = new_group_membership.members.keys().find(|inbox_id| {
+        old_group_membership.get(inbox_id).is_none() && new_mutable_metadata.is_banned(inbox_id)
+    }) {
+        return Err(CommitValidationError::BannedInboxAdded(banned.clone()));
+    }
+
+    // DMs have no super admins, so only groups that had one are checked
+    if !old_mutable_metadata.super_admin_list.is_empty()
+        && new_mutable_metadata.super_admin_list.is_empty()
+    {
+        return Err(CommitValidationError::NoSuperAdminRemaining);
+    }
+
+    Ok(())
+}
+
+/// Checks a commit created by this client, before it is published.
+///
+/// Besides [`validate_commit_rules`], these rules are checked. Received commits are held to rules 1
+/// and 2 in groups that require [`COMMIT_RULES_CAPABILITY_ID`], and to rule 3 in every group,
+/// since older clients never add to the pending remove list. Rule 4 is only enforced when sending:
+/// clients that predate it accept commits that break it, and rejecting a commit that other members
+/// merged would fork the group.
+///
+/// Rules:
+/// 1. Fields with the super admin prefix are only changed by super admins
+/// 2. The ban list is only changed by members allowed by its policy, and super admins can not be
+///    banned
+/// 3. Members only ask for their own removal, and super admins do not ask at all
+/// 4. Inboxes are only taken off the pending remove list once they are no longer members
+#[tracing::instrument(level = "trace", skip_all)]
+pub(crate) fn validate_own_commit(
+    openmls_group: &OpenMlsGroup,
+    staged_commit: &StagedCommit,
+    inbox_id: InboxId,
+    installation_id: Vec<u8>,
+) -> Result<(), CommitValidationError> {
+    let immutable_metadata: GroupMetadata = openmls_group.extensions().try_into()?;
+    let old_mutable_metadata: GroupMutableMetadata = openmls_group.extensions().try_into()?;
+    let new_group_context = staged_commit.group_context();
+    let new_mutable_metadata: GroupMutableMetadata = new_group_context.extensions().try_into()?;
+    let actor = CommitParticipant::build(
+        inbox_id,
+        installation_id,
+        &immutable_metadata,
+        &old_mutable_metadata,
+    );
+
+    validate_commit_rules(openmls_group, &old_mutable_metadata, new_group_context)?;
+
+    // Fields with the super admin prefix stay super admin only, whatever their policy says
+    if !actor.is_super_admin
+        && mutable_metadata_field_changes(&old_mutable_metadata, &new_mutable_metadata)
+            .iter()
+            .any(|change| change.field_name.starts_with(SUPER_ADMIN_METADATA_PREFIX))
+    {
+        return Err(CommitValidationError::InsufficientPermissions);
+    }
+
+    let bans_added = get_added_members(
+        &old_mutable_metadata.ban_list,
+        &new_mutable_metadata.ban_list,
+        &immutable_metadata,
+        &old_mutable_metadata,
+    );
+    let bans_removed = get_removed_members(
+        &old_mutable_metadata.ban_list,
+        &new_mutable_metadata.ban_list,
+        &immutable_metadata,
+        &old_mutable_metadata,
+    );
+    let policies = extract_group_permissions(openmls_group)?.policies;
+    if !policies.evaluate_ban_list_changes(&actor, &bans_added, &bans_removed) {
+        return Err(CommitValidationError::InsufficientPermissions);
+    }
+
+    let pending_removes_added = get_added_members(
+        &old_mutable_metadata.pending_remove_list,
+        &new_mutable_metadata.pending_remove_list,
+        &immutable_metadata,
+        &old_mutable_metadata,
+    );
+    if pending_removes_added
+        .iter()
+        .any(|inbox| inbox.inbox_id != actor.inbox_id || inbox.is_super_admin)
+    {
+        return Err(CommitValidationError::InsufficientPermissions);
+    }
+    let new_group_membership = extract_group_membership(new_group_context.extensions())?;
+    if let Some(cleared) = old_mutable_metadata
+        .pending_remove_list
+        .iter()
+        .find(|inbox_id| {
+            !new_mutable_metadata.is_pending_remove(inbox_id)
+                && new_group_membership.get(inbox_id).is_some()
+        })
+    {
+        return Err(CommitValidationError::PendingRemoveCleared(cleared.clone()));
+    }
+
+    Ok(())
+}
+
 // Returns true if the permissions have changed, false otherwise
 fn extract_permissions_changed(
     old_group_permissions: &GroupMutablePermissions,
+    old_group_context: &GroupContext,
     new_group_context: &GroupContext,
 ) -> Result<bool, CommitValidationError> {
     let new_group_permissions: GroupMutablePermissions =
         new_group_context.extensions().try_into()?;
-    Ok(!old_group_permissions.eq(&new_group_permissions))
+    if !old_group_permissions.eq(&new_group_permissions) {
+        return Ok(true);
+    }
+    // Unsupported policies compare equal regardless of their contents, so fall back to the raw
+    // extension to make sure a change we can't decode is still treated as a permissions change
+    if old_group_permissions
+        .policies
+        .contains_unsupported_policies()
+    {
+        return Ok(permissions_extension_bytes(old_group_context.extensions())
+            != permissions_extension_bytes(new_group_context.extensions()));
+    }
+    Ok(false)
+}
+
+fn permissions_extension_bytes(extensions: &Extensions) -> Option<&Vec<u8>> {
+    extensions.iter().find_map(|extension| match extension {
+        Extension::Unknown(GROUP_PERMISSIONS_EXTENSION_ID, UnknownExtension(bytes)) => Some(bytes),
+        _ => None,
+    })
 }
 
 fn get_added_members(
@@ -706,6 +930,7 @@ fn build_inbox(
         is_admin: mutable_metadata.is_admin(inbox_id),
         is_super_admin: mutable_metadata.is_super_admin(inbox_id),
         is_creator: immutable_metadata.creator_inbox_id.eq(inbox_id),
+        is_pending_remove: mutable_metadata.is_pending_remove(inbox_id),
     }
 }
 
@@ -859,6 +1084,12 @@ impl From<ValidatedCommit> for GroupUpdatedProto {
                 .iter()
                 .map(MetadataFieldChangeProto::from)
                 .collect(),
+            left_inboxes: commit
+                .removed_inboxes
+                .iter()
+                .filter(|inbox| inbox.is_pending_remove)
+                .map(InboxProto::from)
+                .collect(),
         }
     }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    configuration::{CIPHERSUITE, WELCOME_HPKE_LABEL},
+    configuration::{
+        CIPHERSUITE, JOIN_REQUEST_ENVELOPE_PREFIX, JOIN_REQUEST_HPKE_LABEL, WELCOME_HPKE_LABEL,
+        WELCOME_WITH_RATCHET_TREE_REFERENCE_HPKE_LABEL,
+    },
     retry::RetryableError,
     retryable,
     storage::sql_key_store::{SqlKeyStoreError, KEY_PACKAGE_REFERENCES},
@@ -28,6 +31,8 @@ pub enum HpkeError {
     StorageError(#[from] SqlKeyStoreError),
     #[error("Key not found")]
     KeyNotFound,
+    #[error("Not a join request")]
+    NotJoinRequest,
 }
 
 impl RetryableError for HpkeError {
@@ -41,25 +46,83 @@ impl RetryableError for HpkeError {
 
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn encrypt_welcome(welcome_payload: &[u8], hpke_key: &[u8]) -> Result<Vec<u8>, HpkeError> {
-    let crypto = RustCrypto::default();
-    let ciphertext = encrypt_with_label(
+    encrypt(welcome_payload, hpke_key, WELCOME_HPKE_LABEL)
+}
+
+pub fn decrypt_welcome(
+    provider: &XmtpOpenMlsProvider,
+    hpke_public_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    decrypt(provider, hpke_public_key, ciphertext, WELCOME_HPKE_LABEL)
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn encrypt_welcome_with_ratchet_tree_reference(
+    payload: &[u8],
+    hpke_key: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    encrypt(
+        payload,
         hpke_key,
-        WELCOME_HPKE_LABEL,
-        &[],
-        welcome_payload,
-        CIPHERSUITE,
-        &crypto,
-    )?;
+        WELCOME_WITH_RATCHET_TREE_REFERENCE_HPKE_LABEL,
+    )
+}
+
+pub fn decrypt_welcome_with_ratchet_tree_reference(
+    provider: &XmtpOpenMlsProvider,
+    hpke_public_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    decrypt(
+        provider,
+        hpke_public_key,
+        ciphertext,
+        WELCOME_WITH_RATCHET_TREE_REFERENCE_HPKE_LABEL,
+    )
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn encrypt_join_request(request_payload: &[u8], hpke_key: &[u8]) -> Result<Vec<u8>, HpkeError> {
+    let ciphertext = encrypt(request_payload, hpke_key, JOIN_REQUEST_HPKE_LABEL)?;
+    Ok([JOIN_REQUEST_ENVELOPE_PREFIX, ciphertext.as_slice()].concat())
+}
+
+/// Whether a payload received on the welcome topic is a join request rather than a welcome
+pub fn is_join_request(payload: &[u8]) -> bool {
+    payload.starts_with(JOIN_REQUEST_ENVELOPE_PREFIX)
+}
+
+pub fn decrypt_join_request(
+    provider: &XmtpOpenMlsProvider,
+    hpke_public_key: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    let ciphertext = payload
+        .strip_prefix(JOIN_REQUEST_ENVELOPE_PREFIX)
+        .ok_or(HpkeError::NotJoinRequest)?;
+    decrypt(
+        provider,
+        hpke_public_key,
+        ciphertext,
+        JOIN_REQUEST_HPKE_LABEL,
+    )
+}
+
+fn encrypt(payload: &[u8], hpke_key: &[u8], label: &str) -> Result<Vec<u8>, HpkeError> {
+    let crypto = RustCrypto::default();
+    let ciphertext = encrypt_with_label(hpke_key, label, &[], payload, CIPHERSUITE, &crypto)?;
 
     let serialized_ciphertext = ciphertext.tls_serialize_detached()?;
 
     Ok(serialized_ciphertext)
 }
 
-pub fn decrypt_welcome(
+fn decrypt(
     provider: &XmtpOpenMlsProvider,
     hpke_public_key: &[u8],
     ciphertext: &[u8],
+    label: &str,
 ) -> Result<Vec<u8>, HpkeError> {
     let ciphertext = HpkeCiphertext::tls_deserialize_exact(ciphertext)?;
 
@@ -76,7 +139,7 @@ pub fn decrypt_welcome(
         if let Some(kp) = key_package {
             return Ok(decrypt_with_label(
                 kp.init_private_key(),
-                WELCOME_HPKE_LABEL,
+                label,
                 &[],
                 &ciphertext,
                 CIPHERSUITE,
@@ -5,17 +5,24 @@ use crate::configuration::GROUP_PERMISSIONS_EXTENSION_ID;
 use crate::retry::RetryableError;
 use crate::storage::db_connection::DbConnection;
 use crate::storage::identity::StoredIdentity;
+use crate::storage::serialization::db_deserialize;
+use crate::storage::signature_request::StoredSignatureRequest;
 use crate::storage::sql_key_store::{SqlKeyStore, SqlKeyStoreError, KEY_PACKAGE_REFERENCES};
 use crate::storage::EncryptedMessageStore;
+use crate::utils::time::now_ns;
 use crate::{
-    api::{ApiClientWrapper, WrappedApiError},
-    configuration::{CIPHERSUITE, GROUP_MEMBERSHIP_EXTENSION_ID, MUTABLE_METADATA_EXTENSION_ID},
+    api::{ApiClientWrapper, GetIdentityUpdatesV2Filter, WrappedApiError},
+    configuration::{
+        CIPHERSUITE, COMMIT_RULES_CAPABILITY_ID, GROUP_MEMBERSHIP_EXTENSION_ID,
+        MUTABLE_METADATA_EXTENSION_ID, RATCHET_TREE_REFERENCE_CAPABILITY_ID,
+    },
     storage::StorageError,
     xmtp_openmls_provider::XmtpOpenMlsProvider,
     XmtpApi,
 };
 use crate::{retryable, Fetch, Store};
 use ed25519_dalek::SigningKey;
+use futures::future::try_join_all;
 use openmls::prelude::hash_ref::HashReference;
 use openmls::prelude::tls_codec::Serialize;
 use openmls::{
@@ -36,12 +43,13 @@ use sha2::{Digest, Sha512};
 use thiserror::Error;
 use tracing::debug;
 use tracing::info;
+use xmtp_cryptography::signature::normalize_account_address;
 use xmtp_id::associations::unverified::{UnverifiedInstallationKeySignature, UnverifiedSignature};
 use xmtp_id::scw_verifier::SmartContractSignatureVerifier;
 use xmtp_id::{
     associations::{
         builder::{SignatureRequest, SignatureRequestBuilder, SignatureRequestError},
-        generate_inbox_id, sign_with_legacy_key, MemberIdentifier,
+        generate_inbox_id, get_state, sign_with_legacy_key, AssociationError, MemberIdentifier,
     },
     constants::INSTALLATION_KEY_SIGNATURE_CONTEXT,
     InboxId,
@@ -50,10 +58,15 @@ use xmtp_proto::xmtp::identity::MlsCredential;
 
 #[derive(Debug, Clone)]
 pub enum IdentityStrategy {
-    /// Tries to get an identity from the disk store. If not found, getting one from backend.
+    /// Tries to get an identity from the disk store. If not found, resumes the registration saved
+    /// before the app was restarted, or gets one from the backend.
     CreateIfNotFound(InboxId, String, u64, Option<Vec<u8>>), // (inbox_id, address, nonce, legacy_signed_private_key)
     /// Identity that is already in the disk store
     CachedOnly,
+    /// Regain control of an inbox that lost its installations by signing with its recovery address.
+    /// Tries to get an identity from the disk store, if not found every member of the inbox other
+    /// than the recovery address is revoked and a new installation is added.
+    Recover(InboxId, String), // (inbox_id, recovery_address)
     /// An already-built Identity for testing purposes
     #[cfg(test)]
     ExternalIdentity(Identity),
@@ -69,6 +82,13 @@ impl IdentityStrategy {
         info!("Initializing identity");
         let conn = store.conn()?;
         let provider = XmtpOpenMlsProvider::new(conn);
+        // Expired requests may hold private installation keys, drop them before anything else
+        let pruned = provider
+            .conn_ref()
+            .delete_expired_signature_requests(now_ns())?;
+        if pruned > 0 {
+            debug!("pruned {pruned} expired signature requests");
+        }
         let stored_identity: Option<Identity> = provider
             .conn_ref()
             .fetch(&())?
@@ -95,6 +115,11 @@ impl IdentityStrategy {
                     }
 
                     Ok(stored_identity)
+                } else if let Some(registration) = provider
+                    .conn_ref()
+                    .get_registration_signature_request(&inbox_id, now_ns())?
+                {
+                    Identity::from_saved_registration(registration)
                 } else {
                     Identity::new(
                         inbox_id,
@@ -108,6 +133,26 @@ impl IdentityStrategy {
                     .await
                 }
             }
+            IdentityStrategy::Recover(inbox_id, recovery_address) => {
+                if let Some(stored_identity) = stored_identity {
+                    if inbox_id != stored_identity.inbox_id {
+                        return Err(IdentityError::InboxIdMismatch {
+                            id: inbox_id.clone(),
+                            stored: stored_identity.inbox_id,
+                        });
+                    }
+
+                    Ok(stored_identity)
+                } else {
+                    Identity::recover(
+                        inbox_id,
+                        recovery_address,
+                        api_client,
+                        scw_signature_verifier,
+                    )
+                    .await
+                }
+            }
             #[cfg(test)]
             IdentityStrategy::ExternalIdentity(identity) => Ok(identity),
         }
@@ -160,6 +205,10 @@ pub enum IdentityError {
     InboxIdMismatch { id: InboxId, stored: InboxId },
     #[error("The address {0} has no associated InboxID")]
     NoAssociatedInboxId(String),
+    #[error("The address {0} is not the recovery address of the inbox")]
+    NotRecoveryAddress(String),
+    #[error(transparent)]
+    Association(#[from] AssociationError),
     #[error("Required identity was not found in cache.")]
     RequiredIdentityNotFound,
     #[error("error creating new identity: {0}")]
@@ -220,12 +269,12 @@ impl Identity {
         scw_signature_verifier: &dyn SmartContractSignatureVerifier,
     ) -> Result<Self, IdentityError> {
         // check if address is already associated with an inbox_id
-        let address = address.to_lowercase();
+        let address = normalize_account_address(address);
         let inbox_ids = api_client.get_inbox_ids(vec![address.clone()]).await?;
         let associated_inbox_id = inbox_ids.get(&address);
         let signature_keys = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm())?;
         let installation_public_key = signature_keys.public();
-        let member_identifier: MemberIdentifier = address.clone().to_lowercase().into();
+        let member_identifier: MemberIdentifier = address.clone().into();
 
         if let Some(associated_inbox_id) = associated_inbox_id {
             // If an inbox is associated with address, we'd use it to create Identity and ignore the nonce.
@@ -357,6 +406,76 @@ impl Identity {
         }
     }
 
+    /// Create a new [Identity] for an existing inbox from its recovery address.
+    ///
+    /// The signature request revokes every member of the inbox except the recovery address and
+    /// adds the new installation, all of it signed once by the recovery address.
+    /// Once it is registered, the other members of each group see that the inbox was recovered
+    /// the next time they sync all of their groups, and add the new installation.
+    pub(crate) async fn recover<ApiClient: XmtpApi>(
+        inbox_id: InboxId,
+        recovery_address: String,
+        api_client: &ApiClientWrapper<ApiClient>,
+        scw_signature_verifier: &dyn SmartContractSignatureVerifier,
+    ) -> Result<Self, IdentityError> {
+        let recovery_address = normalize_account_address(recovery_address);
+        let updates = api_client
+            .get_identity_updates_v2(vec![GetIdentityUpdatesV2Filter {
+                inbox_id: inbox_id.clone(),
+                sequence_id: None,
+            }])
+            .await?
+            .remove(&inbox_id)
+            .unwrap_or_default();
+        let updates = try_join_all(
+            updates
+                .iter()
+                .map(|update| update.update.to_verified(scw_signature_verifier)),
+        )
+        .await?;
+        let state = get_state(updates)?;
+
+        if normalize_account_address(state.recovery_address().clone()) != recovery_address {
+            return Err(IdentityError::NotRecoveryAddress(recovery_address));
+        }
+
+        let signature_keys = SignatureKeyPair::new(CIPHERSUITE.signature_algorithm())?;
+        let installation_public_key = signature_keys.public();
+        let recovery_identifier: MemberIdentifier = recovery_address.into();
+
+        let mut builder = SignatureRequestBuilder::new(inbox_id.clone());
+        for member in state.members() {
+            if member.identifier != recovery_identifier {
+                builder =
+                    builder.revoke_association(recovery_identifier.clone(), member.identifier);
+            }
+        }
+        let mut signature_request = builder
+            .add_association(installation_public_key.to_vec().into(), recovery_identifier)
+            .build();
+
+        signature_request
+            .add_signature(
+                UnverifiedSignature::InstallationKey(
+                    sign_with_installation_key(
+                        signature_request.signature_text(),
+                        sized_installation_key(signature_keys.private())?,
+                    )
+                    .await?,
+                ),
+                scw_signature_verifier,
+            )
+            .await?;
+
+        Ok(Self {
+            inbox_id: inbox_id.clone(),
+            installation_keys: signature_keys,
+            credential: create_credential(inbox_id)?,
+            signature_request: Some(signature_request),
+            is_ready: AtomicBool::new(false),
+        })
+    }
+
     pub fn inbox_id(&self) -> &InboxId {
         &self.inbox_id
     }
@@ -365,6 +484,34 @@ impl Identity {
         conn.get_latest_sequence_id_for_inbox(self.inbox_id.as_str())
     }
 
+    /// Rebuild an unregistered [Identity] from the keys saved along with its registration request,
+    /// so that the installation the request was signed for is the one that gets registered
+    fn from_saved_registration(
+        registration: StoredSignatureRequest,
+    ) -> Result<Self, IdentityError> {
+        info!("Resuming saved registration {}", registration.id);
+        let installation_keys: SignatureKeyPair = db_deserialize(
+            registration
+                .installation_keys
+                .as_deref()
+                .ok_or(IdentityError::UninitializedIdentity)?,
+        )?;
+        let inbox_id = registration.inbox_id.clone();
+        let signature_request = SignatureRequest::try_from(registration).map_err(|err| {
+            StorageError::Deserialization(format!(
+                "Failed to deserialize signature request: {err:?}"
+            ))
+        })?;
+
+        Ok(Self {
+            credential: create_credential(inbox_id.clone())?,
+            inbox_id,
+            installation_keys,
+            signature_request: Some(signature_request),
+            is_ready: AtomicBool::new(false),
+        })
+    }
+
     #[allow(dead_code)]
     pub fn is_ready(&self) -> bool {
         self.is_ready.load(Ordering::SeqCst)
@@ -407,6 +554,8 @@ impl Identity {
                 ExtensionType::Unknown(GROUP_PERMISSIONS_EXTENSION_ID),
                 ExtensionType::Unknown(MUTABLE_METADATA_EXTENSION_ID),
                 ExtensionType::Unknown(GROUP_MEMBERSHIP_EXTENSION_ID),
+                ExtensionType::Unknown(RATCHET_TREE_REFERENCE_CAPABILITY_ID),
+                ExtensionType::Unknown(COMMIT_RULES_CAPABILITY_ID),
                 ExtensionType::ImmutableMetadata,
             ]),
             Some(&[ProposalType::GroupContextExtensions]),
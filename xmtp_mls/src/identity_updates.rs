@@ -1,11 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use crate::{
-    retry::{Retry, RetryableError},
-    retry_async, retryable,
+    retry::RetryableError, retry_async, retryable,
     storage::association_state::StoredAssociationState,
 };
 use futures::future::try_join_all;
+use openmls::credentials::BasicCredential;
 use thiserror::Error;
 use xmtp_id::{
     associations::{
@@ -15,17 +15,25 @@ use xmtp_id::{
         unverified::{
             UnverifiedIdentityUpdate, UnverifiedInstallationKeySignature, UnverifiedSignature,
         },
-        AssociationError, AssociationState, AssociationStateDiff, IdentityUpdate, MemberIdentifier,
-        SignatureError,
+        AssociationError, AssociationState, AssociationStateDiff, DeserializationError,
+        IdentityUpdate, MemberIdentifier, SignatureError,
     },
     scw_verifier::SmartContractSignatureVerifier,
+    InboxId,
 };
 
 use crate::{
     api::{ApiClientWrapper, GetIdentityUpdatesV2Filter, InboxUpdate},
-    client::ClientError,
+    client::{ClientError, FindGroupParams},
+    configuration::{ASSOCIATION_STATE_SNAPSHOT_INTERVAL, DEFAULT_SIGNATURE_REQUEST_TTL_NS},
     groups::group_membership::{GroupMembership, MembershipDiff},
-    storage::{db_connection::DbConnection, identity_update::StoredIdentityUpdate},
+    identity::{parse_credential, IdentityError},
+    storage::{
+        db_connection::DbConnection, group::GroupMembershipState,
+        identity_update::StoredIdentityUpdate, serialization::db_serialize,
+        signature_request::StoredSignatureRequest, StorageError,
+    },
+    utils::time::now_ns,
     Client, XmtpApi,
 };
 
@@ -41,6 +49,37 @@ pub struct InstallationDiff {
     pub removed_installations: HashSet<Vec<u8>>,
 }
 
+/// An identity update of an inbox along with the changes it made to the inbox
+#[derive(Debug, Clone)]
+pub struct InboxHistoryEntry {
+    pub sequence_id: i64,
+    pub server_timestamp_ns: i64,
+    /// The verified update, its actions carry the signers and signature kinds that authorized them
+    pub update: IdentityUpdate,
+    /// Members added and removed by the update
+    pub diff: AssociationStateDiff,
+    /// The recovery address of the inbox after the update
+    pub recovery_address: String,
+}
+
+/// What revoking a set of installations would change, as far as this installation can tell
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevocationImpact {
+    /// Requested installations that are not members of the inbox, revoking them has no effect
+    pub unknown_installation_ids: Vec<Vec<u8>>,
+    /// Groups of this installation that the revoked installations will be removed from
+    pub affected_groups: Vec<AffectedGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedGroup {
+    pub group_id: Vec<u8>,
+    /// The revoked installations that are members of the group
+    pub removed_installation_ids: Vec<Vec<u8>>,
+    /// Inboxes whose installations stay in the group and have to process the removal commit
+    pub remaining_inbox_ids: Vec<InboxId>,
+}
+
 #[derive(Debug, Error)]
 pub enum InstallationDiffError {
     #[error(transparent)]
@@ -125,23 +164,38 @@ where
         to_sequence_id: Option<i64>,
     ) -> Result<AssociationState, ClientError> {
         let inbox_id = inbox_id.as_ref();
-        // TODO: Refactor this so that we don't have to fetch all the identity updates if the value is in the cache
-        let updates = conn.get_identity_updates(inbox_id, None, to_sequence_id)?;
-        let last_sequence_id = updates
-            .last()
-            .ok_or::<ClientError>(AssociationError::MissingIdentityUpdate.into())?
-            .sequence_id;
-        if let Some(to_sequence_id) = to_sequence_id {
-            if to_sequence_id != last_sequence_id {
-                return Err(AssociationError::MissingIdentityUpdate.into());
+        let last_sequence_id = match to_sequence_id {
+            Some(sequence_id) => sequence_id,
+            None => conn
+                .get_latest_sequence_id(&[inbox_id.to_string()])?
+                .get(inbox_id)
+                .copied()
+                .ok_or::<ClientError>(AssociationError::MissingIdentityUpdate.into())?,
+        };
+        let counters = &self.context.association_state_counters;
+
+        // Start from the closest cached state, only the updates after it need to be applied
+        let snapshot =
+            StoredAssociationState::read_latest_from_cache(conn, inbox_id, last_sequence_id)?;
+        let snapshot_sequence_id = snapshot.as_ref().map(|(sequence_id, _)| *sequence_id);
+        if let Some((sequence_id, association_state)) = &snapshot {
+            if *sequence_id == last_sequence_id {
+                tracing::debug!(
+                    "Loaded association state from cache: {} {}",
+                    inbox_id,
+                    sequence_id
+                );
+                counters.record_cache_hit();
+                return Ok(association_state.clone());
             }
         }
 
-        if let Some(association_state) =
-            StoredAssociationState::read_from_cache(conn, inbox_id.to_string(), last_sequence_id)?
-        {
-            return Ok(association_state);
+        let updates =
+            conn.get_identity_updates(inbox_id, snapshot_sequence_id, Some(last_sequence_id))?;
+        if updates.last().map(|update| update.sequence_id) != Some(last_sequence_id) {
+            return Err(AssociationError::MissingIdentityUpdate.into());
         }
+        let sequence_ids: Vec<i64> = updates.iter().map(|update| update.sequence_id).collect();
 
         let unverified_updates = updates
             .into_iter()
@@ -152,8 +206,34 @@ where
             self.smart_contract_signature_verifier().as_ref(),
         )
         .await?;
+        let replayed = updates.len();
 
-        let association_state = get_state(updates)?;
+        let mut state = snapshot.map(|(_, association_state)| association_state);
+        for (index, (sequence_id, update)) in sequence_ids.into_iter().zip(updates).enumerate() {
+            let new_state = match state {
+                Some(state) => apply_update(state, update)?,
+                None => get_state([update])?,
+            };
+            if (index + 1) % ASSOCIATION_STATE_SNAPSHOT_INTERVAL == 0
+                && sequence_id != last_sequence_id
+            {
+                StoredAssociationState::write_to_cache(
+                    conn,
+                    inbox_id.to_string(),
+                    sequence_id,
+                    new_state.clone(),
+                )?;
+            }
+            state = Some(new_state);
+        }
+        let association_state = state.ok_or(AssociationError::NotCreated)?;
+        counters.record_replay(snapshot_sequence_id.is_some(), replayed);
+        tracing::debug!(
+            "Replayed {} identity updates of {} on top of the cached state at {:?}",
+            replayed,
+            inbox_id,
+            snapshot_sequence_id
+        );
 
         StoredAssociationState::write_to_cache(
             conn,
@@ -233,6 +313,60 @@ where
         Ok(initial_state.diff(&final_state))
     }
 
+    /// Replay every identity update of an inbox, returning each update with the changes it made.
+    ///
+    /// Fetches any updates missing from the local DB first.
+    pub async fn inbox_history<InboxId: AsRef<str>>(
+        &self,
+        inbox_id: InboxId,
+    ) -> Result<Vec<InboxHistoryEntry>, ClientError> {
+        let inbox_id = inbox_id.as_ref();
+        let conn = self.store().conn()?;
+        load_identity_updates(&self.api_client, &conn, vec![inbox_id.to_string()]).await?;
+
+        let stored_updates = conn.get_identity_updates(inbox_id, None, None)?;
+        let positions: Vec<(i64, i64)> = stored_updates
+            .iter()
+            .map(|update| (update.sequence_id, update.server_timestamp_ns))
+            .collect();
+        let unverified_updates = stored_updates
+            .into_iter()
+            .map(UnverifiedIdentityUpdate::try_from)
+            .collect::<Result<Vec<UnverifiedIdentityUpdate>, AssociationError>>()?;
+        let updates = verify_updates(
+            unverified_updates,
+            self.smart_contract_signature_verifier().as_ref(),
+        )
+        .await?;
+
+        let mut state: Option<AssociationState> = None;
+        let mut history = Vec::with_capacity(updates.len());
+        for ((sequence_id, server_timestamp_ns), update) in positions.into_iter().zip(updates) {
+            let (new_state, diff) = match &state {
+                Some(state) => {
+                    let new_state = apply_update(state.clone(), update.clone())?;
+                    let diff = state.diff(&new_state);
+                    (new_state, diff)
+                }
+                None => {
+                    let new_state = get_state([update.clone()])?;
+                    let diff = new_state.as_diff();
+                    (new_state, diff)
+                }
+            };
+            history.push(InboxHistoryEntry {
+                sequence_id,
+                server_timestamp_ns,
+                update,
+                diff,
+                recovery_address: new_state.recovery_address().clone(),
+            });
+            state = Some(new_state);
+        }
+
+        Ok(history)
+    }
+
     pub async fn create_inbox(
         &self,
         wallet_address: String,
@@ -241,7 +375,7 @@ where
         let nonce = maybe_nonce.unwrap_or(0);
         let inbox_id = generate_inbox_id(&wallet_address, &nonce);
         let installation_public_key = self.identity().installation_keys.public();
-        let member_identifier: MemberIdentifier = wallet_address.to_lowercase().into();
+        let member_identifier: MemberIdentifier = wallet_address.into();
 
         let builder = SignatureRequestBuilder::new(inbox_id);
         let mut signature_request = builder
@@ -287,7 +421,7 @@ where
     ) -> Result<SignatureRequest, ClientError> {
         let inbox_id = self.inbox_id();
         let current_state = retry_async!(
-            Retry::default(),
+            self.api_client.retry_strategy(),
             (async {
                 self.get_association_state(&self.store().conn()?, &inbox_id, None)
                     .await
@@ -305,6 +439,31 @@ where
         Ok(builder.build())
     }
 
+    /// Revoke passkeys, identified by their P-256 public key in any SEC1 encoding
+    pub async fn revoke_passkeys(
+        &self,
+        passkeys_to_revoke: Vec<Vec<u8>>,
+    ) -> Result<SignatureRequest, ClientError> {
+        let inbox_id = self.inbox_id();
+        let current_state = retry_async!(
+            self.api_client.retry_strategy(),
+            (async {
+                self.get_association_state(&self.store().conn()?, &inbox_id, None)
+                    .await
+            })
+        )?;
+        let mut builder = SignatureRequestBuilder::new(inbox_id);
+
+        for public_key in passkeys_to_revoke {
+            builder = builder.revoke_association(
+                current_state.recovery_address().clone().into(),
+                MemberIdentifier::new_passkey(&public_key)?,
+            )
+        }
+
+        Ok(builder.build())
+    }
+
     pub async fn revoke_installations(
         &self,
         installation_ids: Vec<Vec<u8>>,
@@ -312,7 +471,7 @@ where
         let inbox_id = self.inbox_id();
 
         let current_state = retry_async!(
-            Retry::default(),
+            self.api_client.retry_strategy(),
             (async {
                 self.get_association_state(&self.store().conn()?, &inbox_id, None)
                     .await
@@ -331,11 +490,147 @@ where
         Ok(builder.build())
     }
 
+    /// Report what [`Self::revoke_installations`] would change without building a signature request.
+    ///
+    /// Only the groups this installation is a member of are known, revoked installations may be
+    /// in other groups as well.
+    pub async fn revoke_installations_dry_run(
+        &self,
+        installation_ids: Vec<Vec<u8>>,
+    ) -> Result<RevocationImpact, ClientError> {
+        let conn = self.store().conn()?;
+        // Installations added or revoked elsewhere would otherwise be reported as unknown
+        load_identity_updates(&self.api_client, &conn, vec![self.inbox_id()]).await?;
+        let current_state = self
+            .get_association_state(&conn, self.inbox_id(), None)
+            .await?;
+        let member_installation_ids: HashSet<Vec<u8>> =
+            current_state.installation_ids().into_iter().collect();
+        let (revoked, unknown_installation_ids): (HashSet<Vec<u8>>, Vec<Vec<u8>>) =
+            installation_ids
+                .into_iter()
+                .partition(|id| member_installation_ids.contains(id));
+        if revoked.is_empty() {
+            return Ok(RevocationImpact {
+                unknown_installation_ids,
+                affected_groups: vec![],
+            });
+        }
+
+        let provider = self.mls_provider()?;
+        let groups = self.find_groups(FindGroupParams {
+            allowed_states: Some(vec![GroupMembershipState::Allowed]),
+            include_dm_groups: true,
+            ..FindGroupParams::default()
+        })?;
+        let mut affected_groups = vec![];
+        for group in groups {
+            let openmls_group = group.load_mls_group(&provider)?;
+            let mut removed_installation_ids = vec![];
+            let mut remaining_inbox_ids = BTreeSet::new();
+            for member in openmls_group.members() {
+                if revoked.contains(&member.signature_key) {
+                    removed_installation_ids.push(member.signature_key);
+                    continue;
+                }
+                let credential =
+                    BasicCredential::try_from(member.credential).map_err(IdentityError::from)?;
+                remaining_inbox_ids.insert(parse_credential(credential.identity())?);
+            }
+
+            if !removed_installation_ids.is_empty() {
+                affected_groups.push(AffectedGroup {
+                    group_id: group.group_id,
+                    removed_installation_ids,
+                    remaining_inbox_ids: remaining_inbox_ids.into_iter().collect(),
+                });
+            }
+        }
+
+        Ok(RevocationImpact {
+            unknown_installation_ids,
+            affected_groups,
+        })
+    }
+
+    /// Save a signature request to the local database so that it can be resumed with
+    /// [`resume_signature_request`](Self::resume_signature_request) if the app is restarted
+    /// while waiting on signatures. Saving a request again stores the signatures collected
+    /// since, keeping its original expiry. Returns the id of the request.
+    ///
+    /// Requests can be resumed for [`DEFAULT_SIGNATURE_REQUEST_TTL_NS`] unless `expires_in_ns` is given.
+    ///
+    /// If this is the request that registers this installation, the installation keys are saved
+    /// with it, and a client built for the same inbox resumes the registration instead of creating
+    /// a new installation.
+    pub fn save_signature_request(
+        &self,
+        signature_request: &SignatureRequest,
+        expires_in_ns: Option<i64>,
+    ) -> Result<String, ClientError> {
+        let identity = self.identity();
+        let registers_installation = !identity.is_ready()
+            && identity
+                .signature_request()
+                .is_some_and(|request| request.id() == signature_request.id());
+        let installation_keys = registers_installation
+            .then(|| db_serialize(&identity.installation_keys))
+            .transpose()?;
+
+        let now = now_ns();
+        let expires_at_ns = now + expires_in_ns.unwrap_or(DEFAULT_SIGNATURE_REQUEST_TTL_NS);
+        let stored = StoredSignatureRequest::new(
+            signature_request.clone(),
+            installation_keys,
+            now,
+            expires_at_ns,
+        );
+        self.store().conn()?.save_signature_request(&stored)?;
+
+        Ok(stored.id)
+    }
+
+    /// Load a saved signature request, with the signatures it had collected when it was last saved.
+    /// Returns `None` if there is no such request or it has expired.
+    pub fn resume_signature_request(
+        &self,
+        id: &str,
+    ) -> Result<Option<SignatureRequest>, ClientError> {
+        let conn = self.store().conn()?;
+        conn.get_signature_request(id, now_ns())?
+            .map(decode_signature_request)
+            .transpose()
+    }
+
+    /// The saved signature requests of this client's inbox that have not expired, oldest first
+    pub fn saved_signature_requests(&self) -> Result<Vec<SignatureRequest>, ClientError> {
+        let conn = self.store().conn()?;
+        conn.get_signature_requests(&self.inbox_id(), now_ns())?
+            .into_iter()
+            .map(decode_signature_request)
+            .collect()
+    }
+
+    pub fn delete_signature_request(&self, id: &str) -> Result<(), ClientError> {
+        Ok(self.store().conn()?.delete_signature_request(id)?)
+    }
+
+    /// Delete expired signature requests from the local database, returning how many were deleted
+    pub fn delete_expired_signature_requests(&self) -> Result<usize, ClientError> {
+        Ok(self
+            .store()
+            .conn()?
+            .delete_expired_signature_requests(now_ns())?)
+    }
+
+    /// Publish the identity update of a completed signature request.
+    /// If the request was saved, it is deleted once the update is published.
     pub async fn apply_signature_request(
         &self,
         signature_request: SignatureRequest,
     ) -> Result<(), ClientError> {
         let inbox_id = signature_request.inbox_id();
+        let request_id = signature_request.id();
         // If the signature request isn't completed, this will error
         let identity_update = signature_request
             .build_identity_update()
@@ -345,10 +640,18 @@ where
         self.api_client
             .publish_identity_update(identity_update)
             .await?;
+        // The update is already published, so a failed cleanup must not fail the request
+        if let Err(err) = self
+            .store()
+            .conn()
+            .and_then(|conn| conn.delete_signature_request(&request_id))
+        {
+            tracing::warn!("failed to delete signature request {request_id}: {err}");
+        }
 
         // Load the identity updates for the inbox so that we have a record in our DB
         retry_async!(
-            Retry::default(),
+            self.api_client.retry_strategy(),
             (async {
                 load_identity_updates(
                     &self.api_client,
@@ -483,6 +786,15 @@ pub async fn load_identity_updates<ApiClient: XmtpApi>(
         .collect::<Vec<StoredIdentityUpdate>>();
 
     conn.insert_or_ignore_identity_updates(&to_store)?;
+
+    // the addresses of an inbox may have changed, look them up again next time
+    let updated_inbox_ids: Vec<String> = updates
+        .iter()
+        .filter(|(_, updates)| !updates.is_empty())
+        .map(|(inbox_id, _)| inbox_id.clone())
+        .collect();
+    conn.invalidate_inbox_id_cache(&updated_inbox_ids)?;
+
     Ok(updates)
 }
 
@@ -498,23 +810,41 @@ async fn verify_updates(
     .await
 }
 
+fn decode_signature_request(
+    stored: StoredSignatureRequest,
+) -> Result<SignatureRequest, ClientError> {
+    Ok(
+        SignatureRequest::try_from(stored).map_err(|err: DeserializationError| {
+            StorageError::Deserialization(format!(
+                "Failed to deserialize signature request: {err:?}"
+            ))
+        })?,
+    )
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
+    use diesel::RunQueryDsl;
     use xmtp_cryptography::utils::generate_local_wallet;
     use xmtp_id::{
         associations::{
-            builder::SignatureRequest, test_utils::add_wallet_signature, AssociationState,
-            MemberIdentifier,
+            builder::{SignatureRequest, SignatureRequestBuilder},
+            test_utils::{add_wallet_signature, MockSmartContractSignatureVerifier},
+            Action, AssociationState, MemberIdentifier, SignatureKind,
         },
         InboxOwner,
     };
 
     use crate::{
         assert_logged,
-        builder::ClientBuilder,
-        groups::group_membership::GroupMembership,
-        storage::{db_connection::DbConnection, identity_update::StoredIdentityUpdate},
-        utils::test::rand_vec,
+        builder::{ClientBuilder, ClientBuilderError},
+        groups::{group_membership::GroupMembership, GroupMetadataOptions},
+        identity::{IdentityError, IdentityStrategy},
+        storage::{
+            db_connection::DbConnection, identity_update::StoredIdentityUpdate,
+            schema::association_state,
+        },
+        utils::test::{rand_vec, register_client},
         Client, XmtpApi,
     };
 
@@ -660,6 +990,68 @@ pub(crate) mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn incremental_association_state() {
+        let wallet = generate_local_wallet();
+        let client = ClientBuilder::new_test_client(&wallet).await;
+        let inbox_id = client.inbox_id();
+        for _ in 0..2 {
+            let new_wallet = generate_local_wallet();
+            let mut add_association_request = client
+                .associate_wallet(wallet.get_address(), new_wallet.get_address())
+                .unwrap();
+            add_wallet_signature(&mut add_association_request, &wallet).await;
+            add_wallet_signature(&mut add_association_request, &new_wallet).await;
+            client
+                .apply_signature_request(add_association_request)
+                .await
+                .unwrap();
+        }
+
+        let conn = client.store().conn().unwrap();
+        conn.raw_query(|conn| diesel::delete(association_state::table).execute(conn))
+            .unwrap();
+        let sequence_ids: Vec<i64> = conn
+            .get_identity_updates(&inbox_id, None, None)
+            .unwrap()
+            .iter()
+            .map(|update| update.sequence_id)
+            .collect();
+        assert_eq!(sequence_ids.len(), 3);
+        let before = client.association_state_stats().unwrap();
+        assert_eq!(before.snapshots, 0);
+
+        let first_state = client
+            .get_association_state(&conn, &inbox_id, Some(sequence_ids[0]))
+            .await
+            .unwrap();
+        assert_eq!(first_state.account_addresses().len(), 1);
+
+        // Resumes from the state at the first update
+        let latest_state = client
+            .get_association_state(&conn, &inbox_id, None)
+            .await
+            .unwrap();
+        assert_eq!(latest_state.account_addresses().len(), 3);
+        client
+            .get_association_state(&conn, &inbox_id, None)
+            .await
+            .unwrap();
+
+        let stats = client.association_state_stats().unwrap();
+        assert_eq!(stats.full_replays - before.full_replays, 1);
+        assert_eq!(stats.snapshot_replays - before.snapshot_replays, 1);
+        assert_eq!(stats.cache_hits - before.cache_hits, 1);
+        assert_eq!(stats.updates_replayed - before.updates_replayed, 3);
+        assert_eq!(stats.snapshots, 2);
+
+        // A sequence id without an identity update is an error, even with an earlier snapshot
+        assert!(client
+            .get_association_state(&conn, &inbox_id, Some(sequence_ids[2] + 1))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn load_identity_updates_if_needed() {
         let wallet = generate_local_wallet();
@@ -866,4 +1258,265 @@ pub(crate) mod tests {
         let association_state = get_association_state(&client1, client1.inbox_id()).await;
         assert_eq!(association_state.installation_ids().len(), 1);
     }
+
+    #[tokio::test]
+    pub async fn resume_saved_signature_request() {
+        let wallet = generate_local_wallet();
+        let second_wallet = generate_local_wallet();
+        let client = ClientBuilder::new_test_client(&wallet).await;
+
+        let mut signature_request = client
+            .associate_wallet(wallet.get_address(), second_wallet.get_address())
+            .unwrap();
+        add_wallet_signature(&mut signature_request, &wallet).await;
+        let id = client
+            .save_signature_request(&signature_request, None)
+            .unwrap();
+
+        let mut resumed = client.resume_signature_request(&id).unwrap().unwrap();
+        assert_eq!(resumed.id(), id);
+        assert_eq!(
+            resumed.missing_address_signatures(),
+            vec![MemberIdentifier::Address(second_wallet.get_address())]
+        );
+        add_wallet_signature(&mut resumed, &second_wallet).await;
+        assert_eq!(client.save_signature_request(&resumed, None).unwrap(), id);
+        assert_eq!(client.saved_signature_requests().unwrap().len(), 1);
+
+        client.apply_signature_request(resumed).await.unwrap();
+        assert!(client.resume_signature_request(&id).unwrap().is_none());
+        assert!(client.saved_signature_requests().unwrap().is_empty());
+        let association_state = get_association_state(&client, client.inbox_id()).await;
+        assert_eq!(association_state.account_addresses().len(), 2);
+
+        let expired_request = client
+            .revoke_wallets(vec![second_wallet.get_address()])
+            .await
+            .unwrap();
+        let expired_id = client
+            .save_signature_request(&expired_request, Some(0))
+            .unwrap();
+        assert!(client
+            .resume_signature_request(&expired_id)
+            .unwrap()
+            .is_none());
+        assert_eq!(client.delete_expired_signature_requests().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn recover_inbox() {
+        let wallet = generate_local_wallet();
+        let second_wallet = generate_local_wallet();
+        let recovery_wallet = generate_local_wallet();
+        let client = ClientBuilder::new_test_client(&wallet).await;
+        let other_client = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+        let inbox_id = client.inbox_id();
+
+        let mut add_wallet_signature_request = client
+            .associate_wallet(wallet.get_address(), second_wallet.get_address())
+            .unwrap();
+        add_wallet_signature(&mut add_wallet_signature_request, &wallet).await;
+        add_wallet_signature(&mut add_wallet_signature_request, &second_wallet).await;
+        client
+            .apply_signature_request(add_wallet_signature_request)
+            .await
+            .unwrap();
+
+        // Move the recovery address to a wallet that is not a member of the inbox
+        let mut change_recovery_request = SignatureRequestBuilder::new(inbox_id.clone())
+            .change_recovery_address(wallet.get_address().into(), recovery_wallet.get_address())
+            .build();
+        add_wallet_signature(&mut change_recovery_request, &wallet).await;
+        client
+            .apply_signature_request(change_recovery_request)
+            .await
+            .unwrap();
+
+        let group = other_client
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        group
+            .add_members_by_inbox_id(&other_client, vec![inbox_id.clone()])
+            .await
+            .unwrap();
+
+        // Only the recovery address can recover the inbox
+        let result = ClientBuilder::new(IdentityStrategy::Recover(
+            inbox_id.clone(),
+            wallet.get_address(),
+        ))
+        .scw_signature_verifier(MockSmartContractSignatureVerifier::new(true))
+        .temp_store()
+        .local_client()
+        .await
+        .build()
+        .await;
+        assert!(matches!(
+            result,
+            Err(ClientBuilderError::Identity(
+                IdentityError::NotRecoveryAddress(_)
+            ))
+        ));
+
+        let recovered_client = ClientBuilder::new(IdentityStrategy::Recover(
+            inbox_id.clone(),
+            recovery_wallet.get_address(),
+        ))
+        .scw_signature_verifier(MockSmartContractSignatureVerifier::new(true))
+        .temp_store()
+        .local_client()
+        .await
+        .build()
+        .await
+        .unwrap();
+        assert_eq!(recovered_client.inbox_id(), inbox_id);
+        register_client(&recovered_client, &recovery_wallet).await;
+
+        // Every wallet and installation of the inbox was revoked
+        let association_state = get_association_state(&recovered_client, inbox_id.clone()).await;
+        assert_eq!(
+            association_state.installation_ids(),
+            vec![recovered_client.installation_public_key()]
+        );
+        assert!(association_state.account_addresses().is_empty());
+        assert_eq!(
+            association_state.recovery_address(),
+            &recovery_wallet.get_address()
+        );
+
+        // Members of the group welcome the recovered installation the next time they sync,
+        // without waiting for the installation update interval
+        other_client
+            .sync_all_groups(vec![group.clone()])
+            .await
+            .unwrap();
+        let groups = recovered_client.sync_welcomes().await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].group_id, group.group_id);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    pub async fn revoke_installations_dry_run() {
+        let wallet = generate_local_wallet();
+        let amal_a = ClientBuilder::new_test_client(&wallet).await;
+        let amal_b = ClientBuilder::new_test_client(&wallet).await;
+        let bola = ClientBuilder::new_test_client(&generate_local_wallet()).await;
+
+        let shared_group = bola
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+        shared_group
+            .add_members_by_inbox_id(&bola, vec![amal_a.inbox_id()])
+            .await
+            .unwrap();
+        amal_a.sync_welcomes().await.unwrap();
+        // A group only amal_a is a member of
+        amal_a
+            .create_group(None, GroupMetadataOptions::default())
+            .unwrap();
+
+        let unknown_installation_id = rand_vec();
+        let impact = amal_a
+            .revoke_installations_dry_run(vec![
+                amal_b.installation_public_key(),
+                unknown_installation_id.clone(),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            impact.unknown_installation_ids,
+            vec![unknown_installation_id]
+        );
+        assert_eq!(impact.affected_groups.len(), 1);
+        let affected = &impact.affected_groups[0];
+        assert_eq!(affected.group_id, shared_group.group_id);
+        assert_eq!(
+            affected.removed_installation_ids,
+            vec![amal_b.installation_public_key()]
+        );
+        let mut remaining_inbox_ids = vec![amal_a.inbox_id(), bola.inbox_id()];
+        remaining_inbox_ids.sort();
+        assert_eq!(affected.remaining_inbox_ids, remaining_inbox_ids);
+
+        // Nothing was revoked
+        let state = get_association_state(&amal_a, amal_a.inbox_id()).await;
+        assert_eq!(state.installation_ids().len(), 2);
+    }
+
+    #[tokio::test]
+    pub async fn inbox_history() {
+        let wallet = generate_local_wallet();
+        let second_wallet = generate_local_wallet();
+        let client = ClientBuilder::new_test_client(&wallet).await;
+
+        let mut add_wallet_signature_request = client
+            .associate_wallet(wallet.get_address(), second_wallet.get_address())
+            .unwrap();
+        add_wallet_signature(&mut add_wallet_signature_request, &wallet).await;
+        add_wallet_signature(&mut add_wallet_signature_request, &second_wallet).await;
+        client
+            .apply_signature_request(add_wallet_signature_request)
+            .await
+            .unwrap();
+
+        let mut revoke_signature_request = client
+            .revoke_wallets(vec![second_wallet.get_address()])
+            .await
+            .unwrap();
+        add_wallet_signature(&mut revoke_signature_request, &wallet).await;
+        client
+            .apply_signature_request(revoke_signature_request)
+            .await
+            .unwrap();
+
+        let history = client.inbox_history(client.inbox_id()).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert!(history
+            .windows(2)
+            .all(|entries| entries[0].sequence_id < entries[1].sequence_id));
+        assert!(history
+            .iter()
+            .all(|entry| entry.server_timestamp_ns > 0
+                && entry.recovery_address == wallet.get_address()));
+
+        // Creating the inbox added the wallet and the installation
+        let created = &history[0];
+        assert!(matches!(
+            created.update.actions[..],
+            [Action::CreateInbox(_), Action::AddAssociation(_)]
+        ));
+        assert_eq!(created.diff.new_members.len(), 2);
+        assert_eq!(
+            created.diff.new_installations(),
+            vec![client.installation_public_key()]
+        );
+
+        // The second wallet was added with signatures from both wallets
+        let added = &history[1];
+        assert_eq!(
+            added.diff.new_members,
+            vec![MemberIdentifier::Address(second_wallet.get_address())]
+        );
+        let signers: Vec<_> = added.update.actions[0]
+            .verified_signatures()
+            .into_iter()
+            .map(|signature| (signature.signer.clone(), signature.kind.clone()))
+            .collect();
+        assert_eq!(
+            signers,
+            vec![
+                (wallet.get_address().into(), SignatureKind::Erc191),
+                (second_wallet.get_address().into(), SignatureKind::Erc191),
+            ]
+        );
+
+        // And revoked by the recovery address
+        let revoked = &history[2];
+        assert!(revoked.diff.new_members.is_empty());
+        assert_eq!(
+            revoked.diff.removed_members,
+            vec![MemberIdentifier::Address(second_wallet.get_address())]
+        );
+        assert!(revoked.update.client_timestamp_ns >= added.update.client_timestamp_ns);
+    }
 }
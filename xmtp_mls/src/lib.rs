@@ -20,6 +20,7 @@ pub mod verified_key_package_v2;
 mod xmtp_openmls_provider;
 
 pub use client::{Client, Network};
+pub use identity_updates::{AffectedGroup, InboxHistoryEntry, RevocationImpact};
 use storage::StorageError;
 
 pub use trait_impls::*;
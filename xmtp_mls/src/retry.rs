@@ -16,9 +16,17 @@
 //! }
 //! ```
 
-use std::time::Duration;
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use parking_lot::Mutex;
 use rand::Rng;
+use xmtp_proto::api_client::ErrorKind;
+
+use crate::configuration::{RETRY_MAX_DELAY_MS, RETRY_MAX_ELAPSED_MS};
 
 /// Specifies which errors are retryable.
 /// All Errors are not retryable by-default.
@@ -26,14 +34,53 @@ pub trait RetryableError: std::error::Error {
     fn is_retryable(&self) -> bool;
 }
 
+/// A hook to classify errors independently of their [`RetryableError`] implementation.
+/// Returning `None` defers to [`RetryableError::is_retryable`].
+pub type RetryClassifier =
+    Arc<dyn Fn(&(dyn std::error::Error + 'static)) -> Option<bool> + Send + Sync>;
+
+/// How the delay between attempts grows
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Backoff {
+    /// Wait `duration` (plus jitter) between every attempt
+    Constant,
+    /// Multiply `duration` by `multiplier` on each subsequent attempt, plus jitter
+    #[default]
+    Exponential,
+    /// Wait a random duration between `duration` and three times the previous delay.
+    /// Spreads out clients that started retrying at the same time.
+    DecorrelatedJitter,
+}
+
 /// Options to specify how to retry a function
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Clone)]
 pub struct Retry {
     retries: usize,
     duration: std::time::Duration,
     // The amount to multiply the duration on each subsequent attempt
     multiplier: u32,
     max_jitter_ms: usize,
+    backoff: Backoff,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+    classifier: Option<RetryClassifier>,
+    budget: Option<RetryBudget>,
+}
+
+impl fmt::Debug for Retry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("retries", &self.retries)
+            .field("duration", &self.duration)
+            .field("multiplier", &self.multiplier)
+            .field("max_jitter_ms", &self.max_jitter_ms)
+            .field("backoff", &self.backoff)
+            .field("max_delay", &self.max_delay)
+            .field("max_elapsed", &self.max_elapsed)
+            .field("classifier", &self.classifier.is_some())
+            .field("budget", &self.budget)
+            .finish()
+    }
 }
 
 impl Default for Retry {
@@ -43,6 +90,11 @@ impl Default for Retry {
             duration: std::time::Duration::from_millis(50),
             multiplier: 3,
             max_jitter_ms: 25,
+            backoff: Backoff::default(),
+            max_delay: None,
+            max_elapsed: None,
+            classifier: None,
+            budget: None,
         }
     }
 }
@@ -62,27 +114,186 @@ impl Retry {
             duration *= self.multiplier;
         }
 
+        self.cap(duration + self.jitter())
+    }
+
+    fn jitter(&self) -> Duration {
         let jitter = rand::thread_rng().gen_range(0..=self.max_jitter_ms);
-        duration + Duration::from_millis(jitter as u64)
+        Duration::from_millis(jitter as u64)
+    }
+
+    fn cap(&self, delay: Duration) -> Duration {
+        match self.max_delay {
+            Some(max_delay) => delay.min(max_delay),
+            None => delay,
+        }
+    }
+
+    /// The delay before attempt number `attempts`, given the previous delay
+    fn delay(&self, attempts: usize, previous: Option<Duration>) -> Duration {
+        match self.backoff {
+            Backoff::Constant => self.cap(self.duration + self.jitter()),
+            Backoff::Exponential => self.duration(attempts),
+            Backoff::DecorrelatedJitter => {
+                let base = self.duration.as_millis() as u64;
+                let upper = previous
+                    .map(|previous| previous.as_millis() as u64 * 3)
+                    .unwrap_or(base)
+                    .max(base);
+                let delay = rand::thread_rng().gen_range(base..=upper);
+                self.cap(Duration::from_millis(delay))
+            }
+        }
+    }
+
+    /// Whether `error` should be retried, consulting the classifier before
+    /// [`RetryableError::is_retryable`]
+    pub fn is_retryable<E>(&self, error: &E) -> bool
+    where
+        E: RetryableError + 'static,
+    {
+        self.classifier
+            .as_ref()
+            .and_then(|classify| classify(error))
+            .unwrap_or_else(|| error.is_retryable())
+    }
+
+    /// Begin retrying an operation
+    pub fn start(&self) -> RetryState {
+        RetryState {
+            attempts: 0,
+            // only read the clock when it is needed
+            started: self.max_elapsed.map(|_| Instant::now()),
+            last_delay: None,
+        }
+    }
+
+    /// Decide whether to retry after `error`, returning how long to wait if so.
+    /// Gives up once the error is not retryable, the attempts or elapsed time run out,
+    /// or the shared retry budget is exhausted.
+    pub fn next_delay<E>(&self, state: &mut RetryState, error: &E) -> Option<Duration>
+    where
+        E: RetryableError + 'static,
+    {
+        if !self.is_retryable(error) || state.attempts >= self.retries {
+            return None;
+        }
+
+        let delay = self.delay(state.attempts + 1, state.last_delay);
+        if let (Some(max_elapsed), Some(started)) = (self.max_elapsed, state.started) {
+            if started.elapsed() + delay > max_elapsed {
+                tracing::info!("giving up after {:?}, out of time to retry", max_elapsed);
+                return None;
+            }
+        }
+        if let Some(budget) = &self.budget {
+            if !budget.withdraw() {
+                tracing::warn!("retry budget exhausted");
+                return None;
+            }
+        }
+
+        state.attempts += 1;
+        state.last_delay = Some(delay);
+        Some(delay)
+    }
+
+    /// Record a successful attempt
+    pub fn on_success(&self) {
+        if let Some(budget) = &self.budget {
+            budget.deposit();
+        }
+    }
+}
+
+/// The progress of a single retried operation
+#[derive(Debug)]
+pub struct RetryState {
+    attempts: usize,
+    started: Option<Instant>,
+    last_delay: Option<Duration>,
+}
+
+impl RetryState {
+    /// The number of retries made so far
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+}
+
+/// A pool of retries shared by every [`Retry`] it is attached to.
+///
+/// Each retry withdraws one token and each success deposits a fraction of one, so when most calls
+/// are failing retries stop until the node recovers, instead of multiplying the load on it.
+#[derive(Clone, Debug)]
+pub struct RetryBudget {
+    tokens: Arc<Mutex<f64>>,
+    max_tokens: f64,
+    deposit: f64,
+}
+
+impl RetryBudget {
+    /// Allow bursts of up to `max_retries` retries, replenishing `deposit` retries per success
+    pub fn new(max_retries: u32, deposit: f64) -> Self {
+        Self {
+            tokens: Arc::new(Mutex::new(max_retries as f64)),
+            max_tokens: max_retries as f64,
+            deposit,
+        }
+    }
+
+    /// The number of retries currently available
+    pub fn remaining(&self) -> f64 {
+        *self.tokens.lock()
+    }
+
+    fn withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock();
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn deposit(&self) {
+        let mut tokens = self.tokens.lock();
+        *tokens = (*tokens + self.deposit).min(self.max_tokens);
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(100, 0.1)
     }
 }
 
 /// Builder for [`Retry`]
-#[derive(Default, PartialEq, Eq, Copy, Clone)]
+#[derive(Default, Clone)]
 pub struct RetryBuilder {
     retries: Option<usize>,
     duration: Option<std::time::Duration>,
+    multiplier: Option<u32>,
+    backoff: Option<Backoff>,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+    classifier: Option<RetryClassifier>,
+    budget: Option<RetryBudget>,
 }
 
 /// Builder for [`Retry`].
 ///
 /// # Example
 /// ```
-/// use xmtp_mls::retry::RetryBuilder;
+/// use xmtp_mls::retry::{Backoff, RetryBudget, RetryBuilder};
 ///
 /// RetryBuilder::default()
 ///     .retries(5)
 ///     .duration(std::time::Duration::from_millis(1000))
+///     .backoff(Backoff::DecorrelatedJitter)
+///     .max_elapsed(std::time::Duration::from_secs(30))
+///     .budget(RetryBudget::default())
 ///     .build();
 /// ```
 impl RetryBuilder {
@@ -98,6 +309,45 @@ impl RetryBuilder {
         self
     }
 
+    /// Specify the amount to multiply the duration by on each attempt, for [`Backoff::Exponential`]
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = Some(multiplier);
+        self
+    }
+
+    /// Specify how the delay grows between attempts
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+
+    /// Never wait longer than `max_delay` between two attempts
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Stop retrying once the next attempt would start after `max_elapsed`
+    pub fn max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Classify errors before falling back to [`RetryableError::is_retryable`]
+    pub fn classifier<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&(dyn std::error::Error + 'static)) -> Option<bool> + Send + Sync + 'static,
+    {
+        self.classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Draw retries from a shared [`RetryBudget`]
+    pub fn budget(mut self, budget: RetryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     /// Build the Retry Strategy
     pub fn build(self) -> Retry {
         let mut retry = Retry::default();
@@ -110,6 +360,19 @@ impl RetryBuilder {
             retry.duration = duration;
         }
 
+        if let Some(multiplier) = self.multiplier {
+            retry.multiplier = multiplier;
+        }
+
+        if let Some(backoff) = self.backoff {
+            retry.backoff = backoff;
+        }
+
+        retry.max_delay = self.max_delay;
+        retry.max_elapsed = self.max_elapsed;
+        retry.classifier = self.classifier;
+        retry.budget = self.budget;
+
         retry
     }
 }
@@ -119,6 +382,34 @@ impl Retry {
     pub fn builder() -> RetryBuilder {
         RetryBuilder::default()
     }
+
+    /// The strategy a client uses for its network calls and group syncs unless one is given to
+    /// the [`ClientBuilder`](crate::builder::ClientBuilder). Every clone draws from the same
+    /// [`RetryBudget`], so a burst of failing syncs can not multiply the load on the node.
+    pub fn client_default() -> Self {
+        Retry::builder()
+            .backoff(Backoff::DecorrelatedJitter)
+            .max_delay(Duration::from_millis(RETRY_MAX_DELAY_MS))
+            .max_elapsed(Duration::from_millis(RETRY_MAX_ELAPSED_MS))
+            .classifier(classify_api_error)
+            .budget(RetryBudget::default())
+            .build()
+    }
+}
+
+/// Errors caused by an invalid TLS configuration will fail the same way on every attempt,
+/// wherever they are in the chain of sources
+pub fn classify_api_error(error: &(dyn std::error::Error + 'static)) -> Option<bool> {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(api_error) = error.downcast_ref::<xmtp_proto::api_client::Error>() {
+            if matches!(api_error.kind(), ErrorKind::SetupTLSConfigError) {
+                return Some(false);
+            }
+        }
+        source = error.source();
+    }
+    None
 }
 
 /// Retry but for an async context
@@ -170,22 +461,25 @@ macro_rules! retry_async {
         use tracing::Instrument as _;
         #[allow(unused)]
         use $crate::retry::RetryableError;
-        let mut attempts = 0;
+        let retry = &$retry;
+        let mut state = retry.start();
         let span = tracing::trace_span!("retry");
         loop {
             let span = span.clone();
             #[allow(clippy::redundant_closure_call)]
             let res = $code.instrument(span).await;
             match res {
-                Ok(v) => break Ok(v),
+                Ok(v) => {
+                    retry.on_success();
+                    break Ok(v);
+                }
                 Err(e) => {
-                    if (&e).is_retryable() && attempts < $retry.retries() {
+                    if let Some(delay) = retry.next_delay(&mut state, &e) {
                         tracing::warn!(
                             "retrying function that failed with error={}",
                             e.to_string()
                         );
-                        attempts += 1;
-                        tokio::time::sleep($retry.duration(attempts)).await;
+                        tokio::time::sleep(delay).await;
                     } else {
                         tracing::info!("error is not retryable. {:?}", e);
                         break Err(e);
@@ -352,4 +646,147 @@ mod tests {
         assert!(backoff_retry.duration(2).as_millis() - 150 <= 25);
         assert!(backoff_retry.duration(3).as_millis() - 450 <= 25);
     }
+
+    #[test]
+    fn decorrelated_jitter_stays_in_bounds() {
+        let retry = Retry::builder()
+            .backoff(Backoff::DecorrelatedJitter)
+            .duration(Duration::from_millis(10))
+            .max_delay(Duration::from_millis(200))
+            .build();
+
+        let mut previous = None;
+        for attempt in 1..20 {
+            let delay = retry.delay(attempt, previous);
+            assert!(delay >= Duration::from_millis(10));
+            assert!(delay <= Duration::from_millis(200));
+            if let Some(previous) = previous {
+                assert!(delay <= previous * 3);
+            }
+            previous = Some(delay);
+        }
+    }
+
+    #[test]
+    fn max_delay_caps_exponential_backoff() {
+        let retry = Retry::builder()
+            .max_delay(Duration::from_millis(100))
+            .build();
+
+        assert_eq!(retry.duration(5), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn client_default_clones_share_a_budget() {
+        let retry = Retry::client_default();
+        let clone = retry.clone();
+        let mut state = clone.start();
+        let before = retry.budget.as_ref().unwrap().remaining();
+
+        assert!(clone
+            .next_delay(&mut state, &SomeError::ARetryableError)
+            .is_some());
+        assert_eq!(retry.budget.as_ref().unwrap().remaining(), before - 1.0);
+    }
+
+    #[derive(Debug, Error)]
+    #[error("wrapped: {0}")]
+    struct Wrapped(#[source] xmtp_proto::api_client::Error);
+
+    impl RetryableError for Wrapped {
+        fn is_retryable(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn classifier_stops_tls_errors_anywhere_in_the_chain() {
+        let retry = Retry::client_default();
+        let tls = Wrapped(xmtp_proto::api_client::Error::new(
+            ErrorKind::SetupTLSConfigError,
+        ));
+        let query = Wrapped(xmtp_proto::api_client::Error::new(ErrorKind::QueryError));
+
+        assert!(!retry.is_retryable(&tls));
+        assert!(retry.is_retryable(&query));
+    }
+
+    #[tokio::test]
+    async fn it_stops_after_max_elapsed() {
+        let mut attempts = 0;
+        let retry = Retry::builder()
+            .retries(100)
+            .backoff(Backoff::Constant)
+            .duration(Duration::from_millis(10))
+            .max_elapsed(Duration::from_millis(50))
+            .build();
+
+        let result: Result<(), SomeError> = retry_async!(
+            retry,
+            (async {
+                attempts += 1;
+                retry_error_fn()
+            })
+        );
+
+        assert!(result.is_err());
+        assert!(attempts < 10);
+    }
+
+    #[tokio::test]
+    async fn classifier_overrides_retryable_error() {
+        let mut attempts = 0;
+        let retry = Retry::builder()
+            .duration(Duration::from_millis(1))
+            .classifier(|e| {
+                e.downcast_ref::<SomeError>()
+                    .map(|e| matches!(e, SomeError::DontRetryThis))
+            })
+            .build();
+
+        let _r: Result<(), SomeError> = retry_async!(
+            retry,
+            (async {
+                attempts += 1;
+                retry_error_fn()
+            })
+        );
+        assert_eq!(attempts, 1);
+
+        attempts = 0;
+        let _r: Result<(), SomeError> = retry_async!(
+            retry,
+            (async {
+                attempts += 1;
+                Err(SomeError::DontRetryThis)
+            })
+        );
+        assert_eq!(attempts, 6);
+    }
+
+    #[tokio::test]
+    async fn budget_is_shared_between_retries() {
+        let budget = RetryBudget::new(3, 0.5);
+        let retry = Retry::builder()
+            .duration(Duration::from_millis(1))
+            .budget(budget.clone())
+            .build();
+
+        let mut attempts = 0;
+        for _ in 0..2 {
+            let _r: Result<(), SomeError> = retry_async!(
+                retry,
+                (async {
+                    attempts += 1;
+                    retry_error_fn()
+                })
+            );
+        }
+        // 2 initial attempts and the 3 retries the budget allows
+        assert_eq!(attempts, 5);
+        assert_eq!(budget.remaining(), 0.0);
+
+        let _r: Result<(), SomeError> = retry_async!(retry, (async { Ok(()) }));
+        assert_eq!(budget.remaining(), 0.5);
+    }
 }
@@ -89,6 +89,42 @@ impl StoredAssociationState {
         result
     }
 
+    /// The most recent cached state at or below `max_sequence_id`, along with its sequence id.
+    /// Updates after that sequence id can be applied on top of it instead of replaying the whole log.
+    pub fn read_latest_from_cache(
+        conn: &DbConnection,
+        inbox_id: &str,
+        max_sequence_id: i64,
+    ) -> Result<Option<(i64, AssociationState)>, StorageError> {
+        let query = dsl::association_state
+            .filter(dsl::inbox_id.eq(inbox_id))
+            .filter(dsl::sequence_id.le(max_sequence_id))
+            .order(dsl::sequence_id.desc())
+            .limit(1);
+
+        let stored_state = conn
+            .raw_query(|query_conn| query.first::<StoredAssociationState>(query_conn).optional())?;
+
+        stored_state
+            .map(|stored_state| {
+                let sequence_id = stored_state.sequence_id;
+                stored_state
+                    .try_into()
+                    .map(|state| (sequence_id, state))
+                    .map_err(|err: DeserializationError| {
+                        StorageError::Deserialization(format!(
+                            "Failed to deserialize stored association state: {err:?}"
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    pub fn count(conn: &DbConnection) -> Result<i64, StorageError> {
+        Ok(conn
+            .raw_query(|query_conn| dsl::association_state.count().get_result::<i64>(query_conn))?)
+    }
+
     pub fn batch_read_from_cache(
         conn: &DbConnection,
         identifiers: Vec<(InboxId, i64)>,
@@ -172,4 +208,45 @@ mod tests {
             assert_eq!(no_results.len(), 0);
         })
     }
+
+    #[test]
+    fn test_read_latest() {
+        with_connection(|conn| {
+            let inbox_id = "1234".to_string();
+            assert!(
+                StoredAssociationState::read_latest_from_cache(conn, &inbox_id, 10)
+                    .unwrap()
+                    .is_none()
+            );
+
+            for sequence_id in [2, 5] {
+                StoredAssociationState::write_to_cache(
+                    conn,
+                    inbox_id.clone(),
+                    sequence_id,
+                    AssociationState::new(inbox_id.clone(), 0),
+                )
+                .unwrap();
+            }
+            // Another inbox with a closer snapshot
+            StoredAssociationState::write_to_cache(
+                conn,
+                "456".to_string(),
+                4,
+                AssociationState::new("456".to_string(), 0),
+            )
+            .unwrap();
+
+            let read_latest = |max_sequence_id| {
+                StoredAssociationState::read_latest_from_cache(conn, &inbox_id, max_sequence_id)
+                    .unwrap()
+                    .map(|(sequence_id, _)| sequence_id)
+            };
+            assert_eq!(read_latest(1), None);
+            assert_eq!(read_latest(2), Some(2));
+            assert_eq!(read_latest(4), Some(2));
+            assert_eq!(read_latest(9), Some(5));
+            assert_eq!(StoredAssociationState::count(conn).unwrap(), 3);
+        })
+    }
 }
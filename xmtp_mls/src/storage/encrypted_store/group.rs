@@ -270,6 +270,8 @@ pub enum GroupMembershipState {
     Rejected = 2,
     /// User is Pending acceptance to the Group
     Pending = 3,
+    /// User asked to leave the Group and is waiting to be removed
+    PendingRemove = 4,
 }
 
 impl ToSql<Integer, Sqlite> for GroupMembershipState
@@ -291,6 +293,7 @@ where
             1 => Ok(GroupMembershipState::Allowed),
             2 => Ok(GroupMembershipState::Rejected),
             3 => Ok(GroupMembershipState::Pending),
+            4 => Ok(GroupMembershipState::PendingRemove),
             x => Err(format!("Unrecognized variant {}", x).into()),
         }
     }
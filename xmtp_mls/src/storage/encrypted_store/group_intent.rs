@@ -37,6 +37,7 @@ pub enum IntentKind {
     UpdateGroupMembership = 4,
     UpdateAdminList = 5,
     UpdatePermission = 6,
+    BatchUpdate = 7,
 }
 
 impl std::fmt::Display for IntentKind {
@@ -48,6 +49,7 @@ impl std::fmt::Display for IntentKind {
             IntentKind::UpdateGroupMembership => "UpdateGroupMembership",
             IntentKind::UpdateAdminList => "UpdateAdminList",
             IntentKind::UpdatePermission => "UpdatePermission",
+            IntentKind::BatchUpdate => "BatchUpdate",
         };
         write!(f, "{}", description)
     }
@@ -244,6 +246,29 @@ impl DbConnection {
         }
     }
 
+    // Replace the `post_commit_data` of a published intent, used to attach data that is only
+    // known once the commit has been merged
+    pub fn set_group_intent_post_commit_data(
+        &self,
+        intent_id: ID,
+        post_commit_data: Vec<u8>,
+    ) -> Result<(), StorageError> {
+        let res = self.raw_query(|conn| {
+            diesel::update(dsl::group_intents)
+                .filter(dsl::id.eq(intent_id))
+                .filter(dsl::state.eq(IntentState::Published))
+                .set(dsl::post_commit_data.eq(Some(post_commit_data)))
+                .execute(conn)
+        })?;
+
+        match res {
+            0 => Err(StorageError::NotFound(format!(
+                "Published intent {intent_id} for post commit data"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
     // Set the intent with the given ID to `ToPublish`. Wipe any values for `payload_hash` and
     // `post_commit_data`
     pub fn set_group_intent_to_publish(&self, intent_id: ID) -> Result<(), StorageError> {
@@ -356,6 +381,7 @@ where
             4 => Ok(IntentKind::UpdateGroupMembership),
             5 => Ok(IntentKind::UpdateAdminList),
             6 => Ok(IntentKind::UpdatePermission),
+            7 => Ok(IntentKind::BatchUpdate),
             x => Err(format!("Unrecognized variant {}", x).into()),
         }
     }
@@ -705,6 +731,13 @@ mod tests {
                 to_publish_result.err().unwrap(),
                 StorageError::NotFound(_)
             ));
+
+            let post_commit_data_result =
+                conn.set_group_intent_post_commit_data(intent.id, rand_vec());
+            assert!(matches!(
+                post_commit_data_result.err().unwrap(),
+                StorageError::NotFound(_)
+            ));
         })
     }
 
@@ -0,0 +1,100 @@
+use diesel::prelude::*;
+
+use super::{
+    db_connection::DbConnection,
+    group,
+    schema::group_invites::{self, dsl},
+};
+use crate::{impl_fetch, impl_store, storage::StorageError};
+
+/// An invite issued by this installation. Only invites recorded here are honoured, and each one
+/// approves at most `max_uses` join requests.
+#[derive(Insertable, Identifiable, Queryable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = group_invites)]
+#[diesel(primary_key(nonce))]
+pub struct StoredGroupInvite {
+    pub nonce: Vec<u8>,
+    pub group_id: group::ID,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub expires_at_ns: i64,
+}
+
+impl_store!(StoredGroupInvite, group_invites);
+impl_fetch!(StoredGroupInvite, group_invites, Vec<u8>);
+
+impl StoredGroupInvite {
+    pub fn new(nonce: Vec<u8>, group_id: Vec<u8>, max_uses: i32, expires_at_ns: i64) -> Self {
+        Self {
+            nonce,
+            group_id,
+            max_uses,
+            uses: 0,
+            expires_at_ns,
+        }
+    }
+
+    pub fn is_used_up(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+}
+
+impl DbConnection {
+    /// Count an approved join request against the invite it was made with.
+    /// Returns false, without counting it, if the invite is unknown or already used up.
+    pub fn use_group_invite(&self, nonce: &[u8]) -> Result<bool, StorageError> {
+        let updated = self.raw_query(|conn| {
+            diesel::update(
+                dsl::group_invites
+                    .filter(dsl::nonce.eq(nonce))
+                    .filter(dsl::uses.lt(dsl::max_uses)),
+            )
+            .set(dsl::uses.eq(dsl::uses + 1))
+            .execute(conn)
+        })?;
+
+        Ok(updated == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::encrypted_store::{
+            group::{GroupMembershipState, StoredGroup},
+            tests::with_connection,
+        },
+        utils::test::rand_vec,
+        Fetch, Store,
+    };
+
+    #[test]
+    fn test_invite_uses_are_limited() {
+        with_connection(|conn| {
+            let group_id = rand_vec();
+            StoredGroup::new(
+                group_id.clone(),
+                100,
+                GroupMembershipState::Allowed,
+                "placeholder_address".to_string(),
+                None,
+            )
+            .store(conn)
+            .unwrap();
+            let nonce = rand_vec();
+            StoredGroupInvite::new(nonce.clone(), group_id, 2, i64::MAX)
+                .store(conn)
+                .unwrap();
+
+            assert!(conn.use_group_invite(&nonce).unwrap());
+            assert!(conn.use_group_invite(&nonce).unwrap());
+            assert!(!conn.use_group_invite(&nonce).unwrap());
+            assert!(!conn.use_group_invite(&rand_vec()).unwrap());
+
+            let invite: StoredGroupInvite = conn.fetch(&nonce).unwrap().unwrap();
+            assert_eq!(invite.uses, 2);
+            assert!(invite.is_used_up());
+        })
+    }
+}
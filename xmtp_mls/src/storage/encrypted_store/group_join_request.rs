@@ -0,0 +1,281 @@
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    prelude::*,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Integer,
+    sqlite::Sqlite,
+};
+
+use super::{
+    db_connection::DbConnection,
+    group,
+    schema::group_join_requests::{self, dsl},
+};
+use crate::{impl_fetch, storage::StorageError};
+
+pub type ID = i32;
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Integer)]
+/// The state of a request to join a group
+pub enum JoinRequestState {
+    /// Waiting for an admin to approve or deny the request
+    Pending = 1,
+    /// The requester was added to the group
+    Approved = 2,
+    /// The request was denied, later requests from the same inbox are ignored
+    Denied = 3,
+    /// Approving the request automatically failed. An admin can still approve or deny it, and a
+    /// later request from the same inbox makes it pending again
+    Failed = 4,
+}
+
+impl ToSql<Integer, Sqlite> for JoinRequestState
+where
+    i32: ToSql<Integer, Sqlite>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(*self as i32);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Integer, Sqlite> for JoinRequestState
+where
+    i32: FromSql<Integer, Sqlite>,
+{
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        match i32::from_sql(bytes)? {
+            1 => Ok(JoinRequestState::Pending),
+            2 => Ok(JoinRequestState::Approved),
+            3 => Ok(JoinRequestState::Denied),
+            4 => Ok(JoinRequestState::Failed),
+            x => Err(format!("Unrecognized variant {}", x).into()),
+        }
+    }
+}
+
+/// A request from an inbox to join a group, received through an invite issued by this installation
+#[derive(Queryable, Identifiable, Debug, PartialEq, Eq, Clone)]
+#[diesel(table_name = group_join_requests)]
+#[diesel(primary_key(id))]
+pub struct StoredGroupJoinRequest {
+    pub id: ID,
+    pub group_id: group::ID,
+    pub requester_inbox_id: String,
+    pub state: JoinRequestState,
+    pub received_at_ns: i64,
+    /// Nonce of the invite the request was made with
+    pub invite_nonce: Vec<u8>,
+}
+
+impl_fetch!(StoredGroupJoinRequest, group_join_requests, ID);
+
+#[derive(Insertable, Debug, PartialEq, Eq, Clone)]
+#[diesel(table_name = group_join_requests)]
+pub struct NewGroupJoinRequest {
+    pub group_id: Vec<u8>,
+    pub requester_inbox_id: String,
+    pub state: JoinRequestState,
+    pub received_at_ns: i64,
+    pub invite_nonce: Vec<u8>,
+}
+
+impl NewGroupJoinRequest {
+    pub fn new(
+        group_id: Vec<u8>,
+        requester_inbox_id: String,
+        invite_nonce: Vec<u8>,
+        received_at_ns: i64,
+    ) -> Self {
+        Self {
+            group_id,
+            requester_inbox_id,
+            state: JoinRequestState::Pending,
+            received_at_ns,
+            invite_nonce,
+        }
+    }
+}
+
+impl DbConnection {
+    /// Stores a join request, or marks an earlier request from the same inbox as pending again.
+    /// Returns `None` if the inbox was previously denied, in which case nothing is changed.
+    pub fn upsert_join_request(
+        &self,
+        request: NewGroupJoinRequest,
+    ) -> Result<Option<StoredGroupJoinRequest>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let existing: Option<StoredGroupJoinRequest> = dsl::group_join_requests
+                    .filter(dsl::group_id.eq(&request.group_id))
+                    .filter(dsl::requester_inbox_id.eq(&request.requester_inbox_id))
+                    .first(conn)
+                    .optional()?;
+
+                match existing {
+                    Some(existing) if existing.state == JoinRequestState::Denied => Ok(None),
+                    Some(existing) => diesel::update(dsl::group_join_requests.find(existing.id))
+                        .set((
+                            dsl::state.eq(JoinRequestState::Pending),
+                            dsl::received_at_ns.eq(request.received_at_ns),
+                            dsl::invite_nonce.eq(&request.invite_nonce),
+                        ))
+                        .get_result(conn)
+                        .map(Some),
+                    None => diesel::insert_into(dsl::group_join_requests)
+                        .values(&request)
+                        .get_result(conn)
+                        .map(Some),
+                }
+            })
+        })?)
+    }
+
+    /// Query join requests, optionally filtering by group and state
+    pub fn find_join_requests(
+        &self,
+        group_id: Option<&[u8]>,
+        state: Option<JoinRequestState>,
+    ) -> Result<Vec<StoredGroupJoinRequest>, StorageError> {
+        let mut query = dsl::group_join_requests.into_boxed();
+
+        if let Some(group_id) = group_id {
+            query = query.filter(dsl::group_id.eq(group_id));
+        }
+
+        if let Some(state) = state {
+            query = query.filter(dsl::state.eq(state));
+        }
+
+        query = query.order(dsl::received_at_ns.asc());
+
+        Ok(self.raw_query(|conn| query.load::<StoredGroupJoinRequest>(conn))?)
+    }
+
+    pub fn set_join_request_state(
+        &self,
+        id: ID,
+        state: JoinRequestState,
+    ) -> Result<(), StorageError> {
+        let updated = self.raw_query(|conn| {
+            diesel::update(dsl::group_join_requests.find(id))
+                .set(dsl::state.eq(state))
+                .execute(conn)
+        })?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(StorageError::NotFound(format!("join request {id}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::encrypted_store::{
+            group::{GroupMembershipState, StoredGroup},
+            tests::with_connection,
+        },
+        utils::test::{rand_string, rand_vec},
+        Fetch, Store,
+    };
+
+    fn insert_group(conn: &DbConnection, group_id: Vec<u8>) {
+        let group = StoredGroup::new(
+            group_id,
+            100,
+            GroupMembershipState::Allowed,
+            "placeholder_address".to_string(),
+            None,
+        );
+        group.store(conn).unwrap();
+    }
+
+    #[test]
+    fn test_upsert_join_request() {
+        with_connection(|conn| {
+            let group_id = rand_vec();
+            let inbox_id = rand_string();
+            insert_group(conn, group_id.clone());
+
+            let request = conn
+                .upsert_join_request(NewGroupJoinRequest::new(
+                    group_id.clone(),
+                    inbox_id.clone(),
+                    rand_vec(),
+                    1,
+                ))
+                .unwrap()
+                .unwrap();
+            assert_eq!(request.state, JoinRequestState::Pending);
+
+            // A repeated request refreshes the existing one
+            conn.set_join_request_state(request.id, JoinRequestState::Approved)
+                .unwrap();
+            let repeated = conn
+                .upsert_join_request(NewGroupJoinRequest::new(
+                    group_id.clone(),
+                    inbox_id.clone(),
+                    rand_vec(),
+                    2,
+                ))
+                .unwrap()
+                .unwrap();
+            assert_eq!(repeated.id, request.id);
+            assert_eq!(repeated.state, JoinRequestState::Pending);
+            assert_eq!(repeated.received_at_ns, 2);
+
+            // Denied inboxes can not request again
+            conn.set_join_request_state(request.id, JoinRequestState::Denied)
+                .unwrap();
+            let denied = conn
+                .upsert_join_request(NewGroupJoinRequest::new(group_id, inbox_id, rand_vec(), 3))
+                .unwrap();
+            assert!(denied.is_none());
+            let stored: StoredGroupJoinRequest = conn.fetch(&request.id).unwrap().unwrap();
+            assert_eq!(stored.state, JoinRequestState::Denied);
+        })
+    }
+
+    #[test]
+    fn test_find_join_requests() {
+        with_connection(|conn| {
+            let group_id = rand_vec();
+            let other_group_id = rand_vec();
+            insert_group(conn, group_id.clone());
+            insert_group(conn, other_group_id.clone());
+
+            for (group_id, received_at_ns) in [(&group_id, 2), (&group_id, 1), (&other_group_id, 3)]
+            {
+                conn.upsert_join_request(NewGroupJoinRequest::new(
+                    group_id.clone(),
+                    rand_string(),
+                    rand_vec(),
+                    received_at_ns,
+                ))
+                .unwrap();
+            }
+
+            let requests = conn.find_join_requests(Some(&group_id), None).unwrap();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[0].received_at_ns, 1);
+
+            conn.set_join_request_state(requests[0].id, JoinRequestState::Denied)
+                .unwrap();
+            let pending = conn
+                .find_join_requests(None, Some(JoinRequestState::Pending))
+                .unwrap();
+            assert_eq!(pending.len(), 2);
+
+            assert!(conn
+                .set_join_request_state(i32::MAX, JoinRequestState::Denied)
+                .is_err());
+        })
+    }
+}
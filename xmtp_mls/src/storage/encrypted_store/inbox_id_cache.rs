@@ -0,0 +1,130 @@
+use diesel::prelude::*;
+
+use super::{
+    db_connection::DbConnection,
+    schema::inbox_id_cache::{self, dsl},
+};
+use crate::{
+    configuration::{INBOX_ID_CACHE_TTL_NS, INBOX_ID_NEGATIVE_CACHE_TTL_NS},
+    storage::StorageError,
+};
+
+/// A cached result of looking up the inbox_id of an account address.
+/// An entry without an `inbox_id` caches that the address had no inbox.
+#[derive(Insertable, Identifiable, Queryable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = inbox_id_cache)]
+#[diesel(primary_key(address))]
+pub struct StoredInboxIdCacheEntry {
+    pub address: String,
+    pub inbox_id: Option<String>,
+    pub cached_at_ns: i64,
+}
+
+impl StoredInboxIdCacheEntry {
+    pub fn new(address: String, inbox_id: Option<String>, cached_at_ns: i64) -> Self {
+        Self {
+            address,
+            inbox_id,
+            cached_at_ns,
+        }
+    }
+
+    /// Negative entries expire sooner, since an address can register an inbox at any time
+    pub fn is_expired(&self, now_ns: i64) -> bool {
+        let ttl = match self.inbox_id {
+            Some(_) => INBOX_ID_CACHE_TTL_NS,
+            None => INBOX_ID_NEGATIVE_CACHE_TTL_NS,
+        };
+        now_ns - self.cached_at_ns > ttl
+    }
+}
+
+impl DbConnection {
+    /// Returns the cached entries for the given addresses, expired or not
+    pub fn get_inbox_id_cache_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<StoredInboxIdCacheEntry>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::inbox_id_cache
+                .filter(dsl::address.eq_any(addresses))
+                .load::<StoredInboxIdCacheEntry>(conn)
+        })?)
+    }
+
+    /// Insert cache entries, replacing existing entries for the same address
+    pub fn insert_or_replace_inbox_id_cache_entries(
+        &self,
+        entries: &[StoredInboxIdCacheEntry],
+    ) -> Result<(), StorageError> {
+        self.raw_query(|conn| {
+            diesel::replace_into(dsl::inbox_id_cache)
+                .values(entries)
+                .execute(conn)
+        })?;
+
+        Ok(())
+    }
+
+    /// Drop the cached addresses of the given inboxes, so the next lookup goes to the network
+    pub fn invalidate_inbox_id_cache(&self, inbox_ids: &[String]) -> Result<usize, StorageError> {
+        if inbox_ids.is_empty() {
+            return Ok(0);
+        }
+
+        Ok(self.raw_query(|conn| {
+            diesel::delete(dsl::inbox_id_cache.filter(dsl::inbox_id.eq_any(inbox_ids)))
+                .execute(conn)
+        })?)
+    }
+
+    pub fn count_inbox_id_cache_entries(&self) -> Result<i64, StorageError> {
+        Ok(self.raw_query(|conn| dsl::inbox_id_cache.count().get_result::<i64>(conn))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        configuration::{INBOX_ID_CACHE_TTL_NS, INBOX_ID_NEGATIVE_CACHE_TTL_NS},
+        storage::encrypted_store::tests::with_connection,
+        utils::test::rand_string,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_insert_and_invalidate() {
+        with_connection(|conn| {
+            let inbox_id = rand_string();
+            let entries = vec![
+                StoredInboxIdCacheEntry::new("0x1".into(), Some(inbox_id.clone()), 1),
+                StoredInboxIdCacheEntry::new("0x2".into(), Some(inbox_id.clone()), 1),
+                StoredInboxIdCacheEntry::new("0x3".into(), None, 1),
+            ];
+            conn.insert_or_replace_inbox_id_cache_entries(&entries)
+                .unwrap();
+
+            let replacement = StoredInboxIdCacheEntry::new("0x3".into(), Some(rand_string()), 2);
+            conn.insert_or_replace_inbox_id_cache_entries(&[replacement.clone()])
+                .unwrap();
+            let stored = conn
+                .get_inbox_id_cache_entries(&["0x3".to_string()])
+                .unwrap();
+            assert_eq!(stored, vec![replacement]);
+
+            assert_eq!(conn.invalidate_inbox_id_cache(&[inbox_id]).unwrap(), 2);
+            assert_eq!(conn.count_inbox_id_cache_entries().unwrap(), 1);
+        })
+    }
+
+    #[test]
+    fn test_expiry() {
+        let positive = StoredInboxIdCacheEntry::new("0x1".into(), Some(rand_string()), 0);
+        let negative = StoredInboxIdCacheEntry::new("0x1".into(), None, 0);
+
+        assert!(!positive.is_expired(INBOX_ID_NEGATIVE_CACHE_TTL_NS + 1));
+        assert!(positive.is_expired(INBOX_ID_CACHE_TTL_NS + 1));
+        assert!(negative.is_expired(INBOX_ID_NEGATIVE_CACHE_TTL_NS + 1));
+    }
+}
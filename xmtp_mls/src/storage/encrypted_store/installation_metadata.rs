@@ -0,0 +1,174 @@
+use diesel::prelude::*;
+use xmtp_proto::xmtp::mls::message_contents::InstallationMetadata;
+
+use super::{
+    db_connection::DbConnection,
+    schema::installation_metadata::{self, dsl},
+};
+use crate::{storage::StorageError, utils::time::now_ns};
+
+/// The device details an installation shares with the other installations of its inbox.
+/// The row of this installation is the source of the details it shares, the rows of other
+/// installations are the details they last shared.
+#[derive(Insertable, Identifiable, Queryable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = installation_metadata)]
+#[diesel(primary_key(installation_id))]
+pub struct StoredInstallationMetadata {
+    pub installation_id: Vec<u8>,
+    pub device_name: String,
+    pub platform: String,
+    pub created_at_ns: i64,
+    /// When the installation last shared its details
+    pub last_seen_ns: i64,
+}
+
+impl StoredInstallationMetadata {
+    pub fn from_proto(
+        installation_id: Vec<u8>,
+        metadata: InstallationMetadata,
+        last_seen_ns: i64,
+    ) -> Self {
+        Self {
+            installation_id,
+            device_name: metadata.device_name,
+            platform: metadata.platform,
+            created_at_ns: metadata.created_at_ns as i64,
+            last_seen_ns,
+        }
+    }
+
+    pub fn to_proto(&self) -> InstallationMetadata {
+        InstallationMetadata {
+            device_name: self.device_name.clone(),
+            platform: self.platform.clone(),
+            created_at_ns: self.created_at_ns as u64,
+        }
+    }
+}
+
+impl DbConnection {
+    pub fn get_installation_metadata(
+        &self,
+        installation_ids: &[Vec<u8>],
+    ) -> Result<Vec<StoredInstallationMetadata>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::installation_metadata
+                .filter(dsl::installation_id.eq_any(installation_ids))
+                .load::<StoredInstallationMetadata>(conn)
+        })?)
+    }
+
+    /// Insert metadata shared by installations, replacing existing metadata for the same installation
+    pub fn insert_or_replace_installation_metadata(
+        &self,
+        metadata: &[StoredInstallationMetadata],
+    ) -> Result<(), StorageError> {
+        self.raw_query(|conn| {
+            diesel::replace_into(dsl::installation_metadata)
+                .values(metadata)
+                .execute(conn)
+        })?;
+
+        Ok(())
+    }
+
+    /// Set the device name and platform of an installation, keeping its creation time.
+    /// Returns whether anything changed.
+    pub fn set_installation_metadata(
+        &self,
+        installation_id: &[u8],
+        device_name: String,
+        platform: String,
+    ) -> Result<bool, StorageError> {
+        let existing = self
+            .get_installation_metadata(&[installation_id.to_vec()])?
+            .pop();
+        if let Some(existing) = &existing {
+            if existing.device_name == device_name && existing.platform == platform {
+                return Ok(false);
+            }
+        }
+
+        let now = now_ns();
+        self.insert_or_replace_installation_metadata(&[StoredInstallationMetadata {
+            installation_id: installation_id.to_vec(),
+            device_name,
+            platform,
+            created_at_ns: existing.map(|m| m.created_at_ns).unwrap_or(now),
+            last_seen_ns: now,
+        }])?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::encrypted_store::tests::with_connection;
+    use crate::utils::test::rand_vec;
+
+    use super::*;
+
+    #[test]
+    fn test_set_installation_metadata() {
+        with_connection(|conn| {
+            let installation_id = rand_vec();
+            assert!(conn
+                .get_installation_metadata(&[installation_id.clone()])
+                .unwrap()
+                .is_empty());
+
+            assert!(conn
+                .set_installation_metadata(&installation_id, "phone".into(), "ios".into())
+                .unwrap());
+            assert!(!conn
+                .set_installation_metadata(&installation_id, "phone".into(), "ios".into())
+                .unwrap());
+            let created = conn
+                .get_installation_metadata(&[installation_id.clone()])
+                .unwrap()
+                .pop()
+                .unwrap();
+
+            assert!(conn
+                .set_installation_metadata(&installation_id, "tablet".into(), "ios".into())
+                .unwrap());
+            let renamed = conn
+                .get_installation_metadata(&[installation_id.clone()])
+                .unwrap()
+                .pop()
+                .unwrap();
+            assert_eq!(renamed.device_name, "tablet");
+            assert_eq!(renamed.created_at_ns, created.created_at_ns);
+        })
+    }
+
+    #[test]
+    fn test_replace_installation_metadata() {
+        with_connection(|conn| {
+            let installation_id = rand_vec();
+            let metadata = InstallationMetadata {
+                device_name: "laptop".into(),
+                platform: "macos".into(),
+                created_at_ns: 1,
+            };
+            let stored = StoredInstallationMetadata::from_proto(
+                installation_id.clone(),
+                metadata.clone(),
+                2,
+            );
+            assert_eq!(stored.to_proto(), metadata);
+            conn.insert_or_replace_installation_metadata(&[stored])
+                .unwrap();
+
+            let seen_again =
+                StoredInstallationMetadata::from_proto(installation_id.clone(), metadata, 3);
+            conn.insert_or_replace_installation_metadata(&[seen_again.clone()])
+                .unwrap();
+            assert_eq!(
+                conn.get_installation_metadata(&[installation_id]).unwrap(),
+                vec![seen_again]
+            );
+        })
+    }
+}
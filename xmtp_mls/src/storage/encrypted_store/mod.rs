@@ -15,13 +15,19 @@ pub mod consent_record;
 pub mod db_connection;
 pub mod group;
 pub mod group_intent;
+pub mod group_invite;
+pub mod group_join_request;
 pub mod group_message;
 pub mod identity;
 pub mod identity_update;
+pub mod inbox_id_cache;
+pub mod installation_metadata;
 pub mod key_package_history;
 pub mod key_store_entry;
 pub mod refresh_state;
 pub mod schema;
+pub mod scw_verification_cache;
+pub mod signature_request;
 mod sqlcipher_connection;
 
 use std::sync::Arc;
@@ -187,6 +193,13 @@ impl EncryptedMessageStore {
         Ok(DbConnection::new(conn))
     }
 
+    /// Get a connection only if one is idle, without waiting on the pool.
+    /// For best effort work that may run while the caller already holds the only connection.
+    pub(crate) fn try_conn(&self) -> Option<DbConnection> {
+        let pool_guard = self.pool.read();
+        pool_guard.as_ref()?.try_get().map(DbConnection::new)
+    }
+
     /// Start a new database transaction with the OpenMLS Provider from XMTP
     /// # Arguments
     /// `fun`: Scoped closure providing a MLSProvider to carry out the transaction
@@ -31,6 +31,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    group_invites (nonce) {
+        nonce -> Binary,
+        group_id -> Binary,
+        max_uses -> Integer,
+        uses -> Integer,
+        expires_at_ns -> BigInt,
+    }
+}
+
+diesel::table! {
+    group_join_requests (id) {
+        id -> Integer,
+        group_id -> Binary,
+        requester_inbox_id -> Text,
+        state -> Integer,
+        received_at_ns -> BigInt,
+        invite_nonce -> Binary,
+    }
+}
+
 diesel::table! {
     group_messages (id) {
         id -> Binary,
@@ -75,6 +96,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    inbox_id_cache (address) {
+        address -> Text,
+        inbox_id -> Nullable<Text>,
+        cached_at_ns -> BigInt,
+    }
+}
+
+diesel::table! {
+    installation_metadata (installation_id) {
+        installation_id -> Binary,
+        device_name -> Text,
+        platform -> Text,
+        created_at_ns -> BigInt,
+        last_seen_ns -> BigInt,
+    }
+}
+
 diesel::table! {
     key_package_history (id) {
         id -> Integer,
@@ -106,19 +145,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    scw_verification_cache (cache_key) {
+        cache_key -> Binary,
+        is_valid -> Bool,
+    }
+}
+
+diesel::table! {
+    signature_requests (id) {
+        id -> Text,
+        inbox_id -> Text,
+        request -> Binary,
+        installation_keys -> Nullable<Binary>,
+        created_at_ns -> BigInt,
+        expires_at_ns -> BigInt,
+    }
+}
+
 diesel::joinable!(group_intents -> groups (group_id));
+diesel::joinable!(group_invites -> groups (group_id));
+diesel::joinable!(group_join_requests -> groups (group_id));
 diesel::joinable!(group_messages -> groups (group_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     association_state,
     consent_records,
     group_intents,
+    group_invites,
+    group_join_requests,
     group_messages,
     groups,
     identity,
     identity_updates,
+    inbox_id_cache,
+    installation_metadata,
     key_package_history,
     openmls_key_store,
     openmls_key_value,
     refresh_state,
+    scw_verification_cache,
+    signature_requests,
 );
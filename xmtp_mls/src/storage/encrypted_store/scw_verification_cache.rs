@@ -0,0 +1,120 @@
+use diesel::{connection::SimpleConnection, prelude::*};
+use xmtp_id::scw_verifier::VerificationCache;
+
+use super::{
+    db_connection::DbConnection,
+    schema::scw_verification_cache::{self, dsl},
+    EncryptedMessageStore,
+};
+use crate::storage::StorageError;
+
+/// The result of verifying a smart contract wallet signature at a pinned block number
+#[derive(Insertable, Identifiable, Queryable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = scw_verification_cache)]
+#[diesel(primary_key(cache_key))]
+pub struct StoredScwVerification {
+    pub cache_key: Vec<u8>,
+    pub is_valid: bool,
+}
+
+impl DbConnection {
+    pub fn get_scw_verification(&self, cache_key: &[u8]) -> Result<Option<bool>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::scw_verification_cache
+                .select(dsl::is_valid)
+                .filter(dsl::cache_key.eq(cache_key))
+                .first::<bool>(conn)
+                .optional()
+        })?)
+    }
+
+    /// Store a verification result without waiting on the write lock.
+    /// Fails with a busy error if another connection is in the middle of a write transaction.
+    pub fn insert_scw_verification_nowait(
+        &self,
+        verification: StoredScwVerification,
+    ) -> Result<(), StorageError> {
+        self.raw_query(|conn| {
+            conn.batch_execute("PRAGMA busy_timeout = 0;")?;
+            let result = diesel::insert_or_ignore_into(dsl::scw_verification_cache)
+                .values(&verification)
+                .execute(conn);
+            conn.batch_execute("PRAGMA busy_timeout = 5000;")?;
+            result
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Verification can happen while the caller holds a connection, or the only connection of an
+/// ephemeral store, so the cache never waits on the pool or on the write lock.
+impl VerificationCache for EncryptedMessageStore {
+    fn get(&self, key: &[u8]) -> Option<bool> {
+        let conn = self.try_conn()?;
+        match conn.get_scw_verification(key) {
+            Ok(is_valid) => is_valid,
+            Err(err) => {
+                tracing::warn!("reading scw verification cache failed: {err}");
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: Vec<u8>, is_valid: bool) {
+        let Some(conn) = self.try_conn() else {
+            return;
+        };
+        if let Err(err) = conn.insert_scw_verification_nowait(StoredScwVerification {
+            cache_key: key,
+            is_valid,
+        }) {
+            tracing::debug!("skipped caching scw verification: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{encrypted_store::tests::with_connection, StorageOption};
+    use crate::utils::test::{rand_vec, tmp_path};
+
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get() {
+        with_connection(|conn| {
+            let key = rand_vec();
+            assert_eq!(conn.get_scw_verification(&key).unwrap(), None);
+
+            conn.insert_scw_verification_nowait(StoredScwVerification {
+                cache_key: key.clone(),
+                is_valid: false,
+            })
+            .unwrap();
+            assert_eq!(conn.get_scw_verification(&key).unwrap(), Some(false));
+        })
+    }
+
+    #[test]
+    fn test_cache_does_not_wait_for_a_connection() {
+        let store = EncryptedMessageStore::new(
+            StorageOption::Persistent(tmp_path()),
+            EncryptedMessageStore::generate_enc_key(),
+        )
+        .unwrap();
+        let key = rand_vec();
+        store.insert(key.clone(), true);
+        assert_eq!(VerificationCache::get(&store, &key), Some(true));
+
+        let ephemeral = EncryptedMessageStore::new(
+            StorageOption::Ephemeral,
+            EncryptedMessageStore::generate_enc_key(),
+        )
+        .unwrap();
+        // Hold the only connection of the pool
+        let _conn = ephemeral.conn().unwrap();
+        ephemeral.insert(key.clone(), true);
+        assert_eq!(VerificationCache::get(&ephemeral, &key), None);
+    }
+}
@@ -0,0 +1,233 @@
+use diesel::{prelude::*, upsert::excluded};
+use xmtp_id::associations::{builder::SignatureRequest, DeserializationError};
+
+use super::{
+    db_connection::DbConnection,
+    schema::signature_requests::{self, dsl},
+};
+use crate::storage::StorageError;
+
+/// A signature request that is waiting on signatures, so that it survives the app being restarted
+#[derive(Insertable, Identifiable, Queryable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = signature_requests)]
+#[diesel(primary_key(id))]
+pub struct StoredSignatureRequest {
+    pub id: String,
+    pub inbox_id: String,
+    pub request: Vec<u8>,
+    /// Serialized keys of the unregistered installation the request registers, so that the
+    /// installation survives the app being restarted before registration completes
+    pub installation_keys: Option<Vec<u8>>,
+    pub created_at_ns: i64,
+    pub expires_at_ns: i64,
+}
+
+impl StoredSignatureRequest {
+    pub fn new(
+        request: SignatureRequest,
+        installation_keys: Option<Vec<u8>>,
+        created_at_ns: i64,
+        expires_at_ns: i64,
+    ) -> Self {
+        Self {
+            id: request.id(),
+            inbox_id: request.inbox_id(),
+            request: request.into(),
+            installation_keys,
+            created_at_ns,
+            expires_at_ns,
+        }
+    }
+}
+
+impl TryFrom<StoredSignatureRequest> for SignatureRequest {
+    type Error = DeserializationError;
+
+    fn try_from(stored: StoredSignatureRequest) -> Result<Self, Self::Error> {
+        SignatureRequest::try_from(stored.request.as_slice())
+    }
+}
+
+impl DbConnection {
+    /// Store a signature request, or update the signatures of one that is already stored.
+    /// The creation and expiry times of an existing request are kept.
+    pub fn save_signature_request(
+        &self,
+        request: &StoredSignatureRequest,
+    ) -> Result<(), StorageError> {
+        self.raw_query(|conn| {
+            diesel::insert_into(dsl::signature_requests)
+                .values(request)
+                .on_conflict(dsl::id)
+                .do_update()
+                .set(dsl::request.eq(excluded(dsl::request)))
+                .execute(conn)
+        })?;
+
+        Ok(())
+    }
+
+    /// Get a signature request that has not expired by `now_ns`
+    pub fn get_signature_request(
+        &self,
+        id: &str,
+        now_ns: i64,
+    ) -> Result<Option<StoredSignatureRequest>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::signature_requests
+                .filter(dsl::id.eq(id))
+                .filter(dsl::expires_at_ns.gt(now_ns))
+                .first::<StoredSignatureRequest>(conn)
+                .optional()
+        })?)
+    }
+
+    /// All signature requests of an inbox that have not expired by `now_ns`, oldest first
+    pub fn get_signature_requests(
+        &self,
+        inbox_id: &str,
+        now_ns: i64,
+    ) -> Result<Vec<StoredSignatureRequest>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::signature_requests
+                .filter(dsl::inbox_id.eq(inbox_id))
+                .filter(dsl::expires_at_ns.gt(now_ns))
+                .order(dsl::created_at_ns.asc())
+                .load::<StoredSignatureRequest>(conn)
+        })?)
+    }
+
+    /// The latest unexpired request of an inbox that registers an unregistered installation
+    pub fn get_registration_signature_request(
+        &self,
+        inbox_id: &str,
+        now_ns: i64,
+    ) -> Result<Option<StoredSignatureRequest>, StorageError> {
+        Ok(self.raw_query(|conn| {
+            dsl::signature_requests
+                .filter(dsl::inbox_id.eq(inbox_id))
+                .filter(dsl::installation_keys.is_not_null())
+                .filter(dsl::expires_at_ns.gt(now_ns))
+                .order(dsl::created_at_ns.desc())
+                .first::<StoredSignatureRequest>(conn)
+                .optional()
+        })?)
+    }
+
+    pub fn delete_signature_request(&self, id: &str) -> Result<(), StorageError> {
+        self.raw_query(|conn| {
+            diesel::delete(dsl::signature_requests.filter(dsl::id.eq(id))).execute(conn)
+        })?;
+
+        Ok(())
+    }
+
+    /// Delete the signature requests that expired by `now_ns`, returning how many were deleted
+    pub fn delete_expired_signature_requests(&self, now_ns: i64) -> Result<usize, StorageError> {
+        Ok(self.raw_query(|conn| {
+            diesel::delete(dsl::signature_requests.filter(dsl::expires_at_ns.le(now_ns)))
+                .execute(conn)
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xmtp_id::associations::builder::SignatureRequestBuilder;
+
+    use crate::storage::encrypted_store::tests::with_connection;
+    use crate::utils::test::{rand_string, rand_vec};
+
+    use super::*;
+
+    fn signature_request(inbox_id: &str) -> SignatureRequest {
+        SignatureRequestBuilder::new(inbox_id.to_string())
+            .change_recovery_address(rand_string().into(), rand_string())
+            .build()
+    }
+
+    #[test]
+    fn test_save_and_get() {
+        with_connection(|conn| {
+            let inbox_id = rand_string();
+            let request = signature_request(&inbox_id);
+            let id = request.id();
+            conn.save_signature_request(&StoredSignatureRequest::new(request.clone(), None, 1, 10))
+                .unwrap();
+            // Saving again keeps the original expiry
+            conn.save_signature_request(&StoredSignatureRequest::new(request.clone(), None, 5, 20))
+                .unwrap();
+
+            let stored = conn.get_signature_request(&id, 2).unwrap().unwrap();
+            assert_eq!(stored.created_at_ns, 1);
+            assert_eq!(stored.expires_at_ns, 10);
+            let resumed: SignatureRequest = stored.try_into().unwrap();
+            assert_eq!(resumed.signature_text(), request.signature_text());
+            assert_eq!(conn.get_signature_requests(&inbox_id, 2).unwrap().len(), 1);
+
+            conn.delete_signature_request(&id).unwrap();
+            assert!(conn.get_signature_request(&id, 2).unwrap().is_none());
+        })
+    }
+
+    #[test]
+    fn test_expiry() {
+        with_connection(|conn| {
+            let inbox_id = rand_string();
+            let expiring = signature_request(&inbox_id);
+            let lasting = signature_request(&inbox_id);
+            conn.save_signature_request(&StoredSignatureRequest::new(
+                expiring.clone(),
+                None,
+                1,
+                10,
+            ))
+            .unwrap();
+            conn.save_signature_request(&StoredSignatureRequest::new(lasting.clone(), None, 2, 30))
+                .unwrap();
+
+            assert!(conn
+                .get_signature_request(&expiring.id(), 10)
+                .unwrap()
+                .is_none());
+            let remaining = conn.get_signature_requests(&inbox_id, 10).unwrap();
+            assert_eq!(remaining.len(), 1);
+            assert_eq!(remaining[0].id, lasting.id());
+
+            assert_eq!(conn.delete_expired_signature_requests(10).unwrap(), 1);
+            assert_eq!(conn.delete_expired_signature_requests(10).unwrap(), 0);
+        })
+    }
+
+    #[test]
+    fn test_registration_request() {
+        with_connection(|conn| {
+            let inbox_id = rand_string();
+            let other = signature_request(&inbox_id);
+            let registration = signature_request(&inbox_id);
+            conn.save_signature_request(&StoredSignatureRequest::new(other, None, 1, 10))
+                .unwrap();
+            assert!(conn
+                .get_registration_signature_request(&inbox_id, 2)
+                .unwrap()
+                .is_none());
+
+            conn.save_signature_request(&StoredSignatureRequest::new(
+                registration.clone(),
+                Some(rand_vec()),
+                2,
+                10,
+            ))
+            .unwrap();
+            let stored = conn
+                .get_registration_signature_request(&inbox_id, 2)
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.id, registration.id());
+            assert!(conn
+                .get_registration_signature_request(&inbox_id, 10)
+                .unwrap()
+                .is_none());
+        })
+    }
+}
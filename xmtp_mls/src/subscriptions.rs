@@ -11,7 +11,6 @@ use crate::{
     api::GroupFilter,
     client::{extract_welcome_message, ClientError},
     groups::{extract_group_id, group_metadata::ConversationType, GroupError, MlsGroup},
-    retry::Retry,
     retry_async,
     storage::{group::StoredGroup, group_message::StoredGroupMessage},
     Client, XmtpApi,
@@ -76,7 +75,7 @@ where
     ) -> Result<MlsGroup, ClientError> {
         let welcome_v1 = extract_welcome_message(welcome)?;
         let creation_result = retry_async!(
-            Retry::default(),
+            self.api_client.retry_strategy(),
             (async {
                 tracing::info!("Trying to process streamed welcome");
                 let welcome_v1 = welcome_v1.clone();
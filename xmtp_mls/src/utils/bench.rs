@@ -3,9 +3,21 @@
 //! benchmarks.
 #![allow(clippy::unwrap_used)]
 
-use crate::{builder::ClientBuilder, Client};
+use crate::{
+    builder::ClientBuilder,
+    configuration::GRPC_DATA_LIMIT,
+    groups::{
+        chunk_welcomes, encrypt_welcomes,
+        intents::Installation,
+        ratchet_tree::{fetch_ratchet_tree, publish_ratchet_tree},
+        MlsGroup,
+    },
+    identity_updates::load_identity_updates,
+    Client,
+};
 use indicatif::{ProgressBar, ProgressStyle};
 use once_cell::sync::OnceCell;
+use openmls::prelude::tls_codec::Serialize as _;
 use serde::{Deserialize, Serialize};
 use std::sync::Once;
 use thiserror::Error;
@@ -205,3 +217,75 @@ pub async fn create_identities_if_dont_exist(
     println!("Wrote {identities} to {}", file_path(is_dev_network));
     addresses
 }
+
+/// Looks up the installation of each identity. Bench identities have a single installation
+pub async fn installation_ids(
+    client: &Client<TestClient>,
+    identities: &[Identity],
+) -> Vec<Vec<u8>> {
+    let conn = client.store().conn().unwrap();
+    let inbox_ids = identities.iter().map(|i| i.inbox_id.clone()).collect();
+    load_identity_updates(&client.api_client, &conn, inbox_ids)
+        .await
+        .unwrap();
+
+    let mut installation_ids = Vec::with_capacity(identities.len());
+    for identity in identities {
+        let state = client
+            .get_association_state(&conn, &identity.inbox_id, None)
+            .await
+            .unwrap();
+        installation_ids.extend(state.installation_ids());
+    }
+    installation_ids
+}
+
+/// Fetches and verifies key packages in batches, the way adding members to a group does.
+/// Returns how many key packages were fetched
+pub async fn fetch_key_packages(
+    client: &Client<TestClient>,
+    installation_ids: Vec<Vec<u8>>,
+) -> usize {
+    client
+        .get_key_packages_for_installation_ids(installation_ids)
+        .await
+        .unwrap()
+        .len()
+}
+
+/// The TLS serialized ratchet tree of a group, as it is sent with welcomes
+pub fn export_ratchet_tree(client: &Client<TestClient>, group: &MlsGroup) -> Vec<u8> {
+    let provider = client.mls_provider().unwrap();
+    group
+        .load_mls_group(&provider)
+        .unwrap()
+        .export_ratchet_tree()
+        .tls_serialize_detached()
+        .unwrap()
+}
+
+/// Publishes a ratchet tree out of band and fetches it back, as the sender and a new member of
+/// a large group do
+pub async fn publish_and_fetch_ratchet_tree(client: &Client<TestClient>, ratchet_tree: &[u8]) {
+    let reference = publish_ratchet_tree(client, ratchet_tree).await.unwrap();
+    fetch_ratchet_tree(client, reference).await.unwrap();
+}
+
+/// Encrypts a welcome to each HPKE key and packs the welcomes into chunks, the way a commit
+/// sends them. Returns the number of chunks, each of which is one request to the node
+pub fn encrypt_and_chunk_welcomes(
+    welcome_payload: &[u8],
+    hpke_public_keys: &[Vec<u8>],
+    ratchet_tree_out_of_band: bool,
+) -> usize {
+    let installations = hpke_public_keys
+        .iter()
+        .map(|key| Installation {
+            installation_key: key.clone(),
+            hpke_public_key: key.clone(),
+        })
+        .collect();
+    let welcomes =
+        encrypt_welcomes(welcome_payload, installations, ratchet_tree_out_of_band).unwrap();
+    chunk_welcomes(welcomes, GRPC_DATA_LIMIT).len()
+}
@@ -25,12 +25,18 @@ use crate::{
 #[cfg(feature = "http-api")]
 use xmtp_api_http::XmtpHttpApiClient;
 
-#[cfg(not(feature = "http-api"))]
+#[cfg(feature = "memory-api")]
+use xmtp_api_memory::{MemoryApiClient, MemoryNode};
+
+#[cfg(not(any(feature = "http-api", feature = "memory-api")))]
 pub type TestClient = GrpcClient;
 
-#[cfg(feature = "http-api")]
+#[cfg(all(feature = "http-api", not(feature = "memory-api")))]
 pub type TestClient = XmtpHttpApiClient;
 
+#[cfg(feature = "memory-api")]
+pub type TestClient = MemoryApiClient;
+
 pub fn rand_string() -> String {
     Alphanumeric.sample_string(&mut rand::thread_rng(), 24)
 }
@@ -64,6 +70,30 @@ impl XmtpTestClient for XmtpHttpApiClient {
     }
 }
 
+/// Every test client in the process connects to the same in-memory node,
+/// just like they would all share the local docker node.
+#[cfg(feature = "memory-api")]
+fn shared_memory_node() -> Arc<MemoryNode> {
+    static NODE: std::sync::OnceLock<Arc<MemoryNode>> = std::sync::OnceLock::new();
+    NODE.get_or_init(|| {
+        Arc::new(MemoryNode::new(MockSmartContractSignatureVerifier::new(
+            true,
+        )))
+    })
+    .clone()
+}
+
+#[cfg(feature = "memory-api")]
+impl XmtpTestClient for MemoryApiClient {
+    async fn create_local() -> Self {
+        MemoryApiClient::new(shared_memory_node())
+    }
+
+    async fn create_dev() -> Self {
+        MemoryApiClient::new(shared_memory_node())
+    }
+}
+
 impl XmtpTestClient for GrpcClient {
     async fn create_local() -> Self {
         GrpcClient::create("http://localhost:5556".into(), false)
@@ -18,7 +18,7 @@ use crate::xmtp::mls::api::v1::{
     SubscribeWelcomeMessagesRequest, UploadKeyPackageRequest, WelcomeMessage,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
     SetupCreateChannelError,
     SetupTLSConfigError,
@@ -49,6 +49,10 @@ impl Error {
         self.source = Some(source.into());
         self
     }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
 }
 
 impl fmt::Debug for Error {
@@ -34,6 +34,35 @@ pub struct SmartContractWalletSignature {
     #[prost(bytes="vec", tag="3")]
     pub signature: ::prost::alloc::vec::Vec<u8>,
 }
+/// WebAuthn assertion made with a passkey
+/// The challenge in the client data is the SHA-256 hash of the signature text
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebAuthnSignature {
+    /// SEC1 encoded P-256 public key of the passkey
+    #[prost(bytes="vec", tag="1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    /// DER or \[R || S\] encoded ECDSA signature
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// Authenticator data returned by the authenticator
+    #[prost(bytes="vec", tag="3")]
+    pub authenticator_data: ::prost::alloc::vec::Vec<u8>,
+    /// The client data JSON, exactly as it was signed
+    #[prost(bytes="vec", tag="4")]
+    pub client_data_json: ::prost::alloc::vec::Vec<u8>,
+}
+/// Ed25519 signature made by a Solana wallet over the signature text
+/// (off-chain message signing). The public key is the base58 decoded address.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SolanaSignature {
+    /// CAIP-10 account id of the wallet, e.g. solana:<genesis hash>:<address>
+    #[prost(string, tag="1")]
+    pub account_id: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
 /// An existing address on xmtpv2 may have already signed a legacy identity key
 /// of type SignedPublicKey via the 'Create Identity' signature.
 /// For migration to xmtpv3, the legacy key is permitted to sign on behalf of the
@@ -57,7 +86,7 @@ pub struct Signature {
     ///     recoverable, or specified as a field.
     /// 2. The signer certifies that the signing payload is correct. The payload
     ///     must be inferred from the context in which the signature is provided.
-    #[prost(oneof="signature::Signature", tags="1, 2, 3, 4")]
+    #[prost(oneof="signature::Signature", tags="1, 2, 3, 4, 5, 6")]
     pub signature: ::core::option::Option<signature::Signature>,
 }
 /// Nested message and enum types in `Signature`.
@@ -78,13 +107,17 @@ pub mod signature {
         InstallationKey(super::RecoverableEd25519Signature),
         #[prost(message, tag="4")]
         DelegatedErc191(super::LegacyDelegatedSignature),
+        #[prost(message, tag="5")]
+        Webauthn(super::WebAuthnSignature),
+        #[prost(message, tag="6")]
+        Solana(super::SolanaSignature),
     }
 }
 /// The identifier for a member of an XID
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MemberIdentifier {
-    #[prost(oneof="member_identifier::Kind", tags="1, 2")]
+    #[prost(oneof="member_identifier::Kind", tags="1, 2, 3")]
     pub kind: ::core::option::Option<member_identifier::Kind>,
 }
 /// Nested message and enum types in `MemberIdentifier`.
@@ -96,6 +129,10 @@ pub mod member_identifier {
         Address(::prost::alloc::string::String),
         #[prost(bytes, tag="2")]
         InstallationPublicKey(::prost::alloc::vec::Vec<u8>),
+        /// SEC1 encoded P-256 public key of a passkey. Members are identified by
+        /// the compressed encoding.
+        #[prost(bytes, tag="3")]
+        Passkey(::prost::alloc::vec::Vec<u8>),
     }
 }
 /// single member that optionally indicates the member that added them
@@ -228,6 +265,46 @@ pub struct AssociationStateDiff {
     #[prost(message, repeated, tag="2")]
     pub removed_members: ::prost::alloc::vec::Vec<MemberIdentifier>,
 }
+/// An identity action that is waiting on signatures, along with the member
+/// expected to sign for each of its signature fields.
+/// The signatures of the action itself are left unset
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingIdentityAction {
+    #[prost(message, optional, tag="1")]
+    pub action: ::core::option::Option<IdentityAction>,
+    #[prost(message, optional, tag="2")]
+    pub initial_address: ::core::option::Option<MemberIdentifier>,
+    #[prost(message, optional, tag="3")]
+    pub existing_member: ::core::option::Option<MemberIdentifier>,
+    #[prost(message, optional, tag="4")]
+    pub new_member: ::core::option::Option<MemberIdentifier>,
+    #[prost(message, optional, tag="5")]
+    pub recovery_address: ::core::option::Option<MemberIdentifier>,
+}
+/// A signature collected for a signature request, keyed by its signer
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectedSignature {
+    #[prost(message, optional, tag="1")]
+    pub signer: ::core::option::Option<MemberIdentifier>,
+    #[prost(message, optional, tag="2")]
+    pub signature: ::core::option::Option<Signature>,
+}
+/// A signature request that is still collecting signatures, persisted so that
+/// it can be resumed by the client that created it
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignatureRequest {
+    #[prost(string, tag="1")]
+    pub inbox_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub client_timestamp_ns: u64,
+    #[prost(message, repeated, tag="3")]
+    pub pending_actions: ::prost::alloc::vec::Vec<PendingIdentityAction>,
+    #[prost(message, repeated, tag="4")]
+    pub signatures: ::prost::alloc::vec::Vec<CollectedSignature>,
+}
 /// Encoded file descriptor set for the `xmtp.identity.associations` package
 pub const FILE_DESCRIPTOR_SET: &[u8] = &[
     0x0a, 0xc3, 0x19, 0x0a, 0x25, 0x69, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x74, 0x79, 0x2f, 0x61, 0x73,
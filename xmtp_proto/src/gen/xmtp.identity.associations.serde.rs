@@ -495,6 +495,114 @@ impl<'de> serde::Deserialize<'de> for ChangeRecoveryAddress {
         deserializer.deserialize_struct("xmtp.identity.associations.ChangeRecoveryAddress", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for CollectedSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.signer.is_some() {
+            len += 1;
+        }
+        if self.signature.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.CollectedSignature", len)?;
+        if let Some(v) = self.signer.as_ref() {
+            struct_ser.serialize_field("signer", v)?;
+        }
+        if let Some(v) = self.signature.as_ref() {
+            struct_ser.serialize_field("signature", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CollectedSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "signer",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Signer,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "signer" => Ok(GeneratedField::Signer),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CollectedSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.identity.associations.CollectedSignature")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<CollectedSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut signer__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Signer => {
+                            if signer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signer"));
+                            }
+                            signer__ = map_.next_value()?;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = map_.next_value()?;
+                        }
+                    }
+                }
+                Ok(CollectedSignature {
+                    signer: signer__,
+                    signature: signature__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.identity.associations.CollectedSignature", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for CreateInbox {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -1156,6 +1264,11 @@ impl serde::Serialize for MemberIdentifier {
                     #[allow(clippy::needless_borrows_for_generic_args)]
                     struct_ser.serialize_field("installationPublicKey", pbjson::private::base64::encode(&v).as_str())?;
                 }
+                member_identifier::Kind::Passkey(v) => {
+                    #[allow(clippy::needless_borrow)]
+                    #[allow(clippy::needless_borrows_for_generic_args)]
+                    struct_ser.serialize_field("passkey", pbjson::private::base64::encode(&v).as_str())?;
+                }
             }
         }
         struct_ser.end()
@@ -1171,12 +1284,14 @@ impl<'de> serde::Deserialize<'de> for MemberIdentifier {
             "address",
             "installation_public_key",
             "installationPublicKey",
+            "passkey",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Address,
             InstallationPublicKey,
+            Passkey,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1200,6 +1315,7 @@ impl<'de> serde::Deserialize<'de> for MemberIdentifier {
                         match value {
                             "address" => Ok(GeneratedField::Address),
                             "installationPublicKey" | "installation_public_key" => Ok(GeneratedField::InstallationPublicKey),
+                            "passkey" => Ok(GeneratedField::Passkey),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1234,6 +1350,12 @@ impl<'de> serde::Deserialize<'de> for MemberIdentifier {
                             }
                             kind__ = map_.next_value::<::std::option::Option<::pbjson::private::BytesDeserialize<_>>>()?.map(|x| member_identifier::Kind::InstallationPublicKey(x.0));
                         }
+                        GeneratedField::Passkey => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("passkey"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<::pbjson::private::BytesDeserialize<_>>>()?.map(|x| member_identifier::Kind::Passkey(x.0));
+                        }
                     }
                 }
                 Ok(MemberIdentifier {
@@ -1352,6 +1474,169 @@ impl<'de> serde::Deserialize<'de> for MemberMap {
         deserializer.deserialize_struct("xmtp.identity.associations.MemberMap", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for PendingIdentityAction {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.action.is_some() {
+            len += 1;
+        }
+        if self.initial_address.is_some() {
+            len += 1;
+        }
+        if self.existing_member.is_some() {
+            len += 1;
+        }
+        if self.new_member.is_some() {
+            len += 1;
+        }
+        if self.recovery_address.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.PendingIdentityAction", len)?;
+        if let Some(v) = self.action.as_ref() {
+            struct_ser.serialize_field("action", v)?;
+        }
+        if let Some(v) = self.initial_address.as_ref() {
+            struct_ser.serialize_field("initialAddress", v)?;
+        }
+        if let Some(v) = self.existing_member.as_ref() {
+            struct_ser.serialize_field("existingMember", v)?;
+        }
+        if let Some(v) = self.new_member.as_ref() {
+            struct_ser.serialize_field("newMember", v)?;
+        }
+        if let Some(v) = self.recovery_address.as_ref() {
+            struct_ser.serialize_field("recoveryAddress", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for PendingIdentityAction {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "action",
+            "initial_address",
+            "initialAddress",
+            "existing_member",
+            "existingMember",
+            "new_member",
+            "newMember",
+            "recovery_address",
+            "recoveryAddress",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Action,
+            InitialAddress,
+            ExistingMember,
+            NewMember,
+            RecoveryAddress,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "action" => Ok(GeneratedField::Action),
+                            "initialAddress" | "initial_address" => Ok(GeneratedField::InitialAddress),
+                            "existingMember" | "existing_member" => Ok(GeneratedField::ExistingMember),
+                            "newMember" | "new_member" => Ok(GeneratedField::NewMember),
+                            "recoveryAddress" | "recovery_address" => Ok(GeneratedField::RecoveryAddress),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PendingIdentityAction;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.identity.associations.PendingIdentityAction")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<PendingIdentityAction, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut action__ = None;
+                let mut initial_address__ = None;
+                let mut existing_member__ = None;
+                let mut new_member__ = None;
+                let mut recovery_address__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Action => {
+                            if action__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("action"));
+                            }
+                            action__ = map_.next_value()?;
+                        }
+                        GeneratedField::InitialAddress => {
+                            if initial_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("initialAddress"));
+                            }
+                            initial_address__ = map_.next_value()?;
+                        }
+                        GeneratedField::ExistingMember => {
+                            if existing_member__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("existingMember"));
+                            }
+                            existing_member__ = map_.next_value()?;
+                        }
+                        GeneratedField::NewMember => {
+                            if new_member__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("newMember"));
+                            }
+                            new_member__ = map_.next_value()?;
+                        }
+                        GeneratedField::RecoveryAddress => {
+                            if recovery_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("recoveryAddress"));
+                            }
+                            recovery_address__ = map_.next_value()?;
+                        }
+                    }
+                }
+                Ok(PendingIdentityAction {
+                    action: action__,
+                    initial_address: initial_address__,
+                    existing_member: existing_member__,
+                    new_member: new_member__,
+                    recovery_address: recovery_address__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.identity.associations.PendingIdentityAction", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for RecoverableEcdsaSignature {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -1700,6 +1985,12 @@ impl serde::Serialize for Signature {
                 signature::Signature::DelegatedErc191(v) => {
                     struct_ser.serialize_field("delegatedErc191", v)?;
                 }
+                signature::Signature::Webauthn(v) => {
+                    struct_ser.serialize_field("webauthn", v)?;
+                }
+                signature::Signature::Solana(v) => {
+                    struct_ser.serialize_field("solana", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -1720,6 +2011,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
             "installationKey",
             "delegated_erc_191",
             "delegatedErc191",
+            "webauthn",
+            "solana",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1728,6 +2021,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
             Erc6492,
             InstallationKey,
             DelegatedErc191,
+            Webauthn,
+            Solana,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1753,6 +2048,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "erc6492" | "erc_6492" => Ok(GeneratedField::Erc6492),
                             "installationKey" | "installation_key" => Ok(GeneratedField::InstallationKey),
                             "delegatedErc191" | "delegated_erc_191" => Ok(GeneratedField::DelegatedErc191),
+                            "webauthn" => Ok(GeneratedField::Webauthn),
+                            "solana" => Ok(GeneratedField::Solana),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1801,6 +2098,20 @@ impl<'de> serde::Deserialize<'de> for Signature {
                                 return Err(serde::de::Error::duplicate_field("delegatedErc191"));
                             }
                             signature__ = map_.next_value::<::std::option::Option<_>>()?.map(signature::Signature::DelegatedErc191)
+;
+                        }
+                        GeneratedField::Webauthn => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("webauthn"));
+                            }
+                            signature__ = map_.next_value::<::std::option::Option<_>>()?.map(signature::Signature::Webauthn)
+;
+                        }
+                        GeneratedField::Solana => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("solana"));
+                            }
+                            signature__ = map_.next_value::<::std::option::Option<_>>()?.map(signature::Signature::Solana)
 ;
                         }
                     }
@@ -1813,7 +2124,7 @@ impl<'de> serde::Deserialize<'de> for Signature {
         deserializer.deserialize_struct("xmtp.identity.associations.Signature", FIELDS, GeneratedVisitor)
     }
 }
-impl serde::Serialize for SmartContractWalletSignature {
+impl serde::Serialize for SignatureRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -1821,51 +2132,58 @@ impl serde::Serialize for SmartContractWalletSignature {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.account_id.is_empty() {
+        if !self.inbox_id.is_empty() {
             len += 1;
         }
-        if self.block_number != 0 {
+        if self.client_timestamp_ns != 0 {
             len += 1;
         }
-        if !self.signature.is_empty() {
+        if !self.pending_actions.is_empty() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.SmartContractWalletSignature", len)?;
-        if !self.account_id.is_empty() {
-            struct_ser.serialize_field("accountId", &self.account_id)?;
+        if !self.signatures.is_empty() {
+            len += 1;
         }
-        if self.block_number != 0 {
-            #[allow(clippy::needless_borrow)]
-            #[allow(clippy::needless_borrows_for_generic_args)]
-            struct_ser.serialize_field("blockNumber", ToString::to_string(&self.block_number).as_str())?;
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.SignatureRequest", len)?;
+        if !self.inbox_id.is_empty() {
+            struct_ser.serialize_field("inboxId", &self.inbox_id)?;
         }
-        if !self.signature.is_empty() {
+        if self.client_timestamp_ns != 0 {
             #[allow(clippy::needless_borrow)]
             #[allow(clippy::needless_borrows_for_generic_args)]
-            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+            struct_ser.serialize_field("clientTimestampNs", ToString::to_string(&self.client_timestamp_ns).as_str())?;
+        }
+        if !self.pending_actions.is_empty() {
+            struct_ser.serialize_field("pendingActions", &self.pending_actions)?;
+        }
+        if !self.signatures.is_empty() {
+            struct_ser.serialize_field("signatures", &self.signatures)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for SmartContractWalletSignature {
+impl<'de> serde::Deserialize<'de> for SignatureRequest {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "account_id",
-            "accountId",
-            "block_number",
-            "blockNumber",
-            "signature",
+            "inbox_id",
+            "inboxId",
+            "client_timestamp_ns",
+            "clientTimestampNs",
+            "pending_actions",
+            "pendingActions",
+            "signatures",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            AccountId,
-            BlockNumber,
-            Signature,
+            InboxId,
+            ClientTimestampNs,
+            PendingActions,
+            Signatures,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1887,9 +2205,10 @@ impl<'de> serde::Deserialize<'de> for SmartContractWalletSignature {
                         E: serde::de::Error,
                     {
                         match value {
-                            "accountId" | "account_id" => Ok(GeneratedField::AccountId),
-                            "blockNumber" | "block_number" => Ok(GeneratedField::BlockNumber),
-                            "signature" => Ok(GeneratedField::Signature),
+                            "inboxId" | "inbox_id" => Ok(GeneratedField::InboxId),
+                            "clientTimestampNs" | "client_timestamp_ns" => Ok(GeneratedField::ClientTimestampNs),
+                            "pendingActions" | "pending_actions" => Ok(GeneratedField::PendingActions),
+                            "signatures" => Ok(GeneratedField::Signatures),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1899,13 +2218,154 @@ impl<'de> serde::Deserialize<'de> for SmartContractWalletSignature {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = SmartContractWalletSignature;
+            type Value = SignatureRequest;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct xmtp.identity.associations.SmartContractWalletSignature")
+                formatter.write_str("struct xmtp.identity.associations.SignatureRequest")
             }
 
-            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SmartContractWalletSignature, V::Error>
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SignatureRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut inbox_id__ = None;
+                let mut client_timestamp_ns__ = None;
+                let mut pending_actions__ = None;
+                let mut signatures__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::InboxId => {
+                            if inbox_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inboxId"));
+                            }
+                            inbox_id__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::ClientTimestampNs => {
+                            if client_timestamp_ns__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientTimestampNs"));
+                            }
+                            client_timestamp_ns__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::PendingActions => {
+                            if pending_actions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("pendingActions"));
+                            }
+                            pending_actions__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Signatures => {
+                            if signatures__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signatures"));
+                            }
+                            signatures__ = Some(map_.next_value()?);
+                        }
+                    }
+                }
+                Ok(SignatureRequest {
+                    inbox_id: inbox_id__.unwrap_or_default(),
+                    client_timestamp_ns: client_timestamp_ns__.unwrap_or_default(),
+                    pending_actions: pending_actions__.unwrap_or_default(),
+                    signatures: signatures__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.identity.associations.SignatureRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SmartContractWalletSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.account_id.is_empty() {
+            len += 1;
+        }
+        if self.block_number != 0 {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.SmartContractWalletSignature", len)?;
+        if !self.account_id.is_empty() {
+            struct_ser.serialize_field("accountId", &self.account_id)?;
+        }
+        if self.block_number != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("blockNumber", ToString::to_string(&self.block_number).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SmartContractWalletSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "account_id",
+            "accountId",
+            "block_number",
+            "blockNumber",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AccountId,
+            BlockNumber,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "accountId" | "account_id" => Ok(GeneratedField::AccountId),
+                            "blockNumber" | "block_number" => Ok(GeneratedField::BlockNumber),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SmartContractWalletSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.identity.associations.SmartContractWalletSignature")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SmartContractWalletSignature, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
@@ -1948,3 +2408,277 @@ impl<'de> serde::Deserialize<'de> for SmartContractWalletSignature {
         deserializer.deserialize_struct("xmtp.identity.associations.SmartContractWalletSignature", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for SolanaSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.account_id.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.SolanaSignature", len)?;
+        if !self.account_id.is_empty() {
+            struct_ser.serialize_field("accountId", &self.account_id)?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SolanaSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "account_id",
+            "accountId",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AccountId,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "accountId" | "account_id" => Ok(GeneratedField::AccountId),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SolanaSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.identity.associations.SolanaSignature")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SolanaSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut account_id__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::AccountId => {
+                            if account_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("accountId"));
+                            }
+                            account_id__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SolanaSignature {
+                    account_id: account_id__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.identity.associations.SolanaSignature", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        if !self.authenticator_data.is_empty() {
+            len += 1;
+        }
+        if !self.client_data_json.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.identity.associations.WebAuthnSignature", len)?;
+        if !self.public_key.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("publicKey", pbjson::private::base64::encode(&self.public_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        if !self.authenticator_data.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("authenticatorData", pbjson::private::base64::encode(&self.authenticator_data).as_str())?;
+        }
+        if !self.client_data_json.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("clientDataJson", pbjson::private::base64::encode(&self.client_data_json).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "public_key",
+            "publicKey",
+            "signature",
+            "authenticator_data",
+            "authenticatorData",
+            "client_data_json",
+            "clientDataJson",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+            AuthenticatorData,
+            ClientDataJson,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" | "public_key" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            "authenticatorData" | "authenticator_data" => Ok(GeneratedField::AuthenticatorData),
+                            "clientDataJson" | "client_data_json" => Ok(GeneratedField::ClientDataJson),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = WebAuthnSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.identity.associations.WebAuthnSignature")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<WebAuthnSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                let mut authenticator_data__ = None;
+                let mut client_data_json__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::AuthenticatorData => {
+                            if authenticator_data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("authenticatorData"));
+                            }
+                            authenticator_data__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ClientDataJson => {
+                            if client_data_json__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientDataJson"));
+                            }
+                            client_data_json__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(WebAuthnSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                    authenticator_data: authenticator_data__.unwrap_or_default(),
+                    client_data_json: client_data_json__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.identity.associations.WebAuthnSignature", FIELDS, GeneratedVisitor)
+    }
+}
@@ -207,6 +207,40 @@ pub mod update_permission_data {
         V1(V1),
     }
 }
+/// The data required to publish several group changes in a single commit
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchUpdateData {
+    #[prost(oneof="batch_update_data::Version", tags="1")]
+    pub version: ::core::option::Option<batch_update_data::Version>,
+}
+/// Nested message and enum types in `BatchUpdateData`.
+pub mod batch_update_data {
+    /// V1 of BatchUpdateData. Each change is the encoded data of its standalone
+    /// intent
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct V1 {
+        /// An encoded UpdateGroupMembershipData
+        #[prost(bytes="vec", optional, tag="1")]
+        pub membership: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+        /// Encoded UpdateMetadataData
+        #[prost(bytes="vec", repeated, tag="2")]
+        pub metadata: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+        /// Encoded UpdateAdminListsData
+        #[prost(bytes="vec", repeated, tag="3")]
+        pub admin_lists: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+        /// Encoded UpdatePermissionData
+        #[prost(bytes="vec", repeated, tag="4")]
+        pub permissions: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Version {
+        #[prost(message, tag="1")]
+        V1(V1),
+    }
+}
 /// Generic data-type for all post-commit actions
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -233,6 +267,12 @@ pub mod post_commit_action {
         pub installations: ::prost::alloc::vec::Vec<Installation>,
         #[prost(bytes="vec", tag="2")]
         pub welcome_message: ::prost::alloc::vec::Vec<u8>,
+        /// The welcome was built without the ratchet tree, which is sent separately
+        #[prost(bool, tag="3")]
+        pub ratchet_tree_out_of_band: bool,
+        /// The ratchet tree of the epoch the installations were added in, once the commit is merged
+        #[prost(bytes="vec", tag="4")]
+        pub ratchet_tree: ::prost::alloc::vec::Vec<u8>,
     }
     #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Oneof)]
@@ -250,6 +290,10 @@ pub enum AdminListUpdateType {
     RemoveAdmin = 2,
     AddSuperAdmin = 3,
     RemoveSuperAdmin = 4,
+    AddBan = 5,
+    RemoveBan = 6,
+    AddPendingRemove = 7,
+    RemovePendingRemove = 8,
 }
 impl AdminListUpdateType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -263,6 +307,10 @@ impl AdminListUpdateType {
             AdminListUpdateType::RemoveAdmin => "ADMIN_LIST_UPDATE_TYPE_REMOVE_ADMIN",
             AdminListUpdateType::AddSuperAdmin => "ADMIN_LIST_UPDATE_TYPE_ADD_SUPER_ADMIN",
             AdminListUpdateType::RemoveSuperAdmin => "ADMIN_LIST_UPDATE_TYPE_REMOVE_SUPER_ADMIN",
+            AdminListUpdateType::AddBan => "ADMIN_LIST_UPDATE_TYPE_ADD_BAN",
+            AdminListUpdateType::RemoveBan => "ADMIN_LIST_UPDATE_TYPE_REMOVE_BAN",
+            AdminListUpdateType::AddPendingRemove => "ADMIN_LIST_UPDATE_TYPE_ADD_PENDING_REMOVE",
+            AdminListUpdateType::RemovePendingRemove => "ADMIN_LIST_UPDATE_TYPE_REMOVE_PENDING_REMOVE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -273,6 +321,10 @@ impl AdminListUpdateType {
             "ADMIN_LIST_UPDATE_TYPE_REMOVE_ADMIN" => Some(Self::RemoveAdmin),
             "ADMIN_LIST_UPDATE_TYPE_ADD_SUPER_ADMIN" => Some(Self::AddSuperAdmin),
             "ADMIN_LIST_UPDATE_TYPE_REMOVE_SUPER_ADMIN" => Some(Self::RemoveSuperAdmin),
+            "ADMIN_LIST_UPDATE_TYPE_ADD_BAN" => Some(Self::AddBan),
+            "ADMIN_LIST_UPDATE_TYPE_REMOVE_BAN" => Some(Self::RemoveBan),
+            "ADMIN_LIST_UPDATE_TYPE_ADD_PENDING_REMOVE" => Some(Self::AddPendingRemove),
+            "ADMIN_LIST_UPDATE_TYPE_REMOVE_PENDING_REMOVE" => Some(Self::RemovePendingRemove),
             _ => None,
         }
     }
@@ -287,6 +339,9 @@ pub enum PermissionUpdateType {
     AddAdmin = 3,
     RemoveAdmin = 4,
     UpdateMetadata = 5,
+    SendMessage = 6,
+    JoinRequest = 7,
+    UpdateBanList = 8,
 }
 impl PermissionUpdateType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -301,6 +356,9 @@ impl PermissionUpdateType {
             PermissionUpdateType::AddAdmin => "PERMISSION_UPDATE_TYPE_ADD_ADMIN",
             PermissionUpdateType::RemoveAdmin => "PERMISSION_UPDATE_TYPE_REMOVE_ADMIN",
             PermissionUpdateType::UpdateMetadata => "PERMISSION_UPDATE_TYPE_UPDATE_METADATA",
+            PermissionUpdateType::SendMessage => "PERMISSION_UPDATE_TYPE_SEND_MESSAGE",
+            PermissionUpdateType::JoinRequest => "PERMISSION_UPDATE_TYPE_JOIN_REQUEST",
+            PermissionUpdateType::UpdateBanList => "PERMISSION_UPDATE_TYPE_UPDATE_BAN_LIST",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -312,6 +370,9 @@ impl PermissionUpdateType {
             "PERMISSION_UPDATE_TYPE_ADD_ADMIN" => Some(Self::AddAdmin),
             "PERMISSION_UPDATE_TYPE_REMOVE_ADMIN" => Some(Self::RemoveAdmin),
             "PERMISSION_UPDATE_TYPE_UPDATE_METADATA" => Some(Self::UpdateMetadata),
+            "PERMISSION_UPDATE_TYPE_SEND_MESSAGE" => Some(Self::SendMessage),
+            "PERMISSION_UPDATE_TYPE_JOIN_REQUEST" => Some(Self::JoinRequest),
+            "PERMISSION_UPDATE_TYPE_UPDATE_BAN_LIST" => Some(Self::UpdateBanList),
             _ => None,
         }
     }
@@ -402,6 +402,10 @@ impl serde::Serialize for AdminListUpdateType {
             Self::RemoveAdmin => "ADMIN_LIST_UPDATE_TYPE_REMOVE_ADMIN",
             Self::AddSuperAdmin => "ADMIN_LIST_UPDATE_TYPE_ADD_SUPER_ADMIN",
             Self::RemoveSuperAdmin => "ADMIN_LIST_UPDATE_TYPE_REMOVE_SUPER_ADMIN",
+            Self::AddBan => "ADMIN_LIST_UPDATE_TYPE_ADD_BAN",
+            Self::RemoveBan => "ADMIN_LIST_UPDATE_TYPE_REMOVE_BAN",
+            Self::AddPendingRemove => "ADMIN_LIST_UPDATE_TYPE_ADD_PENDING_REMOVE",
+            Self::RemovePendingRemove => "ADMIN_LIST_UPDATE_TYPE_REMOVE_PENDING_REMOVE",
         };
         serializer.serialize_str(variant)
     }
@@ -418,6 +422,10 @@ impl<'de> serde::Deserialize<'de> for AdminListUpdateType {
             "ADMIN_LIST_UPDATE_TYPE_REMOVE_ADMIN",
             "ADMIN_LIST_UPDATE_TYPE_ADD_SUPER_ADMIN",
             "ADMIN_LIST_UPDATE_TYPE_REMOVE_SUPER_ADMIN",
+            "ADMIN_LIST_UPDATE_TYPE_ADD_BAN",
+            "ADMIN_LIST_UPDATE_TYPE_REMOVE_BAN",
+            "ADMIN_LIST_UPDATE_TYPE_ADD_PENDING_REMOVE",
+            "ADMIN_LIST_UPDATE_TYPE_REMOVE_PENDING_REMOVE",
         ];
 
         struct GeneratedVisitor;
@@ -463,6 +471,10 @@ impl<'de> serde::Deserialize<'de> for AdminListUpdateType {
                     "ADMIN_LIST_UPDATE_TYPE_REMOVE_ADMIN" => Ok(AdminListUpdateType::RemoveAdmin),
                     "ADMIN_LIST_UPDATE_TYPE_ADD_SUPER_ADMIN" => Ok(AdminListUpdateType::AddSuperAdmin),
                     "ADMIN_LIST_UPDATE_TYPE_REMOVE_SUPER_ADMIN" => Ok(AdminListUpdateType::RemoveSuperAdmin),
+                    "ADMIN_LIST_UPDATE_TYPE_ADD_BAN" => Ok(AdminListUpdateType::AddBan),
+                    "ADMIN_LIST_UPDATE_TYPE_REMOVE_BAN" => Ok(AdminListUpdateType::RemoveBan),
+                    "ADMIN_LIST_UPDATE_TYPE_ADD_PENDING_REMOVE" => Ok(AdminListUpdateType::AddPendingRemove),
+                    "ADMIN_LIST_UPDATE_TYPE_REMOVE_PENDING_REMOVE" => Ok(AdminListUpdateType::RemovePendingRemove),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -470,6 +482,258 @@ impl<'de> serde::Deserialize<'de> for AdminListUpdateType {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for BatchUpdateData {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.version.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.database.BatchUpdateData", len)?;
+        if let Some(v) = self.version.as_ref() {
+            match v {
+                batch_update_data::Version::V1(v) => {
+                    struct_ser.serialize_field("v1", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for BatchUpdateData {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "v1",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            V1,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "v1" => Ok(GeneratedField::V1),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = BatchUpdateData;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.database.BatchUpdateData")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<BatchUpdateData, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::V1 => {
+                            if version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("v1"));
+                            }
+                            version__ = map_.next_value::<::std::option::Option<_>>()?.map(batch_update_data::Version::V1)
+;
+                        }
+                    }
+                }
+                Ok(BatchUpdateData {
+                    version: version__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.database.BatchUpdateData", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for batch_update_data::V1 {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.membership.is_some() {
+            len += 1;
+        }
+        if !self.metadata.is_empty() {
+            len += 1;
+        }
+        if !self.admin_lists.is_empty() {
+            len += 1;
+        }
+        if !self.permissions.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.database.BatchUpdateData.V1", len)?;
+        if let Some(v) = self.membership.as_ref() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("membership", pbjson::private::base64::encode(&v).as_str())?;
+        }
+        if !self.metadata.is_empty() {
+            struct_ser.serialize_field("metadata", &self.metadata.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if !self.admin_lists.is_empty() {
+            struct_ser.serialize_field("adminLists", &self.admin_lists.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if !self.permissions.is_empty() {
+            struct_ser.serialize_field("permissions", &self.permissions.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for batch_update_data::V1 {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "membership",
+            "metadata",
+            "admin_lists",
+            "adminLists",
+            "permissions",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Membership,
+            Metadata,
+            AdminLists,
+            Permissions,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "membership" => Ok(GeneratedField::Membership),
+                            "metadata" => Ok(GeneratedField::Metadata),
+                            "adminLists" | "admin_lists" => Ok(GeneratedField::AdminLists),
+                            "permissions" => Ok(GeneratedField::Permissions),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = batch_update_data::V1;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.database.BatchUpdateData.V1")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<batch_update_data::V1, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut membership__ = None;
+                let mut metadata__ = None;
+                let mut admin_lists__ = None;
+                let mut permissions__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Membership => {
+                            if membership__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("membership"));
+                            }
+                            membership__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::BytesDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::Metadata => {
+                            if metadata__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("metadata"));
+                            }
+                            metadata__ = 
+                                Some(map_.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::AdminLists => {
+                            if admin_lists__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("adminLists"));
+                            }
+                            admin_lists__ = 
+                                Some(map_.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::Permissions => {
+                            if permissions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("permissions"));
+                            }
+                            permissions__ = 
+                                Some(map_.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                    }
+                }
+                Ok(batch_update_data::V1 {
+                    membership: membership__,
+                    metadata: metadata__.unwrap_or_default(),
+                    admin_lists: admin_lists__.unwrap_or_default(),
+                    permissions: permissions__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.database.BatchUpdateData.V1", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for InstallationIds {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -658,6 +922,9 @@ impl serde::Serialize for PermissionUpdateType {
             Self::AddAdmin => "PERMISSION_UPDATE_TYPE_ADD_ADMIN",
             Self::RemoveAdmin => "PERMISSION_UPDATE_TYPE_REMOVE_ADMIN",
             Self::UpdateMetadata => "PERMISSION_UPDATE_TYPE_UPDATE_METADATA",
+            Self::SendMessage => "PERMISSION_UPDATE_TYPE_SEND_MESSAGE",
+            Self::JoinRequest => "PERMISSION_UPDATE_TYPE_JOIN_REQUEST",
+            Self::UpdateBanList => "PERMISSION_UPDATE_TYPE_UPDATE_BAN_LIST",
         };
         serializer.serialize_str(variant)
     }
@@ -675,6 +942,9 @@ impl<'de> serde::Deserialize<'de> for PermissionUpdateType {
             "PERMISSION_UPDATE_TYPE_ADD_ADMIN",
             "PERMISSION_UPDATE_TYPE_REMOVE_ADMIN",
             "PERMISSION_UPDATE_TYPE_UPDATE_METADATA",
+            "PERMISSION_UPDATE_TYPE_SEND_MESSAGE",
+            "PERMISSION_UPDATE_TYPE_JOIN_REQUEST",
+            "PERMISSION_UPDATE_TYPE_UPDATE_BAN_LIST",
         ];
 
         struct GeneratedVisitor;
@@ -721,6 +991,9 @@ impl<'de> serde::Deserialize<'de> for PermissionUpdateType {
                     "PERMISSION_UPDATE_TYPE_ADD_ADMIN" => Ok(PermissionUpdateType::AddAdmin),
                     "PERMISSION_UPDATE_TYPE_REMOVE_ADMIN" => Ok(PermissionUpdateType::RemoveAdmin),
                     "PERMISSION_UPDATE_TYPE_UPDATE_METADATA" => Ok(PermissionUpdateType::UpdateMetadata),
+                    "PERMISSION_UPDATE_TYPE_SEND_MESSAGE" => Ok(PermissionUpdateType::SendMessage),
+                    "PERMISSION_UPDATE_TYPE_JOIN_REQUEST" => Ok(PermissionUpdateType::JoinRequest),
+                    "PERMISSION_UPDATE_TYPE_UPDATE_BAN_LIST" => Ok(PermissionUpdateType::UpdateBanList),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -957,6 +1230,12 @@ impl serde::Serialize for post_commit_action::SendWelcomes {
         if !self.welcome_message.is_empty() {
             len += 1;
         }
+        if self.ratchet_tree_out_of_band {
+            len += 1;
+        }
+        if !self.ratchet_tree.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("xmtp.mls.database.PostCommitAction.SendWelcomes", len)?;
         if !self.installations.is_empty() {
             struct_ser.serialize_field("installations", &self.installations)?;
@@ -966,6 +1245,14 @@ impl serde::Serialize for post_commit_action::SendWelcomes {
             #[allow(clippy::needless_borrows_for_generic_args)]
             struct_ser.serialize_field("welcomeMessage", pbjson::private::base64::encode(&self.welcome_message).as_str())?;
         }
+        if self.ratchet_tree_out_of_band {
+            struct_ser.serialize_field("ratchetTreeOutOfBand", &self.ratchet_tree_out_of_band)?;
+        }
+        if !self.ratchet_tree.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("ratchetTree", pbjson::private::base64::encode(&self.ratchet_tree).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -979,12 +1266,18 @@ impl<'de> serde::Deserialize<'de> for post_commit_action::SendWelcomes {
             "installations",
             "welcome_message",
             "welcomeMessage",
+            "ratchet_tree_out_of_band",
+            "ratchetTreeOutOfBand",
+            "ratchet_tree",
+            "ratchetTree",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Installations,
             WelcomeMessage,
+            RatchetTreeOutOfBand,
+            RatchetTree,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1008,6 +1301,8 @@ impl<'de> serde::Deserialize<'de> for post_commit_action::SendWelcomes {
                         match value {
                             "installations" => Ok(GeneratedField::Installations),
                             "welcomeMessage" | "welcome_message" => Ok(GeneratedField::WelcomeMessage),
+                            "ratchetTreeOutOfBand" | "ratchet_tree_out_of_band" => Ok(GeneratedField::RatchetTreeOutOfBand),
+                            "ratchetTree" | "ratchet_tree" => Ok(GeneratedField::RatchetTree),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1029,6 +1324,8 @@ impl<'de> serde::Deserialize<'de> for post_commit_action::SendWelcomes {
             {
                 let mut installations__ = None;
                 let mut welcome_message__ = None;
+                let mut ratchet_tree_out_of_band__ = None;
+                let mut ratchet_tree__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Installations => {
@@ -1045,11 +1342,27 @@ impl<'de> serde::Deserialize<'de> for post_commit_action::SendWelcomes {
                                 Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::RatchetTreeOutOfBand => {
+                            if ratchet_tree_out_of_band__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ratchetTreeOutOfBand"));
+                            }
+                            ratchet_tree_out_of_band__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::RatchetTree => {
+                            if ratchet_tree__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ratchetTree"));
+                            }
+                            ratchet_tree__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
                     }
                 }
                 Ok(post_commit_action::SendWelcomes {
                     installations: installations__.unwrap_or_default(),
                     welcome_message: welcome_message__.unwrap_or_default(),
+                    ratchet_tree_out_of_band: ratchet_tree_out_of_band__.unwrap_or_default(),
+                    ratchet_tree: ratchet_tree__.unwrap_or_default(),
                 })
             }
         }
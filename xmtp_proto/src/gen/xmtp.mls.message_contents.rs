@@ -72,7 +72,7 @@ pub mod plaintext_envelope {
         /// produce different hashes. May be the sender timestamp.
         #[prost(string, tag="1")]
         pub idempotency_key: ::prost::alloc::string::String,
-        #[prost(oneof="v2::MessageType", tags="2, 3, 4")]
+        #[prost(oneof="v2::MessageType", tags="2, 3, 4, 5")]
         pub message_type: ::core::option::Option<v2::MessageType>,
     }
     /// Nested message and enum types in `V2`.
@@ -89,6 +89,10 @@ pub mod plaintext_envelope {
             /// Some other authorized installation sends a reply
             #[prost(message, tag="4")]
             Reply(super::super::MessageHistoryReply),
+            /// An installation shares its details with the other installations of
+            /// its inbox
+            #[prost(message, tag="5")]
+            InstallationMetadata(super::super::InstallationMetadata),
         }
     }
     /// Selector which declares which version of the EncodedContent this
@@ -143,6 +147,18 @@ pub mod message_history_key_type {
         Chacha20Poly1305(::prost::alloc::vec::Vec<u8>),
     }
 }
+/// Self-reported details of an installation, only sent to the other
+/// installations of its inbox
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InstallationMetadata {
+    #[prost(string, tag="1")]
+    pub device_name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub platform: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub created_at_ns: u64,
+}
 /// Recognized compression algorithms
 /// protolint:disable ENUM_FIELD_NAMES_ZERO_VALUE_END_WITH
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -171,6 +187,61 @@ impl Compression {
         }
     }
 }
+/// An invitation to join a group, issued by one of its admins
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupInvite {
+    #[prost(bytes="vec", tag="1")]
+    pub group_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub inviter_inbox_id: ::prost::alloc::string::String,
+    /// The installation that join requests must be sent to
+    #[prost(bytes="vec", tag="3")]
+    pub inviter_installation_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="4")]
+    pub expires_at_ns: u64,
+    /// Random value that makes every invite unique
+    #[prost(bytes="vec", tag="5")]
+    pub nonce: ::prost::alloc::vec::Vec<u8>,
+}
+/// An invite signed by the installation key of the inviter
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedGroupInvite {
+    #[prost(message, optional, tag="1")]
+    pub invite: ::core::option::Option<GroupInvite>,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A request to join a group, sent to the inviting installation over the welcome topic
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupJoinRequest {
+    #[prost(message, optional, tag="1")]
+    pub invite: ::core::option::Option<SignedGroupInvite>,
+    #[prost(string, tag="2")]
+    pub requester_inbox_id: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="3")]
+    pub requester_installation_key: ::prost::alloc::vec::Vec<u8>,
+    /// Signature of the requester installation key over the request
+    #[prost(bytes="vec", tag="4")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A welcome built without the ratchet tree extension. The ratchet tree is encrypted once and
+/// published to a separate welcome topic, shared by every installation added in the commit
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WelcomeWithRatchetTreeReference {
+    /// TLS serialized MLS welcome
+    #[prost(bytes="vec", tag="1")]
+    pub mls_welcome: ::prost::alloc::vec::Vec<u8>,
+    /// The welcome topic the encrypted ratchet tree was published to
+    #[prost(bytes="vec", tag="2")]
+    pub ratchet_tree_topic: ::prost::alloc::vec::Vec<u8>,
+    /// Symmetric key the ratchet tree was encrypted with
+    #[prost(bytes="vec", tag="3")]
+    pub ratchet_tree_key: ::prost::alloc::vec::Vec<u8>,
+}
 /// Contains a mapping of `inbox_id` -> `sequence_id` for all members of a group.
 /// Designed to be stored in the group context extension of the MLS group
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -256,6 +327,12 @@ pub struct GroupMutableMetadataV1 {
     /// Only super_admin can add/remove other super_admin
     #[prost(message, optional, tag="3")]
     pub super_admin_list: ::core::option::Option<Inboxes>,
+    /// Inboxes that were banned from the group and can not be added back
+    #[prost(message, optional, tag="4")]
+    pub ban_list: ::core::option::Option<Inboxes>,
+    /// Members that asked to leave the group and are waiting to be removed by an admin
+    #[prost(message, optional, tag="5")]
+    pub pending_remove_list: ::core::option::Option<Inboxes>,
 }
 /// Wrapper around a list of repeated Inbox Ids
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -287,12 +364,21 @@ pub struct PolicySet {
     pub remove_admin_policy: ::core::option::Option<PermissionsUpdatePolicy>,
     #[prost(message, optional, tag="6")]
     pub update_permissions_policy: ::core::option::Option<PermissionsUpdatePolicy>,
+    /// Who may send application messages. Every member may send when unset
+    #[prost(message, optional, tag="7")]
+    pub send_message_policy: ::core::option::Option<PermissionsUpdatePolicy>,
+    /// How join requests made with an invite are handled
+    #[prost(enumeration="JoinRequestPolicy", tag="8")]
+    pub join_request_policy: i32,
+    /// Who may add or remove inboxes from the ban list. Admins and super admins when unset
+    #[prost(message, optional, tag="9")]
+    pub update_ban_list_policy: ::core::option::Option<PermissionsUpdatePolicy>,
 }
 /// A policy that governs adding/removing members or installations
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MembershipPolicy {
-    #[prost(oneof="membership_policy::Kind", tags="1, 2, 3")]
+    #[prost(oneof="membership_policy::Kind", tags="1, 2, 3, 4, 5, 6")]
     pub kind: ::core::option::Option<membership_policy::Kind>,
 }
 /// Nested message and enum types in `MembershipPolicy`.
@@ -356,13 +442,22 @@ pub mod membership_policy {
         AndCondition(AndCondition),
         #[prost(message, tag="3")]
         AnyCondition(AnyCondition),
+        /// Allow if the actor is one of the listed inboxes
+        #[prost(message, tag="4")]
+        ActorInList(super::Inboxes),
+        /// Allow if every inbox added or removed is one of the listed inboxes
+        #[prost(message, tag="5")]
+        SubjectInList(super::Inboxes),
+        /// Allow if the commit adds or removes at most this many inboxes
+        #[prost(uint32, tag="6")]
+        MaxChanges(u32),
     }
 }
 /// A policy that governs updating metadata
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MetadataPolicy {
-    #[prost(oneof="metadata_policy::Kind", tags="1, 2, 3")]
+    #[prost(oneof="metadata_policy::Kind", tags="1, 2, 3, 4")]
     pub kind: ::core::option::Option<metadata_policy::Kind>,
 }
 /// Nested message and enum types in `MetadataPolicy`.
@@ -426,13 +521,16 @@ pub mod metadata_policy {
         AndCondition(AndCondition),
         #[prost(message, tag="3")]
         AnyCondition(AnyCondition),
+        /// Allow if the actor is one of the listed inboxes
+        #[prost(message, tag="4")]
+        ActorInList(super::Inboxes),
     }
 }
 /// A policy that governs updating permissions
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PermissionsUpdatePolicy {
-    #[prost(oneof="permissions_update_policy::Kind", tags="1, 2, 3")]
+    #[prost(oneof="permissions_update_policy::Kind", tags="1, 2, 3, 4")]
     pub kind: ::core::option::Option<permissions_update_policy::Kind>,
 }
 /// Nested message and enum types in `PermissionsUpdatePolicy`.
@@ -493,6 +591,43 @@ pub mod permissions_update_policy {
         AndCondition(AndCondition),
         #[prost(message, tag="3")]
         AnyCondition(AnyCondition),
+        /// Allow if the actor is one of the listed inboxes
+        #[prost(message, tag="4")]
+        ActorInList(super::Inboxes),
+    }
+}
+/// How join requests made with an invite are handled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum JoinRequestPolicy {
+    /// Treated as JOIN_REQUEST_POLICY_ADMIN_APPROVAL
+    Unspecified = 0,
+    Deny = 1,
+    AdminApproval = 2,
+    AutoApprove = 3,
+}
+impl JoinRequestPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            JoinRequestPolicy::Unspecified => "JOIN_REQUEST_POLICY_UNSPECIFIED",
+            JoinRequestPolicy::Deny => "JOIN_REQUEST_POLICY_DENY",
+            JoinRequestPolicy::AdminApproval => "JOIN_REQUEST_POLICY_ADMIN_APPROVAL",
+            JoinRequestPolicy::AutoApprove => "JOIN_REQUEST_POLICY_AUTO_APPROVE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "JOIN_REQUEST_POLICY_UNSPECIFIED" => Some(Self::Unspecified),
+            "JOIN_REQUEST_POLICY_DENY" => Some(Self::Deny),
+            "JOIN_REQUEST_POLICY_ADMIN_APPROVAL" => Some(Self::AdminApproval),
+            "JOIN_REQUEST_POLICY_AUTO_APPROVE" => Some(Self::AutoApprove),
+            _ => None,
+        }
     }
 }
 /// A group member and affected installation IDs
@@ -541,6 +676,9 @@ pub struct GroupUpdated {
     /// The metadata changes in the commit
     #[prost(message, repeated, tag="4")]
     pub metadata_field_changes: ::prost::alloc::vec::Vec<group_updated::MetadataFieldChange>,
+    /// The inboxes in removed_inboxes that had asked to leave the group
+    #[prost(message, repeated, tag="5")]
+    pub left_inboxes: ::prost::alloc::vec::Vec<group_updated::Inbox>,
 }
 /// Nested message and enum types in `GroupUpdated`.
 pub mod group_updated {
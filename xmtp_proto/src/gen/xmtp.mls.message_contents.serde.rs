@@ -574,6 +574,337 @@ impl<'de> serde::Deserialize<'de> for EncodedContent {
         deserializer.deserialize_struct("xmtp.mls.message_contents.EncodedContent", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for GroupInvite {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.group_id.is_empty() {
+            len += 1;
+        }
+        if !self.inviter_inbox_id.is_empty() {
+            len += 1;
+        }
+        if !self.inviter_installation_key.is_empty() {
+            len += 1;
+        }
+        if self.expires_at_ns != 0 {
+            len += 1;
+        }
+        if !self.nonce.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.GroupInvite", len)?;
+        if !self.group_id.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("groupId", pbjson::private::base64::encode(&self.group_id).as_str())?;
+        }
+        if !self.inviter_inbox_id.is_empty() {
+            struct_ser.serialize_field("inviterInboxId", &self.inviter_inbox_id)?;
+        }
+        if !self.inviter_installation_key.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("inviterInstallationKey", pbjson::private::base64::encode(&self.inviter_installation_key).as_str())?;
+        }
+        if self.expires_at_ns != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("expiresAtNs", ToString::to_string(&self.expires_at_ns).as_str())?;
+        }
+        if !self.nonce.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("nonce", pbjson::private::base64::encode(&self.nonce).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GroupInvite {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "group_id",
+            "groupId",
+            "inviter_inbox_id",
+            "inviterInboxId",
+            "inviter_installation_key",
+            "inviterInstallationKey",
+            "expires_at_ns",
+            "expiresAtNs",
+            "nonce",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            GroupId,
+            InviterInboxId,
+            InviterInstallationKey,
+            ExpiresAtNs,
+            Nonce,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "groupId" | "group_id" => Ok(GeneratedField::GroupId),
+                            "inviterInboxId" | "inviter_inbox_id" => Ok(GeneratedField::InviterInboxId),
+                            "inviterInstallationKey" | "inviter_installation_key" => Ok(GeneratedField::InviterInstallationKey),
+                            "expiresAtNs" | "expires_at_ns" => Ok(GeneratedField::ExpiresAtNs),
+                            "nonce" => Ok(GeneratedField::Nonce),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GroupInvite;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.GroupInvite")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<GroupInvite, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut group_id__ = None;
+                let mut inviter_inbox_id__ = None;
+                let mut inviter_installation_key__ = None;
+                let mut expires_at_ns__ = None;
+                let mut nonce__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::GroupId => {
+                            if group_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("groupId"));
+                            }
+                            group_id__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::InviterInboxId => {
+                            if inviter_inbox_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inviterInboxId"));
+                            }
+                            inviter_inbox_id__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::InviterInstallationKey => {
+                            if inviter_installation_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inviterInstallationKey"));
+                            }
+                            inviter_installation_key__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExpiresAtNs => {
+                            if expires_at_ns__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("expiresAtNs"));
+                            }
+                            expires_at_ns__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Nonce => {
+                            if nonce__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nonce"));
+                            }
+                            nonce__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(GroupInvite {
+                    group_id: group_id__.unwrap_or_default(),
+                    inviter_inbox_id: inviter_inbox_id__.unwrap_or_default(),
+                    inviter_installation_key: inviter_installation_key__.unwrap_or_default(),
+                    expires_at_ns: expires_at_ns__.unwrap_or_default(),
+                    nonce: nonce__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.GroupInvite", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GroupJoinRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.invite.is_some() {
+            len += 1;
+        }
+        if !self.requester_inbox_id.is_empty() {
+            len += 1;
+        }
+        if !self.requester_installation_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.GroupJoinRequest", len)?;
+        if let Some(v) = self.invite.as_ref() {
+            struct_ser.serialize_field("invite", v)?;
+        }
+        if !self.requester_inbox_id.is_empty() {
+            struct_ser.serialize_field("requesterInboxId", &self.requester_inbox_id)?;
+        }
+        if !self.requester_installation_key.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("requesterInstallationKey", pbjson::private::base64::encode(&self.requester_installation_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GroupJoinRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "invite",
+            "requester_inbox_id",
+            "requesterInboxId",
+            "requester_installation_key",
+            "requesterInstallationKey",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Invite,
+            RequesterInboxId,
+            RequesterInstallationKey,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "invite" => Ok(GeneratedField::Invite),
+                            "requesterInboxId" | "requester_inbox_id" => Ok(GeneratedField::RequesterInboxId),
+                            "requesterInstallationKey" | "requester_installation_key" => Ok(GeneratedField::RequesterInstallationKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GroupJoinRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.GroupJoinRequest")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<GroupJoinRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut invite__ = None;
+                let mut requester_inbox_id__ = None;
+                let mut requester_installation_key__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Invite => {
+                            if invite__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("invite"));
+                            }
+                            invite__ = map_.next_value()?;
+                        }
+                        GeneratedField::RequesterInboxId => {
+                            if requester_inbox_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("requesterInboxId"));
+                            }
+                            requester_inbox_id__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::RequesterInstallationKey => {
+                            if requester_installation_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("requesterInstallationKey"));
+                            }
+                            requester_installation_key__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(GroupJoinRequest {
+                    invite: invite__,
+                    requester_inbox_id: requester_inbox_id__.unwrap_or_default(),
+                    requester_installation_key: requester_installation_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.GroupJoinRequest", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for GroupMembership {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -981,6 +1312,12 @@ impl serde::Serialize for GroupMutableMetadataV1 {
         if self.super_admin_list.is_some() {
             len += 1;
         }
+        if self.ban_list.is_some() {
+            len += 1;
+        }
+        if self.pending_remove_list.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.GroupMutableMetadataV1", len)?;
         if !self.attributes.is_empty() {
             struct_ser.serialize_field("attributes", &self.attributes)?;
@@ -991,6 +1328,12 @@ impl serde::Serialize for GroupMutableMetadataV1 {
         if let Some(v) = self.super_admin_list.as_ref() {
             struct_ser.serialize_field("superAdminList", v)?;
         }
+        if let Some(v) = self.ban_list.as_ref() {
+            struct_ser.serialize_field("banList", v)?;
+        }
+        if let Some(v) = self.pending_remove_list.as_ref() {
+            struct_ser.serialize_field("pendingRemoveList", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -1006,6 +1349,10 @@ impl<'de> serde::Deserialize<'de> for GroupMutableMetadataV1 {
             "adminList",
             "super_admin_list",
             "superAdminList",
+            "ban_list",
+            "banList",
+            "pending_remove_list",
+            "pendingRemoveList",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1013,6 +1360,8 @@ impl<'de> serde::Deserialize<'de> for GroupMutableMetadataV1 {
             Attributes,
             AdminList,
             SuperAdminList,
+            BanList,
+            PendingRemoveList,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1037,6 +1386,8 @@ impl<'de> serde::Deserialize<'de> for GroupMutableMetadataV1 {
                             "attributes" => Ok(GeneratedField::Attributes),
                             "adminList" | "admin_list" => Ok(GeneratedField::AdminList),
                             "superAdminList" | "super_admin_list" => Ok(GeneratedField::SuperAdminList),
+                            "banList" | "ban_list" => Ok(GeneratedField::BanList),
+                            "pendingRemoveList" | "pending_remove_list" => Ok(GeneratedField::PendingRemoveList),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1059,6 +1410,8 @@ impl<'de> serde::Deserialize<'de> for GroupMutableMetadataV1 {
                 let mut attributes__ = None;
                 let mut admin_list__ = None;
                 let mut super_admin_list__ = None;
+                let mut ban_list__ = None;
+                let mut pending_remove_list__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Attributes => {
@@ -1081,12 +1434,26 @@ impl<'de> serde::Deserialize<'de> for GroupMutableMetadataV1 {
                             }
                             super_admin_list__ = map_.next_value()?;
                         }
+                        GeneratedField::BanList => {
+                            if ban_list__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("banList"));
+                            }
+                            ban_list__ = map_.next_value()?;
+                        }
+                        GeneratedField::PendingRemoveList => {
+                            if pending_remove_list__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("pendingRemoveList"));
+                            }
+                            pending_remove_list__ = map_.next_value()?;
+                        }
                     }
                 }
                 Ok(GroupMutableMetadataV1 {
                     attributes: attributes__.unwrap_or_default(),
                     admin_list: admin_list__,
                     super_admin_list: super_admin_list__,
+                    ban_list: ban_list__,
+                    pending_remove_list: pending_remove_list__,
                 })
             }
         }
@@ -1204,6 +1571,9 @@ impl serde::Serialize for GroupUpdated {
         if !self.metadata_field_changes.is_empty() {
             len += 1;
         }
+        if !self.left_inboxes.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.GroupUpdated", len)?;
         if !self.initiated_by_inbox_id.is_empty() {
             struct_ser.serialize_field("initiatedByInboxId", &self.initiated_by_inbox_id)?;
@@ -1217,6 +1587,9 @@ impl serde::Serialize for GroupUpdated {
         if !self.metadata_field_changes.is_empty() {
             struct_ser.serialize_field("metadataFieldChanges", &self.metadata_field_changes)?;
         }
+        if !self.left_inboxes.is_empty() {
+            struct_ser.serialize_field("leftInboxes", &self.left_inboxes)?;
+        }
         struct_ser.end()
     }
 }
@@ -1235,6 +1608,8 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
             "removedInboxes",
             "metadata_field_changes",
             "metadataFieldChanges",
+            "left_inboxes",
+            "leftInboxes",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1243,6 +1618,7 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
             AddedInboxes,
             RemovedInboxes,
             MetadataFieldChanges,
+            LeftInboxes,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1268,6 +1644,7 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
                             "addedInboxes" | "added_inboxes" => Ok(GeneratedField::AddedInboxes),
                             "removedInboxes" | "removed_inboxes" => Ok(GeneratedField::RemovedInboxes),
                             "metadataFieldChanges" | "metadata_field_changes" => Ok(GeneratedField::MetadataFieldChanges),
+                            "leftInboxes" | "left_inboxes" => Ok(GeneratedField::LeftInboxes),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1291,6 +1668,7 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
                 let mut added_inboxes__ = None;
                 let mut removed_inboxes__ = None;
                 let mut metadata_field_changes__ = None;
+                let mut left_inboxes__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::InitiatedByInboxId => {
@@ -1317,6 +1695,12 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
                             }
                             metadata_field_changes__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::LeftInboxes => {
+                            if left_inboxes__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("leftInboxes"));
+                            }
+                            left_inboxes__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(GroupUpdated {
@@ -1324,6 +1708,7 @@ impl<'de> serde::Deserialize<'de> for GroupUpdated {
                     added_inboxes: added_inboxes__.unwrap_or_default(),
                     removed_inboxes: removed_inboxes__.unwrap_or_default(),
                     metadata_field_changes: metadata_field_changes__.unwrap_or_default(),
+                    left_inboxes: left_inboxes__.unwrap_or_default(),
                 })
             }
         }
@@ -1702,36 +2087,244 @@ impl<'de> serde::Deserialize<'de> for Inboxes {
                 }
                 deserializer.deserialize_identifier(GeneratedVisitor)
             }
-        }
-        struct GeneratedVisitor;
-        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = Inboxes;
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Inboxes;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.Inboxes")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<Inboxes, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut inbox_ids__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::InboxIds => {
+                            if inbox_ids__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inboxIds"));
+                            }
+                            inbox_ids__ = Some(map_.next_value()?);
+                        }
+                    }
+                }
+                Ok(Inboxes {
+                    inbox_ids: inbox_ids__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.Inboxes", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for InstallationMetadata {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.device_name.is_empty() {
+            len += 1;
+        }
+        if !self.platform.is_empty() {
+            len += 1;
+        }
+        if self.created_at_ns != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.InstallationMetadata", len)?;
+        if !self.device_name.is_empty() {
+            struct_ser.serialize_field("deviceName", &self.device_name)?;
+        }
+        if !self.platform.is_empty() {
+            struct_ser.serialize_field("platform", &self.platform)?;
+        }
+        if self.created_at_ns != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("createdAtNs", ToString::to_string(&self.created_at_ns).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for InstallationMetadata {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "device_name",
+            "deviceName",
+            "platform",
+            "created_at_ns",
+            "createdAtNs",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            DeviceName,
+            Platform,
+            CreatedAtNs,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "deviceName" | "device_name" => Ok(GeneratedField::DeviceName),
+                            "platform" => Ok(GeneratedField::Platform),
+                            "createdAtNs" | "created_at_ns" => Ok(GeneratedField::CreatedAtNs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = InstallationMetadata;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.InstallationMetadata")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<InstallationMetadata, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut device_name__ = None;
+                let mut platform__ = None;
+                let mut created_at_ns__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::DeviceName => {
+                            if device_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("deviceName"));
+                            }
+                            device_name__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Platform => {
+                            if platform__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("platform"));
+                            }
+                            platform__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::CreatedAtNs => {
+                            if created_at_ns__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("createdAtNs"));
+                            }
+                            created_at_ns__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(InstallationMetadata {
+                    device_name: device_name__.unwrap_or_default(),
+                    platform: platform__.unwrap_or_default(),
+                    created_at_ns: created_at_ns__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.InstallationMetadata", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for JoinRequestPolicy {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "JOIN_REQUEST_POLICY_UNSPECIFIED",
+            Self::Deny => "JOIN_REQUEST_POLICY_DENY",
+            Self::AdminApproval => "JOIN_REQUEST_POLICY_ADMIN_APPROVAL",
+            Self::AutoApprove => "JOIN_REQUEST_POLICY_AUTO_APPROVE",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for JoinRequestPolicy {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "JOIN_REQUEST_POLICY_UNSPECIFIED",
+            "JOIN_REQUEST_POLICY_DENY",
+            "JOIN_REQUEST_POLICY_ADMIN_APPROVAL",
+            "JOIN_REQUEST_POLICY_AUTO_APPROVE",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = JoinRequestPolicy;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct xmtp.mls.message_contents.Inboxes")
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
             }
 
-            fn visit_map<V>(self, mut map_: V) -> std::result::Result<Inboxes, V::Error>
-                where
-                    V: serde::de::MapAccess<'de>,
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
             {
-                let mut inbox_ids__ = None;
-                while let Some(k) = map_.next_key()? {
-                    match k {
-                        GeneratedField::InboxIds => {
-                            if inbox_ids__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("inboxIds"));
-                            }
-                            inbox_ids__ = Some(map_.next_value()?);
-                        }
-                    }
+                match value {
+                    "JOIN_REQUEST_POLICY_UNSPECIFIED" => Ok(JoinRequestPolicy::Unspecified),
+                    "JOIN_REQUEST_POLICY_DENY" => Ok(JoinRequestPolicy::Deny),
+                    "JOIN_REQUEST_POLICY_ADMIN_APPROVAL" => Ok(JoinRequestPolicy::AdminApproval),
+                    "JOIN_REQUEST_POLICY_AUTO_APPROVE" => Ok(JoinRequestPolicy::AutoApprove),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
-                Ok(Inboxes {
-                    inbox_ids: inbox_ids__.unwrap_or_default(),
-                })
             }
         }
-        deserializer.deserialize_struct("xmtp.mls.message_contents.Inboxes", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_any(GeneratedVisitor)
     }
 }
 impl serde::Serialize for MembershipChange {
@@ -1890,6 +2483,15 @@ impl serde::Serialize for MembershipPolicy {
                 membership_policy::Kind::AnyCondition(v) => {
                     struct_ser.serialize_field("anyCondition", v)?;
                 }
+                membership_policy::Kind::ActorInList(v) => {
+                    struct_ser.serialize_field("actorInList", v)?;
+                }
+                membership_policy::Kind::SubjectInList(v) => {
+                    struct_ser.serialize_field("subjectInList", v)?;
+                }
+                membership_policy::Kind::MaxChanges(v) => {
+                    struct_ser.serialize_field("maxChanges", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -1907,6 +2509,12 @@ impl<'de> serde::Deserialize<'de> for MembershipPolicy {
             "andCondition",
             "any_condition",
             "anyCondition",
+            "actor_in_list",
+            "actorInList",
+            "subject_in_list",
+            "subjectInList",
+            "max_changes",
+            "maxChanges",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1914,6 +2522,9 @@ impl<'de> serde::Deserialize<'de> for MembershipPolicy {
             Base,
             AndCondition,
             AnyCondition,
+            ActorInList,
+            SubjectInList,
+            MaxChanges,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1938,6 +2549,9 @@ impl<'de> serde::Deserialize<'de> for MembershipPolicy {
                             "base" => Ok(GeneratedField::Base),
                             "andCondition" | "and_condition" => Ok(GeneratedField::AndCondition),
                             "anyCondition" | "any_condition" => Ok(GeneratedField::AnyCondition),
+                            "actorInList" | "actor_in_list" => Ok(GeneratedField::ActorInList),
+                            "subjectInList" | "subject_in_list" => Ok(GeneratedField::SubjectInList),
+                            "maxChanges" | "max_changes" => Ok(GeneratedField::MaxChanges),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1980,6 +2594,26 @@ impl<'de> serde::Deserialize<'de> for MembershipPolicy {
                             kind__ = map_.next_value::<::std::option::Option<_>>()?.map(membership_policy::Kind::AnyCondition)
 ;
                         }
+                        GeneratedField::ActorInList => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("actorInList"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<_>>()?.map(membership_policy::Kind::ActorInList)
+;
+                        }
+                        GeneratedField::SubjectInList => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("subjectInList"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<_>>()?.map(membership_policy::Kind::SubjectInList)
+;
+                        }
+                        GeneratedField::MaxChanges => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxChanges"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| membership_policy::Kind::MaxChanges(x.0));
+                        }
                     }
                 }
                 Ok(MembershipPolicy {
@@ -2612,6 +3246,9 @@ impl serde::Serialize for MetadataPolicy {
                 metadata_policy::Kind::AnyCondition(v) => {
                     struct_ser.serialize_field("anyCondition", v)?;
                 }
+                metadata_policy::Kind::ActorInList(v) => {
+                    struct_ser.serialize_field("actorInList", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -2629,6 +3266,8 @@ impl<'de> serde::Deserialize<'de> for MetadataPolicy {
             "andCondition",
             "any_condition",
             "anyCondition",
+            "actor_in_list",
+            "actorInList",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -2636,6 +3275,7 @@ impl<'de> serde::Deserialize<'de> for MetadataPolicy {
             Base,
             AndCondition,
             AnyCondition,
+            ActorInList,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -2660,6 +3300,7 @@ impl<'de> serde::Deserialize<'de> for MetadataPolicy {
                             "base" => Ok(GeneratedField::Base),
                             "andCondition" | "and_condition" => Ok(GeneratedField::AndCondition),
                             "anyCondition" | "any_condition" => Ok(GeneratedField::AnyCondition),
+                            "actorInList" | "actor_in_list" => Ok(GeneratedField::ActorInList),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -2700,6 +3341,13 @@ impl<'de> serde::Deserialize<'de> for MetadataPolicy {
                                 return Err(serde::de::Error::duplicate_field("anyCondition"));
                             }
                             kind__ = map_.next_value::<::std::option::Option<_>>()?.map(metadata_policy::Kind::AnyCondition)
+;
+                        }
+                        GeneratedField::ActorInList => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("actorInList"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<_>>()?.map(metadata_policy::Kind::ActorInList)
 ;
                         }
                     }
@@ -2999,6 +3647,9 @@ impl serde::Serialize for PermissionsUpdatePolicy {
                 permissions_update_policy::Kind::AnyCondition(v) => {
                     struct_ser.serialize_field("anyCondition", v)?;
                 }
+                permissions_update_policy::Kind::ActorInList(v) => {
+                    struct_ser.serialize_field("actorInList", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -3016,6 +3667,8 @@ impl<'de> serde::Deserialize<'de> for PermissionsUpdatePolicy {
             "andCondition",
             "any_condition",
             "anyCondition",
+            "actor_in_list",
+            "actorInList",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -3023,6 +3676,7 @@ impl<'de> serde::Deserialize<'de> for PermissionsUpdatePolicy {
             Base,
             AndCondition,
             AnyCondition,
+            ActorInList,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -3047,6 +3701,7 @@ impl<'de> serde::Deserialize<'de> for PermissionsUpdatePolicy {
                             "base" => Ok(GeneratedField::Base),
                             "andCondition" | "and_condition" => Ok(GeneratedField::AndCondition),
                             "anyCondition" | "any_condition" => Ok(GeneratedField::AnyCondition),
+                            "actorInList" | "actor_in_list" => Ok(GeneratedField::ActorInList),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -3087,6 +3742,13 @@ impl<'de> serde::Deserialize<'de> for PermissionsUpdatePolicy {
                                 return Err(serde::de::Error::duplicate_field("anyCondition"));
                             }
                             kind__ = map_.next_value::<::std::option::Option<_>>()?.map(permissions_update_policy::Kind::AnyCondition)
+;
+                        }
+                        GeneratedField::ActorInList => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("actorInList"));
+                            }
+                            kind__ = map_.next_value::<::std::option::Option<_>>()?.map(permissions_update_policy::Kind::ActorInList)
 ;
                         }
                     }
@@ -3611,6 +4273,9 @@ impl serde::Serialize for plaintext_envelope::V2 {
                 plaintext_envelope::v2::MessageType::Reply(v) => {
                     struct_ser.serialize_field("reply", v)?;
                 }
+                plaintext_envelope::v2::MessageType::InstallationMetadata(v) => {
+                    struct_ser.serialize_field("installationMetadata", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -3628,6 +4293,8 @@ impl<'de> serde::Deserialize<'de> for plaintext_envelope::V2 {
             "content",
             "request",
             "reply",
+            "installation_metadata",
+            "installationMetadata",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -3636,6 +4303,7 @@ impl<'de> serde::Deserialize<'de> for plaintext_envelope::V2 {
             Content,
             Request,
             Reply,
+            InstallationMetadata,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -3661,6 +4329,7 @@ impl<'de> serde::Deserialize<'de> for plaintext_envelope::V2 {
                             "content" => Ok(GeneratedField::Content),
                             "request" => Ok(GeneratedField::Request),
                             "reply" => Ok(GeneratedField::Reply),
+                            "installationMetadata" | "installation_metadata" => Ok(GeneratedField::InstallationMetadata),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -3708,6 +4377,13 @@ impl<'de> serde::Deserialize<'de> for plaintext_envelope::V2 {
                                 return Err(serde::de::Error::duplicate_field("reply"));
                             }
                             message_type__ = map_.next_value::<::std::option::Option<_>>()?.map(plaintext_envelope::v2::MessageType::Reply)
+;
+                        }
+                        GeneratedField::InstallationMetadata => {
+                            if message_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("installationMetadata"));
+                            }
+                            message_type__ = map_.next_value::<::std::option::Option<_>>()?.map(plaintext_envelope::v2::MessageType::InstallationMetadata)
 ;
                         }
                     }
@@ -3747,6 +4423,15 @@ impl serde::Serialize for PolicySet {
         if self.update_permissions_policy.is_some() {
             len += 1;
         }
+        if self.send_message_policy.is_some() {
+            len += 1;
+        }
+        if self.join_request_policy != 0 {
+            len += 1;
+        }
+        if self.update_ban_list_policy.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.PolicySet", len)?;
         if let Some(v) = self.add_member_policy.as_ref() {
             struct_ser.serialize_field("addMemberPolicy", v)?;
@@ -3766,6 +4451,17 @@ impl serde::Serialize for PolicySet {
         if let Some(v) = self.update_permissions_policy.as_ref() {
             struct_ser.serialize_field("updatePermissionsPolicy", v)?;
         }
+        if let Some(v) = self.send_message_policy.as_ref() {
+            struct_ser.serialize_field("sendMessagePolicy", v)?;
+        }
+        if self.join_request_policy != 0 {
+            let v = JoinRequestPolicy::try_from(self.join_request_policy)
+                .map_err(|_| serde::ser::Error::custom(format!("Invalid variant {}", self.join_request_policy)))?;
+            struct_ser.serialize_field("joinRequestPolicy", &v)?;
+        }
+        if let Some(v) = self.update_ban_list_policy.as_ref() {
+            struct_ser.serialize_field("updateBanListPolicy", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -3788,6 +4484,12 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
             "removeAdminPolicy",
             "update_permissions_policy",
             "updatePermissionsPolicy",
+            "send_message_policy",
+            "sendMessagePolicy",
+            "join_request_policy",
+            "joinRequestPolicy",
+            "update_ban_list_policy",
+            "updateBanListPolicy",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -3798,6 +4500,9 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
             AddAdminPolicy,
             RemoveAdminPolicy,
             UpdatePermissionsPolicy,
+            SendMessagePolicy,
+            JoinRequestPolicy,
+            UpdateBanListPolicy,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -3825,6 +4530,9 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
                             "addAdminPolicy" | "add_admin_policy" => Ok(GeneratedField::AddAdminPolicy),
                             "removeAdminPolicy" | "remove_admin_policy" => Ok(GeneratedField::RemoveAdminPolicy),
                             "updatePermissionsPolicy" | "update_permissions_policy" => Ok(GeneratedField::UpdatePermissionsPolicy),
+                            "sendMessagePolicy" | "send_message_policy" => Ok(GeneratedField::SendMessagePolicy),
+                            "joinRequestPolicy" | "join_request_policy" => Ok(GeneratedField::JoinRequestPolicy),
+                            "updateBanListPolicy" | "update_ban_list_policy" => Ok(GeneratedField::UpdateBanListPolicy),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -3850,6 +4558,9 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
                 let mut add_admin_policy__ = None;
                 let mut remove_admin_policy__ = None;
                 let mut update_permissions_policy__ = None;
+                let mut send_message_policy__ = None;
+                let mut join_request_policy__ = None;
+                let mut update_ban_list_policy__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::AddMemberPolicy => {
@@ -3890,6 +4601,24 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
                             }
                             update_permissions_policy__ = map_.next_value()?;
                         }
+                        GeneratedField::SendMessagePolicy => {
+                            if send_message_policy__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sendMessagePolicy"));
+                            }
+                            send_message_policy__ = map_.next_value()?;
+                        }
+                        GeneratedField::JoinRequestPolicy => {
+                            if join_request_policy__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("joinRequestPolicy"));
+                            }
+                            join_request_policy__ = Some(map_.next_value::<JoinRequestPolicy>()? as i32);
+                        }
+                        GeneratedField::UpdateBanListPolicy => {
+                            if update_ban_list_policy__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("updateBanListPolicy"));
+                            }
+                            update_ban_list_policy__ = map_.next_value()?;
+                        }
                     }
                 }
                 Ok(PolicySet {
@@ -3899,9 +4628,264 @@ impl<'de> serde::Deserialize<'de> for PolicySet {
                     add_admin_policy: add_admin_policy__,
                     remove_admin_policy: remove_admin_policy__,
                     update_permissions_policy: update_permissions_policy__,
+                    send_message_policy: send_message_policy__,
+                    join_request_policy: join_request_policy__.unwrap_or_default(),
+                    update_ban_list_policy: update_ban_list_policy__,
                 })
             }
         }
         deserializer.deserialize_struct("xmtp.mls.message_contents.PolicySet", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for SignedGroupInvite {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.invite.is_some() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.SignedGroupInvite", len)?;
+        if let Some(v) = self.invite.as_ref() {
+            struct_ser.serialize_field("invite", v)?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SignedGroupInvite {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "invite",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Invite,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "invite" => Ok(GeneratedField::Invite),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SignedGroupInvite;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.SignedGroupInvite")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SignedGroupInvite, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut invite__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Invite => {
+                            if invite__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("invite"));
+                            }
+                            invite__ = map_.next_value()?;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SignedGroupInvite {
+                    invite: invite__,
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.SignedGroupInvite", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for WelcomeWithRatchetTreeReference {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.mls_welcome.is_empty() {
+            len += 1;
+        }
+        if !self.ratchet_tree_topic.is_empty() {
+            len += 1;
+        }
+        if !self.ratchet_tree_key.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("xmtp.mls.message_contents.WelcomeWithRatchetTreeReference", len)?;
+        if !self.mls_welcome.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("mlsWelcome", pbjson::private::base64::encode(&self.mls_welcome).as_str())?;
+        }
+        if !self.ratchet_tree_topic.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("ratchetTreeTopic", pbjson::private::base64::encode(&self.ratchet_tree_topic).as_str())?;
+        }
+        if !self.ratchet_tree_key.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("ratchetTreeKey", pbjson::private::base64::encode(&self.ratchet_tree_key).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for WelcomeWithRatchetTreeReference {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "mls_welcome",
+            "mlsWelcome",
+            "ratchet_tree_topic",
+            "ratchetTreeTopic",
+            "ratchet_tree_key",
+            "ratchetTreeKey",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            MlsWelcome,
+            RatchetTreeTopic,
+            RatchetTreeKey,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "mlsWelcome" | "mls_welcome" => Ok(GeneratedField::MlsWelcome),
+                            "ratchetTreeTopic" | "ratchet_tree_topic" => Ok(GeneratedField::RatchetTreeTopic),
+                            "ratchetTreeKey" | "ratchet_tree_key" => Ok(GeneratedField::RatchetTreeKey),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = WelcomeWithRatchetTreeReference;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct xmtp.mls.message_contents.WelcomeWithRatchetTreeReference")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<WelcomeWithRatchetTreeReference, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut mls_welcome__ = None;
+                let mut ratchet_tree_topic__ = None;
+                let mut ratchet_tree_key__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::MlsWelcome => {
+                            if mls_welcome__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("mlsWelcome"));
+                            }
+                            mls_welcome__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::RatchetTreeTopic => {
+                            if ratchet_tree_topic__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ratchetTreeTopic"));
+                            }
+                            ratchet_tree_topic__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::RatchetTreeKey => {
+                            if ratchet_tree_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ratchetTreeKey"));
+                            }
+                            ratchet_tree_key__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(WelcomeWithRatchetTreeReference {
+                    mls_welcome: mls_welcome__.unwrap_or_default(),
+                    ratchet_tree_topic: ratchet_tree_topic__.unwrap_or_default(),
+                    ratchet_tree_key: ratchet_tree_key__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("xmtp.mls.message_contents.WelcomeWithRatchetTreeReference", FIELDS, GeneratedVisitor)
+    }
+}
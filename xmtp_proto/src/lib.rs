@@ -9,3 +9,143 @@ pub mod api_client;
 
 #[cfg(feature = "convert")]
 pub mod convert;
+
+/// The field numbers and enum values listed in `proto_changes/` are not in the proto repo yet, but
+/// they are already on the wire and in persisted data. Generating code that numbers them
+/// differently must fail here instead of silently breaking compatibility.
+#[cfg(all(test, feature = "proto_full"))]
+mod pending_schema_tests {
+    use prost::Message;
+
+    use crate::xmtp::{
+        identity::associations::{member_identifier, signature, MemberIdentifier, Signature},
+        mls::{
+            database::{post_commit_action, AdminListUpdateType, PermissionUpdateType},
+            message_contents::{
+                group_updated, membership_policy, metadata_policy, permissions_update_policy,
+                plaintext_envelope, GroupMutableMetadataV1, GroupUpdated, Inboxes,
+                JoinRequestPolicy, MembershipPolicy, MetadataPolicy, PermissionsUpdatePolicy,
+                PolicySet,
+            },
+        },
+    };
+
+    /// The field number of the first field in the encoded message
+    fn field_number(message: &impl Message) -> u64 {
+        let bytes = message.encode_to_vec();
+        let mut buf = bytes.as_slice();
+        prost::encoding::decode_varint(&mut buf).unwrap() >> 3
+    }
+
+    #[test]
+    fn identity_association_fields() {
+        let webauthn = Signature {
+            signature: Some(signature::Signature::Webauthn(Default::default())),
+        };
+        assert_eq!(field_number(&webauthn), 5);
+        let solana = Signature {
+            signature: Some(signature::Signature::Solana(Default::default())),
+        };
+        assert_eq!(field_number(&solana), 6);
+        let passkey = MemberIdentifier {
+            kind: Some(member_identifier::Kind::Passkey(vec![])),
+        };
+        assert_eq!(field_number(&passkey), 3);
+    }
+
+    #[test]
+    fn group_permission_fields() {
+        let send_message = PolicySet {
+            send_message_policy: Some(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(field_number(&send_message), 7);
+        let join_request = PolicySet {
+            join_request_policy: JoinRequestPolicy::Deny as i32,
+            ..Default::default()
+        };
+        assert_eq!(field_number(&join_request), 8);
+        let ban_list = PolicySet {
+            update_ban_list_policy: Some(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(field_number(&ban_list), 9);
+
+        let membership_kinds = [
+            membership_policy::Kind::ActorInList(Inboxes::default()),
+            membership_policy::Kind::SubjectInList(Inboxes::default()),
+            membership_policy::Kind::MaxChanges(0),
+        ];
+        for (kind, expected) in membership_kinds.into_iter().zip([4, 5, 6]) {
+            assert_eq!(
+                field_number(&MembershipPolicy { kind: Some(kind) }),
+                expected
+            );
+        }
+        let metadata = MetadataPolicy {
+            kind: Some(metadata_policy::Kind::ActorInList(Inboxes::default())),
+        };
+        assert_eq!(field_number(&metadata), 4);
+        let permissions = PermissionsUpdatePolicy {
+            kind: Some(permissions_update_policy::Kind::ActorInList(
+                Inboxes::default(),
+            )),
+        };
+        assert_eq!(field_number(&permissions), 4);
+
+        assert_eq!(JoinRequestPolicy::Unspecified as i32, 0);
+        assert_eq!(JoinRequestPolicy::Deny as i32, 1);
+        assert_eq!(JoinRequestPolicy::AdminApproval as i32, 2);
+        assert_eq!(JoinRequestPolicy::AutoApprove as i32, 3);
+    }
+
+    #[test]
+    fn group_state_fields() {
+        let ban_list = GroupMutableMetadataV1 {
+            ban_list: Some(Inboxes::default()),
+            ..Default::default()
+        };
+        assert_eq!(field_number(&ban_list), 4);
+        let pending_remove_list = GroupMutableMetadataV1 {
+            pending_remove_list: Some(Inboxes::default()),
+            ..Default::default()
+        };
+        assert_eq!(field_number(&pending_remove_list), 5);
+
+        let left_inboxes = GroupUpdated {
+            left_inboxes: vec![group_updated::Inbox::default()],
+            ..Default::default()
+        };
+        assert_eq!(field_number(&left_inboxes), 5);
+
+        let installation_metadata = plaintext_envelope::V2 {
+            message_type: Some(plaintext_envelope::v2::MessageType::InstallationMetadata(
+                Default::default(),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(field_number(&installation_metadata), 5);
+    }
+
+    #[test]
+    fn intent_fields() {
+        let out_of_band = post_commit_action::SendWelcomes {
+            ratchet_tree_out_of_band: true,
+            ..Default::default()
+        };
+        assert_eq!(field_number(&out_of_band), 3);
+        let ratchet_tree = post_commit_action::SendWelcomes {
+            ratchet_tree: vec![0],
+            ..Default::default()
+        };
+        assert_eq!(field_number(&ratchet_tree), 4);
+
+        assert_eq!(AdminListUpdateType::AddBan as i32, 5);
+        assert_eq!(AdminListUpdateType::RemoveBan as i32, 6);
+        assert_eq!(AdminListUpdateType::AddPendingRemove as i32, 7);
+        assert_eq!(AdminListUpdateType::RemovePendingRemove as i32, 8);
+        assert_eq!(PermissionUpdateType::SendMessage as i32, 6);
+        assert_eq!(PermissionUpdateType::JoinRequest as i32, 7);
+        assert_eq!(PermissionUpdateType::UpdateBanList as i32, 8);
+    }
+}